@@ -0,0 +1,245 @@
+//! Stake distribution, deregistration risk and miner return analysis for subnets
+
+// Top-k levels reported in stake concentration output
+pub const TOP_K_LEVELS: &[usize] = &[1, 3, 5, 10];
+
+// Emission left after the owner cut is split evenly between miners (incentive) and validators (dividends)
+pub const MINER_EMISSION_SHARE: f64 = 0.5;
+
+// At the measured or configured block time
+pub fn blocks_per_day() -> u64 {
+    (86_400.0 / crate::utils::block_time().as_secs_f64()) as u64
+}
+
+#[derive(Debug, Clone)]
+pub struct StakeDistribution {
+    pub validators: usize,
+    pub total_stake: u128,
+    pub top_k_shares: Vec<(usize, f64)>, // (k, share of total stake in 0..1)
+    pub gini: f64,
+    pub nakamoto: usize,
+}
+
+pub fn stake_distribution(stakes: &[u64]) -> StakeDistribution {
+    let mut sorted: Vec<u64> = stakes.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let top_k_shares = TOP_K_LEVELS
+        .iter()
+        .filter(|&&k| k <= sorted.len())
+        .map(|&k| (k, top_k_share(&sorted, k)))
+        .collect();
+
+    StakeDistribution {
+        validators: sorted.len(),
+        total_stake: sorted.iter().map(|&s| s as u128).sum(),
+        top_k_shares,
+        gini: gini_coefficient(&sorted),
+        nakamoto: nakamoto_coefficient(&sorted),
+    }
+}
+
+// Share of total stake held by the k largest holders
+pub fn top_k_share(stakes: &[u64], k: usize) -> f64 {
+    let total: u128 = stakes.iter().map(|&s| s as u128).sum();
+    if total == 0 {
+        return 0.0;
+    }
+
+    let mut sorted = stakes.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+    let top: u128 = sorted.iter().take(k).map(|&s| s as u128).sum();
+
+    top as f64 / total as f64
+}
+
+// Gini coefficient: 0 = perfectly equal, 1 = one holder owns everything
+pub fn gini_coefficient(stakes: &[u64]) -> f64 {
+    let n = stakes.len();
+    let total: u128 = stakes.iter().map(|&s| s as u128).sum();
+    if n == 0 || total == 0 {
+        return 0.0;
+    }
+
+    let mut sorted = stakes.to_vec();
+    sorted.sort_unstable();
+
+    let weighted: f64 = sorted
+        .iter()
+        .enumerate()
+        .map(|(i, &s)| (i + 1) as f64 * s as f64)
+        .sum();
+
+    (2.0 * weighted) / (n as f64 * total as f64) - (n as f64 + 1.0) / n as f64
+}
+
+// Minimum number of holders that together control more than half of the stake
+pub fn nakamoto_coefficient(stakes: &[u64]) -> usize {
+    let total: u128 = stakes.iter().map(|&s| s as u128).sum();
+    if total == 0 {
+        return 0;
+    }
+
+    let mut sorted = stakes.to_vec();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    let mut accumulated = 0u128;
+    for (i, &stake) in sorted.iter().enumerate() {
+        accumulated += stake as u128;
+        if accumulated * 2 > total {
+            return i + 1;
+        }
+    }
+
+    sorted.len()
+}
+
+// Where a neuron stands in the queue for pruning
+#[derive(Debug, Clone)]
+pub struct PruningRisk {
+    pub uid: u16,
+    pub score: u16,
+    pub immunity_remaining: u64, // Blocks until the neuron can be pruned, 0 once exposed
+    pub rank: Option<usize>,     // 1 = next to be pruned, None while immune
+    pub candidates: usize,       // Non-immune neurons competing for survival
+    pub lowest: Vec<(u16, u16)>, // (uid, score) of the lowest non-immune neurons
+}
+
+impl PruningRisk {
+    pub fn in_danger_zone(&self, zone: usize) -> bool {
+        self.rank.is_some_and(|rank| rank <= zone)
+    }
+}
+
+// Rank a UID among non-immune neurons by pruning score, lowest first. Ties go to
+// the neuron registered earlier, then the lower UID, as Subtensor prunes them
+pub fn pruning_risk(
+    uid: u16,
+    scores: &[u16],
+    registered_at: &[u64],
+    current_block: u64,
+    immunity_period: u16,
+    lowest_n: usize,
+) -> Option<PruningRisk> {
+    let score = *scores.get(uid as usize)?;
+    let immunity_remaining = |uid: usize| {
+        let registered = registered_at.get(uid).copied().unwrap_or(0);
+        (registered + immunity_period as u64).saturating_sub(current_block)
+    };
+
+    let mut candidates: Vec<(u16, u16, u64)> = scores
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| immunity_remaining(*i) == 0)
+        .map(|(i, &s)| (i as u16, s, registered_at.get(i).copied().unwrap_or(0)))
+        .collect();
+    candidates.sort_by_key(|&(uid, score, registered)| (score, registered, uid));
+
+    let remaining = immunity_remaining(uid as usize);
+    let rank = if remaining == 0 {
+        candidates
+            .iter()
+            .position(|&(candidate, _, _)| candidate == uid)
+            .map(|i| i + 1)
+    } else {
+        None
+    };
+
+    Some(PruningRisk {
+        uid,
+        score,
+        immunity_remaining: remaining,
+        rank,
+        candidates: candidates.len(),
+        lowest: candidates
+            .iter()
+            .take(lowest_n)
+            .map(|&(uid, score, _)| (uid, score))
+            .collect(),
+    })
+}
+
+// Daily emission reaching miners of a subnet, net of the owner take
+#[derive(Debug, Clone)]
+pub struct MinerReturn {
+    pub gross_per_day: u64, // Whole subnet emission, in RAO
+    pub owner_take_per_day: u64,
+    pub miner_pool_per_day: u64,
+    pub per_miner_per_day: u64,    // Average over current miners
+    pub payback_days: Option<f64>, // Days for an average miner to earn back the burn
+}
+
+// owner_cut is the SubnetOwnerCut fraction of u16::MAX
+pub fn miner_return(
+    emission_per_block: u64,
+    owner_cut: u16,
+    miners: u16,
+    burn: u64,
+) -> MinerReturn {
+    let gross_per_day = emission_per_block.saturating_mul(blocks_per_day());
+    let owner_take_per_day = (gross_per_day as u128 * owner_cut as u128 / u16::MAX as u128) as u64;
+    let miner_pool_per_day =
+        ((gross_per_day - owner_take_per_day) as f64 * MINER_EMISSION_SHARE) as u64;
+    let per_miner_per_day = miner_pool_per_day / miners.max(1) as u64;
+
+    MinerReturn {
+        gross_per_day,
+        owner_take_per_day,
+        miner_pool_per_day,
+        per_miner_per_day,
+        payback_days: (per_miner_per_day > 0).then(|| burn as f64 / per_miner_per_day as f64),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_distribution() {
+        let equal = stake_distribution(&[100, 100, 100, 100]);
+        assert!(equal.gini.abs() < 1e-9);
+        assert_eq!(equal.nakamoto, 3);
+        assert_eq!(equal.top_k_shares, vec![(1, 0.25), (3, 0.75)]);
+
+        let whale = stake_distribution(&[0, 0, 0, 1000]);
+        assert!((whale.gini - 0.75).abs() < 1e-9);
+        assert_eq!(whale.nakamoto, 1);
+
+        let empty = stake_distribution(&[]);
+        assert_eq!(empty.nakamoto, 0);
+        assert_eq!(empty.gini, 0.0);
+    }
+
+    #[test]
+    fn test_pruning_risk() {
+        // UID 0 is still immune, UID 3 ties UID 1 on score but registered earlier
+        let scores = [1, 50, 900, 50];
+        let registered_at = [990, 100, 100, 50];
+
+        let risk = pruning_risk(1, &scores, &registered_at, 1000, 100, 2).unwrap();
+        assert_eq!(risk.rank, Some(2));
+        assert_eq!(risk.candidates, 3);
+        assert_eq!(risk.lowest, vec![(3, 50), (1, 50)]);
+        assert!(risk.in_danger_zone(2));
+
+        let immune = pruning_risk(0, &scores, &registered_at, 1000, 100, 2).unwrap();
+        assert_eq!(immune.rank, None);
+        assert_eq!(immune.immunity_remaining, 90);
+
+        assert!(pruning_risk(9, &scores, &registered_at, 1000, 100, 2).is_none());
+    }
+
+    #[test]
+    fn test_miner_return() {
+        // 18% owner cut (the finney default) on 1000 RAO per block
+        let estimate = miner_return(1_000, 11_796, 100, 1_000_000);
+        assert_eq!(estimate.gross_per_day, 7_200_000);
+        assert_eq!(estimate.owner_take_per_day, 1_295_967);
+        assert_eq!(estimate.miner_pool_per_day, 2_952_016);
+        assert_eq!(estimate.per_miner_per_day, 29_520);
+        assert!((estimate.payback_days.unwrap() - 33.87).abs() < 0.01);
+
+        assert_eq!(miner_return(0, 11_796, 100, 1_000).payback_days, None);
+    }
+}
@@ -0,0 +1,521 @@
+//! Local REST/JSON API for automation: one chain connection kept open behind a few
+//! endpoints, authenticated with a bearer token
+//!
+//!   GET  /subnet/{netuid}           subnet info
+//!   GET  /status/{netuid}/{hotkey}  whether a hotkey (SS58 address) is registered, its UID
+//!   POST /register                  {"netuid", "wallet", "hotkey", "max_burn", "dry_run"?}
+//!
+//! Wallet and hotkey are names under the wallets directory, never paths, seeds or dev keys
+use anyhow::{anyhow, Context, Result};
+use http_body_util::{BodyExt, Full, Limited};
+use hyper::{
+    body::{Body, Bytes},
+    header, Method, Request, Response, StatusCode,
+};
+use serde::Deserialize;
+use serde_json::json;
+use std::{
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tokio::{net::TcpListener, sync::Semaphore};
+
+use crate::chain::ChainApi;
+use crate::errors::{self, ErrorKind, RegistrationError};
+use crate::http;
+use crate::key_utils;
+use crate::register::{QuickRegister, RegistrationOptions};
+
+// Largest request body accepted, registrations are a few hundred bytes
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+#[derive(Debug, Clone)]
+pub struct ApiOptions {
+    pub listen: SocketAddr,
+    pub token: String,
+    pub max_concurrent: usize, // Requests handled at once, more are answered with 429
+    pub max_burn: Option<u64>, // Highest max_burn a request may ask for, in RAO
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RegisterRequest {
+    netuid: u16,
+    wallet: String,
+    hotkey: String,
+    max_burn: u64, // In RAO, required so a request never pays whatever the burn is
+    #[serde(default)]
+    dry_run: bool,
+    #[serde(default)]
+    allow_foreign_hotkey: bool,
+}
+
+struct Api<C: ChainApi> {
+    register: QuickRegister<C>,
+    token: String,
+    wallets: PathBuf, // Where request wallet names are looked up
+    max_burn: Option<u64>,
+    permits: Semaphore,
+    // One registration at a time, so balance checks and nonces of a wallet don't interleave
+    registering: tokio::sync::Mutex<()>,
+}
+
+type ApiResponse = Response<Full<Bytes>>;
+
+fn respond(status: StatusCode, body: serde_json::Value) -> ApiResponse {
+    let mut response = Response::new(Full::new(Bytes::from(body.to_string())));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("application/json"),
+    );
+    response
+}
+
+fn error_response(status: StatusCode, message: impl std::fmt::Display) -> ApiResponse {
+    respond(status, json!({ "error": message.to_string() }))
+}
+
+// Failed chain reads: the node being unreachable is the gateway's fault, a missing subnet
+// the caller's
+fn read_error(error: &anyhow::Error) -> ApiResponse {
+    let message = format!("{:#}", error);
+    let status = if errors::classify(error) == ErrorKind::Connection {
+        StatusCode::BAD_GATEWAY
+    } else if message.contains("does not exist") {
+        StatusCode::NOT_FOUND
+    } else {
+        StatusCode::INTERNAL_SERVER_ERROR
+    };
+    error_response(status, message)
+}
+
+// Failed registrations carry their class and the exit code the CLI would have used
+fn registration_error(error: &anyhow::Error) -> ApiResponse {
+    let kind = errors::classify(error);
+    let status = match kind {
+        ErrorKind::Connection => StatusCode::BAD_GATEWAY,
+        ErrorKind::Fatal => StatusCode::BAD_REQUEST,
+        _ => StatusCode::CONFLICT,
+    };
+    respond(
+        status,
+        json!({
+            "error": format!("{:#}", error),
+            "kind": kind.as_str(),
+            "exit_code": kind.exit_code(),
+        }),
+    )
+}
+
+// Compared in constant time, a timing difference would leak the token byte by byte
+fn token_matches(given: &str, token: &str) -> bool {
+    given.len() == token.len()
+        && given
+            .bytes()
+            .zip(token.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+// A wallet or hotkey name as btcli lays them out under the wallets directory: one path
+// segment, so a request can't reach a key file anywhere else or pass a seed
+fn plain_name<'a>(what: &str, name: &'a str) -> Result<&'a str, (StatusCode, String)> {
+    let valid = !name.is_empty()
+        && !name.contains(['/', '\\'])
+        && !name.contains("..")
+        && !name.starts_with('~');
+    if valid {
+        Ok(name)
+    } else {
+        Err((
+            StatusCode::BAD_REQUEST,
+            format!("Invalid {} name {:?}, expected a plain name", what, name),
+        ))
+    }
+}
+
+// Coldkey and hotkey files of a registration request, both have to exist
+fn key_files(
+    wallets: &Path,
+    request: &RegisterRequest,
+) -> Result<(String, String), (StatusCode, String)> {
+    let wallet = plain_name("wallet", &request.wallet)?;
+    let hotkey = plain_name("hotkey", &request.hotkey)?;
+    let coldkey_file = wallets.join(wallet).join("coldkey");
+    let hotkey_file = wallets.join(wallet).join("hotkeys").join(hotkey);
+    if !coldkey_file.is_file() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("No wallet {} with a coldkey", wallet),
+        ));
+    }
+    if !hotkey_file.is_file() {
+        return Err((
+            StatusCode::NOT_FOUND,
+            format!("No hotkey {} in wallet {}", hotkey, wallet),
+        ));
+    }
+    Ok((
+        coldkey_file.to_string_lossy().into_owned(),
+        hotkey_file.to_string_lossy().into_owned(),
+    ))
+}
+
+impl<C: ChainApi> Api<C> {
+    fn authorized<B>(&self, request: &Request<B>) -> bool {
+        request
+            .headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| token_matches(given.trim(), &self.token))
+    }
+
+    async fn handle<B>(&self, request: Request<B>) -> ApiResponse
+    where
+        B: Body,
+        B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        if !self.authorized(&request) {
+            return error_response(StatusCode::UNAUTHORIZED, "Missing or wrong bearer token");
+        }
+        let Ok(_permit) = self.permits.try_acquire() else {
+            return error_response(StatusCode::TOO_MANY_REQUESTS, "Too many requests in flight");
+        };
+
+        let method = request.method().clone();
+        let path: Vec<String> = request
+            .uri()
+            .path()
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(String::from)
+            .collect();
+        let path: Vec<&str> = path.iter().map(String::as_str).collect();
+        match (&method, path.as_slice()) {
+            (&Method::GET, ["subnet", netuid]) => match netuid.parse() {
+                Ok(netuid) => self.subnet(netuid).await,
+                Err(_) => error_response(StatusCode::BAD_REQUEST, "Invalid netuid"),
+            },
+            (&Method::GET, ["status", netuid, hotkey]) => match netuid.parse() {
+                Ok(netuid) => self.status(netuid, hotkey).await,
+                Err(_) => error_response(StatusCode::BAD_REQUEST, "Invalid netuid"),
+            },
+            (&Method::POST, ["register"]) => match read_json(request).await {
+                Ok(body) => self.register(body).await,
+                Err(e) => error_response(StatusCode::BAD_REQUEST, format!("{:#}", e)),
+            },
+            (_, ["subnet", _]) | (_, ["status", _, _]) | (_, ["register"]) => {
+                error_response(StatusCode::METHOD_NOT_ALLOWED, "Method not allowed")
+            }
+            _ => error_response(StatusCode::NOT_FOUND, "No such endpoint"),
+        }
+    }
+
+    async fn subnet(&self, netuid: u16) -> ApiResponse {
+        match self.register.subnet_info(netuid).await {
+            Ok(info) => respond(StatusCode::OK, json!(info)),
+            Err(e) => read_error(&e),
+        }
+    }
+
+    async fn status(&self, netuid: u16, hotkey: &str) -> ApiResponse {
+        let hotkey_account = match key_utils::account_id_from_string(hotkey) {
+            Ok(account) => account,
+            Err(e) => return error_response(StatusCode::BAD_REQUEST, format!("{:#}", e)),
+        };
+        match self.register.registered_uid(netuid, &hotkey_account).await {
+            Ok(uid) => respond(
+                StatusCode::OK,
+                json!({
+                    "netuid": netuid,
+                    "hotkey": hotkey,
+                    "registered": uid.is_some(),
+                    "uid": uid,
+                }),
+            ),
+            Err(e) => read_error(&e),
+        }
+    }
+
+    async fn register(&self, request: RegisterRequest) -> ApiResponse {
+        if let Some(cap) = self.max_burn.filter(|cap| request.max_burn > *cap) {
+            return error_response(
+                StatusCode::BAD_REQUEST,
+                format!(
+                    "max_burn {} is above the server's --max-burn {}",
+                    request.max_burn, cap
+                ),
+            );
+        }
+        let (wallet, hotkey) = match key_files(&self.wallets, &request) {
+            Ok(files) => files,
+            Err((status, message)) => return error_response(status, message),
+        };
+        let options = RegistrationOptions {
+            max_burn: Some(request.max_burn),
+            dry_run: request.dry_run,
+            assume_yes: true,
+            allow_foreign_hotkey: request.allow_foreign_hotkey,
+            ..Default::default()
+        };
+
+        let _registering = self.registering.lock().await;
+        match self
+            .register
+            .register_to_subnet(request.netuid, &wallet, &hotkey, &options)
+            .await
+        {
+            Ok(outcome) => respond(
+                StatusCode::OK,
+                json!({
+                    "netuid": request.netuid,
+                    "submitted": outcome.tx_hash.is_some(),
+                    "tx_hash": outcome.tx_hash.map(|tx_hash| format!("{:?}", tx_hash)),
+                    "burn": outcome.burn,
+                    "fee": outcome.fee,
+                    "dry_run": request.dry_run,
+                }),
+            ),
+            Err(e) => registration_error(&e),
+        }
+    }
+}
+
+async fn read_json<T, B>(request: Request<B>) -> Result<T>
+where
+    T: serde::de::DeserializeOwned,
+    B: Body,
+    B::Error: Into<Box<dyn std::error::Error + Send + Sync>>,
+{
+    let body = Limited::new(request.into_body(), MAX_BODY_BYTES)
+        .collect()
+        .await
+        .map_err(|e| anyhow!("Failed to read request body: {}", e))?
+        .to_bytes();
+    serde_json::from_slice(&body).context("Invalid request body")
+}
+
+// Serve until Ctrl+C. Binding anywhere but loopback exposes registration to the network,
+// which the token alone then guards
+pub async fn serve<C: ChainApi>(register: QuickRegister<C>, options: ApiOptions) -> Result<()> {
+    if options.token.len() < 16 {
+        return Err(anyhow!("The API token has to be at least 16 characters"));
+    }
+    // A password prompt would block on a terminal nobody watches, holding up every
+    // registration behind it
+    if !key_utils::password_file_set() {
+        return Err(RegistrationError::new(
+            ErrorKind::Fatal,
+            "serve unlocks key files without a prompt, pass --password-file",
+        )
+        .into());
+    }
+    let listener = TcpListener::bind(options.listen)
+        .await
+        .with_context(|| format!("Failed to listen on {}", options.listen))?;
+    println!("🌐 Serving the API on http://{}", options.listen);
+    if !options.listen.ip().is_loopback() {
+        println!("⚠️ Listening beyond loopback, put TLS in front of it");
+    }
+
+    let api = Arc::new(Api {
+        register,
+        token: options.token,
+        wallets: key_utils::wallets_dir(),
+        max_burn: options.max_burn,
+        permits: Semaphore::new(options.max_concurrent.max(1)),
+        registering: tokio::sync::Mutex::new(()),
+    });
+    http::serve(listener, move |request| {
+        let api = api.clone();
+        async move { api.handle(request).await }
+    })
+    .await;
+
+    println!("🛑 API stopped");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::chain::MockChain;
+    use crate::register::tests::{mock_register, mock_subnet, uid_key, BURN, NETUID};
+    use sp_core::crypto::Ss58Codec;
+
+    const TOKEN: &str = "0123456789abcdef";
+
+    fn mock_api(chain: &Arc<MockChain>, wallets: PathBuf) -> Api<MockChain> {
+        Api {
+            register: mock_register(chain),
+            token: TOKEN.to_string(),
+            wallets,
+            max_burn: Some(2 * BURN),
+            permits: Semaphore::new(1),
+            registering: tokio::sync::Mutex::new(()),
+        }
+    }
+
+    async fn call(
+        api: &Api<MockChain>,
+        method: Method,
+        path: &str,
+        body: &str,
+    ) -> (StatusCode, serde_json::Value) {
+        let request = Request::builder()
+            .method(method)
+            .uri(path)
+            .header(header::AUTHORIZATION, format!("Bearer {}", TOKEN))
+            .body(Full::new(Bytes::from(body.to_string())))
+            .unwrap();
+        let response = api.handle(request).await;
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[test]
+    fn test_api_requests() {
+        assert!(token_matches("0123456789abcdef", "0123456789abcdef"));
+        assert!(!token_matches("0123456789abcdeX", "0123456789abcdef"));
+        assert!(!token_matches("0123456789abcde", "0123456789abcdef"));
+
+        let request: RegisterRequest = serde_json::from_str(
+            r#"{"netuid": 1, "wallet": "miner", "hotkey": "default", "max_burn": 5}"#,
+        )
+        .unwrap();
+        assert_eq!((request.netuid, request.max_burn), (1, 5));
+        assert!(!request.dry_run);
+        assert!(serde_json::from_str::<RegisterRequest>(
+            r#"{"netuid": 1, "wallet": "miner", "hotkey": "default", "burn": 5}"#
+        )
+        .is_err());
+        // No max_burn, no registration at whatever the burn happens to be
+        assert!(serde_json::from_str::<RegisterRequest>(
+            r#"{"netuid": 1, "wallet": "miner", "hotkey": "default"}"#
+        )
+        .is_err());
+
+        for name in ["../miner", "miner/hotkeys", "//Alice", "~", "..", ""] {
+            assert!(plain_name("wallet", name).is_err(), "{}", name);
+        }
+        assert!(plain_name("hotkey", "default").is_ok());
+
+        let closed = errors::RegistrationError::new(ErrorKind::RegistrationClosed, "Full").into();
+        assert_eq!(registration_error(&closed).status(), StatusCode::CONFLICT);
+        let missing = anyhow!("Network 99 does not exist");
+        assert_eq!(read_error(&missing).status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_api_routes() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let api = mock_api(&chain, std::env::temp_dir());
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let status_path = format!("/status/{}/{}", NETUID, bob.to_ss58check());
+
+        let (status, body) = call(&api, Method::GET, &status_path, "").await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["registered"], false);
+        chain.insert(&uid_key(&bob), 4u16);
+        let (_, body) = call(&api, Method::GET, &status_path, "").await;
+        assert_eq!(
+            (&body["registered"], &body["uid"]),
+            (&json!(true), &json!(4))
+        );
+
+        let (status, _) = call(&api, Method::GET, "/status/x/y", "").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        let (status, _) = call(&api, Method::DELETE, "/register", "").await;
+        assert_eq!(status, StatusCode::METHOD_NOT_ALLOWED);
+        let (status, _) = call(&api, Method::GET, "/wallets", "").await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        let (status, _) = call(&api, Method::POST, "/register", "{").await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        // A wrong token is turned away before anything else
+        let request = Request::builder()
+            .uri(&status_path)
+            .header(header::AUTHORIZATION, "Bearer 0123456789abcdeX")
+            .body(Full::new(Bytes::new()))
+            .unwrap();
+        assert_eq!(api.handle(request).await.status(), StatusCode::UNAUTHORIZED);
+
+        // With every permit taken, more requests are answered with 429
+        let _busy = api.permits.try_acquire().unwrap();
+        let (status, _) = call(&api, Method::GET, &status_path, "").await;
+        assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    }
+
+    #[tokio::test]
+    async fn test_api_register() {
+        let wallets = std::env::temp_dir().join(format!("btreg-api-{}", std::process::id()));
+        std::fs::create_dir_all(wallets.join("miner").join("hotkeys")).unwrap();
+        std::fs::write(wallets.join("miner").join("coldkey"), "//Alice").unwrap();
+        std::fs::write(
+            wallets.join("miner").join("hotkeys").join("default"),
+            "//Bob",
+        )
+        .unwrap();
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        let api = mock_api(&chain, wallets.clone());
+        let register = |wallet: &str, hotkey: &str, max_burn: u64| {
+            json!({ "netuid": NETUID, "wallet": wallet, "hotkey": hotkey, "max_burn": max_burn })
+                .to_string()
+        };
+
+        // Paths, dev keys, missing wallets and burns above the server's cap never submit
+        for (body, expected) in [
+            (
+                register("../miner", "default", BURN),
+                StatusCode::BAD_REQUEST,
+            ),
+            (register("//Alice", "//Bob", BURN), StatusCode::BAD_REQUEST),
+            (
+                register("miner", "miner/hotkeys/default", BURN),
+                StatusCode::BAD_REQUEST,
+            ),
+            (register("other", "default", BURN), StatusCode::NOT_FOUND),
+            (register("miner", "other", BURN), StatusCode::NOT_FOUND),
+            (
+                register("miner", "default", 3 * BURN),
+                StatusCode::BAD_REQUEST,
+            ),
+        ] {
+            let (status, response) = call(&api, Method::POST, "/register", &body).await;
+            assert_eq!(status, expected, "{} -> {}", body, response);
+        }
+        assert!(chain.submitted().is_empty());
+
+        // A max_burn below the burn is the chain's refusal, classed as such
+        let (status, body) = call(
+            &api,
+            Method::POST,
+            "/register",
+            &register("miner", "default", BURN / 2),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        assert_eq!(body["kind"], "registration closed");
+
+        let (status, body) = call(
+            &api,
+            Method::POST,
+            "/register",
+            &register("miner", "default", BURN),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK, "{}", body);
+        assert_eq!(
+            (&body["submitted"], &body["burn"]),
+            (&json!(true), &json!(BURN))
+        );
+        assert_eq!(chain.submitted().len(), 1);
+        std::fs::remove_dir_all(&wallets).unwrap();
+    }
+}
@@ -0,0 +1,72 @@
+//! Exponential backoff with jitter between registration retries and RPC reconnects, set in
+//! the config's [backoff] section
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Backoff {
+    pub initial_ms: u64,
+    pub multiplier: f64,
+    pub max_ms: u64,
+    pub jitter: f64, // Fraction of the delay added or taken off at random, 0 to 1
+}
+
+impl Default for Backoff {
+    fn default() -> Self {
+        Self {
+            initial_ms: 1_000,
+            multiplier: 2.0,
+            max_ms: 60_000,
+            jitter: 0.2,
+        }
+    }
+}
+
+impl Backoff {
+    // Delay before retry number attempt, counting from 1, before jitter
+    pub fn base_delay(&self, attempt: u32) -> Duration {
+        let exponent = attempt.saturating_sub(1).min(64) as i32;
+        let delay = self.initial_ms as f64 * self.multiplier.max(1.0).powi(exponent);
+        Duration::from_millis(delay.min(self.max_ms as f64) as u64)
+    }
+
+    // Jittered so clients failing together don't all come back at the same moment
+    pub fn delay(&self, attempt: u32) -> Duration {
+        self.jittered(self.base_delay(attempt), random_unit())
+    }
+
+    // random in 0..1 maps onto -jitter..+jitter of the delay
+    fn jittered(&self, delay: Duration, random: f64) -> Duration {
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        delay.mul_f64(1.0 + jitter * (2.0 * random - 1.0))
+    }
+}
+
+fn random_unit() -> f64 {
+    let mut bytes = [0u8; 4];
+    match getrandom::getrandom(&mut bytes) {
+        Ok(()) => u32::from_le_bytes(bytes) as f64 / (u32::MAX as f64 + 1.0),
+        Err(_) => 0.5,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_delays() {
+        let backoff = Backoff::default();
+        assert_eq!(backoff.base_delay(1), Duration::from_secs(1));
+        assert_eq!(backoff.base_delay(3), Duration::from_secs(4));
+        assert_eq!(backoff.base_delay(10), Duration::from_secs(60));
+        assert_eq!(backoff.base_delay(u32::MAX), Duration::from_secs(60));
+
+        let base = Duration::from_secs(10);
+        assert_eq!(backoff.jittered(base, 0.0), Duration::from_secs(8));
+        assert_eq!(backoff.jittered(base, 0.5), base);
+        let delay = backoff.delay(4);
+        assert!(delay >= Duration::from_millis(6_400) && delay <= Duration::from_millis(9_600));
+    }
+}
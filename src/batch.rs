@@ -0,0 +1,297 @@
+//! Batch config files (JSON, YAML or TOML) and their validation before anything runs
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, path::Path};
+
+use crate::key_utils;
+use crate::utils;
+
+pub const OPERATIONS: &[&str] = &[
+    "register",
+    "auto_register",
+    "check_status",
+    "transfer",
+    "add_stake",
+    "serve_axon",
+    "swap_hotkey",
+];
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchConfig {
+    pub operations: Vec<BatchOperation>,
+    #[serde(default = "default_concurrency")]
+    pub concurrency: usize, // Operations in flight at once, those sharing a coldkey still take turns
+}
+
+fn default_concurrency() -> usize {
+    1
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct BatchOperation {
+    pub operation: String, // One of OPERATIONS
+    pub subnet: Option<u16>,
+    pub wallet: Option<String>,
+    #[serde(default)]
+    pub hotkey: String,
+    pub max_retries: Option<usize>,
+    // Parameters of the operations that need more than a subnet and keys
+    pub transfer: Option<TransferParams>,
+    pub stake: Option<StakeParams>,
+    pub axon: Option<AxonParams>,
+    pub swap: Option<SwapParams>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct TransferParams {
+    pub to: String,
+    pub amount: u64, // RAO
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct StakeParams {
+    pub amount: u64, // RAO, staked from the wallet's coldkey to the operation's hotkey
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AxonParams {
+    pub ip: String,
+    pub port: u16,
+    #[serde(default = "default_axon_protocol")]
+    pub protocol: u8,
+    #[serde(default)]
+    pub version: u32,
+}
+
+fn default_axon_protocol() -> u8 {
+    4
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SwapParams {
+    pub new_hotkey: String, // Replaces the operation's hotkey
+}
+
+impl BatchOperation {
+    pub fn is_registration(&self) -> bool {
+        matches!(self.operation.as_str(), "register" | "auto_register")
+    }
+
+    fn needs_subnet(&self) -> bool {
+        matches!(
+            self.operation.as_str(),
+            "register" | "auto_register" | "check_status" | "serve_axon"
+        )
+    }
+
+    // serve_axon is signed by the hotkey and check_status only reads
+    fn needs_wallet(&self) -> bool {
+        !matches!(self.operation.as_str(), "check_status" | "serve_axon")
+    }
+
+    // Parameter blocks given, by name, and the one this operation needs
+    fn param_blocks(&self) -> (Vec<&'static str>, Option<&'static str>) {
+        let given = [
+            ("transfer", self.transfer.is_some()),
+            ("stake", self.stake.is_some()),
+            ("axon", self.axon.is_some()),
+            ("swap", self.swap.is_some()),
+        ]
+        .into_iter()
+        .filter_map(|(name, present)| present.then_some(name))
+        .collect();
+        let needed = match self.operation.as_str() {
+            "transfer" => Some("transfer"),
+            "add_stake" => Some("stake"),
+            "serve_axon" => Some("axon"),
+            "swap_hotkey" => Some("swap"),
+            _ => None,
+        };
+        (given, needed)
+    }
+
+    // Short description for logs and reports
+    pub fn target(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(netuid) = self.subnet {
+            parts.push(format!("subnet {}", netuid));
+        }
+        if !self.hotkey.is_empty() {
+            parts.push(self.hotkey.clone());
+        }
+        if let Some(transfer) = &self.transfer {
+            parts.push(format!("to {}", transfer.to));
+        }
+        parts.join(" ")
+    }
+}
+
+impl BatchConfig {
+    // Parse by file extension, JSON unless it is .yaml/.yml or .toml
+    pub fn parse(path: &str, content: &str) -> Result<Self> {
+        let extension = Path::new(path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase());
+        let parsed = match extension.as_deref() {
+            Some("yaml") | Some("yml") => serde_yaml::from_str(content).map_err(|e| anyhow!(e)),
+            Some("toml") => toml::from_str(content).map_err(|e| anyhow!(e)),
+            _ => serde_json::from_str(content).map_err(|e| anyhow!(e)),
+        };
+        parsed.with_context(|| format!("Invalid batch config {}", path))
+    }
+
+    pub fn load(path: &str) -> Result<(Self, String)> {
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("Failed to read {}", path))?;
+        let config = Self::parse(path, &content)?;
+        config.validate(path)?;
+        Ok((config, content))
+    }
+
+    // Every problem in the file at once, so it can be fixed in one pass
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.operations.is_empty() {
+            problems.push("no operations".to_string());
+        }
+        if self.concurrency == 0 {
+            problems.push("concurrency must be at least 1".to_string());
+        }
+
+        let mut registrations = HashSet::new();
+        for (i, operation) in self.operations.iter().enumerate() {
+            let at = format!("operation {} ({})", i + 1, operation.operation);
+            if !OPERATIONS.contains(&operation.operation.as_str()) {
+                problems.push(format!(
+                    "{}: unknown operation, use one of {}",
+                    at,
+                    OPERATIONS.join(", ")
+                ));
+                continue;
+            }
+            if operation.needs_subnet() && operation.subnet.is_none() {
+                problems.push(format!("{}: subnet is required", at));
+            }
+            if operation.operation != "transfer" && operation.hotkey.trim().is_empty() {
+                problems.push(format!("{}: hotkey is required", at));
+            }
+            let wallet = operation.wallet.as_deref().filter(|w| !w.is_empty());
+            if operation.needs_wallet() && wallet.is_none() {
+                problems.push(format!("{}: wallet is required", at));
+            }
+
+            let (given, needed) = operation.param_blocks();
+            if let Some(needed) = needed.filter(|needed| !given.contains(needed)) {
+                problems.push(format!("{}: {} parameters are required", at, needed));
+            }
+            for block in given.iter().filter(|block| Some(**block) != needed) {
+                problems.push(format!("{}: {} parameters don't apply here", at, block));
+            }
+            if operation.transfer.as_ref().is_some_and(|t| t.amount == 0)
+                || operation.stake.as_ref().is_some_and(|s| s.amount == 0)
+            {
+                problems.push(format!("{}: amount must be above zero", at));
+            }
+            if let Some(transfer) = &operation.transfer {
+                if key_utils::parse_address(&transfer.to).is_err() {
+                    problems.push(format!("{}: invalid destination {}", at, transfer.to));
+                }
+            }
+            if let Some(axon) = &operation.axon {
+                if let Err(e) = utils::parse_ip(&axon.ip) {
+                    problems.push(format!("{}: {}", at, e));
+                }
+            }
+
+            if let (true, Some(wallet), Some(netuid)) =
+                (operation.is_registration(), wallet, operation.subnet)
+            {
+                let hotkey = key_utils::resolve_hotkey(Some(wallet), &operation.hotkey);
+                if !registrations.insert((netuid, hotkey)) {
+                    problems.push(format!(
+                        "{}: hotkey {} is already registered into subnet {} by an earlier operation",
+                        at, operation.hotkey, netuid
+                    ));
+                }
+            }
+        }
+        problems
+    }
+
+    pub fn validate(&self, path: &str) -> Result<()> {
+        let problems = self.problems();
+        if problems.is_empty() {
+            return Ok(());
+        }
+        Err(anyhow!(
+            "Invalid batch config {}, nothing was run:\n  - {}",
+            path,
+            problems.join("\n  - ")
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_config_formats_and_validation() {
+        let yaml = "concurrency: 2\noperations:\n  - operation: register\n    subnet: 1\n    wallet: //Alice\n    hotkey: //Bob\n";
+        let config = BatchConfig::parse("batch.yaml", yaml).unwrap();
+        assert_eq!(config.concurrency, 2);
+        assert!(config.problems().is_empty());
+
+        let toml = "[[operations]]\noperation = \"check_status\"\nsubnet = 3\nhotkey = \"//Bob\"\n";
+        let config = BatchConfig::parse("batch.toml", toml).unwrap();
+        assert_eq!(config.concurrency, 1);
+        assert!(config.problems().is_empty());
+
+        // Typos in field names are caught instead of silently ignored
+        assert!(
+            BatchConfig::parse("batch.json", r#"{"operations": [], "concurency": 2}"#).is_err()
+        );
+
+        let json = r#"{"operations": [
+            {"operation": "register", "subnet": 1, "hotkey": "//Bob"},
+            {"operation": "regsiter", "subnet": 1, "hotkey": "//Bob"},
+            {"operation": "register", "subnet": 2, "wallet": "//Alice", "hotkey": "//Bob"},
+            {"operation": "auto_register", "subnet": 2, "wallet": "//Alice", "hotkey": "//Bob"}
+        ]}"#;
+        let problems = BatchConfig::parse("batch.json", json).unwrap().problems();
+        assert_eq!(problems.len(), 3);
+        assert!(problems[0].contains("wallet is required"));
+        assert!(problems[1].contains("unknown operation"));
+        assert!(problems[2].starts_with("operation 4"));
+
+        // Onboarding steps carry their own parameter blocks
+        let yaml = "operations:
+  - operation: transfer
+    wallet: treasury
+    transfer: { to: 5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY, amount: 1000000000 }
+  - operation: add_stake
+    wallet: miner
+    hotkey: default
+  - operation: serve_axon
+    subnet: 1
+    hotkey: default
+    axon: { ip: 1.2.3.4, port: 8091 }
+    swap: { new_hotkey: other }
+";
+        let problems = BatchConfig::parse("batch.yml", yaml).unwrap().problems();
+        assert_eq!(
+            problems,
+            vec![
+                "operation 2 (add_stake): stake parameters are required",
+                "operation 3 (serve_axon): swap parameters don't apply here",
+            ]
+        );
+    }
+}
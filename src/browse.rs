@@ -0,0 +1,146 @@
+//! Interactive subnet browser (Browse): fuzzy search, drill into a subnet, then estimate or register
+use anyhow::{Context, Result};
+use dialoguer::{theme::ColorfulTheme, FuzzySelect, Input, Select};
+
+use crate::client::SubnetInfo;
+use crate::utils;
+
+// One line of the subnet list
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubnetRow {
+    pub netuid: u16,
+    pub name: Option<String>,
+    pub burn: u64,
+    pub neurons: u16,
+    pub max_neurons: u16,
+    pub emission: u64,
+    pub registration_allowed: bool,
+}
+
+impl SubnetRow {
+    pub fn from_info(info: &SubnetInfo, name: Option<String>) -> Self {
+        Self {
+            netuid: info.netuid,
+            name,
+            burn: info.burn,
+            neurons: info.subnetwork_n,
+            max_neurons: info.max_n,
+            emission: info.emission_value,
+            registration_allowed: info.registration_allowed,
+        }
+    }
+
+    // Searchable by netuid and name
+    pub fn label(&self) -> String {
+        let name: String = self
+            .name
+            .as_deref()
+            .unwrap_or("-")
+            .chars()
+            .take(20)
+            .collect();
+        format!(
+            "{:>4}  {:<20} {:>14}  {:>5}/{:<5} slots  emission {:>12}{}",
+            self.netuid,
+            name,
+            utils::format_tao(self.burn as u128),
+            self.neurons,
+            self.max_neurons,
+            utils::format_tao(self.emission as u128),
+            if self.registration_allowed {
+                ""
+            } else {
+                "  (closed)"
+            }
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubnetAction {
+    EstimateCost,
+    Register,
+    Back,
+    Quit,
+}
+
+impl SubnetAction {
+    const ALL: [SubnetAction; 4] = [
+        SubnetAction::EstimateCost,
+        SubnetAction::Register,
+        SubnetAction::Back,
+        SubnetAction::Quit,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            SubnetAction::EstimateCost => "💰 Estimate cost",
+            SubnetAction::Register => "🚀 Register",
+            SubnetAction::Back => "↩️ Back to list",
+            SubnetAction::Quit => "👋 Quit",
+        }
+    }
+}
+
+// Scrollable list filtered as you type, None when dismissed with Esc
+pub fn pick_subnet(rows: &[SubnetRow], selected: usize) -> Result<Option<usize>> {
+    let labels: Vec<String> = rows.iter().map(SubnetRow::label).collect();
+    FuzzySelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Subnet (type to search, Esc to quit)")
+        .items(&labels)
+        .default(selected.min(labels.len().saturating_sub(1)))
+        .max_length(20)
+        .interact_opt()
+        .context("Failed to read selection")
+}
+
+pub fn pick_action(netuid: u16) -> Result<SubnetAction> {
+    let labels: Vec<&str> = SubnetAction::ALL.iter().map(SubnetAction::label).collect();
+    let choice = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!("Subnet {}", netuid))
+        .items(&labels)
+        .default(0)
+        .interact_opt()
+        .context("Failed to read selection")?;
+    Ok(choice.map_or(SubnetAction::Back, |i| SubnetAction::ALL[i]))
+}
+
+// Wallet and hotkey for a registration started from the browser
+pub fn prompt_keys() -> Result<(String, String)> {
+    let theme = ColorfulTheme::default();
+    let wallet: String = Input::with_theme(&theme)
+        .with_prompt("Wallet (name, coldkey file or //Dev)")
+        .interact_text()
+        .context("Failed to read wallet")?;
+    let hotkey: String = Input::with_theme(&theme)
+        .with_prompt("Hotkey")
+        .default("default".to_string())
+        .interact_text()
+        .context("Failed to read hotkey")?;
+    Ok((wallet, hotkey))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subnet_row_label() {
+        let row = SubnetRow {
+            netuid: 7,
+            name: Some("allways".into()),
+            burn: 1_500_000_000,
+            neurons: 250,
+            max_neurons: 256,
+            emission: 0,
+            registration_allowed: false,
+        };
+        let label = row.label();
+        assert!(label.starts_with("   7  allways"));
+        assert!(label.contains("250/256"));
+        assert!(label.ends_with("(closed)"));
+
+        let unnamed = SubnetRow { name: None, ..row };
+        assert!(unnamed.label().starts_with("   7  -"));
+    }
+}
@@ -0,0 +1,195 @@
+//! btcli-style commands (`subnets register`, `wallet balance`, `stake add`) and flags
+//! (--netuid, --wallet.name, --amount in TAO) mapped onto this tool's own, for scripts and
+//! habits carried over from btcli. Active when run as `btcli`, e.g. through a symlink, or
+//! with --btcli as the first argument
+use anyhow::{anyhow, Result};
+use std::path::Path;
+
+use crate::utils;
+
+struct Alias {
+    group: &'static str,
+    command: &'static str,
+    target: &'static str,
+    extra: &'static [&'static str], // Arguments the btcli command implies
+}
+
+const fn alias(group: &'static str, command: &'static str, target: &'static str) -> Alias {
+    Alias {
+        group,
+        command,
+        target,
+        extra: &[],
+    }
+}
+
+const ALIASES: &[Alias] = &[
+    alias("subnets", "register", "register"),
+    Alias {
+        extra: &["--all"],
+        ..alias("subnets", "list", "network-stats")
+    },
+    alias("subnets", "show", "metagraph"),
+    alias("subnets", "metagraph", "metagraph"),
+    alias("subnets", "hyperparameters", "subnet-info"),
+    alias("subnets", "burn-cost", "estimate-cost"),
+    alias("wallet", "balance", "balance"),
+    alias("wallet", "overview", "overview"),
+    alias("wallet", "transfer", "transfer"),
+    alias("wallet", "create", "new-key"),
+    Alias {
+        extra: &["--hotkey-only"],
+        ..alias("wallet", "new-hotkey", "new-key")
+    },
+    alias("wallet", "swap-hotkey", "swap-hotkey"),
+    alias("wallet", "faucet", "faucet"),
+    alias("stake", "add", "stake"),
+    alias("stake", "remove", "unstake"),
+    alias("stake", "list", "stake-info"),
+    alias("axon", "set", "serve-axon"),
+];
+
+// btcli's short group names
+const GROUPS: &[(&str, &str)] = &[
+    ("s", "subnets"),
+    ("subnet", "subnets"),
+    ("w", "wallet"),
+    ("wallets", "wallet"),
+    ("st", "stake"),
+];
+
+// Commands taking --yes, the only ones btcli's --no-prompt carries over to
+const CONFIRMING: &[&str] = &["register", "transfer", "swap-hotkey", "stake", "unstake"];
+
+// Our flag for a btcli one, None when it doesn't apply to the command
+fn translate_flag(flag: &str, target: &str) -> Option<&'static str> {
+    let flag = flag.replace('_', "-");
+    Some(match flag.as_str() {
+        "--netuid" => "--subnet",
+        "--wallet.name" | "--wallet-name" | "--name" => match target {
+            "balance" => "--account",
+            "transfer" => "--from",
+            "stake-info" => "--coldkey",
+            _ => "--wallet",
+        },
+        "--wallet.hotkey" | "--wallet-hotkey" | "--hotkey" => "--hotkey",
+        "--wallet.path" | "--wallet-path" => "--wallet-path",
+        "--subtensor.network" | "--network" => "--network",
+        "--subtensor.chain-endpoint" | "--chain" => "--rpc-url",
+        "--destination" | "--dest" => "--to",
+        "--amount" => "--amount",
+        "--no-prompt" | "--yes" | "-y" => return CONFIRMING.contains(&target).then_some("--yes"),
+        _ => return None,
+    })
+}
+
+// Command line in this tool's terms. Anything that isn't a btcli invocation, or a btcli
+// command without an equivalent, is passed through for clap to judge
+pub fn translate(args: Vec<String>) -> Result<Vec<String>> {
+    let invoked_as_btcli = args
+        .first()
+        .and_then(|program| Path::new(program).file_stem())
+        .is_some_and(|stem| stem == "btcli");
+    let flagged = args.get(1).is_some_and(|arg| arg == "--btcli");
+    if !invoked_as_btcli && !flagged {
+        return Ok(args);
+    }
+
+    let mut args = args.into_iter();
+    let program = args.next().unwrap_or_default();
+    let rest: Vec<String> = args.skip(flagged as usize).collect();
+    let group = rest.first().map(|group| {
+        GROUPS
+            .iter()
+            .find(|(short, _)| short == group)
+            .map_or(group.as_str(), |(_, name)| name)
+    });
+    let alias = ALIASES.iter().find(|alias| {
+        Some(alias.group) == group && rest.get(1).map(String::as_str) == Some(alias.command)
+    });
+    let Some(alias) = alias else {
+        return Ok(std::iter::once(program).chain(rest).collect());
+    };
+
+    let mut translated = vec![program, alias.target.to_string()];
+    translated.extend(alias.extra.iter().map(|arg| arg.to_string()));
+    let mut args = rest.into_iter().skip(2);
+    while let Some(arg) = args.next() {
+        if !arg.starts_with('-') {
+            translated.push(arg);
+            continue;
+        }
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None => (arg.clone(), None),
+        };
+        let Some(ours) = translate_flag(&flag, alias.target) else {
+            // Unknown flags are left for clap to reject, with its suggestions
+            if flag.replace('_', "-") != "--no-prompt" {
+                translated.push(arg);
+            }
+            continue;
+        };
+        translated.push(ours.to_string());
+        if ours == "--yes" {
+            continue;
+        }
+        let value = match inline_value {
+            Some(value) => value,
+            None => args
+                .next()
+                .ok_or_else(|| anyhow!("{} needs a value", flag))?,
+        };
+        // btcli amounts are TAO, ours are RAO
+        if ours == "--amount" {
+            translated.push(utils::parse_tao(&value)?.to_string());
+        } else {
+            translated.push(value);
+        }
+    }
+    Ok(translated)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_btcli_aliases() {
+        // Our own command lines are never touched
+        let own = args("btreg register --subnet 1 --wallet w -H h");
+        assert_eq!(translate(own.clone()).unwrap(), own);
+
+        assert_eq!(
+            translate(args(
+                "btcli subnets register --netuid 3 --wallet.name miner --wallet.hotkey h1 --no-prompt"
+            ))
+            .unwrap(),
+            args("btcli register --subnet 3 --wallet miner --hotkey h1 --yes")
+        );
+        assert_eq!(
+            translate(args(
+                "btreg --btcli st add --wallet-name=miner --hotkey h1 --amount 1.5"
+            ))
+            .unwrap(),
+            args("btreg stake --wallet miner --hotkey h1 --amount 1500000000")
+        );
+        assert_eq!(
+            translate(args("btcli w balance --wallet.name miner --no-prompt")).unwrap(),
+            args("btcli balance --account miner")
+        );
+        assert_eq!(
+            translate(args("btcli wallet new-hotkey --wallet.name miner")).unwrap(),
+            args("btcli new-key --hotkey-only --wallet miner")
+        );
+        assert_eq!(
+            translate(args("btcli wallet overview --wallet.name miner")).unwrap(),
+            args("btcli overview --wallet miner")
+        );
+        assert!(translate(args("btcli stake add --amount")).is_err());
+    }
+}
@@ -0,0 +1,104 @@
+//! In-memory storage cache for long-running commands, invalidated per subnet on chain changes
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+// Items that change every block and would never be served fresh from a cache
+const UNCACHED_STORAGE: &[&str] = &["BlocksSinceLastStep", "RegistrationsThisBlock"];
+
+#[derive(Debug, Clone)]
+struct CachedValue {
+    value: Option<Vec<u8>>,
+    netuid: Option<u16>,
+    stored_at: Instant,
+}
+
+#[derive(Debug, Clone)]
+pub struct StorageCache {
+    entries: HashMap<String, CachedValue>,
+    ttl: Duration,
+}
+
+impl StorageCache {
+    pub fn new(ttl: Duration) -> Self {
+        Self {
+            entries: HashMap::new(),
+            ttl,
+        }
+    }
+
+    pub fn is_cacheable(storage_name: &str) -> bool {
+        !UNCACHED_STORAGE.contains(&storage_name)
+    }
+
+    // Cached value for a storage key, None on a miss or once the entry is older than the TTL
+    pub fn get(&self, key: &str, now: Instant) -> Option<Option<Vec<u8>>> {
+        self.entries
+            .get(key)
+            .filter(|entry| now.duration_since(entry.stored_at) < self.ttl)
+            .map(|entry| entry.value.clone())
+    }
+
+    pub fn insert(
+        &mut self,
+        key: String,
+        netuid: Option<u16>,
+        value: Option<Vec<u8>>,
+        now: Instant,
+    ) {
+        self.entries.insert(
+            key,
+            CachedValue {
+                value,
+                netuid,
+                stored_at: now,
+            },
+        );
+    }
+
+    // Drop every entry belonging to a subnet, returns how many were removed
+    pub fn invalidate_subnet(&mut self, netuid: u16) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|_, entry| entry.netuid != Some(netuid));
+        before - self.entries.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_storage_cache_invalidation() {
+        let mut cache = StorageCache::new(Duration::from_secs(60));
+        let now = Instant::now();
+        cache.insert("0xburn1".into(), Some(1), Some(vec![1]), now);
+        cache.insert("0xburn2".into(), Some(2), Some(vec![2]), now);
+        cache.insert("0xtotal".into(), None, None, now);
+
+        assert_eq!(cache.get("0xburn1", now), Some(Some(vec![1])));
+        assert_eq!(cache.get("0xtotal", now), Some(None));
+        assert_eq!(cache.get("0xmissing", now), None);
+        assert_eq!(cache.get("0xburn1", now + Duration::from_secs(61)), None);
+
+        assert_eq!(cache.invalidate_subnet(1), 1);
+        assert_eq!(cache.get("0xburn1", now), None);
+        assert_eq!(cache.len(), 2);
+
+        assert!(!StorageCache::is_cacheable("BlocksSinceLastStep"));
+        assert!(StorageCache::is_cacheable("Burn"));
+    }
+}
@@ -0,0 +1,204 @@
+//! Registration campaign planning: when and where to spend a budget before a deadline
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{io::Write, time::Duration};
+
+use crate::utils;
+
+// Wait for an adjustment only when it should cut the burn by at least this much
+const MIN_PROJECTED_DROP: f64 = 0.10;
+
+// Limits for a registration campaign
+#[derive(Debug, Clone)]
+pub struct CampaignOptions {
+    pub target: usize,            // New registrations to make
+    pub budget: u64,              // Total burn budget, in RAO
+    pub deadline: DateTime<Utc>,  // Stop deciding after this
+    pub max_burn: Option<u64>,    // Never pay more than this per registration, in RAO
+    pub interval: u64,            // Seconds between decisions
+    pub log_path: Option<String>, // Append every decision here as JSON lines
+    pub summary_path: Option<String>,
+}
+
+// What the planner knows about one candidate subnet
+#[derive(Debug, Clone)]
+pub struct Candidate {
+    pub netuid: u16,
+    pub burn: u64,
+    pub projected_burn: u64,
+    pub blocks_to_adjustment: u64,
+    pub competition: f64, // Registrations this interval relative to the target, 1.0 = on target
+    pub open: bool,       // Registration window open
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "action", rename_all = "snake_case")]
+pub enum Decision {
+    Register {
+        netuid: u16,
+        burn: u64,
+        reason: String,
+    },
+    Wait {
+        reason: String,
+    },
+    Stop {
+        reason: String,
+    },
+}
+
+// Pick the cheapest open subnet, unless its burn is about to drop before the deadline
+pub fn decide(
+    candidates: &[Candidate],
+    remaining_budget: u64,
+    max_burn: Option<u64>,
+    time_left: Duration,
+) -> Decision {
+    let affordable = candidates
+        .iter()
+        .filter(|c| c.open && c.burn <= remaining_budget)
+        .filter(|c| max_burn.is_none_or(|max| c.burn <= max));
+
+    let Some(best) = affordable.min_by(|a, b| {
+        a.burn
+            .cmp(&b.burn)
+            .then(a.competition.total_cmp(&b.competition))
+    }) else {
+        return Decision::Wait {
+            reason: "no open subnet within budget and max burn".to_string(),
+        };
+    };
+
+    let drop = 1.0 - best.projected_burn as f64 / best.burn.max(1) as f64;
+    if drop >= MIN_PROJECTED_DROP
+        && utils::blocks_to_duration(best.blocks_to_adjustment) < time_left
+    {
+        return Decision::Wait {
+            reason: format!(
+                "subnet {} burn projected to drop {:.0}% to {} in {}",
+                best.netuid,
+                drop * 100.0,
+                utils::format_tao(best.projected_burn as u128),
+                utils::format_countdown(best.blocks_to_adjustment)
+            ),
+        };
+    }
+
+    Decision::Register {
+        netuid: best.netuid,
+        burn: best.burn,
+        reason: format!(
+            "cheapest open subnet, competition {:.0}% of target",
+            best.competition * 100.0
+        ),
+    }
+}
+
+#[derive(Serialize)]
+struct DecisionRecord<'a> {
+    at: DateTime<Utc>,
+    block: u64,
+    spent: u64,
+    registered: &'a [u16],
+    #[serde(flatten)]
+    decision: &'a Decision,
+}
+
+// Print a decision and append it to the campaign log
+pub fn log_decision(
+    path: Option<&str>,
+    block: u64,
+    spent: u64,
+    registered: &[u16],
+    decision: &Decision,
+) -> Result<()> {
+    match decision {
+        Decision::Register {
+            netuid,
+            burn,
+            reason,
+        } => println!(
+            "🧭 Block {}: register in subnet {} for {} ({})",
+            utils::format_thousands(block),
+            netuid,
+            utils::format_tao(*burn as u128),
+            reason
+        ),
+        Decision::Wait { reason } => println!(
+            "🧭 Block {}: wait, {}",
+            utils::format_thousands(block),
+            reason
+        ),
+        Decision::Stop { reason } => println!(
+            "🧭 Block {}: stop, {}",
+            utils::format_thousands(block),
+            reason
+        ),
+    }
+
+    if let Some(path) = path {
+        let record = DecisionRecord {
+            at: Utc::now(),
+            block,
+            spent,
+            registered,
+            decision,
+        };
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .with_context(|| format!("Failed to open campaign log {}", path))?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidate(netuid: u16, burn: u64, projected_burn: u64) -> Candidate {
+        Candidate {
+            netuid,
+            burn,
+            projected_burn,
+            blocks_to_adjustment: 100,
+            competition: 1.0,
+            open: true,
+        }
+    }
+
+    #[test]
+    fn test_campaign_decisions() {
+        let hour = Duration::from_secs(3600);
+        let candidates = [candidate(1, 3_000, 3_000), candidate(3, 2_000, 2_000)];
+        assert!(matches!(
+            decide(&candidates, 10_000, None, hour),
+            Decision::Register { netuid: 3, .. }
+        ));
+
+        // Burn about to halve within the deadline: wait for it
+        let dropping = [candidate(3, 2_000, 1_000)];
+        assert!(matches!(
+            decide(&dropping, 10_000, None, hour),
+            Decision::Wait { .. }
+        ));
+        // ... unless the adjustment lands after the deadline
+        assert!(matches!(
+            decide(&dropping, 10_000, None, Duration::from_secs(60)),
+            Decision::Register { netuid: 3, .. }
+        ));
+
+        assert!(matches!(
+            decide(&candidates, 1_000, None, hour),
+            Decision::Wait { .. }
+        ));
+        assert!(matches!(
+            decide(&candidates, 10_000, Some(2_500), hour),
+            Decision::Register { netuid: 3, .. }
+        ));
+    }
+}
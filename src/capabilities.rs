@@ -0,0 +1,224 @@
+//! Build capability report for fleet tooling
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+
+use crate::constants;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CallIndex {
+    pub name: &'static str,
+    pub pallet_index: u8,
+    pub call_index: u8,
+    pub args: Vec<&'static str>, // Argument encodings, newtypes by what they wrap
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Features {
+    pub gpu_solver: bool,
+    pub ledger: bool,
+    pub light_client: bool,
+    pub notifier_backends: Vec<&'static str>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    pub name: &'static str,
+    pub version: &'static str,
+    pub extrinsic_version: u8,
+    pub signed_extensions: Vec<&'static str>,
+    pub calls: Vec<CallIndex>,
+    pub features: Features,
+    pub networks: Vec<&'static str>,
+}
+
+impl Capabilities {
+    // What this binary was built to do, no network access needed
+    pub fn current() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME"),
+            version: env!("CARGO_PKG_VERSION"),
+            extrinsic_version: 4,
+            signed_extensions: vec![
+                "CheckSpecVersion",
+                "CheckTxVersion",
+                "CheckGenesis",
+                "CheckMortality",
+                "CheckNonce",
+                "ChargeTransactionPayment",
+                "CheckMetadataHash",
+            ],
+            calls: vec![
+                CallIndex {
+                    name: "SubtensorModule.register",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::REGISTER_CALL_INDEX,
+                    args: vec!["u16", "u64", "u64", "Vec<u8>", "[u8; 32]", "[u8; 32]"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.burned_register",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::BURNED_REGISTER_CALL_INDEX,
+                    args: vec!["u16", "[u8; 32]"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.add_stake",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::ADD_STAKE_CALL_INDEX,
+                    args: vec!["[u8; 32]", "u16", "u64"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.remove_stake",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::REMOVE_STAKE_CALL_INDEX,
+                    args: vec!["[u8; 32]", "u16", "u64"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.serve_axon",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::SERVE_AXON_CALL_INDEX,
+                    args: vec!["u16", "u32", "u128", "u16", "u8", "u8", "u8", "u8"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.move_stake",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::MOVE_STAKE_CALL_INDEX,
+                    args: vec!["[u8; 32]", "[u8; 32]", "u16", "u16", "u64"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.set_children",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::SET_CHILDREN_CALL_INDEX,
+                    args: vec!["[u8; 32]", "u16", "Vec<(u64, [u8; 32])>"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.set_identity",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::SET_IDENTITY_CALL_INDEX,
+                    args: vec![
+                        "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>",
+                    ],
+                },
+                CallIndex {
+                    name: "SubtensorModule.swap_hotkey",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::SWAP_HOTKEY_CALL_INDEX,
+                    args: vec!["[u8; 32]", "[u8; 32]"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.root_register",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::ROOT_REGISTER_CALL_INDEX,
+                    args: vec!["[u8; 32]"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.commit_weights",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::COMMIT_WEIGHTS_CALL_INDEX,
+                    args: vec!["u16", "[u8; 32]"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.reveal_weights",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::REVEAL_WEIGHTS_CALL_INDEX,
+                    args: vec!["u16", "Vec<u16>", "Vec<u16>", "Vec<u16>", "u64"],
+                },
+                CallIndex {
+                    name: "SubtensorModule.faucet",
+                    pallet_index: constants::SUBTENSOR_MODULE_INDEX,
+                    call_index: constants::FAUCET_CALL_INDEX,
+                    args: vec!["u64", "u64", "Vec<u8>"],
+                },
+                CallIndex {
+                    name: "Balances.transfer_keep_alive",
+                    pallet_index: constants::BALANCES_MODULE_INDEX,
+                    call_index: constants::TRANSFER_KEEP_ALIVE_CALL_INDEX,
+                    args: vec!["MultiAddress", "Compact<u64>"],
+                },
+            ],
+            features: Features {
+                gpu_solver: false,
+                ledger: false,
+                light_client: cfg!(feature = "light-client"),
+                notifier_backends: vec!["console", "webhook", "telegram", "discord"],
+            },
+            networks: constants::DEFAULT_RPC_ENDPOINTS.to_vec(),
+        }
+    }
+}
+
+pub fn show_capabilities(output: &str) -> Result<()> {
+    let capabilities = Capabilities::current();
+
+    match output {
+        "json" => println!("{}", serde_json::to_string_pretty(&capabilities)?),
+        "text" => {
+            println!("🧰 {} v{}", capabilities.name, capabilities.version);
+            println!("   Extrinsic version: {}", capabilities.extrinsic_version);
+            println!(
+                "   Signed extensions: {}",
+                capabilities.signed_extensions.join(", ")
+            );
+            println!("   Calls:");
+            for call in &capabilities.calls {
+                println!(
+                    "     {} ({}:{})",
+                    call.name, call.pallet_index, call.call_index
+                );
+            }
+            println!(
+                "   GPU solver: {}",
+                if capabilities.features.gpu_solver {
+                    "yes"
+                } else {
+                    "no"
+                }
+            );
+            println!(
+                "   Ledger: {}",
+                if capabilities.features.ledger {
+                    "yes"
+                } else {
+                    "no"
+                }
+            );
+            println!(
+                "   Light client: {}",
+                if capabilities.features.light_client {
+                    "yes"
+                } else {
+                    "no"
+                }
+            );
+            println!(
+                "   Notifiers: {}",
+                capabilities.features.notifier_backends.join(", ")
+            );
+            println!("   Networks:");
+            for network in &capabilities.networks {
+                println!("     {}", network);
+            }
+        }
+        other => {
+            return Err(anyhow!(
+                "Unknown output format: {}. Use text or json",
+                other
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capabilities_json() {
+        let json = serde_json::to_value(Capabilities::current()).unwrap();
+        assert_eq!(json["version"], env!("CARGO_PKG_VERSION"));
+        assert_eq!(json["calls"][1]["call_index"], 7);
+        assert_eq!(json["calls"][1]["args"][0], "u16");
+        assert!(show_capabilities("yaml").is_err());
+    }
+}
@@ -0,0 +1,890 @@
+//! The node behind BittensorClient, as the operations the client needs from it: storage
+//! reads, block info, runtime calls, submission and subscriptions. ChainApi is served by a
+//! WebSocket (or light client) connection in production and by an in-memory chain in tests,
+//! so the flows built on the client run without a node
+use anyhow::Result;
+use async_trait::async_trait;
+use futures::stream::{BoxStream, StreamExt};
+use jsonrpsee::core::{
+    client::{ClientT, Error as ClientError, SubscriptionClientT},
+    params::ArrayParams,
+    traits::ToRpcParams,
+};
+use jsonrpsee::rpc_params;
+use jsonrpsee::types::{error::METHOD_NOT_FOUND_CODE, ErrorObject};
+use jsonrpsee::ws_client::WsClient;
+use primitive_types::H256;
+use serde::{de::DeserializeOwned, Deserialize};
+use serde_json::Value;
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use crate::client::{BlockHeader, RuntimeVersion};
+use crate::rpc_stats;
+use crate::transport;
+
+#[cfg(test)]
+pub use mock::{event_record, MockChain};
+
+// Raw notifications of a subscription, ending when the node closes it
+pub type Notifications = BoxStream<'static, Result<Value, ClientError>>;
+
+// Storage values that changed in one block, as returned by state_queryStorageAt / state_subscribeStorage
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageChangeSet {
+    pub changes: Vec<(String, Option<String>)>,
+}
+
+// Where a watched extrinsic is, as author_submitAndWatchExtrinsic reports it
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxStatus {
+    Future,
+    Ready,
+    Broadcast(Vec<String>),
+    InBlock(H256),
+    Retracted(H256),
+    FinalityTimeout(H256),
+    Finalized(H256),
+    Usurped(H256),
+    Dropped,
+    Invalid,
+}
+
+// State queries read at the given block, the best one when it is None. Errors keep the
+// RPC client's classes, so a lost connection can be told from an answered request
+#[async_trait]
+// async-trait marks the boxed futures it returns #[must_use], on top of Future's own
+#[allow(clippy::double_must_use)]
+pub trait ChainApi: Send + Sync + 'static {
+    // Value under an encoded storage key, None when it is unset
+    async fn storage(&self, key: &str, at: Option<H256>) -> Result<Option<Vec<u8>>, ClientError>;
+    // Values of many keys in one round trip, in key order
+    async fn storage_batch(
+        &self,
+        keys: &[String],
+        at: Option<H256>,
+    ) -> Result<Vec<Option<Vec<u8>>>, ClientError>;
+    // Up to count keys under prefix, after start_key when given
+    async fn storage_keys(
+        &self,
+        prefix: &str,
+        count: u32,
+        start_key: Option<&str>,
+        at: Option<H256>,
+    ) -> Result<Vec<String>, ClientError>;
+    // Hash of a block by number, the best block when None. None when it doesn't exist yet
+    async fn block_hash(&self, number: Option<u64>) -> Result<Option<H256>, ClientError>;
+    async fn header(&self, block_hash: H256) -> Result<BlockHeader, ClientError>;
+    // The SCALE encoded extrinsics of a block, in order
+    async fn block_extrinsics(&self, block_hash: H256) -> Result<Vec<Vec<u8>>, ClientError>;
+    async fn runtime_version(&self) -> Result<RuntimeVersion, ClientError>;
+    // SCALE encoded runtime metadata
+    async fn metadata(&self) -> Result<Vec<u8>, ClientError>;
+    // A runtime API through state_call, SCALE encoded both ways
+    async fn runtime_call(
+        &self,
+        method: &str,
+        data: &[u8],
+        at: Option<H256>,
+    ) -> Result<Vec<u8>, ClientError>;
+    // One of Subtensor's own RPCs (neuronInfo_*, delegateInfo_*), a Call error from nodes
+    // that don't serve it
+    async fn subtensor_rpc(
+        &self,
+        method: &str,
+        args: &[u16],
+        at: Option<H256>,
+    ) -> Result<Vec<u8>, ClientError>;
+    // ApplyExtrinsicResult of executing an extrinsic without submitting it
+    async fn dry_run(&self, extrinsic: &[u8]) -> Result<Vec<u8>, ClientError>;
+    // Fee in RAO the chain would charge for an extrinsic
+    async fn partial_fee(&self, extrinsic: &[u8]) -> Result<u128, ClientError>;
+    // Hands an extrinsic to the pool, returns its hash
+    async fn submit(&self, extrinsic: &[u8]) -> Result<H256, ClientError>;
+    async fn submit_and_watch(
+        &self,
+        extrinsic: &[u8],
+    ) -> Result<Subscription<TxStatus>, ClientError>;
+    async fn subscribe_storage(
+        &self,
+        keys: &[String],
+    ) -> Result<Subscription<StorageChangeSet>, ClientError>;
+    // New best blocks, or finalized ones
+    async fn subscribe_heads(
+        &self,
+        finalized: bool,
+    ) -> Result<Subscription<BlockHeader>, ClientError>;
+    // Replace a dropped connection, requests after it go through the new one
+    async fn reconnect(&self) -> Result<()>;
+}
+
+// Notifications decoded as they arrive, whichever backend delivers them
+pub struct Subscription<T> {
+    notifications: Notifications,
+    item: PhantomData<fn() -> T>,
+}
+
+impl<T: DeserializeOwned> Subscription<T> {
+    pub fn new(notifications: Notifications) -> Self {
+        Self {
+            notifications,
+            item: PhantomData,
+        }
+    }
+
+    pub async fn next(&mut self) -> Option<Result<T, ClientError>> {
+        let notification = self.notifications.next().await?;
+        Some(
+            notification
+                .and_then(|value| serde_json::from_value(value).map_err(ClientError::ParseError)),
+        )
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    format!("0x{}", hex::encode(bytes))
+}
+
+fn from_hex(data: &str) -> Result<Vec<u8>, ClientError> {
+    hex::decode(data.trim_start_matches("0x"))
+        .map_err(|e| ClientError::Custom(format!("Invalid hex in response: {}", e)))
+}
+
+// State queries take the block to read at as their last, optional parameter
+fn at_block(mut params: ArrayParams, at: Option<H256>) -> ArrayParams {
+    if let Some(block_hash) = at {
+        params.insert(block_hash).expect("a block hash serializes");
+    }
+    params
+}
+
+// RPCs a light client doesn't serve: unsafe ones like system_dryRun, and Subtensor's own
+// neuronInfo_* and delegateInfo_*. Refused up front as not found, so callers take their
+// fallbacks instead of waiting on a request that never gets an answer
+fn light_client_unsupported(method: &str) -> Result<(), ClientError> {
+    if !transport::light_client() {
+        return Ok(());
+    }
+    Err(ClientError::Call(ErrorObject::owned(
+        METHOD_NOT_FOUND_CODE,
+        format!("{} is unsupported with --light-client", method),
+        None::<()>,
+    )))
+}
+
+// A node reached through transport::connect, reconnected in place when it drops
+pub struct WsChain {
+    endpoint: String,
+    client: Mutex<Arc<WsClient>>,
+    reconnecting: tokio::sync::Mutex<()>,
+}
+
+impl WsChain {
+    pub async fn connect(endpoint: &str) -> Result<Self> {
+        let client = transport::connect(endpoint).await?;
+        Ok(Self {
+            endpoint: endpoint.to_string(),
+            client: Mutex::new(Arc::new(client)),
+            reconnecting: tokio::sync::Mutex::new(()),
+        })
+    }
+
+    // The current connection, held by a request or subscription until it finishes
+    fn ws(&self) -> Arc<WsClient> {
+        self.client
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+
+    // Every RPC request goes through here so --rpc-stats can account for it
+    async fn request<R: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ArrayParams,
+    ) -> Result<R, ClientError> {
+        let sent = params
+            .clone()
+            .to_rpc_params()
+            .ok()
+            .flatten()
+            .map_or(0, |raw| raw.get().len());
+        let started = Instant::now();
+        let response: Result<Value, _> = self.ws().request(method, params).await;
+        let received = response.as_ref().map_or(0, |value| value.to_string().len());
+        rpc_stats::record_call(method, sent, received, started.elapsed(), response.is_ok());
+        serde_json::from_value(response?).map_err(ClientError::ParseError)
+    }
+
+    async fn subscribe<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: ArrayParams,
+        unsubscribe_method: &str,
+    ) -> Result<Subscription<T>, ClientError> {
+        let started = Instant::now();
+        let subscription: Result<jsonrpsee::core::client::Subscription<Value>, _> = self
+            .ws()
+            .subscribe(method, params, unsubscribe_method)
+            .await;
+        rpc_stats::record_call(method, 0, 0, started.elapsed(), subscription.is_ok());
+        Ok(Subscription::new(
+            subscription?
+                .map(|notification| notification.map_err(Into::into))
+                .boxed(),
+        ))
+    }
+}
+
+#[async_trait]
+impl ChainApi for WsChain {
+    async fn storage(&self, key: &str, at: Option<H256>) -> Result<Option<Vec<u8>>, ClientError> {
+        let value: Option<String> = self
+            .request("state_getStorage", at_block(rpc_params![key], at))
+            .await?;
+        value.as_deref().map(from_hex).transpose()
+    }
+
+    async fn storage_batch(
+        &self,
+        keys: &[String],
+        at: Option<H256>,
+    ) -> Result<Vec<Option<Vec<u8>>>, ClientError> {
+        let change_sets: Vec<StorageChangeSet> = self
+            .request("state_queryStorageAt", at_block(rpc_params![keys], at))
+            .await?;
+        let mut values = HashMap::new();
+        for (key, value) in change_sets.into_iter().flat_map(|set| set.changes) {
+            if let Some(data) = value {
+                values.insert(key.to_lowercase(), from_hex(&data)?);
+            }
+        }
+        Ok(keys
+            .iter()
+            .map(|key| values.remove(&key.to_lowercase()))
+            .collect())
+    }
+
+    async fn storage_keys(
+        &self,
+        prefix: &str,
+        count: u32,
+        start_key: Option<&str>,
+        at: Option<H256>,
+    ) -> Result<Vec<String>, ClientError> {
+        self.request(
+            "state_getKeysPaged",
+            at_block(rpc_params![prefix, count, start_key], at),
+        )
+        .await
+    }
+
+    async fn block_hash(&self, number: Option<u64>) -> Result<Option<H256>, ClientError> {
+        let params = match number {
+            Some(number) => rpc_params![number],
+            None => rpc_params![],
+        };
+        self.request("chain_getBlockHash", params).await
+    }
+
+    async fn header(&self, block_hash: H256) -> Result<BlockHeader, ClientError> {
+        let header: Option<BlockHeader> = self
+            .request("chain_getHeader", rpc_params![block_hash])
+            .await?;
+        header.ok_or_else(|| ClientError::Custom(format!("Unknown block {:?}", block_hash)))
+    }
+
+    async fn block_extrinsics(&self, block_hash: H256) -> Result<Vec<Vec<u8>>, ClientError> {
+        let block: Value = self
+            .request("chain_getBlock", rpc_params![block_hash])
+            .await?;
+        let extrinsics = block["block"]["extrinsics"]
+            .as_array()
+            .ok_or_else(|| ClientError::Custom("Block without an extrinsics list".to_string()))?;
+        extrinsics
+            .iter()
+            .map(|extrinsic| from_hex(extrinsic.as_str().unwrap_or("")))
+            .collect()
+    }
+
+    async fn runtime_version(&self) -> Result<RuntimeVersion, ClientError> {
+        self.request("state_getRuntimeVersion", rpc_params![]).await
+    }
+
+    async fn metadata(&self) -> Result<Vec<u8>, ClientError> {
+        let metadata: String = self.request("state_getMetadata", rpc_params![]).await?;
+        from_hex(&metadata)
+    }
+
+    async fn runtime_call(
+        &self,
+        method: &str,
+        data: &[u8],
+        at: Option<H256>,
+    ) -> Result<Vec<u8>, ClientError> {
+        let result: String = self
+            .request(
+                "state_call",
+                at_block(rpc_params![method, to_hex(data)], at),
+            )
+            .await?;
+        from_hex(&result)
+    }
+
+    async fn subtensor_rpc(
+        &self,
+        method: &str,
+        args: &[u16],
+        at: Option<H256>,
+    ) -> Result<Vec<u8>, ClientError> {
+        light_client_unsupported(method)?;
+        let mut params = ArrayParams::new();
+        for arg in args {
+            params.insert(arg).expect("a u16 serializes");
+        }
+        self.request(method, at_block(params, at)).await
+    }
+
+    async fn dry_run(&self, extrinsic: &[u8]) -> Result<Vec<u8>, ClientError> {
+        light_client_unsupported("system_dryRun")?;
+        let result: String = self
+            .request("system_dryRun", rpc_params![to_hex(extrinsic)])
+            .await?;
+        from_hex(&result)
+    }
+
+    async fn partial_fee(&self, extrinsic: &[u8]) -> Result<u128, ClientError> {
+        let info: Value = self
+            .request("payment_queryInfo", rpc_params![to_hex(extrinsic)])
+            .await?;
+        let invalid = || ClientError::Custom(format!("Invalid partialFee in {}", info));
+        match &info["partialFee"] {
+            Value::String(fee) => fee.parse().map_err(|_| invalid()),
+            Value::Number(fee) => fee.as_u64().map(u128::from).ok_or_else(invalid),
+            _ => Err(invalid()),
+        }
+    }
+
+    async fn submit(&self, extrinsic: &[u8]) -> Result<H256, ClientError> {
+        self.request("author_submitExtrinsic", rpc_params![to_hex(extrinsic)])
+            .await
+    }
+
+    async fn submit_and_watch(
+        &self,
+        extrinsic: &[u8],
+    ) -> Result<Subscription<TxStatus>, ClientError> {
+        self.subscribe(
+            "author_submitAndWatchExtrinsic",
+            rpc_params![to_hex(extrinsic)],
+            "author_unwatchExtrinsic",
+        )
+        .await
+    }
+
+    async fn subscribe_storage(
+        &self,
+        keys: &[String],
+    ) -> Result<Subscription<StorageChangeSet>, ClientError> {
+        self.subscribe(
+            "state_subscribeStorage",
+            rpc_params![keys],
+            "state_unsubscribeStorage",
+        )
+        .await
+    }
+
+    async fn subscribe_heads(
+        &self,
+        finalized: bool,
+    ) -> Result<Subscription<BlockHeader>, ClientError> {
+        if finalized {
+            self.subscribe(
+                "chain_subscribeFinalizedHeads",
+                rpc_params![],
+                "chain_unsubscribeFinalizedHeads",
+            )
+            .await
+        } else {
+            self.subscribe(
+                "chain_subscribeNewHeads",
+                rpc_params![],
+                "chain_unsubscribeNewHeads",
+            )
+            .await
+        }
+    }
+
+    // Callers that saw the connection fail concurrently wait here, then find it already
+    // replaced
+    async fn reconnect(&self) -> Result<()> {
+        let _reconnecting = self.reconnecting.lock().await;
+        if self.ws().is_connected() {
+            return Ok(());
+        }
+        let client = transport::connect(&self.endpoint).await?;
+        *self.client.lock().unwrap_or_else(|e| e.into_inner()) = Arc::new(client);
+        println!("🔗 Reconnected to {}", transport::describe(&self.endpoint));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod mock {
+    use super::*;
+    use codec::{Compact, Encode};
+    use futures::stream;
+    use std::collections::{BTreeMap, VecDeque};
+
+    use crate::client::storage_key;
+
+    // An in-memory chain: storage, a best block that advances with every included
+    // extrinsic, the System::Events each block emitted and what was submitted to it.
+    // Block n has the hash from_low_u64_be(n + 1)
+    #[derive(Default)]
+    pub struct MockChain {
+        state: Mutex<MockState>,
+    }
+
+    #[derive(Default)]
+    struct MockState {
+        storage: BTreeMap<String, Vec<u8>>,
+        history: HashMap<(u64, String), Vec<u8>>, // Values read at earlier blocks instead
+        on_inclusion: VecDeque<(String, Vec<u8>)>, // Written by included extrinsics in turn
+        events_on_inclusion: Vec<Vec<u8>>,        // Emitted by the next included extrinsic
+        fail_next: Option<Vec<u8>>, // DispatchError the next included extrinsic fails with
+        best: u64,
+        blocks: HashMap<u64, Vec<Vec<u8>>>,
+        events: HashMap<u64, Vec<u8>>, // Encoded Vec<EventRecord> per block
+        submitted: Vec<Vec<u8>>,
+        requests: HashMap<String, usize>,
+        dropped: u32,  // Requests still to fail with a lost connection
+        rejected: u32, // Requests still to be answered with an error
+        reconnects: u32,
+        metadata: Option<Vec<u8>>, // Served instead of the test runtime's
+        pool_drops: u32,           // Watched submissions still to be dropped unincluded
+        rpc: HashMap<(String, Vec<u16>), Vec<u8>>, // Subtensor RPC answers by method and args
+        change_sets: Vec<serde_json::Value>, // Sent to the next storage subscription
+        pruned_before: u64,        // State of older blocks is gone, as on a non-archive node
+    }
+
+    fn block_hash(number: u64) -> H256 {
+        H256::from_low_u64_be(number + 1)
+    }
+
+    fn not_found(method: &str) -> ClientError {
+        ClientError::Call(ErrorObject::owned(
+            -32601,
+            format!("Method not found: {}", method),
+            None::<()>,
+        ))
+    }
+
+    fn events_key() -> String {
+        storage_key("System", "Events", &[])
+    }
+
+    // One EventRecord: ApplyExtrinsic(index) phase, the event with its encoded fields and
+    // no topics
+    pub fn event_record(index: u32, pallet: u8, variant: u8, fields: &[u8]) -> Vec<u8> {
+        let mut record = vec![0u8];
+        index.encode_to(&mut record);
+        record.extend_from_slice(&[pallet, variant]);
+        record.extend_from_slice(fields);
+        Vec::<H256>::new().encode_to(&mut record);
+        record
+    }
+
+    // DispatchInfo: weight (ref_time, proof_size), class Normal, pays fee Yes
+    fn dispatch_info() -> (Compact<u64>, Compact<u64>, u8, u8) {
+        (Compact(1_000_000), Compact(0), 0, 0)
+    }
+
+    impl MockChain {
+        pub fn new(best: u64) -> Arc<Self> {
+            let chain = Self::default();
+            chain.state().best = best;
+            Arc::new(chain)
+        }
+
+        fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+            self.state.lock().unwrap()
+        }
+
+        pub fn insert(&self, key: &str, value: impl Encode) {
+            self.state()
+                .storage
+                .insert(key.to_lowercase(), value.encode());
+        }
+
+        // A value as read at an earlier block, the way an archive node keeps it
+        pub fn insert_at(&self, number: u64, key: &str, value: impl Encode) {
+            self.state()
+                .history
+                .insert((number, key.to_lowercase()), value.encode());
+        }
+
+        // Stored once an extrinsic is included, e.g. the UID a registration assigns. Each
+        // inclusion stores the next one queued
+        pub fn insert_on_inclusion(&self, key: &str, value: impl Encode) {
+            self.state()
+                .on_inclusion
+                .push_back((key.to_lowercase(), value.encode()));
+        }
+
+        // An event the next included extrinsic emits besides ExtrinsicSuccess, its record
+        // built with event_record(0, ..)
+        pub fn emit_on_inclusion(&self, record: Vec<u8>) {
+            self.state().events_on_inclusion.push(record);
+        }
+
+        // The next included extrinsic fails with this encoded DispatchError
+        pub fn fail_next(&self, dispatch_error: Vec<u8>) {
+            self.state().fail_next = Some(dispatch_error);
+        }
+
+        pub fn submitted(&self) -> Vec<Vec<u8>> {
+            self.state().submitted.clone()
+        }
+
+        pub fn requests(&self, method: &str) -> usize {
+            self.state().requests.get(method).copied().unwrap_or(0)
+        }
+
+        // Fail the next requests as if the connection dropped
+        pub fn drop_connection(&self, requests: u32) {
+            self.state().dropped = requests;
+        }
+
+        // Answer the next requests with an error, as a node does for pruned state
+        pub fn reject(&self, requests: u32) {
+            self.state().rejected = requests;
+        }
+
+        // Keep only the state of this block and later ones
+        pub fn prune_before(&self, number: u64) {
+            self.state().pruned_before = number;
+        }
+
+        pub fn set_metadata(&self, metadata: Vec<u8>) {
+            self.state().metadata = Some(metadata);
+        }
+
+        // Queue one block's storage changes for the next storage subscription, which stays
+        // unsupported while none are queued
+        pub fn push_change_set(&self, changes: &[(&str, Option<Vec<u8>>)]) {
+            let changes: Vec<_> = changes
+                .iter()
+                .map(|(key, value)| {
+                    serde_json::json!([
+                        key,
+                        value
+                            .as_ref()
+                            .map(|value| format!("0x{}", hex::encode(value)))
+                    ])
+                })
+                .collect();
+            self.state()
+                .change_sets
+                .push(serde_json::json!({ "block": block_hash(0), "changes": changes }));
+        }
+
+        // Answer a Subtensor RPC called with these args, others stay method-not-found
+        pub fn serve_rpc(&self, method: &str, args: &[u16], response: Vec<u8>) {
+            self.state()
+                .rpc
+                .insert((method.to_string(), args.to_vec()), response);
+        }
+
+        // Drop the next watched submissions from the pool, as a congested node does
+        pub fn drop_from_pool(&self, submissions: u32) {
+            self.state().pool_drops = submissions;
+        }
+
+        pub fn reconnects(&self) -> u32 {
+            self.state().reconnects
+        }
+
+        pub fn best_hash(&self) -> H256 {
+            block_hash(self.state().best)
+        }
+
+        // Counts the request and fails it while the connection is down or rejecting
+        fn serve(&self, method: &str) -> Result<(), ClientError> {
+            let mut state = self.state();
+            *state.requests.entry(method.to_string()).or_default() += 1;
+            if state.dropped > 0 {
+                state.dropped -= 1;
+                return Err(ClientError::RestartNeeded(Arc::new(ClientError::Custom(
+                    "connection reset".to_string(),
+                ))));
+            }
+            if state.rejected > 0 {
+                state.rejected -= 1;
+                return Err(ClientError::Call(ErrorObject::owned(
+                    4003,
+                    "State already discarded",
+                    None::<()>,
+                )));
+            }
+            Ok(())
+        }
+
+        // Puts the extrinsic in a new best block with its events, returns that block's hash
+        fn include(&self, extrinsic: Vec<u8>) -> H256 {
+            let mut state = self.state();
+            state.best += 1;
+            let best = state.best;
+            state.blocks.insert(best, vec![extrinsic.clone()]);
+            state.submitted.push(extrinsic);
+            if let Some((key, value)) = state.on_inclusion.pop_front() {
+                state.storage.insert(key, value);
+            }
+
+            // System (pallet 0) ExtrinsicFailed (1) or ExtrinsicSuccess (0), as in the
+            // metadata this chain serves
+            let mut records = std::mem::take(&mut state.events_on_inclusion);
+            records.push(match state.fail_next.take() {
+                Some(error) => event_record(0, 0, 1, &[error, dispatch_info().encode()].concat()),
+                None => event_record(0, 0, 0, &dispatch_info().encode()),
+            });
+            let mut events = Compact(records.len() as u32).encode();
+            events.extend(records.concat());
+            state.events.insert(best, events);
+            block_hash(best)
+        }
+
+        fn block_number(&self, at: Option<H256>) -> Result<u64, ClientError> {
+            let best = self.state().best;
+            let Some(hash) = at else {
+                return Ok(best);
+            };
+            let number = hash.to_low_u64_be().wrapping_sub(1);
+            if number > best || block_hash(number) != hash {
+                return Err(ClientError::Custom(format!("Unknown block {:?}", hash)));
+            }
+            Ok(number)
+        }
+
+        fn value(&self, key: &str, at: Option<H256>) -> Result<Option<Vec<u8>>, ClientError> {
+            let key = key.to_lowercase();
+            if key == events_key() {
+                let number = self.block_number(at)?;
+                return Ok(self.state().events.get(&number).cloned());
+            }
+            if at.is_some() {
+                let number = self.block_number(at)?;
+                if number < self.state().pruned_before {
+                    return Err(ClientError::Call(ErrorObject::owned(
+                        4003,
+                        "State already discarded",
+                        None::<()>,
+                    )));
+                }
+                if let Some(value) = self.state().history.get(&(number, key.clone())) {
+                    return Ok(Some(value.clone()));
+                }
+            }
+            Ok(self.state().storage.get(&key).cloned())
+        }
+    }
+
+    #[async_trait]
+    impl ChainApi for MockChain {
+        async fn storage(
+            &self,
+            key: &str,
+            at: Option<H256>,
+        ) -> Result<Option<Vec<u8>>, ClientError> {
+            self.serve("storage")?;
+            self.value(key, at)
+        }
+
+        async fn storage_batch(
+            &self,
+            keys: &[String],
+            at: Option<H256>,
+        ) -> Result<Vec<Option<Vec<u8>>>, ClientError> {
+            self.serve("storage_batch")?;
+            keys.iter().map(|key| self.value(key, at)).collect()
+        }
+
+        async fn storage_keys(
+            &self,
+            prefix: &str,
+            count: u32,
+            start_key: Option<&str>,
+            _at: Option<H256>,
+        ) -> Result<Vec<String>, ClientError> {
+            self.serve("storage_keys")?;
+            let (prefix, start) = (
+                prefix.to_lowercase(),
+                start_key.unwrap_or_default().to_lowercase(),
+            );
+            Ok(self
+                .state()
+                .storage
+                .keys()
+                .filter(|key| key.starts_with(&prefix) && **key > start)
+                .take(count as usize)
+                .cloned()
+                .collect())
+        }
+
+        async fn block_hash(&self, number: Option<u64>) -> Result<Option<H256>, ClientError> {
+            self.serve("block_hash")?;
+            let best = self.state().best;
+            Ok(match number {
+                Some(number) if number > best => None,
+                Some(number) => Some(block_hash(number)),
+                None => Some(block_hash(best)),
+            })
+        }
+
+        async fn header(&self, hash: H256) -> Result<BlockHeader, ClientError> {
+            self.serve("header")?;
+            let number = self.block_number(Some(hash))?;
+            Ok(BlockHeader {
+                number,
+                parent_hash: Some(block_hash(number.saturating_sub(1))),
+            })
+        }
+
+        async fn block_extrinsics(&self, hash: H256) -> Result<Vec<Vec<u8>>, ClientError> {
+            self.serve("block_extrinsics")?;
+            let number = self.block_number(Some(hash))?;
+            Ok(self
+                .state()
+                .blocks
+                .get(&number)
+                .cloned()
+                .unwrap_or_default())
+        }
+
+        async fn runtime_version(&self) -> Result<RuntimeVersion, ClientError> {
+            self.serve("runtime_version")?;
+            Ok(RuntimeVersion {
+                spec_version: 1,
+                transaction_version: 1,
+            })
+        }
+
+        async fn metadata(&self) -> Result<Vec<u8>, ClientError> {
+            self.serve("metadata")?;
+            Ok(self
+                .state()
+                .metadata
+                .clone()
+                .unwrap_or_else(crate::metadata::runtime::metadata_bytes))
+        }
+
+        async fn runtime_call(
+            &self,
+            method: &str,
+            _data: &[u8],
+            _at: Option<H256>,
+        ) -> Result<Vec<u8>, ClientError> {
+            Err(not_found(method))
+        }
+
+        async fn subtensor_rpc(
+            &self,
+            method: &str,
+            args: &[u16],
+            _at: Option<H256>,
+        ) -> Result<Vec<u8>, ClientError> {
+            self.serve(method)?;
+            self.state()
+                .rpc
+                .get(&(method.to_string(), args.to_vec()))
+                .cloned()
+                .ok_or_else(|| not_found(method))
+        }
+
+        async fn dry_run(&self, _extrinsic: &[u8]) -> Result<Vec<u8>, ClientError> {
+            Err(not_found("system_dryRun"))
+        }
+
+        // Same fee for every extrinsic
+        async fn partial_fee(&self, _extrinsic: &[u8]) -> Result<u128, ClientError> {
+            self.serve("partial_fee")?;
+            Ok(125_000)
+        }
+
+        async fn submit(&self, extrinsic: &[u8]) -> Result<H256, ClientError> {
+            self.serve("submit")?;
+            let tx_hash = H256(sp_core::blake2_256(extrinsic));
+            self.include(extrinsic.to_vec());
+            Ok(tx_hash)
+        }
+
+        // A watched extrinsic is included right away and finalized with its block, unless
+        // it is one the pool drops
+        async fn submit_and_watch(
+            &self,
+            extrinsic: &[u8],
+        ) -> Result<Subscription<TxStatus>, ClientError> {
+            self.serve("submit_and_watch")?;
+            {
+                let mut state = self.state();
+                if state.pool_drops > 0 {
+                    state.pool_drops -= 1;
+                    state.submitted.push(extrinsic.to_vec());
+                    let statuses = vec![
+                        Ok(serde_json::json!("ready")),
+                        Ok(serde_json::json!("dropped")),
+                    ];
+                    return Ok(Subscription::new(stream::iter(statuses).boxed()));
+                }
+            }
+            let block_hash = self.include(extrinsic.to_vec());
+            let statuses = vec![
+                Ok(serde_json::json!("ready")),
+                Ok(serde_json::json!({ "inBlock": block_hash })),
+                Ok(serde_json::json!({ "finalized": block_hash })),
+            ];
+            Ok(Subscription::new(stream::iter(statuses).boxed()))
+        }
+
+        async fn subscribe_storage(
+            &self,
+            keys: &[String],
+        ) -> Result<Subscription<StorageChangeSet>, ClientError> {
+            self.serve("subscribe_storage")?;
+            let change_sets = std::mem::take(&mut self.state().change_sets);
+            if change_sets.is_empty() {
+                return Err(not_found(&format!(
+                    "state_subscribeStorage({})",
+                    keys.len()
+                )));
+            }
+            Ok(Subscription::new(
+                stream::iter(change_sets.into_iter().map(Ok)).boxed(),
+            ))
+        }
+
+        // The best block's header, then the subscription ends
+        async fn subscribe_heads(
+            &self,
+            _finalized: bool,
+        ) -> Result<Subscription<BlockHeader>, ClientError> {
+            self.serve("subscribe_heads")?;
+            let best = self.state().best;
+            let header = serde_json::json!({
+                "number": format!("0x{:x}", best),
+                "parentHash": block_hash(best.saturating_sub(1)),
+            });
+            Ok(Subscription::new(stream::iter(vec![Ok(header)]).boxed()))
+        }
+
+        async fn reconnect(&self) -> Result<()> {
+            self.state().reconnects += 1;
+            Ok(())
+        }
+    }
+}
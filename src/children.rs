@@ -0,0 +1,95 @@
+//! Child hotkeys: a parent hotkey lends its stake weight in a subnet to up to five children,
+//! each taking a proportion stored on chain as a fraction of u64::MAX
+use anyhow::{anyhow, Result};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::collections::HashSet;
+
+use crate::utils;
+
+pub const MAX_CHILDREN: usize = 5;
+
+// Proportions are typed with up to 9 decimals, like TAO amounts
+const PARTS: u128 = 1_000_000_000;
+
+// "hotkey:0.25" into the hotkey and its proportion in billionths
+pub fn parse_child(spec: &str) -> Result<(String, u64)> {
+    let (hotkey, proportion) = spec
+        .rsplit_once(':')
+        .ok_or_else(|| anyhow!("Invalid child {}, expected hotkey:proportion", spec))?;
+    let parts = utils::parse_tao(proportion)
+        .map_err(|_| anyhow!("Invalid proportion in {}, expected e.g. 0.25", spec))?;
+    if parts as u128 > PARTS {
+        return Err(anyhow!("Proportion in {} is above 1", spec));
+    }
+    Ok((hotkey.to_string(), parts))
+}
+
+// Chain proportion from billionths, rounded down so a full split never overflows u64::MAX
+pub fn to_proportion(parts: u64) -> u64 {
+    (parts as u128 * u64::MAX as u128 / PARTS) as u64
+}
+
+pub fn proportion_percent(proportion: u64) -> f64 {
+    proportion as f64 / u64::MAX as f64 * 100.0
+}
+
+// The checks set_children makes on chain, before a fee is paid to find out
+pub fn validate(parent: &AccountId32, children: &[(u64, AccountId32)]) -> Result<()> {
+    if children.len() > MAX_CHILDREN {
+        return Err(anyhow!(
+            "{} children given, a hotkey can have at most {}",
+            children.len(),
+            MAX_CHILDREN
+        ));
+    }
+    let mut seen = HashSet::new();
+    for (_, child) in children {
+        if child == parent {
+            return Err(anyhow!("A hotkey can't be its own child"));
+        }
+        if !seen.insert(child) {
+            return Err(anyhow!("Child {} is listed twice", child.to_ss58check()));
+        }
+    }
+    let total: u128 = children
+        .iter()
+        .map(|(proportion, _)| *proportion as u128)
+        .sum();
+    if total > u64::MAX as u128 {
+        return Err(anyhow!(
+            "Proportions add up to {:.2}%, more than 100%",
+            total as f64 / u64::MAX as f64 * 100.0
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_children_validation() {
+        let (hotkey, parts) = parse_child("5Child:0.25").unwrap();
+        assert_eq!((hotkey.as_str(), parts), ("5Child", 250_000_000));
+        assert!(parse_child("5Child").is_err());
+        assert!(parse_child("5Child:1.5").is_err());
+        assert!(parse_child("5Child:abc").is_err());
+
+        // Halves, thirds and a full split all fit
+        assert_eq!(to_proportion(1_000_000_000), u64::MAX);
+        assert!(to_proportion(500_000_000) as u128 * 2 <= u64::MAX as u128);
+        assert!(to_proportion(333_333_333) as u128 * 3 <= u64::MAX as u128);
+
+        let parent = AccountId32::new([1u8; 32]);
+        let child = |byte, parts| (to_proportion(parts), AccountId32::new([byte; 32]));
+        let halves = [child(2, 500_000_000), child(3, 500_000_000)];
+        assert!(validate(&parent, &halves).is_ok());
+        assert!(validate(&parent, &[]).is_ok());
+        assert!(validate(&parent, &[child(2, 600_000_000), child(3, 500_000_000)]).is_err());
+        assert!(validate(&parent, &[child(1, 100)]).is_err());
+        assert!(validate(&parent, &[child(2, 100), child(2, 100)]).is_err());
+        let many: Vec<_> = (2..8).map(|byte| child(byte, 100)).collect();
+        assert!(validate(&parent, &many).is_err());
+    }
+}
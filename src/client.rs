@@ -1,17 +1,37 @@
 use anyhow::{anyhow, Context, Result};
-use codec::{Decode, Encode};
-use jsonrpsee::{core::client::ClientT, rpc_params, ws_client::WsClientBuilder};
+use codec::{Compact, Decode, DecodeAll, Encode};
+use futures::stream::{self, Stream, StreamExt, TryStreamExt};
+use jsonrpsee::core::client::Error as ClientError;
 use primitive_types::{H256, U256};
 use serde::{Deserialize, Serialize};
-use sp_core::{
-    blake2_128,
-    crypto::{AccountId32, Ss58Codec},
-    sr25519::Pair as Sr25519Pair,
-    twox_128, Pair,
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::{
+    collections::{BTreeMap, HashMap},
+    future::Future,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+    time::{Duration, Instant},
 };
-use std::{str::FromStr, time::Duration};
-
+use tokio_util::sync::CancellationToken;
+
+use crate::backoff::Backoff;
+use crate::cache::StorageCache;
+use crate::capabilities::Capabilities;
+use crate::chain::{ChainApi, StorageChangeSet, Subscription, TxStatus, WsChain};
+use crate::commit_reveal::{self, WeightsCommit};
+use crate::constants;
+use crate::era::{self, Era};
+use crate::errors::{self, ErrorKind, RegistrationError};
+use crate::faucet::FaucetWork;
+use crate::key_utils::KeyPair;
+use crate::metadata::{EventRecord, Metadata};
+use crate::rpc_stats;
+use crate::transport;
 use crate::utils;
+use crate::warnings::{self, WarningCode};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct RegistrationData {
@@ -20,9 +40,11 @@ pub struct RegistrationData {
     pub coldkey: AccountId32,
     pub burn_amount: u64, // In RAO
     pub block_number: u64,
+    #[serde(default)]
+    pub fee: Option<u128>, // Estimated extrinsic fee in RAO, None when the node can't tell
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SubnetInfo {
     pub netuid: u16,
     pub difficulty: U256,
@@ -33,6 +55,8 @@ pub struct SubnetInfo {
     pub max_n: u16, // max neurons
     pub tempo: u16,
     pub burn: u64,          // Burned registration cost in RAO
+    pub min_burn: u64,      // Lower bound for the burn in RAO
+    pub max_burn: u64,      // Upper bound for the burn in RAO
     pub owner_ss58: String, // Owner as SS58 address
     pub emission_value: u64,
     pub rho: u16,
@@ -41,6 +65,10 @@ pub struct SubnetInfo {
     pub subnetwork_n: u16, // Current number of registered neurons
     pub blocks_since_epoch: u64,
     pub modality: u16,
+    pub registration_allowed: bool,
+    pub max_registrations_per_block: u16,
+    pub target_registrations_per_interval: u16,
+    pub registrations_this_interval: u16,
     // Legacy fields for backward compatibility
     pub network_modality: u16, // Same as modality
     pub network_connect: Vec<u16>,
@@ -72,50 +100,377 @@ pub struct NeuronInfo {
     pub pruning_score: u16,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct AxonInfo {
-    block: u64,
-    version: u32,
-    ip: u128,
-    port: u16,
-    ip_type: u8,
-    protocol: u8,
-    placeholder1: u8,
-    placeholder2: u8,
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Decode)]
+pub struct AxonInfo {
+    pub block: u64,
+    pub version: u32,
+    pub ip: u128,
+    pub port: u16,
+    pub ip_type: u8,
+    pub protocol: u8,
+    pub placeholder1: u8,
+    pub placeholder2: u8,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Decode)]
+pub struct PrometheusInfo {
+    pub block: u64,
+    pub version: u32,
+    pub ip: u128,
+    pub port: u16,
+    pub ip_type: u8,
+}
+
+// A subnet owner's on-chain identity, fields its identity version lacks left empty
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct SubnetIdentity {
+    pub name: String,
+    pub github: String,
+    pub contact: String,
+    pub url: String,
+    pub discord: String,
+    pub description: String,
+    pub logo_url: String,
+    pub additional: String,
+}
+
+// Identity storages from newest to oldest, with their version
+const SUBNET_IDENTITY_STORAGES: &[(&str, u8)] = &[
+    ("SubnetIdentitiesV3", 3),
+    ("SubnetIdentitiesV2", 2),
+    ("SubnetIdentities", 1),
+];
+
+impl SubnetIdentity {
+    // Version 1 holds name, github and contact. Version 2 adds url, discord, description and
+    // additional, version 3 a logo_url before additional. Every field is a byte string
+    fn decode_version(version: u8, bytes: &[u8]) -> Result<Self> {
+        let count = match version {
+            1 => 3,
+            2 => 7,
+            _ => 8,
+        };
+        let input = &mut &bytes[..];
+        let mut fields = Vec::with_capacity(count);
+        for _ in 0..count {
+            let field = Vec::<u8>::decode(input)
+                .map_err(|e| anyhow!("Failed to decode subnet identity: {:?}", e))?;
+            fields.push(String::from_utf8_lossy(&field).trim().to_string());
+        }
+        let mut fields = fields.into_iter();
+        let mut next = || fields.next().unwrap_or_default();
+
+        let mut identity = Self {
+            name: next(),
+            github: next(),
+            contact: next(),
+            ..Default::default()
+        };
+        if version >= 2 {
+            identity.url = next();
+            identity.discord = next();
+            identity.description = next();
+            if version >= 3 {
+                identity.logo_url = next();
+            }
+            identity.additional = next();
+        }
+        Ok(identity)
+    }
+}
+
+// A coldkey's on-chain identity, set with set_identity
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ChainIdentity {
+    pub name: String,
+    pub url: String,
+    pub github: String,
+    pub image: String,
+    pub discord: String,
+    pub description: String,
+    pub additional: String,
+}
+
+// Identity storages from newest to oldest, with their version
+const IDENTITY_STORAGES: &[(&str, u8)] = &[("IdentitiesV2", 2), ("Identities", 1)];
+
+impl ChainIdentity {
+    // Version 1 holds name, url, image, discord, description and additional, version 2 adds
+    // github after url. Every field is a byte string
+    fn decode_version(version: u8, bytes: &[u8]) -> Result<Self> {
+        let count = if version >= 2 { 7 } else { 6 };
+        let input = &mut &bytes[..];
+        let mut fields = Vec::with_capacity(count);
+        for _ in 0..count {
+            let field = Vec::<u8>::decode(input)
+                .map_err(|e| anyhow!("Failed to decode identity: {:?}", e))?;
+            fields.push(String::from_utf8_lossy(&field).trim().to_string());
+        }
+        let mut fields = fields.into_iter();
+        let mut next = || fields.next().unwrap_or_default();
+
+        Ok(Self {
+            name: next(),
+            url: next(),
+            github: if version >= 2 { next() } else { String::new() },
+            image: next(),
+            discord: next(),
+            description: next(),
+            additional: next(),
+        })
+    }
+
+    // Fields with the pallet's byte limit for each, in call order
+    pub fn fields(&self) -> [(&'static str, &str, usize); 7] {
+        [
+            ("name", &self.name, 256),
+            ("url", &self.url, 256),
+            ("github", &self.github, 256),
+            ("image", &self.image, 1024),
+            ("discord", &self.discord, 256),
+            ("description", &self.description, 1024),
+            ("additional", &self.additional, 1024),
+        ]
+    }
+
+    // The runtime rejects an identity with any field over its limit, check before paying a fee
+    pub fn validate(&self) -> Result<()> {
+        for (field, value, limit) in self.fields() {
+            if value.len() > limit {
+                return Err(RegistrationError::new(
+                    ErrorKind::Fatal,
+                    format!(
+                        "Identity {} is {} bytes, the limit is {}",
+                        field,
+                        value.len(),
+                        limit
+                    ),
+                )
+                .into());
+            }
+        }
+        Ok(())
+    }
+}
+
+// NeuronInfo exactly as returned by NeuronInfoRuntimeApi
+#[derive(Debug, Decode)]
+struct RawNeuronInfo {
+    hotkey: AccountId32,
+    coldkey: AccountId32,
+    uid: Compact<u16>,
+    netuid: Compact<u16>,
+    active: bool,
+    axon_info: AxonInfo,
+    prometheus_info: PrometheusInfo,
+    stake: Vec<(AccountId32, Compact<u64>)>,
+    rank: Compact<u16>,
+    emission: Compact<u64>,
+    incentive: Compact<u16>,
+    consensus: Compact<u16>,
+    trust: Compact<u16>,
+    validator_trust: Compact<u16>,
+    dividends: Compact<u16>,
+    last_update: Compact<u64>,
+    validator_permit: bool,
+    weights: Vec<(Compact<u16>, Compact<u16>)>,
+    bonds: Vec<(Compact<u16>, Compact<u16>)>,
+    pruning_score: Compact<u16>,
+}
+
+fn decode_compact_pairs(pairs: Vec<(Compact<u16>, Compact<u16>)>) -> Vec<(u16, u16)> {
+    pairs.into_iter().map(|(a, b)| (a.0, b.0)).collect()
+}
+
+impl From<RawNeuronInfo> for NeuronInfo {
+    fn from(raw: RawNeuronInfo) -> Self {
+        Self {
+            hotkey: raw.hotkey,
+            coldkey: raw.coldkey,
+            uid: raw.uid.0,
+            netuid: raw.netuid.0,
+            active: raw.active,
+            axon_info: raw.axon_info,
+            prometheus_info: raw.prometheus_info,
+            stake: raw.stake.into_iter().map(|(c, s)| (c, s.0)).collect(),
+            rank: raw.rank.0,
+            emission: raw.emission.0,
+            incentive: raw.incentive.0,
+            consensus: raw.consensus.0,
+            trust: raw.trust.0,
+            validator_trust: raw.validator_trust.0,
+            dividends: raw.dividends.0,
+            last_update: raw.last_update.0,
+            validator_permit: raw.validator_permit,
+            weights: decode_compact_pairs(raw.weights),
+            bonds: decode_compact_pairs(raw.bonds),
+            pruning_score: raw.pruning_score.0,
+        }
+    }
+}
+
+// Neuron without weights and bonds, one row of the metagraph
+#[derive(Debug, Clone, Serialize)]
+pub struct NeuronInfoLite {
+    pub hotkey: AccountId32,
+    pub coldkey: AccountId32,
+    pub uid: u16,
+    pub netuid: u16,
+    pub active: bool,
+    pub axon_info: AxonInfo,
+    pub stake: u64, // Total stake across coldkeys, in RAO
+    pub rank: u16,
+    pub emission: u64,
+    pub incentive: u16,
+    pub consensus: u16,
+    pub trust: u16,
+    pub validator_trust: u16,
+    pub dividends: u16,
+    pub last_update: u64,
+    pub validator_permit: bool,
+    pub pruning_score: u16,
+}
+
+// NeuronInfoLite exactly as returned by NeuronInfoRuntimeApi
+#[derive(Debug, Decode)]
+struct RawNeuronInfoLite {
+    hotkey: AccountId32,
+    coldkey: AccountId32,
+    uid: Compact<u16>,
+    netuid: Compact<u16>,
+    active: bool,
+    axon_info: AxonInfo,
+    #[allow(dead_code)]
+    prometheus_info: PrometheusInfo,
+    stake: Vec<(AccountId32, Compact<u64>)>,
+    rank: Compact<u16>,
+    emission: Compact<u64>,
+    incentive: Compact<u16>,
+    consensus: Compact<u16>,
+    trust: Compact<u16>,
+    validator_trust: Compact<u16>,
+    dividends: Compact<u16>,
+    last_update: Compact<u64>,
+    validator_permit: bool,
+    pruning_score: Compact<u16>,
+}
+
+// A hotkey that takes nominations, from the delegateInfo RPC
+#[derive(Debug, Clone, Serialize)]
+pub struct DelegateInfo {
+    pub hotkey: AccountId32,
+    pub owner: AccountId32,
+    pub take: u16, // Fraction of u16::MAX kept by the delegate
+    pub nominators: Vec<(AccountId32, u64)>, // Stake per nominator, summed over subnets, in RAO
+    pub registrations: Vec<u16>,
+    pub validator_permits: Vec<u16>,
+    pub return_per_1000: u64,
+    pub total_daily_return: u64,
+}
+
+impl DelegateInfo {
+    pub fn total_stake(&self) -> u64 {
+        self.nominators.iter().map(|(_, stake)| stake).sum()
+    }
+}
+
+// Stake of one nominator on each subnet
+type SubnetStakes = Vec<(Compact<u16>, Compact<u64>)>;
+
+// DelegateInfo as encoded since dynamic TAO, nominator stake listed per subnet
+#[derive(Debug, Decode)]
+struct RawDelegateInfo {
+    delegate_ss58: AccountId32,
+    take: Compact<u16>,
+    nominators: Vec<(AccountId32, SubnetStakes)>,
+    owner_ss58: AccountId32,
+    registrations: Vec<Compact<u16>>,
+    validator_permits: Vec<Compact<u16>>,
+    return_per_1000: Compact<u64>,
+    total_daily_return: Compact<u64>,
+}
+
+// DelegateInfo as encoded before dynamic TAO, one stake per nominator
+#[derive(Debug, Decode)]
+struct RawLegacyDelegateInfo {
+    delegate_ss58: AccountId32,
+    take: Compact<u16>,
+    nominators: Vec<(AccountId32, Compact<u64>)>,
+    owner_ss58: AccountId32,
+    registrations: Vec<Compact<u16>>,
+    validator_permits: Vec<Compact<u16>>,
+    return_per_1000: Compact<u64>,
+    total_daily_return: Compact<u64>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-struct PrometheusInfo {
-    block: u64,
-    version: u32,
-    ip: u128,
-    port: u16,
-    ip_type: u8,
+impl From<RawDelegateInfo> for DelegateInfo {
+    fn from(raw: RawDelegateInfo) -> Self {
+        Self {
+            hotkey: raw.delegate_ss58,
+            owner: raw.owner_ss58,
+            take: raw.take.0,
+            nominators: raw
+                .nominators
+                .into_iter()
+                .map(|(nominator, stakes)| (nominator, stakes.iter().map(|(_, s)| s.0).sum()))
+                .collect(),
+            registrations: raw.registrations.into_iter().map(|n| n.0).collect(),
+            validator_permits: raw.validator_permits.into_iter().map(|n| n.0).collect(),
+            return_per_1000: raw.return_per_1000.0,
+            total_daily_return: raw.total_daily_return.0,
+        }
+    }
 }
 
-impl Default for AxonInfo {
-    fn default() -> Self {
+impl From<RawLegacyDelegateInfo> for DelegateInfo {
+    fn from(raw: RawLegacyDelegateInfo) -> Self {
         Self {
-            block: 0,
-            version: 0,
-            ip: 0,
-            port: 0,
-            ip_type: 0,
-            protocol: 0,
-            placeholder1: 0,
-            placeholder2: 0,
+            hotkey: raw.delegate_ss58,
+            owner: raw.owner_ss58,
+            take: raw.take.0,
+            nominators: raw
+                .nominators
+                .into_iter()
+                .map(|(nominator, stake)| (nominator, stake.0))
+                .collect(),
+            registrations: raw.registrations.into_iter().map(|n| n.0).collect(),
+            validator_permits: raw.validator_permits.into_iter().map(|n| n.0).collect(),
+            return_per_1000: raw.return_per_1000.0,
+            total_daily_return: raw.total_daily_return.0,
         }
     }
 }
 
-impl Default for PrometheusInfo {
-    fn default() -> Self {
+// Delegates in either encoding, whichever consumes the whole response
+fn decode_delegates(bytes: &[u8]) -> Result<Vec<DelegateInfo>> {
+    if let Ok(delegates) = Vec::<RawDelegateInfo>::decode_all(&mut &bytes[..]) {
+        return Ok(delegates.into_iter().map(DelegateInfo::from).collect());
+    }
+    Vec::<RawLegacyDelegateInfo>::decode_all(&mut &bytes[..])
+        .map(|delegates| delegates.into_iter().map(DelegateInfo::from).collect())
+        .map_err(|e| anyhow!("Failed to decode delegates: {:?}", e))
+}
+
+impl From<RawNeuronInfoLite> for NeuronInfoLite {
+    fn from(raw: RawNeuronInfoLite) -> Self {
         Self {
-            block: 0,
-            version: 0,
-            ip: 0,
-            port: 0,
-            ip_type: 0,
+            hotkey: raw.hotkey,
+            coldkey: raw.coldkey,
+            uid: raw.uid.0,
+            netuid: raw.netuid.0,
+            active: raw.active,
+            axon_info: raw.axon_info,
+            stake: raw.stake.iter().map(|(_, s)| s.0).sum(),
+            rank: raw.rank.0,
+            emission: raw.emission.0,
+            incentive: raw.incentive.0,
+            consensus: raw.consensus.0,
+            trust: raw.trust.0,
+            validator_trust: raw.validator_trust.0,
+            dividends: raw.dividends.0,
+            last_update: raw.last_update.0,
+            validator_permit: raw.validator_permit,
+            pruning_score: raw.pruning_score.0,
         }
     }
 }
@@ -123,8 +478,11 @@ impl Default for PrometheusInfo {
 #[derive(Debug, Decode)]
 struct AccountInfo {
     nonce: u32,
+    #[allow(dead_code)]
     consumers: u32,
+    #[allow(dead_code)]
     providers: u32,
+    #[allow(dead_code)]
     sufficients: u32,
     data: AccountData,
 }
@@ -139,684 +497,2526 @@ struct AccountData {
     flags: u128,  // ExtraFlags - additional account metadata
 }
 
-pub struct BittensorClient {
-    client: jsonrpsee::ws_client::WsClient,
-    endpoint: String,
-}
-
-impl BittensorClient {
-    pub async fn new(endpoint: String) -> Result<Self> {
-        println!("🔗 Connecting to Bittensor network: {}", endpoint);
-
-        let client = WsClientBuilder::default()
-            .connection_timeout(Duration::from_secs(30))
-            .request_timeout(Duration::from_secs(60))
-            .build(&endpoint)
-            .await
-            .context("Failed to connect to Bittensor RPC endpoint")?;
-
-        println!("✅ Connected to Bittensor network");
-
-        Ok(Self { client, endpoint })
+impl SubnetInfo {
+    // Subtensor rejects registrations once an interval holds three times the target
+    pub fn registration_interval_limit(&self) -> u16 {
+        self.target_registrations_per_interval.saturating_mul(3)
     }
 
-    // Getting subnet information
-    pub async fn get_subnet_info(&self, netuid: u16, show_info: bool) -> Result<SubnetInfo> {
-        if show_info {
-            println!(
-                "🔍 Fetching subnet {} information from blockchain...",
-                netuid
-            );
+    pub fn registration_window(&self) -> RegistrationWindow {
+        if !self.registration_allowed {
+            return RegistrationWindow::Disabled;
+        }
+        let limit = self.registration_interval_limit();
+        if limit > 0 && self.registrations_this_interval >= limit {
+            return RegistrationWindow::IntervalFull {
+                registrations: self.registrations_this_interval,
+                limit,
+            };
         }
+        RegistrationWindow::Open
+    }
 
-        // Debug: Let's see what the storage key looks like
-        let storage_key: String = self.encode_bittensor_storage_key("SubnetworkN", &[netuid]);
-        //println!("🐛 DEBUG: Storage key for SubnetworkN[{}]: {}", netuid, storage_key);
+    // Reject burn amounts the chain would refuse for this subnet
+    pub fn check_burn_bounds(&self, burn_amount: u64) -> Result<()> {
+        if burn_amount < self.min_burn {
+            return Err(anyhow!(
+                "Burn amount {} RAO is below the subnet {} minimum of {} RAO",
+                burn_amount,
+                self.netuid,
+                self.min_burn
+            ));
+        }
+        if self.max_burn > 0 && burn_amount > self.max_burn {
+            return Err(anyhow!(
+                "Burn amount {} RAO is above the subnet {} maximum of {} RAO",
+                burn_amount,
+                self.netuid,
+                self.max_burn
+            ));
+        }
+        Ok(())
+    }
+}
 
-        // Try to get network parameters. If any core parameter doesn't exist, subnet doesn't exist.
-        // Start with SubnetworkN which should exist for any active subnet
-        let subnetwork_n_raw = self.get_bittensor_storage("SubnetworkN", &[netuid]).await?;
-        //println!("🐛 DEBUG: Raw storage result: {:?}", subnetwork_n_raw);
+// Whether the chain would accept a registration on a subnet right now
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegistrationWindow {
+    Open,
+    Disabled,
+    IntervalFull { registrations: u16, limit: u16 },
+}
 
-        if subnetwork_n_raw.is_none() {
-            // Let's also try to get the total subnet count to see if we can get any storage at all
-            let total_networks_key = self.encode_bittensor_storage_key("TotalNetworks", &[]);
-            //println!("🐛 DEBUG: Trying TotalNetworks storage key: {}", total_networks_key);
-            let total_networks = self.get_bittensor_storage("TotalNetworks", &[]).await?;
-            //println!("🐛 DEBUG: TotalNetworks result: {:?}", total_networks);
+// Burn parameters that drive registration cost adjustments
+#[derive(Debug, Clone, Default)]
+pub struct BurnSchedule {
+    pub burn: u64,     // In RAO
+    pub min_burn: u64, // In RAO
+    pub max_burn: u64, // In RAO
+    pub adjustment_interval: u16,
+    pub adjustment_alpha: u64, // Weight kept by the old burn, as a fraction of u64::MAX
+    pub last_adjustment_block: u64,
+    pub target_registrations_per_interval: u16,
+    pub registrations_this_interval: u16,
+}
 
-            if let Some(total_bytes) = total_networks {
-                let total = u16::from_le_bytes([total_bytes[0], total_bytes[1]]);
-                return Err(anyhow!(
-                    "Network {} does not exist. Total networks on chain: {}. Try checking which specific subnet IDs are active.",
-                    netuid, total
-                ));
-            }
+impl BurnSchedule {
+    pub fn next_adjustment_block(&self) -> u64 {
+        self.last_adjustment_block + self.adjustment_interval.max(1) as u64
+    }
 
-            return Err(anyhow!("Network {} does not exist", netuid));
+    // Burn after the next adjustment if no one else registers before it, following
+    // Subtensor's upgraded_burn (burned registrations only, PoW is off on mainnet):
+    //   adjusted = burn * (registrations + target) / (2 * target)
+    //   next = alpha * burn + (1 - alpha) * adjusted, clamped to [min_burn, max_burn]
+    pub fn projected_burn(&self) -> u64 {
+        if self.target_registrations_per_interval == 0 {
+            return self.burn;
         }
 
-        let subnetwork_n = u16::from_le_bytes([
-            subnetwork_n_raw.as_ref().unwrap()[0],
-            subnetwork_n_raw.as_ref().unwrap()[1],
-        ]);
+        let burn = self.burn as f64;
+        let target = self.target_registrations_per_interval as f64;
+        let adjusted = burn * (self.registrations_this_interval as f64 + target) / (2.0 * target);
+        let alpha = self.adjustment_alpha as f64 / u64::MAX as f64;
+        let next = (alpha * burn + (1.0 - alpha) * adjusted) as u64;
 
-        if subnetwork_n == 0 {
-            return Err(anyhow!("Network {} does not exist (has 0 neurons)", netuid));
+        if self.max_burn > 0 && next >= self.max_burn {
+            self.max_burn
+        } else if next <= self.min_burn {
+            self.min_burn
+        } else {
+            next
         }
+    }
+}
 
-        // Get network parameters using correct Bittensor storage keys
-        let difficulty = self.get_bittensor_u256("Difficulty", &[netuid]).await?;
-        let tempo = self.get_bittensor_u16("Tempo", &[netuid]).await?;
-        let immunity_period = self.get_bittensor_u16("ImmunityPeriod", &[netuid]).await?;
-        let min_allowed_weights = self
-            .get_bittensor_u16("MinAllowedWeights", &[netuid])
-            .await?;
-        let max_weight_limit = self.get_bittensor_u16("MaxWeightsLimit", &[netuid]).await?;
-        let max_allowed_validators = self
-            .get_bittensor_u16("MaxAllowedValidators", &[netuid])
-            .await?;
-        let max_n = self.get_bittensor_u16("MaxAllowedUids", &[netuid]).await?;
-        let burn = self.get_bittensor_u64("Burn", &[netuid]).await?;
-        let owner_account = self.get_bittensor_account("SubnetOwner", &[netuid]).await?;
-        let owner_ss58 = owner_account.to_ss58check();
-        let modality = self.get_bittensor_u16("NetworkModality", &[netuid]).await?;
-        let emission_value = self.get_bittensor_u64("EmissionValues", &[netuid]).await?;
-        let rho = self.get_bittensor_u16("Rho", &[netuid]).await?;
-        let kappa = self.get_bittensor_u16("Kappa", &[netuid]).await?;
-        let scaling_law_power = self.get_bittensor_u16("ScalingLawPower", &[netuid]).await?;
-        let blocks_since_epoch = self
-            .get_bittensor_u64("BlocksSinceLastStep", &[netuid])
-            .await?;
-
-        let current_block = self.get_current_block().await?;
-
-        if show_info {
-            println!("📋 Subnet {} info retrieved:", netuid);
-            println!("   Difficulty: {}", difficulty);
-            println!("   Tempo: {}", tempo);
-            println!("   Immunity period: {}", immunity_period);
-            println!("   Min allowed weights: {}", min_allowed_weights);
-            println!("   Registration burn: {} RAO", burn);
-            println!("   Registered neurons: {}", subnetwork_n);
-            println!("   Current block: {}", current_block);
-            println!("   Owner: {}", owner_ss58);
-            println!("🐛 DEBUG: Full owner address: {}", owner_ss58);
+// A subnet's dTAO pool: TAO and alpha reserves of its AMM, and the alpha held outside it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SubnetPool {
+    pub netuid: u16,
+    pub tao_in: u64,    // SubnetTAO, TAO reserve in RAO
+    pub alpha_in: u64,  // SubnetAlphaIn, alpha reserve in the pool
+    pub alpha_out: u64, // SubnetAlphaOut, alpha staked outside the pool
+}
 
-            // Debug: Test account info with the subnet owner (known to exist)
-            println!("🐛 DEBUG: Testing account info with subnet owner...");
-            if let Err(e) = self.debug_account_info(&owner_account).await {
-                println!("🐛 DEBUG: Account info test failed: {}", e);
-            }
+impl SubnetPool {
+    // TAO per alpha. The root subnet has no pool and stakes TAO one to one
+    pub fn price(&self) -> f64 {
+        if self.netuid == 0 {
+            1.0
+        } else if self.alpha_in == 0 {
+            0.0
+        } else {
+            self.tao_in as f64 / self.alpha_in as f64
         }
-
-        Ok(SubnetInfo {
-            netuid,
-            difficulty,
-            immunity_period,
-            min_allowed_weights,
-            max_weight_limit,
-            max_allowed_validators,
-            max_n,
-            tempo,
-            burn,
-            owner_ss58,
-            emission_value,
-            rho,
-            kappa,
-            scaling_law_power,
-            subnetwork_n,
-            blocks_since_epoch,
-            modality,
-            // Legacy fields for backward compatibility
-            network_modality: modality,
-            network_connect: vec![],
-            max_allowed_uids: max_n,
-            registered_neurons: subnetwork_n,
-        })
     }
 
-    // Bittensor-specific storage key generation
-    fn encode_bittensor_storage_key(&self, storage_name: &str, keys: &[u16]) -> String {
-        use sp_core::twox_128;
-
-        // Bittensor uses "SubtensorModule" as the pallet name
-        let pallet_hash = twox_128(b"SubtensorModule");
-        let storage_hash = twox_128(storage_name.as_bytes());
-
-        let mut final_key = Vec::new();
-        final_key.extend_from_slice(&pallet_hash);
-        final_key.extend_from_slice(&storage_hash);
-
-        // For map storage items with Identity hasher, use the key directly (no hashing)
-        // SubnetworkN uses Identity hasher according to the source code
-        if !keys.is_empty() {
-            for &key in keys {
-                // NetUid is u16, encode as little-endian bytes
-                final_key.extend_from_slice(&key.to_le_bytes());
-            }
+    // Alpha the pool gives for tao RAO, constant product with no fee, so including slippage
+    pub fn alpha_for_tao(&self, tao: u64) -> u64 {
+        if self.netuid == 0 {
+            return tao;
         }
-
-        format!("0x{}", hex::encode(final_key))
+        let tao_in = self.tao_in as u128;
+        if tao_in + tao as u128 == 0 {
+            return 0;
+        }
+        (self.alpha_in as u128 * tao as u128 / (tao_in + tao as u128)) as u64
     }
 
-    // Get raw storage data from Bittensor
-    async fn get_bittensor_storage(
-        &self,
-        storage_name: &str,
-        keys: &[u16],
-    ) -> Result<Option<Vec<u8>>> {
-        let storage_key = self.encode_bittensor_storage_key(storage_name, keys);
-
-        let result: Option<String> = self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
-            .context(format!(
-                "Failed to get {} from SubtensorModule",
-                storage_name
-            ))?;
-
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data in storage")?;
-            Ok(Some(bytes))
-        } else {
-            Ok(None)
+    // TAO in RAO the pool gives for alpha, the reverse swap
+    pub fn tao_for_alpha(&self, alpha: u64) -> u64 {
+        if self.netuid == 0 {
+            return alpha;
+        }
+        let alpha_in = self.alpha_in as u128;
+        if alpha_in + alpha as u128 == 0 {
+            return 0;
         }
+        (self.tao_in as u128 * alpha as u128 / (alpha_in + alpha as u128)) as u64
     }
 
-    // Get and decode storage data from Bittensor
-    async fn get_bittensor_storage_decoded<T>(&self, storage_name: &str, keys: &[u16]) -> Result<T>
-    where
-        T: codec::Decode,
-    {
-        if let Some(bytes) = self.get_bittensor_storage(storage_name, keys).await? {
-            let value = T::decode(&mut &bytes[..])
-                .map_err(|e| anyhow!("Failed to decode {}: {:?}", storage_name, e))?;
-            Ok(value)
+    // Alpha for tao RAO at the spot price, and what the swap gives
+    pub fn quote_stake(&self, tao: u64) -> (u64, u64) {
+        let spot = if self.netuid == 0 {
+            tao
+        } else if self.price() > 0.0 {
+            (tao as f64 / self.price()) as u64
         } else {
-            Err(anyhow!("Storage key not found: {}", storage_name))
-        }
+            0
+        };
+        (spot, self.alpha_for_tao(tao))
     }
 
-    // Specialized getters for different types
-    async fn get_bittensor_u16(&self, storage_name: &str, keys: &[u16]) -> Result<u16> {
-        self.get_bittensor_storage_decoded(storage_name, keys)
-            .await
-            .or_else(|_| Ok(0u16))
+    // TAO in RAO for alpha at the spot price, and what the swap gives
+    pub fn quote_unstake(&self, alpha: u64) -> (u64, u64) {
+        let spot = if self.netuid == 0 {
+            alpha
+        } else {
+            (alpha as f64 * self.price()) as u64
+        };
+        (spot, self.tao_for_alpha(alpha))
     }
 
-    async fn get_bittensor_u64(&self, storage_name: &str, keys: &[u16]) -> Result<u64> {
-        self.get_bittensor_storage_decoded(storage_name, keys)
-            .await
-            .or_else(|_| Ok(0u64))
+    // Value of the staked alpha at the current price, in RAO
+    pub fn alpha_out_value(&self) -> u64 {
+        (self.alpha_out as f64 * self.price()) as u64
     }
+}
 
-    async fn get_bittensor_u256(&self, storage_name: &str, keys: &[u16]) -> Result<U256> {
-        self.get_bittensor_storage_decoded(storage_name, keys)
-            .await
-            .or_else(|_| Ok(U256::zero()))
+// Alpha moved from one hotkey and subnet to another, for move_stake
+#[derive(Debug, Clone)]
+pub struct StakeMove {
+    pub origin_hotkey: AccountId32,
+    pub destination_hotkey: AccountId32,
+    pub origin_netuid: u16,
+    pub destination_netuid: u16,
+    pub amount: u64, // Alpha on the origin subnet
+}
+
+// Alpha moved across subnets: the TAO the origin pool gives for it, then the destination
+// alpha at the spot prices and what its pool gives for that TAO
+pub fn quote_move(origin: &SubnetPool, destination: &SubnetPool, alpha: u64) -> (u64, u64, u64) {
+    let spot = if destination.price() > 0.0 {
+        (alpha as f64 * origin.price() / destination.price()) as u64
+    } else {
+        0
+    };
+    let tao = origin.tao_for_alpha(alpha);
+    (tao, spot, destination.alpha_for_tao(tao))
+}
+
+// Percentage by which a swap returns less than the spot price promised
+pub fn slippage_percent(spot: u64, received: u64) -> f64 {
+    if spot == 0 {
+        return 0.0;
     }
+    spot.saturating_sub(received) as f64 / spot as f64 * 100.0
+}
 
-    async fn get_bittensor_account(&self, storage_name: &str, keys: &[u16]) -> Result<AccountId32> {
-        self.get_bittensor_storage_decoded(storage_name, keys)
-            .await
-            .or_else(|_| Ok(AccountId32::new([0u8; 32])))
+// Earliest block at which a registration for a hotkey can go through
+#[derive(Debug, Clone)]
+pub struct RegistrationCooldown {
+    pub current_block: u64,
+    pub ready_at_block: u64,
+    pub reason: Option<String>,
+}
+
+impl RegistrationCooldown {
+    pub fn blocks_remaining(&self) -> u64 {
+        self.ready_at_block.saturating_sub(self.current_block)
     }
+}
 
-    // Specialized method for account-based storage keys
-    async fn get_bittensor_storage_with_account(
-        &self,
-        storage_name: &str,
-        netuid: u16,
-        account: &AccountId32,
-    ) -> Result<Option<Vec<u8>>> {
-        use sp_core::{blake2_256, twox_128};
+// Block header as delivered by chain_getHeader / chain_subscribeNewHeads
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockHeader {
+    #[serde(deserialize_with = "deserialize_block_number")]
+    pub number: u64,
+    #[serde(default, rename = "parentHash")]
+    pub parent_hash: Option<H256>,
+}
 
-        let pallet_hash = twox_128(b"SubtensorModule");
-        let storage_hash = twox_128(storage_name.as_bytes());
+#[derive(Debug, Clone, Copy)]
+pub struct CommitRevealParams {
+    pub enabled: bool,
+    pub version_key: u64,
+    pub tempo: u16,
+    pub reveal_period: u64, // Epochs between a commit and its reveal
+}
 
-        let mut final_key = Vec::new();
-        final_key.extend_from_slice(&pallet_hash);
-        final_key.extend_from_slice(&storage_hash);
+// What the events of its block say about an included extrinsic
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExtrinsicOutcome {
+    Success,
+    Failed(String), // The dispatch error, described
+}
 
-        // Create the composite key for double map (netuid, account)
-        let mut map_key = Vec::new();
-        map_key.extend_from_slice(&netuid.to_le_bytes());
-        map_key.extend_from_slice(account.as_ref());
+// A registration as its block's events report it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RegistrationEvents {
+    pub outcome: Option<ExtrinsicOutcome>,
+    pub uid: Option<u16>, // From NeuronRegistered for the hotkey
+}
 
-        let key_hash = blake2_256(&map_key);
-        final_key.extend_from_slice(&key_hash);
+// Where a submitted extrinsic landed. State read at block_hash already reflects it
+#[derive(Debug, Clone, Copy)]
+pub struct Inclusion {
+    pub tx_hash: H256,
+    pub block_hash: H256,
+}
 
-        let storage_key = format!("0x{}", hex::encode(final_key));
+fn deserialize_block_number<'de, D>(deserializer: D) -> std::result::Result<u64, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let hex_number = String::deserialize(deserializer)?;
+    u64::from_str_radix(hex_number.trim_start_matches("0x"), 16).map_err(serde::de::Error::custom)
+}
 
-        let result: Option<String> = self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
-            .context(format!(
-                "Failed to get {} from SubtensorModule",
-                storage_name
-            ))?;
+// Keys fetched per state_getKeysPaged call
+const STORAGE_KEYS_PAGE: u32 = 1000;
 
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data in storage")?;
-            Ok(Some(bytes))
-        } else {
-            Ok(None)
-        }
-    }
+// Reconnects tried for one request before its connection error is returned
+const RPC_RECONNECT_ATTEMPTS: u32 = 5;
 
-    // Helper method to encode storage keys (legacy - keep for compatibility)
-    fn encode_storage_key(&self, _module: &str, storage: &str, keys: &[u16]) -> Result<String> {
-        // Use the new Bittensor-specific method
-        Ok(self.encode_bittensor_storage_key(storage, keys))
-    }
+// Neuron requests in flight while streaming a subnet
+const NEURON_STREAM_WINDOW: usize = 8;
 
-    // Generic storage value getter
-    async fn get_storage_value<T>(&self, module: &str, storage: &str, keys: &[u16]) -> Result<T>
-    where
-        T: codec::Decode + Default,
-    {
-        let storage_key = self.encode_storage_key(module, storage, keys)?;
+// Subnets per state_queryStorageAt when fetching many subnets' info, and such queries in flight
+const SUBNET_INFO_CHUNK: usize = 16;
+const SUBNET_INFO_WINDOW: usize = 4;
 
-        let result: Option<String> = self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
-            .context(format!("Failed to get {} from {}", storage, module))?;
+// Account state needed to sign an extrinsic without further RPC round trips
+#[derive(Debug, Clone)]
+pub struct SigningContext {
+    pub nonce: u64,
+    pub block_number: u64,
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    pub genesis_hash: H256,
+    pub era: Era,
+    pub era_block_hash: H256, // Genesis hash for immortal transactions
+    pub tip: u64,             // RAO paid to the block author on top of the fee, raises priority
+}
 
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data in storage")?;
-            let value = T::decode(&mut &bytes[..])
-                .map_err(|_| anyhow!("Failed to decode storage value"))?;
-            Ok(value)
-        } else {
-            Ok(T::default())
-        }
-    }
+// Re-signing with a higher tip at the same nonce when a transaction is dropped from the pool
+// or sits there without being included, for registrations competing in hot blocks
+#[derive(Debug, Clone, Copy)]
+pub struct TipEscalation {
+    pub step: u64,         // RAO added to the tip on every resubmission
+    pub max_tip: u64,      // RAO, the tip never goes beyond it
+    pub stuck_blocks: u64, // Blocks spent ready or future in the pool before resubmitting
+}
 
-    // Raw storage getter for custom decoding
-    async fn get_storage_raw(&self, module: &str, storage: &str, keys: &[u16]) -> Result<Vec<u8>> {
-        let storage_key = self.encode_storage_key(module, storage, keys)?;
+// Sequential nonces for extrinsics signed by the same account while earlier ones are still
+// pending, so concurrent submitters never reuse a nonce
+#[derive(Debug, Default)]
+pub struct NonceManager {
+    accounts: Mutex<HashMap<AccountId32, AccountNonces>>,
+}
 
-        let result: Option<String> = self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
-            .context(format!("Failed to get {} from {}", storage, module))?;
+#[derive(Debug, Default)]
+struct AccountNonces {
+    next: u64,
+    pending: BTreeMap<u64, Option<H256>>, // Reserved nonces, with the hash once submitted
+}
 
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data in storage")?;
-            Ok(bytes)
-        } else {
-            Err(anyhow!("Storage key not found"))
+impl NonceManager {
+    // Next unused nonce. The on-chain nonce seeds an account the first time it is seen and
+    // clears pending nonces it has overtaken, i.e. extrinsics that made it into a block
+    pub fn reserve(&self, account: &AccountId32, on_chain: u64) -> u64 {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        let nonces = accounts
+            .entry(account.clone())
+            .or_insert_with(|| AccountNonces {
+                next: on_chain,
+                ..Default::default()
+            });
+        nonces.pending.retain(|nonce, _| *nonce >= on_chain);
+        nonces.next = nonces.next.max(on_chain);
+
+        let nonce = nonces.next;
+        nonces.next += 1;
+        nonces.pending.insert(nonce, None);
+        nonce
+    }
+
+    // The pool accepted the extrinsic using this nonce
+    pub fn submitted(&self, account: &AccountId32, nonce: u64, tx_hash: H256) {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(pending) = accounts
+            .get_mut(account)
+            .and_then(|nonces| nonces.pending.get_mut(&nonce))
+        {
+            *pending = Some(tx_hash);
         }
     }
 
-    // Checking neuron registration
-    pub async fn check_registration(
-        &self,
-        netuid: u16,
-        hotkey: &AccountId32,
-    ) -> Result<Option<NeuronInfo>> {
-        println!("🔍 Checking registration status for hotkey: {}", hotkey);
+    // The extrinsic never reached the pool, its nonce is free again if nothing came after it
+    pub fn release(&self, account: &AccountId32, nonce: u64) {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(nonces) = accounts.get_mut(account) {
+            nonces.pending.remove(&nonce);
+            if nonces.next == nonce + 1 {
+                nonces.next = nonce;
+            }
+        }
+    }
 
-        // Get UID for hotkey using Bittensor storage
-        let uid_data = self
-            .get_bittensor_storage_with_account("Uids", netuid, hotkey)
-            .await?;
+    // Forget what we handed out, the next reservation starts over from the chain
+    pub fn resync(&self, account: &AccountId32) {
+        let mut accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        accounts.remove(account);
+    }
 
-        let uid = match uid_data {
-            Some(bytes) if bytes.len() >= 2 => u16::from_le_bytes([bytes[0], bytes[1]]),
-            _ => {
-                println!("❌ Hotkey not registered in subnet {}", netuid);
-                return Ok(None);
-            }
-        };
+    pub fn pending(&self, account: &AccountId32) -> usize {
+        let accounts = self.accounts.lock().unwrap_or_else(|e| e.into_inner());
+        accounts
+            .get(account)
+            .map_or(0, |nonces| nonces.pending.len())
+    }
+}
 
-        // Get neuron info using UID - this requires a different storage key format
-        let neuron_data = self
-            .get_bittensor_storage("Neurons", &[netuid, uid])
-            .await?;
+// The pool already holds or has included an extrinsic with this nonce
+fn is_outdated_nonce(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    message.contains("Transaction is outdated") || message.contains("Priority is too low")
+}
 
-        match neuron_data {
-            Some(bytes) => {
-                // For now, create a simplified neuron info since full decoding is complex
-                // In a real implementation, you'd need to properly decode the neuron struct
-                let neuron_info = NeuronInfo {
-                    hotkey: hotkey.clone(),
-                    coldkey: AccountId32::new([0u8; 32]), // Would need proper decoding
-                    uid,
-                    netuid,
-                    active: true,
-                    axon_info: AxonInfo::default(),
-                    prometheus_info: PrometheusInfo::default(),
-                    stake: vec![],
-                    rank: 0,
-                    emission: 0,
-                    incentive: 0,
-                    consensus: 0,
-                    trust: 0,
-                    validator_trust: 0,
-                    dividends: 0,
-                    last_update: 0,
-                    validator_permit: false,
-                    weights: vec![],
-                    bonds: vec![],
-                    pruning_score: 0,
-                };
+// The websocket is gone, unlike a request the node answered with an error
+fn is_connection_lost(error: &ClientError) -> bool {
+    matches!(
+        error,
+        ClientError::RestartNeeded(_) | ClientError::Transport(_)
+    )
+}
 
-                println!("✅ Neuron registered:");
-                println!("   UID: {}", uid);
-                println!("   Hotkey: {}", hotkey);
-                println!("   Active: {}", neuron_info.active);
-                println!("   Raw data length: {} bytes", bytes.len());
-                Ok(Some(neuron_info))
-            }
-            None => {
-                println!("❌ No neuron data found for UID {}", uid);
-                Ok(None)
-            }
-        }
-    }
+// Dropped from the pool or never picked up, a higher tip may get it in
+fn is_stalled(error: &anyhow::Error) -> bool {
+    let message = format!("{:#}", error);
+    message.contains("Transaction dropped") || message.contains("not included after")
+}
 
-    // Helper method to encode storage keys with hotkey
-    fn encode_hotkey_storage_key(
-        &self,
-        module: &str,
-        storage: &str,
-        netuid: u16,
-        hotkey: &AccountId32,
-    ) -> Result<String> {
-        use sp_core::blake2_256;
+// TransactionPaymentApi_query_info result, weight as (ref_time, proof_size)
+#[derive(Debug, Decode)]
+struct RuntimeDispatchInfo {
+    _weight: (Compact<u64>, Compact<u64>),
+    _class: u8,
+    partial_fee: u64,
+}
 
-        let module_hash = blake2_256(module.as_bytes());
-        let storage_hash = blake2_256(storage.as_bytes());
+// Runtime versions committed to by every signature
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RuntimeVersion {
+    pub spec_version: u32,
+    pub transaction_version: u32,
+}
 
-        let mut key = Vec::new();
-        key.extend_from_slice(&module_hash);
-        key.extend_from_slice(&storage_hash);
-        key.extend_from_slice(&netuid.to_le_bytes());
-        key.extend_from_slice(hotkey.as_ref());
+// Submit to runtimes whose metadata doesn't match this build's encodings anyway (--force)
+static ALLOW_UNVERIFIED_RUNTIME: OnceLock<bool> = OnceLock::new();
 
-        Ok(format!("0x{}", hex::encode(key)))
-    }
+pub fn set_allow_unverified_runtime(allow: bool) {
+    let _ = ALLOW_UNVERIFIED_RUNTIME.set(allow);
+}
 
-    // Helper method to encode storage keys with UID
-    fn encode_uid_storage_key(
-        &self,
-        module: &str,
-        storage: &str,
-        netuid: u16,
-        uid: u16,
-    ) -> Result<String> {
-        use sp_core::blake2_256;
+// Read storage that fails to query or decode as its default instead of failing (--lenient)
+static LENIENT_STORAGE: OnceLock<bool> = OnceLock::new();
 
-        let module_hash = blake2_256(module.as_bytes());
-        let storage_hash = blake2_256(storage.as_bytes());
+pub fn set_lenient(lenient: bool) {
+    let _ = LENIENT_STORAGE.set(lenient);
+}
 
-        let mut key = Vec::new();
-        key.extend_from_slice(&module_hash);
-        key.extend_from_slice(&storage_hash);
-        key.extend_from_slice(&netuid.to_le_bytes());
-        key.extend_from_slice(&uid.to_le_bytes());
+fn is_lenient() -> bool {
+    LENIENT_STORAGE.get().copied().unwrap_or(false)
+}
 
-        Ok(format!("0x{}", hex::encode(key)))
+// The default in lenient mode, with a warning so the stand-in value isn't mistaken for data
+fn default_if_lenient<T>(
+    result: Result<T>,
+    default: T,
+    storage_name: &str,
+    lenient: bool,
+) -> Result<T> {
+    match result {
+        Err(e) if lenient => {
+            warnings::emit(
+                WarningCode::StorageDefaulted,
+                format!("Using the default for {}: {:#}", storage_name, e),
+            );
+            Ok(default)
+        }
+        result => result,
     }
+}
 
-    // Getting current block number
-    pub async fn get_current_block(&self) -> Result<u64> {
-        let block_hash: H256 = self
-            .client
-            .request("chain_getBlockHash", rpc_params![])
-            .await
-            .context("Failed to get current block hash")?;
+// RAO tipped on every signed extrinsic (--tip), none unless set
+static DEFAULT_TIP: OnceLock<u64> = OnceLock::new();
 
-        let header: serde_json::Value = self
-            .client
-            .request("chain_getHeader", rpc_params![block_hash])
-            .await
-            .context("Failed to get block header")?;
+pub fn set_default_tip(tip: u64) {
+    let _ = DEFAULT_TIP.set(tip);
+}
+
+// Whether an extrinsic would be accepted, without submitting it
+#[derive(Debug, Clone)]
+pub struct DryRunOutcome {
+    pub would_succeed: bool,
+    pub detail: String,
+}
 
-        let block_number = header["number"]
-            .as_str()
-            .and_then(|s| u64::from_str_radix(s.trim_start_matches("0x"), 16).ok())
-            .ok_or_else(|| anyhow!("Invalid block number format"))?;
+// Hashers used by storage map keys
+#[derive(Debug, Clone, Copy)]
+pub enum StorageHasher {
+    Identity,
+    Twox64Concat,
+    Blake2_128Concat,
+}
+
+impl StorageHasher {
+    fn hash(&self, data: &[u8]) -> Vec<u8> {
+        use sp_core::{blake2_128, twox_64};
 
-        Ok(block_number)
+        match self {
+            StorageHasher::Identity => data.to_vec(),
+            StorageHasher::Twox64Concat => [&twox_64(data)[..], data].concat(),
+            StorageHasher::Blake2_128Concat => [&blake2_128(data)[..], data].concat(),
+        }
     }
+}
 
-    // Creating a signed extrinsic
-    async fn create_signed_extrinsic(
-        &self,
-        call: Vec<u8>,
-        signer: &Sr25519Pair,
-    ) -> Result<Vec<u8>> {
-        let account_id = AccountId32::from(signer.public().0);
-        let account_info = self.get_account_info(&account_id).await?;
-        let current_block = self.get_current_block().await?;
+// Storage key of a map entry, each key hashed with its own hasher
+pub fn storage_key(pallet: &str, storage_name: &str, keys: &[(StorageHasher, &[u8])]) -> String {
+    use sp_core::twox_128;
 
-        // Getting genesis hash and current block hash
-        let _genesis_hash = self.get_genesis_hash().await?;
-        let _block_hash = self.get_block_hash(None).await?;
+    let mut final_key = Vec::new();
+    final_key.extend_from_slice(&twox_128(pallet.as_bytes()));
+    final_key.extend_from_slice(&twox_128(storage_name.as_bytes()));
 
-        // Creating signed extra
-        let extra = self.create_signed_extra(account_info.nonce as u64, current_block)?;
+    for (hasher, key) in keys {
+        final_key.extend_from_slice(&hasher.hash(key));
+    }
 
-        // Creating payload for signing
-        let mut payload = Vec::new();
-        call.encode_to(&mut payload);
-        extra.encode_to(&mut payload);
+    format!("0x{}", hex::encode(final_key))
+}
 
-        // If payload is more than 256 bytes, hash it
-        let signing_payload = if payload.len() > 256 {
-            sp_core::blake2_256(&payload).to_vec()
-        } else {
-            payload
-        };
+// Storage items a SubnetInfo is built from, fetched together for one or many subnets
+pub const SUBNET_INFO_STORAGE: &[&str] = &[
+    "SubnetworkN",
+    "Difficulty",
+    "Tempo",
+    "ImmunityPeriod",
+    "MinAllowedWeights",
+    "MaxWeightsLimit",
+    "MaxAllowedValidators",
+    "MaxAllowedUids",
+    "Burn",
+    "MinBurn",
+    "MaxBurn",
+    "SubnetOwner",
+    "NetworkModality",
+    "EmissionValues",
+    "Rho",
+    "Kappa",
+    "ScalingLawPower",
+    "BlocksSinceLastStep",
+    "NetworkRegistrationAllowed",
+    "MaxRegistrationsPerBlock",
+    "TargetRegistrationsPerInterval",
+    "RegistrationsThisInterval",
+];
+
+// One subnet's SUBNET_INFO_STORAGE values, in the same order
+#[derive(Debug, Clone)]
+struct SubnetStorage {
+    values: Vec<Option<Vec<u8>>>,
+    lenient: bool,
+}
 
-        // Signing
-        let signature = signer.sign(&signing_payload);
+impl SubnetStorage {
+    fn new(lenient: bool) -> Self {
+        Self {
+            values: vec![None; SUBNET_INFO_STORAGE.len()],
+            lenient,
+        }
+    }
 
-        // Creating extrinsic
-        let mut extrinsic = Vec::new();
+    fn raw(&self, storage_name: &str) -> Option<&[u8]> {
+        SUBNET_INFO_STORAGE
+            .iter()
+            .position(|name| *name == storage_name)
+            .and_then(|item| self.values[item].as_deref())
+    }
 
-        // Version и signature type
-        extrinsic.push(0x84u8); // Version 4 with signature
+    // Unset items read as the default, like the single-item getters. Undecodable ones are
+    // errors unless --lenient
+    fn decode_or<T: Decode>(&self, storage_name: &str, default: T) -> Result<T> {
+        let Some(bytes) = self.raw(storage_name) else {
+            return Ok(default);
+        };
+        let value = T::decode(&mut &bytes[..])
+            .map_err(|e| anyhow!("Failed to decode {}: {:?}", storage_name, e));
+        default_if_lenient(value, default, storage_name, self.lenient)
+    }
+}
 
-        // Signer
-        account_id.encode_to(&mut extrinsic);
+// SubnetworkN exists for any active subnet, so without it (or with no neurons) there is none
+fn subnet_info_from_storage(netuid: u16, storage: &SubnetStorage) -> Result<SubnetInfo> {
+    if storage.raw("SubnetworkN").is_none() {
+        return Err(anyhow!("Network {} does not exist", netuid));
+    }
+    let subnetwork_n = storage.decode_or("SubnetworkN", 0u16)?;
+    if subnetwork_n == 0 {
+        return Err(anyhow!("Network {} does not exist (has 0 neurons)", netuid));
+    }
 
-        // Signature
-        signature.encode_to(&mut extrinsic);
+    let max_n = storage.decode_or("MaxAllowedUids", 0u16)?;
+    let modality = storage.decode_or("NetworkModality", 0u16)?;
+    Ok(SubnetInfo {
+        netuid,
+        difficulty: storage.decode_or("Difficulty", U256::zero())?,
+        immunity_period: storage.decode_or("ImmunityPeriod", 0)?,
+        min_allowed_weights: storage.decode_or("MinAllowedWeights", 0)?,
+        max_weight_limit: storage.decode_or("MaxWeightsLimit", 0)?,
+        max_allowed_validators: storage.decode_or("MaxAllowedValidators", 0)?,
+        max_n,
+        tempo: storage.decode_or("Tempo", 0)?,
+        burn: storage.decode_or("Burn", 0)?,
+        min_burn: storage.decode_or("MinBurn", 0)?,
+        max_burn: storage.decode_or("MaxBurn", 0)?,
+        owner_ss58: storage
+            .decode_or("SubnetOwner", AccountId32::new([0u8; 32]))?
+            .to_ss58check(),
+        emission_value: storage.decode_or("EmissionValues", 0)?,
+        rho: storage.decode_or("Rho", 0)?,
+        kappa: storage.decode_or("Kappa", 0)?,
+        scaling_law_power: storage.decode_or("ScalingLawPower", 0)?,
+        subnetwork_n,
+        blocks_since_epoch: storage.decode_or("BlocksSinceLastStep", 0)?,
+        modality,
+        // Unset means the runtime default, which allows registration
+        registration_allowed: storage.decode_or("NetworkRegistrationAllowed", true)?,
+        max_registrations_per_block: storage.decode_or("MaxRegistrationsPerBlock", 0)?,
+        target_registrations_per_interval: storage
+            .decode_or("TargetRegistrationsPerInterval", 0)?,
+        registrations_this_interval: storage.decode_or("RegistrationsThisInterval", 0)?,
+        // Legacy fields for backward compatibility
+        network_modality: modality,
+        network_connect: vec![],
+        max_allowed_uids: max_n,
+        registered_neurons: subnetwork_n,
+    })
+}
 
-        // Extra
-        extra.encode_to(&mut extrinsic);
+// Subnet storage items whose changes mean cached subnet info is stale
+// (new registrations, pruning, burn adjustments, registration toggles)
+const SUBNET_CHANGE_STORAGE: &[&str] = &[
+    "SubnetworkN",
+    "RegistrationsThisInterval",
+    "Burn",
+    "LastAdjustmentBlock",
+    "NetworkRegistrationAllowed",
+    "MaxAllowedUids",
+];
+
+// What one block changed among the watched keys
+#[derive(Debug, Clone, Default)]
+pub struct ChainChanges {
+    pub netuids: Vec<u16>,      // Subnets with any relevant change
+    pub burns: Vec<(u16, u64)>, // New burn of subnets whose burn changed
+    pub uids: Vec<(u16, AccountId32, Option<u16>)>, // Watched hotkeys registered or pruned
+}
 
-        // Call
-        call.encode_to(&mut extrinsic);
+// Storage subscription reporting which watched subnets and hotkeys changed
+pub struct SubnetChangeWatch {
+    subscription: Subscription<StorageChangeSet>,
+    keys: HashMap<String, (u16, &'static str)>,
+    uid_keys: HashMap<String, (u16, AccountId32)>,
+}
 
-        // Add length prefix
-        let mut final_extrinsic = Vec::new();
-        ((extrinsic.len() as u32) | 0x8000_0000).encode_to(&mut final_extrinsic);
-        final_extrinsic.extend(extrinsic);
+impl SubnetChangeWatch {
+    // Changes in the next block that touches a watched key, None once the subscription ends
+    pub async fn next(&mut self) -> Option<Result<ChainChanges>> {
+        let change_set = match self.subscription.next().await? {
+            Ok(change_set) => change_set,
+            Err(e) => return Some(Err(anyhow!("Storage subscription failed: {}", e))),
+        };
 
-        Ok(final_extrinsic)
+        let mut changes = ChainChanges::default();
+        for (key, value) in &change_set.changes {
+            let key = key.to_lowercase();
+            let value = value
+                .as_deref()
+                .and_then(|hex_data| hex::decode(hex_data.trim_start_matches("0x")).ok());
+            if let Some(&(netuid, storage_name)) = self.keys.get(&key) {
+                changes.netuids.push(netuid);
+                if storage_name == "Burn" {
+                    let burn = value.and_then(|bytes| u64::decode(&mut &bytes[..]).ok());
+                    changes.burns.push((netuid, burn.unwrap_or(0)));
+                }
+            } else if let Some((netuid, hotkey)) = self.uid_keys.get(&key) {
+                // Written in the block a NeuronRegistered for the hotkey lands in, and
+                // removed when it is pruned
+                let uid = value.and_then(|bytes| u16::decode(&mut &bytes[..]).ok());
+                changes.uids.push((*netuid, hotkey.clone(), uid));
+            }
+        }
+        changes.netuids.sort_unstable();
+        changes.netuids.dedup();
+        Some(Ok(changes))
     }
+}
 
-    fn create_signed_extra(&self, nonce: u64, block_number: u64) -> Result<Vec<u8>> {
-        let mut extra = Vec::new();
+pub struct BittensorClient<C: ChainApi = WsChain> {
+    chain: Arc<C>,
+    endpoint: String,
+    backoff: Backoff, // Waits between reconnects when the connection drops
+    cache: Mutex<Option<StorageCache>>,
+    nonces: NonceManager,
+    custom_rpc: AtomicBool, // Cleared once the node turns out not to serve Subtensor's own RPCs
+    unverified_runtime: Mutex<Option<String>>, // Set when submitting is refused, with the reason
+    tip_escalation: Mutex<Option<TipEscalation>>,
+    cancel: CancellationToken, // Stops waits and watches, cancelled on Ctrl+C with every client
+    at_block: OnceLock<H256>,  // State queries read this block instead of the best one
+    metadata: Mutex<Option<Arc<Metadata>>>, // Decodes events, fetched on first use
+    lenient: bool, // Failed and undecodable storage reads fall back to defaults (--lenient)
+}
 
-        // Era (mortal)
-        let era_period = 64u64;
-        let phase = block_number % era_period;
-        let era = ((era_period.trailing_zeros() - 1).max(1) as u8)
-            | ((phase / (era_period >> 4)) as u8) << 6;
-        extra.push(era);
-        extra.push(0u8);
+impl BittensorClient<WsChain> {
+    pub async fn new(endpoint: String, backoff: Backoff) -> Result<Self> {
+        println!(
+            "🔗 Connecting to Bittensor network: {}",
+            transport::describe(&endpoint)
+        );
+        let chain = WsChain::connect(&endpoint).await?;
+        println!("✅ Connected to Bittensor network");
 
-        // Nonce
-        nonce.encode_to(&mut extra);
+        Ok(Self::with_chain(endpoint, Arc::new(chain), backoff))
+    }
+}
 
-        // Tip
-        0u64.encode_to(&mut extra); // No tip
+impl<C: ChainApi> BittensorClient<C> {
+    // A client on any backend, e.g. an in-memory chain in tests
+    pub fn with_chain(endpoint: String, chain: Arc<C>, backoff: Backoff) -> Self {
+        Self {
+            chain,
+            endpoint,
+            backoff,
+            cache: Mutex::new(None),
+            nonces: NonceManager::default(),
+            custom_rpc: AtomicBool::new(true),
+            unverified_runtime: Mutex::new(None),
+            tip_escalation: Mutex::new(None),
+            cancel: utils::shutdown_token().child_token(),
+            at_block: OnceLock::new(),
+            metadata: Mutex::new(None),
+            lenient: is_lenient(),
+        }
+    }
 
-        Ok(extra)
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
     }
 
-    async fn get_genesis_hash(&self) -> Result<H256> {
-        let result: String = self
-            .client
-            .request("chain_getBlockHash", rpc_params![0])
+    // Read all state as of a past block, given by number or hash. Only archive nodes keep
+    // state older than a few hundred blocks, others fail the probe query
+    pub async fn pin_block(&self, block: &str) -> Result<(u64, H256)> {
+        let block = block.trim();
+        let block_hash = if block.starts_with("0x") && block.len() == 66 {
+            H256::from_str(&block[2..]).map_err(|_| anyhow!("Invalid block hash {}", block))?
+        } else {
+            let number: u64 = block
+                .replace([',', '_'], "")
+                .parse()
+                .map_err(|_| anyhow!("Invalid block {}, expected a number or 0x hash", block))?;
+            self.retrying("block_hash", || self.chain.block_hash(Some(number)))
+                .await
+                .context("Failed to get block hash")?
+                .ok_or_else(|| anyhow!("Block {} doesn't exist yet", number))?
+        };
+        let number = self
+            .get_block_number(block_hash)
             .await
-            .context("Failed to get genesis hash")?;
+            .with_context(|| format!("Block {:?} not found", block_hash))?;
+
+        let probe = self.encode_bittensor_storage_key("TotalNetworks", &[]);
+        let probe = self
+            .retrying("storage", || self.chain.storage(&probe, Some(block_hash)))
+            .await;
+        if let Err(e) = probe {
+            return Err(anyhow!(
+                "State at block {} isn't available on {} ({}), historical queries need an archive node",
+                number,
+                self.endpoint,
+                e
+            ));
+        }
 
-        Ok(H256::from_str(&result[2..])?)
+        self.at_block
+            .set(block_hash)
+            .map_err(|_| anyhow!("Queries are already pinned to a block"))?;
+        Ok((number, block_hash))
     }
 
-    async fn get_block_hash(&self, block_number: Option<u64>) -> Result<H256> {
-        let params = if let Some(block) = block_number {
-            rpc_params![block]
-        } else {
-            rpc_params![]
-        };
+    pub fn pinned_block(&self) -> Option<H256> {
+        self.at_block.get().copied()
+    }
 
-        let result: String = self
-            .client
-            .request("chain_getBlockHash", params)
-            .await
-            .context("Failed to get block hash")?;
+    // Cancelling it stops this client's long waits, not the rest of the process
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.cancel.clone()
+    }
 
-        Ok(H256::from_str(&result[2..])?)
+    pub fn ensure_not_cancelled(&self) -> Result<()> {
+        if self.cancel.is_cancelled() {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Cancelled").into());
+        }
+        Ok(())
     }
 
-    async fn submit_extrinsic(&self, extrinsic: String) -> Result<H256> {
-        let result: String = self
-            .client
-            .request(
-                "author_submitExtrinsic",
-                rpc_params![format!("0x{}", extrinsic)],
-            )
-            .await
-            .context("Failed to submit extrinsic")?;
+    // Sleep, cut short with an error when the operation is cancelled
+    pub async fn pause(&self, duration: Duration) -> Result<()> {
+        tokio::select! {
+            _ = tokio::time::sleep(duration) => Ok(()),
+            _ = self.cancel.cancelled() => {
+                Err(RegistrationError::new(ErrorKind::Fatal, "Cancelled").into())
+            }
+        }
+    }
 
-        Ok(H256::from_str(&result[2..])?)
+    // Resubmit stalled transactions that wait for inclusion with a higher tip
+    pub fn enable_tip_escalation(&self, escalation: TipEscalation) {
+        if let Ok(mut tip_escalation) = self.tip_escalation.lock() {
+            *tip_escalation = Some(escalation);
+        }
     }
 
-    // Getting burn registration cost
-    /*async fn get_burn_cost(&self, netuid: u16) -> Result<u64> {
-        let params = rpc_params![
-            "SubtensorModule",
-            "Burn",
-            format!("0x{}", netuid.to_be_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>())
-        ];
+    // Serve repeated subnet storage reads from memory, for long-running commands
+    pub fn enable_storage_cache(&self, ttl: Duration) {
+        if let Ok(mut cache) = self.cache.lock() {
+            *cache = Some(StorageCache::new(ttl));
+        }
+    }
 
-        let result: Option<String> = self.client
-            .request("state_getStorage", params)
+    pub fn disable_storage_cache(&self) {
+        if let Ok(mut cache) = self.cache.lock() {
+            *cache = None;
+        }
+    }
+
+    // Forget cached storage for a subnet, returns how many entries were dropped
+    pub fn invalidate_subnet(&self, netuid: u16) -> usize {
+        self.cache
+            .lock()
+            .ok()
+            .and_then(|mut cache| cache.as_mut().map(|cache| cache.invalidate_subnet(netuid)))
+            .unwrap_or(0)
+    }
+
+    // Subscribing to storage changes that affect the given subnets, and to the UID of each
+    // (netuid, hotkey) pair so registrations and pruning arrive as they happen
+    pub async fn watch_subnet_changes(
+        &self,
+        netuids: &[u16],
+        hotkeys: &[(u16, AccountId32)],
+    ) -> Result<SubnetChangeWatch> {
+        let mut keys = HashMap::new();
+        for &netuid in netuids {
+            for storage_name in SUBNET_CHANGE_STORAGE {
+                keys.insert(
+                    self.encode_bittensor_storage_key(storage_name, &[netuid]),
+                    (netuid, *storage_name),
+                );
+            }
+        }
+        let uid_keys: HashMap<String, (u16, AccountId32)> = hotkeys
+            .iter()
+            .map(|(netuid, hotkey)| {
+                let key = self.encode_storage_map_key(
+                    "SubtensorModule",
+                    "Uids",
+                    &[
+                        (StorageHasher::Identity, &netuid.to_le_bytes()),
+                        (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    ],
+                );
+                (key, (*netuid, hotkey.clone()))
+            })
+            .collect();
+
+        let storage_keys: Vec<String> = keys.keys().chain(uid_keys.keys()).cloned().collect();
+        let subscription = self
+            .chain
+            .subscribe_storage(&storage_keys)
             .await
-            .context("Failed to get burn cost")?;
+            .context("Failed to subscribe to subnet storage changes")?;
 
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..])
-                .context("Invalid hex data")?;
-            let burn_cost = u64::from_le_bytes(
-                bytes.try_into()
-                    .map_err(|_| anyhow!("Invalid burn cost data"))?
+        Ok(SubnetChangeWatch {
+            subscription,
+            keys,
+            uid_keys,
+        })
+    }
+
+    // Every request to the node goes through here. A lost connection is replaced and the
+    // request retried, errors the node answered with are returned as they are
+    async fn retrying<T, F, Fut>(
+        &self,
+        method: &str,
+        call: F,
+    ) -> std::result::Result<T, ClientError>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = std::result::Result<T, ClientError>>,
+    {
+        let mut attempt = 0;
+        loop {
+            match call().await {
+                Err(e) if is_connection_lost(&e) && attempt < RPC_RECONNECT_ATTEMPTS => {
+                    attempt += 1;
+                    let delay = self.backoff.delay(attempt);
+                    println!(
+                        "🔌 Connection lost ({}), reconnecting in {} ({}/{})",
+                        e,
+                        utils::format_duration(delay),
+                        attempt,
+                        RPC_RECONNECT_ATTEMPTS
+                    );
+                    rpc_stats::record_retry(method);
+                    tokio::select! {
+                        _ = tokio::time::sleep(delay) => {}
+                        _ = self.cancel.cancelled() => return Err(e),
+                    }
+                    // A failed reconnect shows up as another lost connection on the retry
+                    if let Err(e) = self.chain.reconnect().await {
+                        log::debug!("Reconnect to {} failed: {:#}", self.endpoint, e);
+                    }
+                }
+                result => return result,
+            }
+        }
+    }
+
+    // Getting subnet information
+    pub async fn get_subnet_info(&self, netuid: u16, show_info: bool) -> Result<SubnetInfo> {
+        if show_info {
+            println!(
+                "🔍 Fetching subnet {} information from blockchain...",
+                netuid
             );
-            Ok(burn_cost)
-        } else {
-            // Default burn cost if not set
-            Ok(1_000_000_000) // 1 TAO in RAO
         }
-    }*/
 
-    // Getting account balance
-    pub async fn get_account_balance(&self, account: &AccountId32) -> Result<u64> {
-        let account_info = self.get_account_info(account).await?;
-        Ok(account_info.data.free as u64)
+        let storage = self.get_subnet_storage(&[netuid]).await?.remove(0);
+        if storage.raw("SubnetworkN").is_none() {
+            // Let's also try to get the total subnet count to see if we can get any storage at all
+            let total_networks = self.get_bittensor_storage("TotalNetworks", &[]).await?;
+
+            if let Some(total_bytes) = total_networks {
+                let total = u16::from_le_bytes([total_bytes[0], total_bytes[1]]);
+                return Err(anyhow!(
+                    "Network {} does not exist. Total networks on chain: {}. Try checking which specific subnet IDs are active.",
+                    netuid, total
+                ));
+            }
+        }
+        let info = subnet_info_from_storage(netuid, &storage)?;
+
+        if show_info {
+            let current_block = self.get_current_block().await?;
+            println!("📋 Subnet {} info retrieved:", netuid);
+            println!("   Difficulty: {}", info.difficulty);
+            println!("   Tempo: {}", info.tempo);
+            println!("   Immunity period: {}", info.immunity_period);
+            println!("   Min allowed weights: {}", info.min_allowed_weights);
+            println!("   Registration burn: {} RAO", info.burn);
+            println!("   Burn bounds: {} - {} RAO", info.min_burn, info.max_burn);
+            println!("   Registered neurons: {}", info.subnetwork_n);
+            println!("   Current block: {}", current_block);
+            println!("   Owner: {}", info.owner_ss58);
+        }
+
+        Ok(info)
     }
 
-    async fn get_account_info(&self, account: &AccountId32) -> Result<AccountInfo> {
-        // Create storage key for System::Account
-        let storage_key = self.encode_system_account_storage_key(account);
-        let result: Option<String> = match self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
+    // Info for many subnets, SUBNET_INFO_CHUNK subnets per storage query with a few queries
+    // in flight. An error stands in for each subnet that doesn't exist
+    pub async fn get_subnet_infos(&self, netuids: &[u16]) -> Result<Vec<Result<SubnetInfo>>> {
+        let chunks: Vec<Vec<SubnetStorage>> = stream::iter(netuids.chunks(SUBNET_INFO_CHUNK))
+            .map(|chunk| self.get_subnet_storage(chunk))
+            .buffered(SUBNET_INFO_WINDOW)
+            .try_collect()
+            .await?;
+        Ok(netuids
+            .iter()
+            .zip(chunks.iter().flatten())
+            .map(|(&netuid, storage)| subnet_info_from_storage(netuid, storage))
+            .collect())
+    }
+
+    // SUBNET_INFO_STORAGE for each subnet: cached values where fresh, the rest in a single
+    // state_queryStorageAt round trip
+    async fn get_subnet_storage(&self, netuids: &[u16]) -> Result<Vec<SubnetStorage>> {
+        let now = Instant::now();
+        let mut storage = vec![SubnetStorage::new(self.lenient); netuids.len()];
+        let mut missing = Vec::new(); // (subnet, item, storage key)
         {
-            Ok(res) => res,
-            Err(e) => {
-                return Err(anyhow::anyhow!("Failed to get account info: {}", e));
+            let cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            for (subnet, &netuid) in netuids.iter().enumerate() {
+                for (item, storage_name) in SUBNET_INFO_STORAGE.iter().enumerate() {
+                    let storage_key = self.encode_bittensor_storage_key(storage_name, &[netuid]);
+                    let cached = cache
+                        .as_ref()
+                        .filter(|_| StorageCache::is_cacheable(storage_name))
+                        .and_then(|cache| cache.get(&storage_key, now));
+                    match cached {
+                        Some(value) => storage[subnet].values[item] = value,
+                        None => missing.push((subnet, item, storage_key)),
+                    }
+                }
             }
-        };
+        }
 
-        if let Some(hex_data) = result {
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data in account info")?;
+        let storage_keys = missing.iter().map(|(_, _, key)| key.clone()).collect();
+        let values = self.get_storage_batch(storage_keys, "subnet info").await?;
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        for ((subnet, item, storage_key), value) in missing.into_iter().zip(values) {
+            if let Some(cache) = cache.as_mut() {
+                if StorageCache::is_cacheable(SUBNET_INFO_STORAGE[item]) {
+                    cache.insert(storage_key, Some(netuids[subnet]), value.clone(), now);
+                }
+            }
+            storage[subnet].values[item] = value;
+        }
+        Ok(storage)
+    }
 
-            // Use proper SCALE decoding
-            match AccountInfo::decode(&mut &bytes[..]) {
-                Ok(account_info) => Ok(account_info),
-                Err(e) => {
-                    // Manual parsing following Python Bittensor approach
-                    // AccountInfo structure: nonce(4) + consumers(4) + providers(4) + sufficients(4) + AccountData(40)
-                    // AccountData structure: free(16) + reserved(16) + frozen(8) + flags(8) = 48 bytes
-                    // But we're seeing 56 bytes total, so AccountData is actually 40 bytes: free(16) + reserved(16) + frozen(8)
-                    if bytes.len() >= 56 {
-                        // Parse AccountInfo fields (first 16 bytes)
-                        let nonce = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
-                        let consumers =
-                            u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
-                        let providers =
-                            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
-                        let sufficients =
-                            u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+    // Bittensor-specific storage key generation
+    fn encode_bittensor_storage_key(&self, storage_name: &str, keys: &[u16]) -> String {
+        use sp_core::twox_128;
 
-                        // Parse AccountData fields (next 40 bytes, starting at byte 16)
-                        // AccountData: free(16) + reserved(16) + misc_frozen(8) + fee_frozen(8) = 48 bytes
-                        // But actual data shows 40 bytes, so structure might be: free(16) + reserved(16) + frozen_data(8)
+        // Bittensor uses "SubtensorModule" as the pallet name
+        let pallet_hash = twox_128(b"SubtensorModule");
+        let storage_hash = twox_128(storage_name.as_bytes());
 
-                        // Free balance (bytes 16-31: 16 bytes for u128)
-                        let free_bytes = &bytes[16..32];
-                        let free = u128::from_le_bytes([
-                            free_bytes[0],
-                            free_bytes[1],
-                            free_bytes[2],
-                            free_bytes[3],
-                            free_bytes[4],
-                            free_bytes[5],
-                            free_bytes[6],
-                            free_bytes[7],
-                            free_bytes[8],
-                            free_bytes[9],
-                            free_bytes[10],
-                            free_bytes[11],
-                            free_bytes[12],
-                            free_bytes[13],
-                            free_bytes[14],
-                            free_bytes[15],
-                        ]);
+        let mut final_key = Vec::new();
+        final_key.extend_from_slice(&pallet_hash);
+        final_key.extend_from_slice(&storage_hash);
 
-                        // Reserved balance (bytes 32-47: 16 bytes for u128)
-                        let reserved = if bytes.len() >= 48 {
-                            let reserved_bytes = &bytes[32..48];
-                            u128::from_le_bytes([
-                                reserved_bytes[0],
-                                reserved_bytes[1],
-                                reserved_bytes[2],
-                                reserved_bytes[3],
-                                reserved_bytes[4],
-                                reserved_bytes[5],
-                                reserved_bytes[6],
-                                reserved_bytes[7],
-                                reserved_bytes[8],
-                                reserved_bytes[9],
-                                reserved_bytes[10],
-                                reserved_bytes[11],
-                                reserved_bytes[12],
-                                reserved_bytes[13],
-                                reserved_bytes[14],
-                                reserved_bytes[15],
+        // For map storage items with Identity hasher, use the key directly (no hashing)
+        // SubnetworkN uses Identity hasher according to the source code
+        if !keys.is_empty() {
+            for &key in keys {
+                // NetUid is u16, encode as little-endian bytes
+                final_key.extend_from_slice(&key.to_le_bytes());
+            }
+        }
+
+        format!("0x{}", hex::encode(final_key))
+    }
+
+    // Storage key generation for maps with explicit hashers per key
+    fn encode_storage_map_key(
+        &self,
+        pallet: &str,
+        storage_name: &str,
+        keys: &[(StorageHasher, &[u8])],
+    ) -> String {
+        storage_key(pallet, storage_name, keys)
+    }
+
+    // Get raw storage data for an already encoded storage key
+    async fn get_storage_by_key(
+        &self,
+        storage_key: String,
+        storage_name: &str,
+    ) -> Result<Option<Vec<u8>>> {
+        let at = self.pinned_block();
+        self.retrying("storage", || self.chain.storage(&storage_key, at))
+            .await
+            .context(format!("Failed to get {} from storage", storage_name))
+    }
+
+    // Get raw storage data for many keys in one round trip, values in key order
+    async fn get_storage_batch(
+        &self,
+        storage_keys: Vec<String>,
+        storage_name: &str,
+    ) -> Result<Vec<Option<Vec<u8>>>> {
+        if storage_keys.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let at = self.pinned_block();
+        self.retrying("storage_batch", || {
+            self.chain.storage_batch(&storage_keys, at)
+        })
+        .await
+        .context(format!("Failed to get {} from storage", storage_name))
+    }
+
+    // Get raw storage data from a SubtensorModule map
+    async fn get_bittensor_map_storage(
+        &self,
+        storage_name: &str,
+        keys: &[(StorageHasher, &[u8])],
+    ) -> Result<Option<Vec<u8>>> {
+        let storage_key = self.encode_storage_map_key("SubtensorModule", storage_name, keys);
+        self.get_storage_by_key(storage_key, storage_name).await
+    }
+
+    // Get raw storage data from Bittensor
+    async fn get_bittensor_storage(
+        &self,
+        storage_name: &str,
+        keys: &[u16],
+    ) -> Result<Option<Vec<u8>>> {
+        let storage_key = self.encode_bittensor_storage_key(storage_name, keys);
+        if !StorageCache::is_cacheable(storage_name) {
+            return self.get_storage_by_key(storage_key, storage_name).await;
+        }
+
+        let cached = self.cache.lock().ok().and_then(|cache| {
+            cache
+                .as_ref()
+                .and_then(|cache| cache.get(&storage_key, Instant::now()))
+        });
+        if let Some(value) = cached {
+            return Ok(value);
+        }
+
+        let value = self
+            .get_storage_by_key(storage_key.clone(), storage_name)
+            .await?;
+        if let Ok(mut cache) = self.cache.lock() {
+            if let Some(cache) = cache.as_mut() {
+                cache.insert(
+                    storage_key,
+                    keys.first().copied(),
+                    value.clone(),
+                    Instant::now(),
+                );
+            }
+        }
+        Ok(value)
+    }
+
+    // Get and decode storage data from Bittensor, None if the key is absent. Failed queries
+    // and undecodable values are errors
+    async fn get_bittensor_storage_decoded<T>(
+        &self,
+        storage_name: &str,
+        keys: &[u16],
+    ) -> Result<Option<T>>
+    where
+        T: codec::Decode,
+    {
+        match self.get_bittensor_storage(storage_name, keys).await? {
+            Some(bytes) => T::decode(&mut &bytes[..])
+                .map(Some)
+                .map_err(|e| anyhow!("Failed to decode {}: {:?}", storage_name, e)),
+            None => Ok(None),
+        }
+    }
+
+    // Typed value of a storage item, an absent key reading as zero the way the runtime reads
+    // unset values. Errors are only swallowed with --lenient
+    async fn get_bittensor_or_default<T>(&self, storage_name: &str, keys: &[u16]) -> Result<T>
+    where
+        T: codec::Decode + Default,
+    {
+        let value = self
+            .get_bittensor_storage_decoded(storage_name, keys)
+            .await
+            .map(Option::unwrap_or_default);
+        default_if_lenient(value, T::default(), storage_name, self.lenient)
+    }
+
+    // Specialized getters for different types
+    async fn get_bittensor_u16(&self, storage_name: &str, keys: &[u16]) -> Result<u16> {
+        self.get_bittensor_or_default(storage_name, keys).await
+    }
+
+    async fn get_bittensor_u64(&self, storage_name: &str, keys: &[u16]) -> Result<u64> {
+        self.get_bittensor_or_default(storage_name, keys).await
+    }
+
+    // Checking neuron registration
+    pub async fn check_registration(
+        &self,
+        netuid: u16,
+        hotkey: &AccountId32,
+    ) -> Result<Option<NeuronInfo>> {
+        println!("🔍 Checking registration status for hotkey: {}", hotkey);
+
+        let Some(uid) = self.get_uid(netuid, hotkey).await? else {
+            println!("❌ Hotkey not registered in subnet {}", netuid);
+            return Ok(None);
+        };
+
+        match self.get_neuron(netuid, uid).await? {
+            Some(neuron_info) => {
+                println!("✅ Neuron registered:");
+                println!("   UID: {}", uid);
+                println!("   Hotkey: {}", hotkey);
+                println!("   Active: {}", neuron_info.active);
+                Ok(Some(neuron_info))
+            }
+            None => {
+                println!("❌ No neuron data found for UID {}", uid);
+                Ok(None)
+            }
+        }
+    }
+
+    // Calling a runtime API through state_call and decoding its SCALE result
+    async fn runtime_api_call<T>(&self, method: &str, params: Vec<u8>) -> Result<T>
+    where
+        T: codec::Decode,
+    {
+        let at = self.pinned_block();
+        let bytes = self
+            .retrying("runtime_call", || {
+                self.chain.runtime_call(method, &params, at)
+            })
+            .await
+            .context(format!("Failed to call runtime API {}", method))?;
+
+        T::decode(&mut &bytes[..]).map_err(|e| anyhow!("Failed to decode {}: {:?}", method, e))
+    }
+
+    // Raw SCALE bytes from one of Subtensor's custom RPCs (neuronInfo_*, delegateInfo_*),
+    // None when the node doesn't serve them
+    async fn custom_rpc_bytes(&self, method: &str, args: &[u16]) -> Result<Option<Vec<u8>>> {
+        if !self.custom_rpc.load(Ordering::Relaxed) {
+            return Ok(None);
+        }
+
+        let at = self.pinned_block();
+        match self
+            .retrying(method, || self.chain.subtensor_rpc(method, args, at))
+            .await
+        {
+            Ok(bytes) => Ok(Some(bytes)),
+            // Method not found and friends, later calls go straight to the fallback
+            Err(ClientError::Call(e)) => {
+                log::debug!("{} unavailable: {}", method, e);
+                self.custom_rpc.store(false, Ordering::Relaxed);
+                Ok(None)
+            }
+            Err(e) => Err(e).context(format!("Failed to call {}", method)),
+        }
+    }
+
+    // Calling a custom RPC, falling back to the equivalent runtime API
+    async fn custom_rpc_call<T>(
+        &self,
+        method: &str,
+        args: &[u16],
+        runtime_api: &str,
+        runtime_params: Vec<u8>,
+    ) -> Result<T>
+    where
+        T: codec::Decode,
+    {
+        match self.custom_rpc_bytes(method, args).await? {
+            Some(bytes) => T::decode(&mut &bytes[..])
+                .map_err(|e| anyhow!("Failed to decode {}: {:?}", method, e)),
+            None => self.runtime_api_call(runtime_api, runtime_params).await,
+        }
+    }
+
+    // Getting full neuron info, through neuronInfo_getNeuron where available
+    pub async fn get_neuron(&self, netuid: u16, uid: u16) -> Result<Option<NeuronInfo>> {
+        let neuron: Option<RawNeuronInfo> = self
+            .custom_rpc_call(
+                "neuronInfo_getNeuron",
+                &[netuid, uid],
+                "NeuronInfoRuntimeApi_get_neuron",
+                (netuid, uid).encode(),
+            )
+            .await?;
+
+        Ok(neuron.map(NeuronInfo::from))
+    }
+
+    // Getting every neuron of a subnet, through neuronInfo_getNeuronsLite where available
+    pub async fn get_neurons_lite(&self, netuid: u16) -> Result<Vec<NeuronInfoLite>> {
+        let neurons: Vec<RawNeuronInfoLite> = self
+            .custom_rpc_call(
+                "neuronInfo_getNeuronsLite",
+                &[netuid],
+                "NeuronInfoRuntimeApi_get_neurons_lite",
+                netuid.encode(),
+            )
+            .await?;
+
+        Ok(neurons.into_iter().map(NeuronInfoLite::from).collect())
+    }
+
+    // Getting one neuron without weights and bonds, through neuronInfo_getNeuronLite where available
+    pub async fn get_neuron_lite(&self, netuid: u16, uid: u16) -> Result<Option<NeuronInfoLite>> {
+        let neuron: Option<RawNeuronInfoLite> = self
+            .custom_rpc_call(
+                "neuronInfo_getNeuronLite",
+                &[netuid, uid],
+                "NeuronInfoRuntimeApi_get_neuron_lite",
+                (netuid, uid).encode(),
+            )
+            .await?;
+
+        Ok(neuron.map(NeuronInfoLite::from))
+    }
+
+    // Neurons in UID order, fetched at most NEURON_STREAM_WINDOW ahead of the consumer so
+    // memory stays flat however large the subnet
+    pub async fn stream_neurons_lite(
+        &self,
+        netuid: u16,
+    ) -> Result<impl Stream<Item = Result<NeuronInfoLite>> + '_> {
+        let subnetwork_n = self.get_bittensor_u16("SubnetworkN", &[netuid]).await?;
+        Ok(stream::iter(0..subnetwork_n)
+            .map(move |uid| self.get_neuron_lite(netuid, uid))
+            .buffered(NEURON_STREAM_WINDOW)
+            .filter_map(|neuron| async move { neuron.transpose() }))
+    }
+
+    // Getting every delegate, through delegateInfo_getDelegates where available
+    pub async fn get_delegates(&self) -> Result<Vec<DelegateInfo>> {
+        let bytes = match self
+            .custom_rpc_bytes("delegateInfo_getDelegates", &[])
+            .await?
+        {
+            Some(bytes) => bytes,
+            // The runtime API returns the same encoding, read it back as raw bytes
+            None => self
+                .retrying("runtime_call", || {
+                    self.chain
+                        .runtime_call("DelegateInfoRuntimeApi_get_delegates", &[], None)
+                })
+                .await
+                .context("Failed to call runtime API DelegateInfoRuntimeApi_get_delegates")?,
+        };
+        decode_delegates(&bytes)
+    }
+
+    // Get and decode storage data from a SubtensorModule map, None if the key is absent
+    async fn get_bittensor_map_decoded<T>(
+        &self,
+        storage_name: &str,
+        keys: &[(StorageHasher, &[u8])],
+    ) -> Result<Option<T>>
+    where
+        T: codec::Decode,
+    {
+        match self.get_bittensor_map_storage(storage_name, keys).await? {
+            Some(bytes) => T::decode(&mut &bytes[..])
+                .map(Some)
+                .map_err(|e| anyhow!("Failed to decode {}: {:?}", storage_name, e)),
+            None => Ok(None),
+        }
+    }
+
+    // Getting UID of a hotkey in a subnet
+    pub async fn get_uid(&self, netuid: u16, hotkey: &AccountId32) -> Result<Option<u16>> {
+        self.get_bittensor_map_decoded(
+            "Uids",
+            &[
+                (StorageHasher::Identity, &netuid.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            ],
+        )
+        .await
+    }
+
+    // Getting UID of a hotkey as of a given block, bypassing the storage cache
+    pub async fn get_uid_at(
+        &self,
+        netuid: u16,
+        hotkey: &AccountId32,
+        block_hash: H256,
+    ) -> Result<Option<u16>> {
+        let storage_key = self.encode_storage_map_key(
+            "SubtensorModule",
+            "Uids",
+            &[
+                (StorageHasher::Identity, &netuid.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            ],
+        );
+        let result = self
+            .retrying("storage", || {
+                self.chain.storage(&storage_key, Some(block_hash))
+            })
+            .await
+            .context("Failed to get Uids from storage")?;
+
+        result
+            .map(|bytes| {
+                u16::decode(&mut &bytes[..]).map_err(|e| anyhow!("Failed to decode Uids: {:?}", e))
+            })
+            .transpose()
+    }
+
+    // Getting pruning scores indexed by UID, empty for unknown subnets
+    pub async fn get_pruning_scores(&self, netuid: u16) -> Result<Vec<u16>> {
+        match self
+            .get_bittensor_storage("PruningScores", &[netuid])
+            .await?
+        {
+            Some(bytes) => Vec::<u16>::decode(&mut &bytes[..])
+                .map_err(|e| anyhow!("Failed to decode PruningScores: {:?}", e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // Getting the block each UID registered at, fetched in a single batch
+    pub async fn get_registration_blocks(
+        &self,
+        netuid: u16,
+        subnetwork_n: u16,
+    ) -> Result<Vec<u64>> {
+        let storage_keys = (0..subnetwork_n)
+            .map(|uid| {
+                self.encode_storage_map_key(
+                    "SubtensorModule",
+                    "BlockAtRegistration",
+                    &[
+                        (StorageHasher::Identity, &netuid.to_le_bytes()),
+                        (StorageHasher::Identity, &uid.to_le_bytes()),
+                    ],
+                )
+            })
+            .collect();
+
+        self.get_storage_batch(storage_keys, "BlockAtRegistration")
+            .await?
+            .into_iter()
+            .map(|bytes| match bytes {
+                Some(bytes) => u64::decode(&mut &bytes[..])
+                    .map_err(|e| anyhow!("Failed to decode BlockAtRegistration: {:?}", e)),
+                None => Ok(0),
+            })
+            .collect()
+    }
+
+    // Getting hotkey registered under a UID
+    pub async fn get_total_networks(&self) -> Result<u16> {
+        self.get_bittensor_or_default("TotalNetworks", &[]).await
+    }
+
+    // Identities of many subnets, one batch per identity version, the newest version set wins
+    pub async fn get_subnet_identities(
+        &self,
+        netuids: &[u16],
+    ) -> Result<Vec<Option<SubnetIdentity>>> {
+        let mut identities: Vec<Option<SubnetIdentity>> = vec![None; netuids.len()];
+        for &(storage, version) in SUBNET_IDENTITY_STORAGES {
+            let missing: Vec<usize> = (0..netuids.len())
+                .filter(|&i| identities[i].is_none())
+                .collect();
+            if missing.is_empty() {
+                break;
+            }
+            let storage_keys = missing
+                .iter()
+                .map(|&i| {
+                    self.encode_storage_map_key(
+                        "SubtensorModule",
+                        storage,
+                        &[(StorageHasher::Blake2_128Concat, &netuids[i].to_le_bytes())],
+                    )
+                })
+                .collect();
+            let values = self.get_storage_batch(storage_keys, storage).await?;
+            for (i, bytes) in missing.into_iter().zip(values) {
+                if let Some(bytes) = bytes {
+                    identities[i] = Some(SubnetIdentity::decode_version(version, &bytes)?);
+                }
+            }
+        }
+        Ok(identities)
+    }
+
+    pub async fn get_subnet_identity(&self, netuid: u16) -> Result<Option<SubnetIdentity>> {
+        Ok(self.get_subnet_identities(&[netuid]).await?.pop().flatten())
+    }
+
+    // Subnet name from the owner's on-chain identity
+    pub async fn get_subnet_name(&self, netuid: u16) -> Result<Option<String>> {
+        Ok(self
+            .get_subnet_identity(netuid)
+            .await?
+            .map(|identity| identity.name)
+            .filter(|name| !name.is_empty()))
+    }
+
+    // Netuid of the subnet whose identity carries this name, ignoring case
+    pub async fn find_subnet_by_name(&self, name: &str) -> Result<u16> {
+        let netuids = self.get_netuids().await?;
+        let identities = self.get_subnet_identities(&netuids).await?;
+        let matches: Vec<u16> = netuids
+            .into_iter()
+            .zip(identities)
+            .filter(|(_, identity)| {
+                identity
+                    .as_ref()
+                    .is_some_and(|identity| identity.name.eq_ignore_ascii_case(name.trim()))
+            })
+            .map(|(netuid, _)| netuid)
+            .collect();
+        match matches[..] {
+            [netuid] => Ok(netuid),
+            [] => Err(anyhow!("No subnet is named {}", name)),
+            _ => Err(anyhow!(
+                "Several subnets are named {} ({}), pick one with --subnet",
+                name,
+                matches
+                    .iter()
+                    .map(|netuid| netuid.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    // Every subnet that exists, from the NetworksAdded keys. Dissolved subnets leave gaps,
+    // so counting up to TotalNetworks would miss the highest netuids
+    pub async fn get_netuids(&self) -> Result<Vec<u16>> {
+        let prefix = self.encode_storage_map_key("SubtensorModule", "NetworksAdded", &[]);
+        let mut netuids = Vec::new();
+        let mut start_key: Option<String> = None;
+        let at = self.pinned_block();
+        loop {
+            let keys = self
+                .retrying("storage_keys", || {
+                    self.chain
+                        .storage_keys(&prefix, STORAGE_KEYS_PAGE, start_key.as_deref(), at)
+                })
+                .await
+                .context("Failed to list NetworksAdded keys")?;
+            for key in &keys {
+                // Identity hashed, the netuid is the key's last two bytes
+                let bytes =
+                    hex::decode(key.trim_start_matches("0x")).context("Invalid storage key")?;
+                let netuid = bytes
+                    .len()
+                    .checked_sub(2)
+                    .and_then(|start| u16::decode(&mut &bytes[start..]).ok())
+                    .ok_or_else(|| anyhow!("Malformed NetworksAdded key {}", key))?;
+                netuids.push(netuid);
+            }
+            if keys.len() < STORAGE_KEYS_PAGE as usize {
+                break;
+            }
+            start_key = keys.last().cloned();
+        }
+        netuids.sort_unstable();
+        Ok(netuids)
+    }
+
+    // Every (netuid, uid) a hotkey is registered under, all subnets read in one batch
+    pub async fn find_registrations(&self, hotkey: &AccountId32) -> Result<Vec<(u16, u16)>> {
+        let netuids = self.get_netuids().await?;
+        let storage_keys = netuids
+            .iter()
+            .map(|netuid| {
+                self.encode_storage_map_key(
+                    "SubtensorModule",
+                    "Uids",
+                    &[
+                        (StorageHasher::Identity, &netuid.to_le_bytes()),
+                        (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    ],
+                )
+            })
+            .collect();
+
+        let mut registrations = Vec::new();
+        let uids = self.get_storage_batch(storage_keys, "Uids").await?;
+        for (netuid, uid) in netuids.into_iter().zip(uids) {
+            if let Some(bytes) = uid {
+                let uid = u16::decode(&mut &bytes[..])
+                    .map_err(|e| anyhow!("Failed to decode Uids: {:?}", e))?;
+                registrations.push((netuid, uid));
+            }
+        }
+
+        Ok(registrations)
+    }
+
+    // Hotkeys a coldkey has registered or staked to, by the coldkey's own record
+    pub async fn get_owned_hotkeys(&self, coldkey: &AccountId32) -> Result<Vec<AccountId32>> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                "OwnedHotkeys",
+                &[(StorageHasher::Blake2_128Concat, coldkey.as_ref())],
+            )
+            .await?
+            .unwrap_or_default())
+    }
+
+    // Coldkey that owns a hotkey, None for a hotkey never registered or staked to
+    pub async fn get_hotkey_owner(&self, hotkey: &AccountId32) -> Result<Option<AccountId32>> {
+        self.get_bittensor_map_decoded(
+            "Owner",
+            &[(StorageHasher::Blake2_128Concat, hotkey.as_ref())],
+        )
+        .await
+    }
+
+    // Block a UID was registered at
+    pub async fn get_block_at_registration(&self, netuid: u16, uid: u16) -> Result<u64> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                "BlockAtRegistration",
+                &[
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                    (StorageHasher::Identity, &uid.to_le_bytes()),
+                ],
+            )
+            .await?
+            .unwrap_or(0))
+    }
+
+    pub async fn get_immunity_period(&self, netuid: u16) -> Result<u16> {
+        self.get_bittensor_u16("ImmunityPeriod", &[netuid]).await
+    }
+
+    pub async fn get_hotkey_by_uid(&self, netuid: u16, uid: u16) -> Result<Option<AccountId32>> {
+        self.get_bittensor_map_decoded(
+            "Keys",
+            &[
+                (StorageHasher::Identity, &netuid.to_le_bytes()),
+                (StorageHasher::Identity, &uid.to_le_bytes()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn get_axon_info(
+        &self,
+        netuid: u16,
+        hotkey: &AccountId32,
+    ) -> Result<Option<AxonInfo>> {
+        self.get_bittensor_map_decoded(
+            "Axons",
+            &[
+                (StorageHasher::Identity, &netuid.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn get_prometheus_info(
+        &self,
+        netuid: u16,
+        hotkey: &AccountId32,
+    ) -> Result<Option<PrometheusInfo>> {
+        self.get_bittensor_map_decoded(
+            "Prometheus",
+            &[
+                (StorageHasher::Identity, &netuid.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            ],
+        )
+        .await
+    }
+
+    // Getting weights set by a UID as (target uid, weight) pairs
+    pub async fn get_weights(&self, netuid: u16, uid: u16) -> Result<Vec<(u16, u16)>> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                "Weights",
+                &[
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                    (StorageHasher::Identity, &uid.to_le_bytes()),
+                ],
+            )
+            .await?
+            .unwrap_or_default())
+    }
+
+    // Getting a per-UID vector stored under the netuid (ValidatorPermit, Emission, ...)
+    async fn get_subnet_vector<T>(&self, storage_name: &str, netuid: u16) -> Result<Vec<T>>
+    where
+        T: codec::Decode,
+    {
+        Ok(self
+            .get_bittensor_map_decoded(
+                storage_name,
+                &[(StorageHasher::Identity, &netuid.to_le_bytes())],
+            )
+            .await?
+            .unwrap_or_default())
+    }
+
+    pub async fn get_validator_permits(&self, netuid: u16) -> Result<Vec<bool>> {
+        self.get_subnet_vector("ValidatorPermit", netuid).await
+    }
+
+    pub async fn get_emissions(&self, netuid: u16) -> Result<Vec<u64>> {
+        self.get_subnet_vector("Emission", netuid).await
+    }
+
+    // A coldkey's alpha on a hotkey in a subnet. Alpha holds shares of the hotkey's pool
+    // (U64F64 bits), worth TotalHotkeyAlpha pro rata over TotalHotkeyShares
+    pub async fn get_alpha_stake(
+        &self,
+        hotkey: &AccountId32,
+        coldkey: &AccountId32,
+        netuid: u16,
+    ) -> Result<u64> {
+        let netuid_bytes = netuid.to_le_bytes();
+        let shares: u128 = self
+            .get_bittensor_map_decoded(
+                "Alpha",
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Blake2_128Concat, coldkey.as_ref()),
+                    (StorageHasher::Identity, &netuid_bytes),
+                ],
+            )
+            .await?
+            .unwrap_or(0);
+        if shares == 0 {
+            return Ok(0);
+        }
+        let total_shares: u128 = self
+            .get_bittensor_map_decoded(
+                "TotalHotkeyShares",
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Identity, &netuid_bytes),
+                ],
+            )
+            .await?
+            .unwrap_or(0);
+        let total_alpha = self.get_hotkey_alpha(hotkey, netuid).await?;
+        Ok(alpha_for_shares(shares, total_shares, total_alpha))
+    }
+
+    // TAO staked on a hotkey by all coldkeys, in RAO: its root alpha, which trades 1:1
+    pub async fn get_root_stake(&self, hotkey: &AccountId32) -> Result<u64> {
+        self.get_hotkey_alpha(hotkey, constants::ROOT_NETUID).await
+    }
+
+    pub async fn get_hotkey_alpha(&self, hotkey: &AccountId32, netuid: u16) -> Result<u64> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                "TotalHotkeyAlpha",
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                ],
+            )
+            .await?
+            .unwrap_or(0))
+    }
+
+    // Getting stake of every validator permit holder in a subnet
+    pub async fn get_validator_stakes(&self, netuid: u16) -> Result<Vec<(u16, AccountId32, u64)>> {
+        let permits = self.get_validator_permits(netuid).await?;
+
+        let mut stakes = Vec::new();
+        for (uid, _) in permits.iter().enumerate().filter(|(_, permit)| **permit) {
+            let uid = uid as u16;
+            let Some(hotkey) = self.get_hotkey_by_uid(netuid, uid).await? else {
+                continue;
+            };
+            let stake = self.get_hotkey_alpha(&hotkey, netuid).await?;
+
+            stakes.push((uid, hotkey, stake));
+        }
+
+        Ok(stakes)
+    }
+
+    // On-chain identity of a coldkey from the newest identity storage that has one
+    pub async fn get_identity(&self, coldkey: &AccountId32) -> Result<Option<ChainIdentity>> {
+        for &(storage, version) in IDENTITY_STORAGES {
+            if let Some(bytes) = self
+                .get_bittensor_map_storage(
+                    storage,
+                    &[(StorageHasher::Blake2_128Concat, coldkey.as_ref())],
+                )
+                .await?
+            {
+                return ChainIdentity::decode_version(version, &bytes).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    // Commit-reveal settings of a subnet: whether it is on, the weights version key reveals
+    // carry, tempo and the reveal period in epochs
+    pub async fn get_commit_reveal_params(&self, netuid: u16) -> Result<CommitRevealParams> {
+        let enabled = self
+            .get_bittensor_storage_decoded("CommitRevealWeightsEnabled", &[netuid])
+            .await?
+            .unwrap_or(false);
+        let reveal_period = match self
+            .get_bittensor_u64("RevealPeriodEpochs", &[netuid])
+            .await?
+        {
+            0 => commit_reveal::DEFAULT_REVEAL_PERIOD_EPOCHS,
+            period => period,
+        };
+        Ok(CommitRevealParams {
+            enabled,
+            version_key: self
+                .get_bittensor_u64("WeightsVersionKey", &[netuid])
+                .await?,
+            tempo: self.get_bittensor_u16("Tempo", &[netuid]).await?,
+            reveal_period,
+        })
+    }
+
+    // Child hotkeys of a hotkey in a subnet, with their proportions of u64::MAX
+    pub async fn get_children(
+        &self,
+        hotkey: &AccountId32,
+        netuid: u16,
+    ) -> Result<Vec<(u64, AccountId32)>> {
+        self.get_child_relations("ChildKeys", hotkey, netuid).await
+    }
+
+    // Hotkeys that have this one as a child in a subnet
+    pub async fn get_parents(
+        &self,
+        hotkey: &AccountId32,
+        netuid: u16,
+    ) -> Result<Vec<(u64, AccountId32)>> {
+        self.get_child_relations("ParentKeys", hotkey, netuid).await
+    }
+
+    async fn get_child_relations(
+        &self,
+        storage_name: &str,
+        hotkey: &AccountId32,
+        netuid: u16,
+    ) -> Result<Vec<(u64, AccountId32)>> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                storage_name,
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                ],
+            )
+            .await?
+            .unwrap_or_default())
+    }
+
+    // Share of a child hotkey's dividends it keeps, as a fraction of u16::MAX
+    pub async fn get_childkey_take(&self, hotkey: &AccountId32, netuid: u16) -> Result<u16> {
+        Ok(self
+            .get_bittensor_map_decoded(
+                "ChildkeyTake",
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                ],
+            )
+            .await?
+            .unwrap_or(0))
+    }
+
+    // Root network validators with their total stake, lowest stake first
+    pub async fn get_root_validators(&self) -> Result<Vec<(u16, AccountId32, u64)>> {
+        let registered = self
+            .get_bittensor_u16("SubnetworkN", &[constants::ROOT_NETUID])
+            .await?;
+
+        let mut validators = Vec::new();
+        for uid in 0..registered {
+            let Some(hotkey) = self.get_hotkey_by_uid(constants::ROOT_NETUID, uid).await? else {
+                continue;
+            };
+            let stake = self.get_root_stake(&hotkey).await?;
+            validators.push((uid, hotkey, stake));
+        }
+        validators.sort_by_key(|(_, _, stake)| *stake);
+
+        Ok(validators)
+    }
+
+    // Root network slots, registrations beyond it have to outstake the lowest validator
+    pub async fn get_root_max_validators(&self) -> Result<u16> {
+        self.get_bittensor_u16("MaxAllowedUids", &[constants::ROOT_NETUID])
+            .await
+    }
+
+    // Current senate, the SenateMembers pallet's member list
+    pub async fn get_senate_members(&self) -> Result<Vec<AccountId32>> {
+        let storage_key = self.encode_storage_map_key("SenateMembers", "Members", &[]);
+        match self.get_storage_by_key(storage_key, "Members").await? {
+            Some(bytes) => Vec::<AccountId32>::decode(&mut &bytes[..])
+                .map_err(|e| anyhow!("Failed to decode senate members: {:?}", e)),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    // Getting the share of subnet emission paid to subnet owners, as a fraction of u16::MAX
+    pub async fn get_subnet_owner_cut(&self) -> Result<u16> {
+        self.get_bittensor_u16("SubnetOwnerCut", &[]).await
+    }
+
+    // Getting the burn as of a past block, None when that state is no longer available
+    pub async fn get_burn_at(&self, netuid: u16, block_number: u64) -> Result<Option<u64>> {
+        let block_hash = self.get_block_hash(Some(block_number)).await?;
+        let storage_key = self.encode_bittensor_storage_key("Burn", &[netuid]);
+
+        let result = match self
+            .retrying("storage", || {
+                self.chain.storage(&storage_key, Some(block_hash))
+            })
+            .await
+        {
+            Ok(result) => result,
+            // Non-archive nodes prune old state and reject the query
+            Err(ClientError::Call(_)) => return Ok(None),
+            Err(e) => return Err(e).context("Failed to get Burn from storage"),
+        };
+
+        result
+            .map(|bytes| {
+                u64::decode(&mut &bytes[..]).map_err(|e| anyhow!("Failed to decode Burn: {:?}", e))
+            })
+            .transpose()
+    }
+
+    // Getting the dTAO pool of a subnet
+    pub async fn get_subnet_pool(&self, netuid: u16) -> Result<SubnetPool> {
+        Ok(SubnetPool {
+            netuid,
+            tao_in: self.get_bittensor_u64("SubnetTAO", &[netuid]).await?,
+            alpha_in: self.get_bittensor_u64("SubnetAlphaIn", &[netuid]).await?,
+            alpha_out: self.get_bittensor_u64("SubnetAlphaOut", &[netuid]).await?,
+        })
+    }
+
+    // Getting burn and its adjustment schedule
+    pub async fn get_burn_schedule(&self, netuid: u16) -> Result<BurnSchedule> {
+        // No zero fallback here, a missing burn must never look like a free registration
+        let burn: u64 = self
+            .get_bittensor_storage_decoded("Burn", &[netuid])
+            .await?
+            .ok_or_else(|| anyhow!("Storage key not found: Burn"))?;
+        let adjustment_interval = self
+            .get_bittensor_u16("AdjustmentInterval", &[netuid])
+            .await?;
+        let last_adjustment_block = self
+            .get_bittensor_u64("LastAdjustmentBlock", &[netuid])
+            .await?;
+        let adjustment_alpha = self.get_bittensor_u64("AdjustmentAlpha", &[netuid]).await?;
+        let min_burn = self.get_bittensor_u64("MinBurn", &[netuid]).await?;
+        let max_burn = self.get_bittensor_u64("MaxBurn", &[netuid]).await?;
+        let target_registrations_per_interval = self
+            .get_bittensor_u16("TargetRegistrationsPerInterval", &[netuid])
+            .await?;
+        let registrations_this_interval = self
+            .get_bittensor_u16("RegistrationsThisInterval", &[netuid])
+            .await?;
+
+        Ok(BurnSchedule {
+            burn,
+            min_burn,
+            max_burn,
+            adjustment_interval,
+            adjustment_alpha,
+            last_adjustment_block,
+            target_registrations_per_interval,
+            registrations_this_interval,
+        })
+    }
+
+    // Getting rate limits that would reject a registration right now
+    pub async fn get_registration_cooldown(
+        &self,
+        netuid: u16,
+        hotkey: &AccountId32,
+    ) -> Result<RegistrationCooldown> {
+        let current_block = self.get_current_block().await?;
+        let mut cooldown = RegistrationCooldown {
+            current_block,
+            ready_at_block: current_block,
+            reason: None,
+        };
+
+        // Transaction rate limit on the hotkey since its last transaction
+        let tx_rate_limit = self.get_bittensor_u64("TxRateLimit", &[]).await?;
+        let last_tx_block: u64 = self
+            .get_bittensor_map_decoded("LastTxBlock", &[(StorageHasher::Identity, hotkey.as_ref())])
+            .await?
+            .unwrap_or(0);
+        if last_tx_block > 0 && last_tx_block + tx_rate_limit > current_block {
+            cooldown.ready_at_block = last_tx_block + tx_rate_limit;
+            cooldown.reason = Some(format!(
+                "hotkey transaction rate limit ({} blocks since block {})",
+                tx_rate_limit, last_tx_block
+            ));
+        }
+
+        // Per-block registration cap, frees up on the next block
+        let max_per_block = self
+            .get_bittensor_u16("MaxRegistrationsPerBlock", &[netuid])
+            .await?;
+        let this_block = self
+            .get_bittensor_u16("RegistrationsThisBlock", &[netuid])
+            .await?;
+        if max_per_block > 0
+            && this_block >= max_per_block
+            && cooldown.ready_at_block <= current_block
+        {
+            cooldown.ready_at_block = current_block + 1;
+            cooldown.reason = Some(format!(
+                "registrations this block {}/{}",
+                this_block, max_per_block
+            ));
+        }
+
+        Ok(cooldown)
+    }
+
+    // Subscribing to new block headers
+    pub async fn subscribe_new_heads(&self) -> Result<Subscription<BlockHeader>> {
+        self.chain
+            .subscribe_heads(false)
+            .await
+            .context("Failed to subscribe to new heads")
+    }
+
+    // Subscribing to finalized block headers
+    pub async fn subscribe_finalized_heads(&self) -> Result<Subscription<BlockHeader>> {
+        self.chain
+            .subscribe_heads(true)
+            .await
+            .context("Failed to subscribe to finalized heads")
+    }
+
+    // Round-trip times of a cheap call, to judge the endpoint before timed submissions
+    pub async fn measure_latency(&self, samples: usize) -> Result<Vec<Duration>> {
+        let mut latencies = Vec::with_capacity(samples);
+        for _ in 0..samples {
+            let started = Instant::now();
+            self.retrying("block_hash", || self.chain.block_hash(None))
+                .await
+                .context("Latency probe failed")?;
+            latencies.push(started.elapsed());
+        }
+        Ok(latencies)
+    }
+
+    // Getting the Timestamp.Now of a block in milliseconds, None when its state is pruned
+    async fn get_timestamp_at(&self, block_hash: H256) -> Result<Option<u64>> {
+        let storage_key = self.encode_storage_map_key("Timestamp", "Now", &[]);
+        let result = match self
+            .retrying("storage", || {
+                self.chain.storage(&storage_key, Some(block_hash))
+            })
+            .await
+        {
+            Ok(result) => result,
+            Err(ClientError::Call(_)) => return Ok(None),
+            Err(e) => return Err(e).context("Failed to get Timestamp from storage"),
+        };
+
+        result
+            .map(|bytes| {
+                u64::decode(&mut &bytes[..])
+                    .map_err(|e| anyhow!("Failed to decode Timestamp: {:?}", e))
+            })
+            .transpose()
+    }
+
+    // Average block time over the last sample_blocks blocks, None when it can't be measured
+    pub async fn measure_block_time(&self, sample_blocks: u64) -> Result<Option<Duration>> {
+        let current_block = self.get_current_block().await?;
+        let sample_blocks = sample_blocks.min(current_block);
+        if sample_blocks == 0 {
+            return Ok(None);
+        }
+
+        let latest = self.get_block_hash(Some(current_block)).await?;
+        let earliest = self
+            .get_block_hash(Some(current_block - sample_blocks))
+            .await?;
+        let (Some(latest), Some(earliest)) = (
+            self.get_timestamp_at(latest).await?,
+            self.get_timestamp_at(earliest).await?,
+        ) else {
+            return Ok(None);
+        };
+
+        Ok((latest > earliest).then(|| Duration::from_millis((latest - earliest) / sample_blocks)))
+    }
+
+    // Position of an extrinsic in a block, found by hashing the block's extrinsics
+    pub async fn find_extrinsic_index(
+        &self,
+        block_hash: H256,
+        tx_hash: H256,
+    ) -> Result<Option<u32>> {
+        let extrinsics = self
+            .retrying("block_extrinsics", || {
+                self.chain.block_extrinsics(block_hash)
+            })
+            .await
+            .context("Failed to get block")?;
+
+        Ok(extrinsics
+            .iter()
+            .position(|extrinsic| sp_core::blake2_256(extrinsic) == tx_hash.0)
+            .map(|index| index as u32))
+    }
+
+    // The runtime's metadata, decoded once per client. Refetched when asked to, after a
+    // runtime upgrade changed what it describes
+    async fn runtime_metadata(&self, refresh: bool) -> Result<Arc<Metadata>> {
+        if !refresh {
+            if let Some(metadata) = self.metadata.lock().unwrap().clone() {
+                return Ok(metadata);
+            }
+        }
+        let metadata = Arc::new(Metadata::decode(&self.get_metadata().await?)?);
+        *self.metadata.lock().unwrap() = Some(metadata.clone());
+        Ok(metadata)
+    }
+
+    // System::Events of a block, decoded with the runtime's metadata
+    pub async fn get_block_events(&self, block_hash: H256) -> Result<Vec<EventRecord>> {
+        let storage_key = self.encode_storage_map_key("System", "Events", &[]);
+        let events = self
+            .retrying("storage", || {
+                self.chain.storage(&storage_key, Some(block_hash))
+            })
+            .await
+            .context("Failed to get Events from storage")?;
+        let Some(events) = events else {
+            return Ok(Vec::new());
+        };
+        match self.runtime_metadata(false).await?.decode_events(&events) {
+            Ok(records) => Ok(records),
+            Err(_) => self
+                .runtime_metadata(true)
+                .await?
+                .decode_events(&events)
+                .context("Failed to decode block events"),
+        }
+    }
+
+    // Outcome of an included registration and the UID it was given, read from the events of
+    // its block. None when the extrinsic can't be found in the block
+    pub async fn get_registration_events(
+        &self,
+        inclusion: &Inclusion,
+        netuid: u16,
+        hotkey: &AccountId32,
+    ) -> Result<Option<RegistrationEvents>> {
+        let Some(index) = self
+            .find_extrinsic_index(inclusion.block_hash, inclusion.tx_hash)
+            .await?
+        else {
+            return Ok(None);
+        };
+        let events = self.get_block_events(inclusion.block_hash).await?;
+        let metadata = self.runtime_metadata(false).await?;
+        Ok(Some(RegistrationEvents {
+            outcome: extrinsic_outcome(&metadata, &events, index),
+            uid: neuron_registered(&events, index, netuid, hotkey),
+        }))
+    }
+
+    // Number of a block by its hash, e.g. the one an extrinsic was included in
+    pub async fn get_block_number(&self, block_hash: H256) -> Result<u64> {
+        let header = self
+            .retrying("header", || self.chain.header(block_hash))
+            .await
+            .context("Failed to get block header")?;
+        Ok(header.number)
+    }
+
+    // Getting the parent of a block, to read the state just before it
+    pub async fn get_parent_hash(&self, block_hash: H256) -> Result<H256> {
+        let header = self
+            .retrying("header", || self.chain.header(block_hash))
+            .await
+            .context("Failed to get block header")?;
+        header
+            .parent_hash
+            .ok_or_else(|| anyhow!("Block header without a parent hash"))
+    }
+
+    // Getting current block number, the pinned one when reading historical state
+    pub async fn get_current_block(&self) -> Result<u64> {
+        let block_hash = match self.pinned_block() {
+            Some(block_hash) => block_hash,
+            None => self
+                .retrying("block_hash", || self.chain.block_hash(None))
+                .await
+                .context("Failed to get current block hash")?
+                .ok_or_else(|| anyhow!("Node returned no best block"))?,
+        };
+
+        self.get_block_number(block_hash).await
+    }
+
+    // Signing and submitting a call without waiting for it to be included
+    async fn submit_signed_call(&self, call: Vec<u8>, signer: &KeyPair) -> Result<H256> {
+        let (tx_hash, _) = self.sign_and_submit(call, signer, false).await?;
+        Ok(tx_hash)
+    }
+
+    // Signing and submitting a call, then following it into a block
+    async fn submit_signed_call_until_included(
+        &self,
+        call: Vec<u8>,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        let (tx_hash, block_hash) = self.sign_and_submit(call, signer, true).await?;
+        Ok(Inclusion {
+            tx_hash,
+            block_hash: block_hash
+                .ok_or_else(|| anyhow!("Extrinsic was submitted but not followed into a block"))?,
+        })
+    }
+
+    // Signing with a nonce from the nonce manager and submitting, resyncing once when the
+    // chain says the nonce was already used. Returns the inclusion block when asked to wait
+    async fn sign_and_submit(
+        &self,
+        call: Vec<u8>,
+        signer: &KeyPair,
+        wait_for_inclusion: bool,
+    ) -> Result<(H256, Option<H256>)> {
+        let account_id = signer.account_id();
+
+        for attempt in 1..=2 {
+            let mut context = self.fetch_signing_context(&account_id).await?;
+            context.nonce = self.nonces.reserve(&account_id, context.nonce);
+            let pending = self.nonces.pending(&account_id);
+            if pending > 1 {
+                println!(
+                    "   Nonce {} ({} pending from this account)",
+                    context.nonce, pending
+                );
+            }
+
+            let submitted = if wait_for_inclusion {
+                self.watch_with_tip_escalation(&call, signer, &mut context)
+                    .await
+                    .map(|(tx_hash, block_hash)| (tx_hash, Some(block_hash)))
+            } else {
+                let extrinsic = sign_extrinsic(&call, signer, &context)?;
+                self.submit_extrinsic(&extrinsic)
+                    .await
+                    .map(|tx_hash| (tx_hash, None))
+            };
+            match submitted {
+                Ok((tx_hash, block_hash)) => {
+                    self.nonces.submitted(&account_id, context.nonce, tx_hash);
+                    return Ok((tx_hash, block_hash));
+                }
+                Err(e) if attempt == 1 && is_outdated_nonce(&e) => {
+                    println!("   🔄 Nonce {} is outdated, resyncing...", context.nonce);
+                    self.nonces.resync(&account_id);
+                }
+                Err(e) => {
+                    self.nonces.release(&account_id, context.nonce);
+                    return Err(e);
+                }
+            }
+        }
+
+        Err(anyhow!("Nonce still outdated after resyncing"))
+    }
+
+    // Following a signed call into a block. With tip escalation enabled, a dropped or stuck
+    // transaction is replaced by one with a higher tip at the same nonce until max_tip
+    async fn watch_with_tip_escalation(
+        &self,
+        call: &[u8],
+        signer: &KeyPair,
+        context: &mut SigningContext,
+    ) -> Result<(H256, H256)> {
+        let escalation = self.tip_escalation.lock().ok().and_then(|e| *e);
+        let initial_tip = context.tip;
+
+        loop {
+            let extrinsic = sign_extrinsic(call, signer, context)?;
+            let tx_hash = H256::from(sp_core::blake2_256(&extrinsic));
+            let escalation = escalation.filter(|e| context.tip < e.max_tip);
+            let stuck_blocks = escalation.map(|e| e.stuck_blocks);
+
+            match self.watch_extrinsic(&extrinsic, false, stuck_blocks).await {
+                Ok(block_hash) => return Ok((tx_hash, block_hash)),
+                Err(e) => {
+                    // Once escalated, a replacement the pool refuses is outbid by our own
+                    // earlier submission, which is still pending
+                    let escalated = context.tip > initial_tip;
+                    let stalled = is_stalled(&e)
+                        || (escalated && format!("{:#}", e).contains("Priority is too low"));
+                    match escalation {
+                        Some(escalation) if stalled => {
+                            context.tip = (context.tip + escalation.step).min(escalation.max_tip);
+                            println!(
+                                "   📈 {:#}, resubmitting with a {} tip",
+                                e,
+                                utils::format_tao(context.tip as u128)
+                            );
+                        }
+                        // Resyncing the nonce and submitting again could pay twice
+                        _ if escalated && is_outdated_nonce(&e) => {
+                            return Err(anyhow!(
+                                "An earlier, lower-tipped submission of this transaction \
+                                 (nonce {}) is pending or was included, check before retrying",
+                                context.nonce
+                            ));
+                        }
+                        _ => return Err(e),
+                    }
+                }
+            }
+        }
+    }
+
+    // Fetching nonce, runtime versions and block hashes required for signing
+    pub async fn fetch_signing_context(&self, account_id: &AccountId32) -> Result<SigningContext> {
+        let account_info = self.get_account_info(account_id).await?;
+        let current_block = self.get_current_block().await?;
+        let runtime_version = self.get_runtime_version().await?;
+
+        let genesis_hash = self.get_genesis_hash().await?;
+        let era = era::for_block(current_block);
+        let era_block_hash = match era {
+            Era::Immortal => genesis_hash,
+            Era::Mortal { .. } => self.get_block_hash(Some(era.birth(current_block))).await?,
+        };
+
+        Ok(SigningContext {
+            nonce: account_info.nonce as u64,
+            block_number: current_block,
+            spec_version: runtime_version.spec_version,
+            transaction_version: runtime_version.transaction_version,
+            genesis_hash,
+            era,
+            era_block_hash,
+            tip: DEFAULT_TIP.get().copied().unwrap_or(0),
+        })
+    }
+
+    // Getting the runtime spec and transaction versions
+    pub async fn get_runtime_version(&self) -> Result<RuntimeVersion> {
+        self.retrying("runtime_version", || self.chain.runtime_version())
+            .await
+            .context("Failed to get runtime version")
+    }
+
+    // SCALE encoded runtime metadata
+    pub async fn get_metadata(&self) -> Result<Vec<u8>> {
+        self.retrying("metadata", || self.chain.metadata())
+            .await
+            .context("Failed to get runtime metadata")
+    }
+
+    // Check every hardcoded call, storage and extension encoding against the node's runtime
+    // metadata. Reads keep working, submitting is refused when any encoding drifted (or the
+    // metadata can't be read) unless --force was given
+    pub async fn check_runtime_compatibility(&self) -> Result<()> {
+        let version = self.get_runtime_version().await?;
+        let metadata = self.get_metadata().await;
+        // Hash of the runtime metadata, identifies the exact runtime in reports
+        let metadata_hash = match &metadata {
+            Ok(bytes) => format!("{:?}", H256(sp_core::blake2_256(bytes))),
+            Err(e) => format!("unknown ({})", e),
+        };
+        let drift = metadata
+            .and_then(|bytes| Metadata::decode(&bytes))
+            .map(|metadata| metadata.drift(&Capabilities::current()));
+        let reason = match drift {
+            Ok(drift) if drift.is_empty() => {
+                log::debug!(
+                    "Spec version {} metadata {} matches every encoding",
+                    version.spec_version,
+                    metadata_hash
+                );
+                return Ok(());
+            }
+            Ok(drift) => format!(
+                "spec version {}, {} encoding mismatches",
+                version.spec_version,
+                drift.len()
+            ),
+            Err(e) => format!(
+                "spec version {}, metadata unchecked ({})",
+                version.spec_version, e
+            ),
+        };
+        let forced = ALLOW_UNVERIFIED_RUNTIME.get().copied().unwrap_or(false);
+        println!(
+            "⚠️ Runtime not verified with this tool: {}, metadata {}",
+            reason, metadata_hash
+        );
+        if forced {
+            println!("   --force given, extrinsics will be submitted anyway");
+        } else {
+            println!("   Extrinsics won't be submitted, their encoding may no longer match. Pass --force to override");
+            if let Ok(mut unverified) = self.unverified_runtime.lock() {
+                *unverified = Some(reason);
+            }
+        }
+        Ok(())
+    }
+
+    fn ensure_runtime_verified(&self) -> Result<()> {
+        match self.unverified_runtime.lock().ok().and_then(|r| r.clone()) {
+            Some(reason) => Err(RegistrationError::new(
+                ErrorKind::Fatal,
+                format!(
+                    "Refusing to submit to an unverified runtime ({}). Pass --force to submit anyway",
+                    reason
+                ),
+            )
+            .into()),
+            None => Ok(()),
+        }
+    }
+
+    pub async fn get_genesis_hash(&self) -> Result<H256> {
+        self.get_block_hash(Some(0))
+            .await
+            .context("Failed to get genesis hash")
+    }
+
+    pub async fn get_block_hash(&self, block_number: Option<u64>) -> Result<H256> {
+        self.retrying("block_hash", || self.chain.block_hash(block_number))
+            .await
+            .context("Failed to get block hash")?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Block {} doesn't exist yet",
+                    block_number.unwrap_or_default()
+                )
+            })
+    }
+
+    async fn submit_extrinsic(&self, extrinsic: &[u8]) -> Result<H256> {
+        self.ensure_runtime_verified()?;
+        self.retrying("submit", || self.chain.submit(extrinsic))
+            .await
+            .map_err(submit_error)
+    }
+
+    // Getting burn registration cost
+    /*async fn get_burn_cost(&self, netuid: u16) -> Result<u64> {
+        let params = rpc_params![
+            "SubtensorModule",
+            "Burn",
+            format!("0x{}", netuid.to_be_bytes().iter().map(|b| format!("{:02x}", b)).collect::<String>())
+        ];
+
+        let result: Option<String> = self.client
+            .request("state_getStorage", params)
+            .await
+            .context("Failed to get burn cost")?;
+
+        if let Some(hex_data) = result {
+            let bytes = hex::decode(&hex_data[2..])
+                .context("Invalid hex data")?;
+            let burn_cost = u64::from_le_bytes(
+                bytes.try_into()
+                    .map_err(|_| anyhow!("Invalid burn cost data"))?
+            );
+            Ok(burn_cost)
+        } else {
+            // Default burn cost if not set
+            Ok(1_000_000_000) // 1 TAO in RAO
+        }
+    }*/
+
+    // Getting account balance
+    pub async fn get_account_balance(&self, account: &AccountId32) -> Result<u64> {
+        let account_info = self.get_account_info(account).await?;
+        Ok(account_info.data.free as u64)
+    }
+
+    // Free balance as of a given block, e.g. the one a transaction was included in
+    pub async fn get_account_balance_at(
+        &self,
+        account: &AccountId32,
+        block_hash: H256,
+    ) -> Result<u64> {
+        let account_info = self.get_account_info_at(account, Some(block_hash)).await?;
+        Ok(account_info.data.free as u64)
+    }
+
+    async fn get_account_info(&self, account: &AccountId32) -> Result<AccountInfo> {
+        self.get_account_info_at(account, None).await
+    }
+
+    // Account info as of a given block, or the latest one
+    async fn get_account_info_at(
+        &self,
+        account: &AccountId32,
+        block_hash: Option<H256>,
+    ) -> Result<AccountInfo> {
+        // Create storage key for System::Account
+        let storage_key = self.encode_system_account_storage_key(account);
+        let at = block_hash.or(self.pinned_block());
+        let result = match self
+            .retrying("storage", || self.chain.storage(&storage_key, at))
+            .await
+        {
+            Ok(res) => res,
+            Err(e) => {
+                return Err(anyhow::anyhow!("Failed to get account info: {}", e));
+            }
+        };
+
+        if let Some(bytes) = result {
+            // Use proper SCALE decoding
+            match AccountInfo::decode(&mut &bytes[..]) {
+                Ok(account_info) => Ok(account_info),
+                Err(_) => {
+                    // Manual parsing following Python Bittensor approach
+                    // AccountInfo structure: nonce(4) + consumers(4) + providers(4) + sufficients(4) + AccountData(40)
+                    // AccountData structure: free(16) + reserved(16) + frozen(8) + flags(8) = 48 bytes
+                    // But we're seeing 56 bytes total, so AccountData is actually 40 bytes: free(16) + reserved(16) + frozen(8)
+                    if bytes.len() >= 56 {
+                        // Parse AccountInfo fields (first 16 bytes)
+                        let nonce = u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+                        let consumers =
+                            u32::from_le_bytes([bytes[4], bytes[5], bytes[6], bytes[7]]);
+                        let providers =
+                            u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]);
+                        let sufficients =
+                            u32::from_le_bytes([bytes[12], bytes[13], bytes[14], bytes[15]]);
+
+                        // Parse AccountData fields (next 40 bytes, starting at byte 16)
+                        // AccountData: free(16) + reserved(16) + misc_frozen(8) + fee_frozen(8) = 48 bytes
+                        // But actual data shows 40 bytes, so structure might be: free(16) + reserved(16) + frozen_data(8)
+
+                        // Free balance (bytes 16-31: 16 bytes for u128)
+                        let free_bytes = &bytes[16..32];
+                        let free = u128::from_le_bytes([
+                            free_bytes[0],
+                            free_bytes[1],
+                            free_bytes[2],
+                            free_bytes[3],
+                            free_bytes[4],
+                            free_bytes[5],
+                            free_bytes[6],
+                            free_bytes[7],
+                            free_bytes[8],
+                            free_bytes[9],
+                            free_bytes[10],
+                            free_bytes[11],
+                            free_bytes[12],
+                            free_bytes[13],
+                            free_bytes[14],
+                            free_bytes[15],
+                        ]);
+
+                        // Reserved balance (bytes 32-47: 16 bytes for u128)
+                        let reserved = if bytes.len() >= 48 {
+                            let reserved_bytes = &bytes[32..48];
+                            u128::from_le_bytes([
+                                reserved_bytes[0],
+                                reserved_bytes[1],
+                                reserved_bytes[2],
+                                reserved_bytes[3],
+                                reserved_bytes[4],
+                                reserved_bytes[5],
+                                reserved_bytes[6],
+                                reserved_bytes[7],
+                                reserved_bytes[8],
+                                reserved_bytes[9],
+                                reserved_bytes[10],
+                                reserved_bytes[11],
+                                reserved_bytes[12],
+                                reserved_bytes[13],
+                                reserved_bytes[14],
+                                reserved_bytes[15],
                             ])
                         } else {
                             0u128
@@ -893,138 +3093,889 @@ impl BittensorClient {
         }
     }
 
-    // Helper function to encode System::Account storage key
-    fn encode_system_account_storage_key(&self, account: &AccountId32) -> String {
-        use sp_core::{blake2_128, twox_128};
+    // Helper function to encode System::Account storage key
+    fn encode_system_account_storage_key(&self, account: &AccountId32) -> String {
+        use sp_core::{blake2_128, twox_128};
+
+        // System pallet hash
+        let pallet_hash = twox_128(b"System");
+
+        // Account storage hash
+        let storage_hash = twox_128(b"Account");
+
+        // For System::Account, Substrate uses Blake2_128Concat hasher
+        // This means: blake2_128(key) + key
+        let account_hash = blake2_128(account.as_ref());
+
+        let mut final_key = Vec::new();
+        final_key.extend_from_slice(&pallet_hash); // 16 bytes
+        final_key.extend_from_slice(&storage_hash); // 16 bytes
+        final_key.extend_from_slice(&account_hash); // 16 bytes
+        final_key.extend_from_slice(account.as_ref()); // 32 bytes
+
+        format!("0x{}", hex::encode(final_key))
+    }
+
+    // Claiming test TAO with proof of work, waits for inclusion
+    pub async fn submit_faucet(&self, work: &FaucetWork, signer: &KeyPair) -> Result<Inclusion> {
+        let call = encode_faucet_call(work);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending burned registration
+    pub async fn submit_burned_registration(
+        &self,
+        registration_data: &RegistrationData,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🔥 Submitting burned registration transaction...");
+
+        // Creating extrinsic for burned registration
+        let call = self.encode_burned_register_call(
+            registration_data.subnet_id,
+            registration_data.hotkey.clone(),
+        )?;
+
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending root_register, the root network takes no burn and no netuid
+    pub async fn submit_root_register(
+        &self,
+        hotkey: &AccountId32,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🌳 Submitting root_register transaction...");
+
+        let call = encode_root_register_call(hotkey);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending set_children, replacing the hotkey's children in a subnet
+    pub async fn submit_set_children(
+        &self,
+        hotkey: &AccountId32,
+        netuid: u16,
+        children: &[(u64, AccountId32)],
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("👶 Submitting set_children transaction...");
+
+        let call = encode_set_children_call(hotkey, netuid, children);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending set_identity for the signer's coldkey
+    pub async fn submit_set_identity(
+        &self,
+        identity: &ChainIdentity,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🪪 Submitting set_identity transaction...");
+
+        let call = encode_set_identity_call(identity);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending commit_weights, signed by the hotkey that sets the weights
+    pub async fn submit_commit_weights(
+        &self,
+        netuid: u16,
+        commit_hash: H256,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🔒 Submitting commit_weights transaction...");
+
+        let call = encode_commit_weights_call(netuid, commit_hash);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending reveal_weights with the weights and salt behind an earlier commit
+    pub async fn submit_reveal_weights(
+        &self,
+        commit: &WeightsCommit,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🔓 Submitting reveal_weights transaction...");
+
+        let call = encode_reveal_weights_call(commit);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending dTAO add_stake, TAO from the signer's coldkey into a subnet's alpha on a hotkey
+    pub async fn submit_add_stake_alpha(
+        &self,
+        hotkey: &AccountId32,
+        netuid: u16,
+        amount: u64,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!(
+            "📥 Submitting add_stake transaction for subnet {}...",
+            netuid
+        );
+
+        let call =
+            encode_subnet_stake_call(constants::ADD_STAKE_CALL_INDEX, hotkey, netuid, amount);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending dTAO remove_stake, alpha swapped back to TAO on the signer's coldkey
+    pub async fn submit_remove_stake_alpha(
+        &self,
+        hotkey: &AccountId32,
+        netuid: u16,
+        amount: u64,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!(
+            "📤 Submitting remove_stake transaction for subnet {}...",
+            netuid
+        );
+
+        let call =
+            encode_subnet_stake_call(constants::REMOVE_STAKE_CALL_INDEX, hotkey, netuid, amount);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Sending move_stake, the signer's alpha moved between hotkeys and subnets
+    pub async fn submit_move_stake(
+        &self,
+        stake_move: &StakeMove,
+        signer: &KeyPair,
+    ) -> Result<Inclusion> {
+        println!("🔀 Submitting move_stake transaction...");
+
+        let call = encode_move_stake_call(stake_move);
+        self.submit_signed_call_until_included(call, signer).await
+    }
+
+    // Moving a hotkey's registrations and stake to a new hotkey, signed by the coldkey
+    pub async fn submit_swap_hotkey(
+        &self,
+        old_hotkey: &AccountId32,
+        new_hotkey: &AccountId32,
+        coldkey_signer: &KeyPair,
+    ) -> Result<H256> {
+        println!("🔁 Submitting swap_hotkey transaction...");
+
+        let mut call = vec![
+            constants::SUBTENSOR_MODULE_INDEX,
+            constants::SWAP_HOTKEY_CALL_INDEX,
+        ];
+        old_hotkey.encode_to(&mut call);
+        new_hotkey.encode_to(&mut call);
+
+        self.submit_signed_call(call, coldkey_signer).await
+    }
+
+    // Publishing axon endpoint details, signed by the hotkey
+    pub async fn submit_serve_axon(
+        &self,
+        netuid: u16,
+        axon: &AxonInfo,
+        hotkey_signer: &KeyPair,
+    ) -> Result<H256> {
+        println!("📡 Submitting serve_axon transaction...");
+
+        let call = encode_serve_axon_call(netuid, axon);
+        self.submit_signed_call(call, hotkey_signer).await
+    }
+
+    // Sending transfer_keep_alive without waiting for inclusion, so several can share a block
+    pub async fn submit_transfer(
+        &self,
+        dest: &AccountId32,
+        amount: u64,
+        signer: &KeyPair,
+    ) -> Result<H256> {
+        let call = encode_transfer_keep_alive_call(dest, amount);
+        self.submit_signed_call(call, signer).await
+    }
+
+    // Building a signed transfer_keep_alive without touching the network
+    pub fn build_transfer(
+        &self,
+        dest: &AccountId32,
+        amount: u64,
+        signer: &KeyPair,
+        context: &SigningContext,
+    ) -> Result<Vec<u8>> {
+        let call = encode_transfer_keep_alive_call(dest, amount);
+        sign_extrinsic(&call, signer, context)
+    }
+
+    // Submitting an extrinsic and following it until it is finalized, returns the block hash
+    pub async fn submit_and_watch_extrinsic(&self, extrinsic: &[u8]) -> Result<H256> {
+        self.watch_extrinsic(extrinsic, true, None).await
+    }
+
+    // Submitting an extrinsic and following it until it is in a block (or finalized), returns
+    // that block's hash
+    // Gives up with a "not included after" error once stuck_blocks pass without inclusion
+    async fn watch_extrinsic(
+        &self,
+        extrinsic: &[u8],
+        until_finalized: bool,
+        stuck_blocks: Option<u64>,
+    ) -> Result<H256> {
+        self.ensure_runtime_verified()?;
+        let mut subscription = self
+            .chain
+            .submit_and_watch(extrinsic)
+            .await
+            .map_err(submit_error)?;
+        let stuck = async {
+            match stuck_blocks {
+                Some(blocks) => tokio::time::sleep(utils::blocks_to_duration(blocks)).await,
+                None => std::future::pending().await,
+            }
+        };
+        tokio::pin!(stuck);
+        let mut included = false;
+
+        loop {
+            let status = tokio::select! {
+                status = subscription.next() => status,
+                _ = &mut stuck, if !included => {
+                    return Err(RegistrationError::new(
+                        ErrorKind::Chain,
+                        format!(
+                            "Transaction not included after {} blocks",
+                            stuck_blocks.unwrap_or_default()
+                        ),
+                    )
+                    .into())
+                }
+                // The extrinsic is already out, only following it stops
+                _ = self.cancel.cancelled() => {
+                    return Err(RegistrationError::new(
+                        ErrorKind::Fatal,
+                        "Cancelled, the extrinsic was submitted and may still be included",
+                    )
+                    .into())
+                }
+            };
+            let Some(status) = status else { break };
+            let status = status.context("Failed to read extrinsic status")?;
+
+            // The pool gave up on it, or another transaction took its place
+            let ended = match status {
+                TxStatus::InBlock(block_hash) => {
+                    println!("   📦 Included in block {:?}", block_hash);
+                    included = true;
+                    if !until_finalized {
+                        return Ok(block_hash);
+                    }
+                    continue;
+                }
+                TxStatus::Finalized(block_hash) => return Ok(block_hash),
+                TxStatus::Ready => {
+                    println!("   ⏳ Transaction ready");
+                    continue;
+                }
+                TxStatus::Future => {
+                    println!("   ⏳ Transaction future");
+                    continue;
+                }
+                TxStatus::Broadcast(_) | TxStatus::Retracted(_) => continue,
+                TxStatus::FinalityTimeout(_) => "finalityTimeout",
+                TxStatus::Usurped(_) => "usurped",
+                TxStatus::Dropped => "dropped",
+                TxStatus::Invalid => "invalid",
+            };
+            return Err(
+                RegistrationError::new(ErrorKind::Chain, format!("Transaction {}", ended)).into(),
+            );
+        }
+
+        Err(RegistrationError::new(
+            ErrorKind::Connection,
+            "Extrinsic status subscription closed before finalization",
+        )
+        .into())
+    }
+
+    // Building a signed burned registration without touching the network
+    pub fn build_burned_registration(
+        &self,
+        registration_data: &RegistrationData,
+        signer: &KeyPair,
+        context: &SigningContext,
+    ) -> Result<Vec<u8>> {
+        let call = self.encode_burned_register_call(
+            registration_data.subnet_id,
+            registration_data.hotkey.clone(),
+        )?;
+
+        sign_extrinsic(&call, signer, context)
+    }
+
+    // Executing an extrinsic against the current state without submitting it
+    pub async fn dry_run_extrinsic(&self, extrinsic: &[u8]) -> Result<DryRunOutcome> {
+        // system_dryRun is an unsafe RPC, public nodes often only allow validation
+        let dry_run = self
+            .retrying("dry_run", || self.chain.dry_run(extrinsic))
+            .await;
+
+        let bytes = match dry_run {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                println!(
+                    "ℹ️ system_dryRun unavailable ({}), validating transaction instead",
+                    e
+                );
+                return self.validate_extrinsic(extrinsic).await;
+            }
+        };
+
+        // ApplyExtrinsicResult = Result<Result<(), DispatchError>, TransactionValidityError>
+        Ok(match bytes.as_slice() {
+            [0, 0, ..] => DryRunOutcome {
+                would_succeed: true,
+                detail: "extrinsic dispatched successfully".to_string(),
+            },
+            [0, 1, error @ ..] => DryRunOutcome {
+                would_succeed: false,
+                detail: format!("dispatch error: {}", describe_dispatch_error(error)),
+            },
+            [1, error @ ..] => DryRunOutcome {
+                would_succeed: false,
+                detail: format!("invalid transaction: {}", describe_validity_error(error)),
+            },
+            _ => {
+                return Err(anyhow!(
+                    "Unexpected dry run result: 0x{}",
+                    hex::encode(&bytes)
+                ))
+            }
+        })
+    }
+
+    // Checking an extrinsic against the transaction pool rules
+    async fn validate_extrinsic(&self, extrinsic: &[u8]) -> Result<DryRunOutcome> {
+        let block_hash = self.get_block_hash(None).await?;
+
+        let mut params = vec![2u8]; // TransactionSource::External
+        params.extend_from_slice(extrinsic);
+        block_hash.encode_to(&mut params);
+
+        let bytes = self
+            .retrying("runtime_call", || {
+                self.chain.runtime_call(
+                    "TaggedTransactionQueue_validate_transaction",
+                    &params,
+                    None,
+                )
+            })
+            .await
+            .context("Failed to validate transaction")?;
+
+        // TransactionValidity = Result<ValidTransaction, TransactionValidityError>
+        Ok(match bytes.as_slice() {
+            [0, ..] => DryRunOutcome {
+                would_succeed: true,
+                detail: "transaction is valid (dispatch not simulated)".to_string(),
+            },
+            [1, error @ ..] => DryRunOutcome {
+                would_succeed: false,
+                detail: format!("invalid transaction: {}", describe_validity_error(error)),
+            },
+            _ => {
+                return Err(anyhow!(
+                    "Unexpected validation result: 0x{}",
+                    hex::encode(&bytes)
+                ))
+            }
+        })
+    }
+
+    // Getting the fee the chain would charge for an extrinsic, in RAO
+    // Through payment_queryInfo, or the TransactionPaymentApi where that RPC is disabled
+    pub async fn get_extrinsic_fee(&self, extrinsic: &[u8]) -> Result<u128> {
+        match self
+            .retrying("partial_fee", || self.chain.partial_fee(extrinsic))
+            .await
+        {
+            Ok(fee) => Ok(fee),
+            Err(e) => {
+                log::debug!("payment_queryInfo failed, using the runtime API: {:#}", e);
+                let mut params = extrinsic.to_vec();
+                (extrinsic.len() as u32).encode_to(&mut params);
+                let info: RuntimeDispatchInfo = self
+                    .runtime_api_call("TransactionPaymentApi_query_info", params)
+                    .await
+                    .context("Failed to query fee info")?;
+                Ok(info.partial_fee as u128)
+            }
+        }
+    }
+
+    // Fee of a call from account before it can be signed, e.g. for a view-only coldkey. The
+    // fee only depends on the call, its length and the tip, so a blank sr25519 signature does
+    pub async fn estimate_call_fee(&self, call: &[u8], account: &AccountId32) -> Result<u128> {
+        let context = self.fetch_signing_context(account).await?;
+        let mut signature = vec![0x01u8];
+        signature.extend_from_slice(&[0u8; 64]);
+        let extrinsic = assemble_extrinsic(call, account, &signature, &context);
+        self.get_extrinsic_fee(&extrinsic).await
+    }
+
+    // Sending an already signed extrinsic
+    pub async fn submit_signed_extrinsic(&self, extrinsic: &[u8]) -> Result<H256> {
+        self.submit_extrinsic(extrinsic).await
+    }
+
+    // Encoding burned register call
+    // burned_register(netuid, hotkey). The burn isn't an argument, the chain takes the
+    // subnet's burn at inclusion
+    pub fn encode_burned_register_call(&self, netuid: u16, hotkey: AccountId32) -> Result<Vec<u8>> {
+        let mut call = vec![
+            constants::SUBTENSOR_MODULE_INDEX,
+            constants::BURNED_REGISTER_CALL_INDEX,
+        ];
+        netuid.encode_to(&mut call);
+        hotkey.encode_to(&mut call);
+
+        Ok(call)
+    }
+}
+
+// Signing a call locally with a prefetched context (extrinsic format v4)
+pub fn sign_extrinsic(call: &[u8], signer: &KeyPair, context: &SigningContext) -> Result<Vec<u8>> {
+    let signature = signer.sign_multi(&signing_payload(call, context));
+    Ok(assemble_extrinsic(
+        call,
+        &signer.account_id(),
+        &signature,
+        context,
+    ))
+}
+
+// Bytes the signer signs: call ++ extra ++ additional signed, hashed when over 256 bytes
+pub fn signing_payload(call: &[u8], context: &SigningContext) -> Vec<u8> {
+    let mut payload = call.to_vec();
+    payload.extend(encode_signed_extra(context));
+    payload.extend(encode_additional_signed(context));
+
+    if payload.len() > 256 {
+        sp_core::blake2_256(&payload).to_vec()
+    } else {
+        payload
+    }
+}
+
+// Signed extrinsic from a call and a SCALE-encoded MultiSignature made elsewhere
+pub fn assemble_extrinsic(
+    call: &[u8],
+    account_id: &AccountId32,
+    signature: &[u8],
+    context: &SigningContext,
+) -> Vec<u8> {
+    let mut extrinsic = Vec::new();
 
-        // System pallet hash
-        let pallet_hash = twox_128(b"System");
+    // Version 4 with signature
+    extrinsic.push(0x84u8);
 
-        // Account storage hash
-        let storage_hash = twox_128(b"Account");
+    // Signer as MultiAddress::Id
+    extrinsic.push(0x00u8);
+    account_id.encode_to(&mut extrinsic);
 
-        // For System::Account, Substrate uses Blake2_128Concat hasher
-        // This means: blake2_128(key) + key
-        let account_hash = blake2_128(account.as_ref());
+    // Signature as MultiSignature, variant chosen by the signer's scheme
+    extrinsic.extend_from_slice(signature);
 
-        let mut final_key = Vec::new();
-        final_key.extend_from_slice(&pallet_hash); // 16 bytes
-        final_key.extend_from_slice(&storage_hash); // 16 bytes
-        final_key.extend_from_slice(&account_hash); // 16 bytes
-        final_key.extend_from_slice(account.as_ref()); // 32 bytes
+    extrinsic.extend(encode_signed_extra(context));
+    extrinsic.extend_from_slice(call);
 
-        format!("0x{}", hex::encode(final_key))
-    }
+    // Compact length prefix
+    let mut final_extrinsic = Compact(extrinsic.len() as u32).encode();
+    final_extrinsic.extend(extrinsic);
 
-    // Debug function to test account info with known accounts
-    pub async fn debug_account_info(&self, account: &AccountId32) -> Result<()> {
-        println!(
-            "🐛 DEBUG: Testing account info for: {}",
-            account.to_ss58check()
-        );
+    final_extrinsic
+}
 
-        // First test the raw storage access
-        let storage_key = self.encode_system_account_storage_key(account);
-        println!("🐛 DEBUG: Storage key: {}", storage_key);
-        println!(
-            "🐛 DEBUG: Storage key length: {} bytes",
-            (storage_key.len() - 2) / 2
-        );
+// Explicit signed extensions, in runtime order: CheckEra, CheckNonce,
+// ChargeTransactionPayment and CheckMetadataHash (the rest encode nothing)
+fn encode_signed_extra(context: &SigningContext) -> Vec<u8> {
+    let mut extra = Vec::new();
 
-        let result: Option<String> = self
-            .client
-            .request("state_getStorage", rpc_params![storage_key])
-            .await
-            .context("Failed to get account info")?;
+    extra.extend(context.era.encode());
 
-        println!("🐛 DEBUG: Raw result: {:?}", result);
+    // Nonce
+    Compact(context.nonce as u32).encode_to(&mut extra);
 
-        if let Some(hex_data) = result {
-            println!("🐛 DEBUG: Hex data: {}", hex_data);
-            println!("🐛 DEBUG: Data length: {} bytes", (hex_data.len() - 2) / 2);
+    // Tip
+    Compact(context.tip as u128).encode_to(&mut extra);
 
-            let bytes = hex::decode(&hex_data[2..]).context("Invalid hex data")?;
-            println!("🐛 DEBUG: Decoded bytes length: {}", bytes.len());
-            println!(
-                "🐛 DEBUG: First 20 bytes: {:?}",
-                &bytes[..20.min(bytes.len())]
-            );
+    // Metadata hash check disabled
+    extra.push(0u8);
 
-            // Now test the account info parsing
-            println!("🐛 DEBUG: Testing get_account_info parsing...");
-            match self.get_account_info(account).await {
-                Ok(account_info) => {
-                    println!("🐛 DEBUG: ✅ Account info parsed successfully!");
-                    println!("🐛 DEBUG: Nonce: {}", account_info.nonce);
-                    println!("🐛 DEBUG: Consumers: {}", account_info.consumers);
-                    println!("🐛 DEBUG: Providers: {}", account_info.providers);
-                    println!("🐛 DEBUG: Sufficients: {}", account_info.sufficients);
-                    println!("🐛 DEBUG: Free balance: {} RAO", account_info.data.free);
-                    println!(
-                        "🐛 DEBUG: Free balance: {:.6} TAO",
-                        utils::format_tao(account_info.data.free)
-                    );
-                    println!("🐛 DEBUG: Reserved: {} RAO", account_info.data.reserved);
-                    println!("🐛 DEBUG: Frozen balance: {} RAO", account_info.data.frozen);
-                    println!("🐛 DEBUG: Flags: {:#x}", account_info.data.flags);
-                }
-                Err(e) => {
-                    println!("🐛 DEBUG: ❌ Failed to parse account info: {}", e);
-                }
-            }
-        } else {
-            println!("🐛 DEBUG: No data returned - account doesn't exist or wrong storage key");
-        }
+    extra
+}
 
-        Ok(())
+// Implicit data the signature commits to: CheckSpecVersion, CheckTxVersion,
+// CheckGenesis, CheckEra and CheckMetadataHash
+fn encode_additional_signed(context: &SigningContext) -> Vec<u8> {
+    let mut additional = Vec::new();
+    context.spec_version.encode_to(&mut additional);
+    context.transaction_version.encode_to(&mut additional);
+    additional.extend_from_slice(context.genesis_hash.as_bytes());
+    additional.extend_from_slice(context.era_block_hash.as_bytes());
+    None::<[u8; 32]>.encode_to(&mut additional);
+    additional
+}
+
+// faucet(block_number, nonce, work), the work being the 32 seal bytes
+fn encode_faucet_call(work: &FaucetWork) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::FAUCET_CALL_INDEX,
+    ];
+    work.block_number.encode_to(&mut call);
+    work.nonce.encode_to(&mut call);
+    work.seal.as_bytes().to_vec().encode_to(&mut call);
+    call
+}
+
+// root_register(hotkey)
+fn encode_root_register_call(hotkey: &AccountId32) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::ROOT_REGISTER_CALL_INDEX,
+    ];
+    hotkey.encode_to(&mut call);
+    call
+}
+
+// set_children(hotkey, netuid, children), each child a (proportion, hotkey) pair
+fn encode_set_children_call(
+    hotkey: &AccountId32,
+    netuid: u16,
+    children: &[(u64, AccountId32)],
+) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::SET_CHILDREN_CALL_INDEX,
+    ];
+    hotkey.encode_to(&mut call);
+    netuid.encode_to(&mut call);
+    children.encode_to(&mut call);
+    call
+}
+
+// set_identity(name, url, github_repo, image, discord, description, additional)
+fn encode_set_identity_call(identity: &ChainIdentity) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::SET_IDENTITY_CALL_INDEX,
+    ];
+    for (_, value, _) in identity.fields() {
+        value.as_bytes().encode_to(&mut call);
     }
+    call
+}
 
-    // Sending burned registration
-    pub async fn submit_burned_registration(
-        &self,
-        registration_data: &RegistrationData,
-        signer: &Sr25519Pair,
-    ) -> Result<H256> {
-        println!("🔥 Submitting burned registration transaction...");
+// commit_weights(netuid, commit_hash)
+fn encode_commit_weights_call(netuid: u16, commit_hash: H256) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::COMMIT_WEIGHTS_CALL_INDEX,
+    ];
+    netuid.encode_to(&mut call);
+    call.extend_from_slice(commit_hash.as_bytes());
+    call
+}
 
-        // Creating extrinsic for burned registration
-        let call = self.encode_burned_register_call(
-            registration_data.subnet_id,
-            registration_data.hotkey.clone(),
-            registration_data.burn_amount,
-        )?;
+// reveal_weights(netuid, uids, values, salt, version_key)
+fn encode_reveal_weights_call(commit: &WeightsCommit) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::REVEAL_WEIGHTS_CALL_INDEX,
+    ];
+    commit.netuid.encode_to(&mut call);
+    commit.uids.encode_to(&mut call);
+    commit.values.encode_to(&mut call);
+    commit.salt.encode_to(&mut call);
+    commit.version_key.encode_to(&mut call);
+    call
+}
 
-        let extrinsic = self.create_signed_extrinsic(call, signer).await?;
-        self.submit_extrinsic(hex::encode(extrinsic)).await
+// Shares of a hotkey's pool in alpha, rounded down like the runtime. Both share counts are
+// U64F64 bits, so their scale cancels out
+fn alpha_for_shares(shares: u128, total_shares: u128, total_alpha: u64) -> u64 {
+    if total_shares == 0 {
+        return 0;
     }
+    let alpha = U256::from(total_alpha) * U256::from(shares) / U256::from(total_shares);
+    alpha.min(U256::from(total_alpha)).as_u64()
+}
 
-    // Encoding burned register call
-    fn encode_burned_register_call(
-        &self,
-        netuid: u16,
-        hotkey: AccountId32,
-        burn_amount: u64,
-    ) -> Result<Vec<u8>> {
-        let mut call = Vec::new();
+// dTAO add_stake and remove_stake: (hotkey, netuid, amount)
+fn encode_subnet_stake_call(
+    call_index: u8,
+    hotkey: &AccountId32,
+    netuid: u16,
+    amount: u64,
+) -> Vec<u8> {
+    let mut call = vec![constants::SUBTENSOR_MODULE_INDEX, call_index];
+    hotkey.encode_to(&mut call);
+    netuid.encode_to(&mut call);
+    amount.encode_to(&mut call);
+    call
+}
 
-        // Module index (SubtensorModule)
-        call.push(8u8);
+// move_stake(origin_hotkey, destination_hotkey, origin_netuid, destination_netuid, alpha_amount)
+fn encode_move_stake_call(stake_move: &StakeMove) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::MOVE_STAKE_CALL_INDEX,
+    ];
+    stake_move.origin_hotkey.encode_to(&mut call);
+    stake_move.destination_hotkey.encode_to(&mut call);
+    stake_move.origin_netuid.encode_to(&mut call);
+    stake_move.destination_netuid.encode_to(&mut call);
+    stake_move.amount.encode_to(&mut call);
+    call
+}
 
-        // Call index (burned_register)
-        call.push(1u8);
+// serve_axon(netuid, version, ip, port, ip_type, protocol, placeholder1, placeholder2)
+fn encode_serve_axon_call(netuid: u16, axon: &AxonInfo) -> Vec<u8> {
+    let mut call = vec![
+        constants::SUBTENSOR_MODULE_INDEX,
+        constants::SERVE_AXON_CALL_INDEX,
+    ];
+    netuid.encode_to(&mut call);
+    axon.version.encode_to(&mut call);
+    axon.ip.encode_to(&mut call);
+    axon.port.encode_to(&mut call);
+    axon.ip_type.encode_to(&mut call);
+    axon.protocol.encode_to(&mut call);
+    axon.placeholder1.encode_to(&mut call);
+    axon.placeholder2.encode_to(&mut call);
+    call
+}
 
-        // Parameters
-        netuid.encode_to(&mut call);
-        hotkey.encode_to(&mut call);
-        burn_amount.encode_to(&mut call);
+// Balances::transfer_keep_alive(MultiAddress::Id(dest), Compact(amount))
+fn encode_transfer_keep_alive_call(dest: &AccountId32, amount: u64) -> Vec<u8> {
+    let mut call = vec![
+        constants::BALANCES_MODULE_INDEX,
+        constants::TRANSFER_KEEP_ALIVE_CALL_INDEX,
+        0x00,
+    ];
+    dest.encode_to(&mut call);
+    Compact(amount).encode_to(&mut call);
+    call
+}
 
-        Ok(call)
+// A submission the node refused, classed by the reason it gave. A lost connection keeps its
+// ClientError, so it still classifies as one
+fn submit_error(error: ClientError) -> anyhow::Error {
+    match error {
+        ClientError::Call(_) => {
+            let message = format!("Failed to submit extrinsic: {}", error);
+            let kind = errors::node_error_kind(&message).unwrap_or(ErrorKind::Chain);
+            RegistrationError::new(kind, message).into()
+        }
+        error => anyhow::Error::new(error).context("Failed to submit extrinsic"),
+    }
+}
+
+// System::ExtrinsicSuccess or ExtrinsicFailed of an extrinsic, with its dispatch error
+fn extrinsic_outcome(
+    metadata: &Metadata,
+    events: &[EventRecord],
+    index: u32,
+) -> Option<ExtrinsicOutcome> {
+    events
+        .iter()
+        .filter(|event| event.extrinsic_index == Some(index))
+        .find_map(|event| {
+            if event.is("System", "ExtrinsicSuccess") {
+                return Some(ExtrinsicOutcome::Success);
+            }
+            if !event.is("System", "ExtrinsicFailed") {
+                return None;
+            }
+            let error = event.field("dispatch_error").map_or_else(
+                || "unknown".to_string(),
+                |error| metadata.describe_dispatch_error(error.ty, &error.bytes),
+            );
+            Some(ExtrinsicOutcome::Failed(format!(
+                "dispatch error: {}",
+                error
+            )))
+        })
+}
+
+// UID from SubtensorModule::NeuronRegistered(netuid, uid, hotkey) for this hotkey
+fn neuron_registered(
+    events: &[EventRecord],
+    index: u32,
+    netuid: u16,
+    hotkey: &AccountId32,
+) -> Option<u16> {
+    events
+        .iter()
+        .filter(|event| {
+            event.extrinsic_index == Some(index) && event.is("SubtensorModule", "NeuronRegistered")
+        })
+        .find_map(|event| {
+            let [registered_netuid, uid, registered] = &event.fields[..] else {
+                return None;
+            };
+            let registered_netuid = u16::decode_all(&mut &registered_netuid.bytes[..]).ok()?;
+            let registered = AccountId32::decode_all(&mut &registered.bytes[..]).ok()?;
+            if registered_netuid != netuid || registered != *hotkey {
+                return None;
+            }
+            u16::decode_all(&mut &uid.bytes[..]).ok()
+        })
+}
+
+// A DispatchError as dry runs return it, without metadata to name module errors
+fn describe_dispatch_error(error: &[u8]) -> String {
+    const VARIANTS: &[&str] = &[
+        "Other",
+        "CannotLookup",
+        "BadOrigin",
+        "Module",
+        "ConsumerRemaining",
+        "NoProviders",
+        "TooManyConsumers",
+        "Token",
+        "Arithmetic",
+        "Transactional",
+        "Exhausted",
+        "Corruption",
+        "Unavailable",
+        "RootNotAllowed",
+    ];
+
+    match error {
+        [3, pallet, index, ..] => format!("Module(pallet {}, error {})", pallet, index),
+        [variant, ..] => VARIANTS
+            .get(*variant as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("unknown variant {}", variant)),
+        [] => "empty error".to_string(),
+    }
+}
+
+fn describe_validity_error(error: &[u8]) -> String {
+    const INVALID: &[&str] = &[
+        "Call",
+        "Payment (insufficient balance for fees)",
+        "Future (nonce too high)",
+        "Stale (nonce already used)",
+        "BadProof (bad signature)",
+        "AncientBirthBlock",
+        "ExhaustsResources",
+        "Custom",
+        "BadMandatory",
+        "MandatoryValidation",
+        "BadSigner",
+    ];
+
+    match error {
+        [0, 7, code, ..] => format!("Custom({})", code),
+        [0, variant, ..] => INVALID
+            .get(*variant as usize)
+            .map(|name| name.to_string())
+            .unwrap_or_else(|| format!("unknown invalid variant {}", variant)),
+        [1, code, ..] => format!("Unknown({})", code),
+        _ => format!("0x{}", hex::encode(error)),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::chain::{event_record, MockChain};
+    use sp_core::{sr25519::Pair as Sr25519Pair, Pair};
+
+    // Retries and reconnects without the waits
+    fn mock_client(chain: &Arc<MockChain>) -> BittensorClient<MockChain> {
+        let backoff = Backoff {
+            initial_ms: 1,
+            max_ms: 1,
+            ..Backoff::default()
+        };
+        BittensorClient::with_chain("mock".to_string(), chain.clone(), backoff)
+    }
+
+    #[tokio::test]
+    async fn test_runtime_compatibility() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        client.check_runtime_compatibility().await.unwrap();
+        client.ensure_runtime_verified().unwrap();
+
+        // Metadata that can't be checked refuses submitting, reads keep working
+        chain.set_metadata(b"junk".to_vec());
+        let client = mock_client(&chain);
+        client.check_runtime_compatibility().await.unwrap();
+        let error = client.ensure_runtime_verified().unwrap_err();
+        assert!(error.to_string().contains("metadata unchecked"));
+        assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+    }
+
+    #[test]
+    fn test_nonce_manager() {
+        let nonces = NonceManager::default();
+        let account = AccountId32::new([1; 32]);
+
+        // Concurrent submitters get consecutive nonces from the first on-chain value
+        assert_eq!(nonces.reserve(&account, 5), 5);
+        assert_eq!(nonces.reserve(&account, 5), 6);
+        nonces.submitted(&account, 5, H256::zero());
+        assert_eq!(nonces.pending(&account), 2);
+
+        // A failed last submission gives its nonce back
+        nonces.release(&account, 6);
+        assert_eq!(nonces.reserve(&account, 5), 6);
+
+        // Included extrinsics drop out once the chain nonce passes them
+        assert_eq!(nonces.reserve(&account, 7), 7);
+        assert_eq!(nonces.pending(&account), 1);
+
+        nonces.resync(&account);
+        assert_eq!(nonces.pending(&account), 0);
+        assert_eq!(nonces.reserve(&account, 3), 3);
+
+        assert!(is_outdated_nonce(&anyhow!(
+            "Invalid Transaction: Transaction is outdated"
+        )));
+        assert!(!is_outdated_nonce(&anyhow!("Inability to pay some fees")));
+    }
+
+    #[test]
+    fn test_projected_burn() {
+        let schedule = BurnSchedule {
+            burn: 1_000_000_000,
+            min_burn: 500_000_000,
+            max_burn: 100_000_000_000,
+            target_registrations_per_interval: 2,
+            registrations_this_interval: 6,
+            ..Default::default()
+        };
+        // No smoothing: 4x the target doubles the burn
+        assert_eq!(schedule.projected_burn(), 2_000_000_000);
+
+        // Half the weight kept on the old burn
+        let smoothed = BurnSchedule {
+            adjustment_alpha: u64::MAX / 2,
+            ..schedule.clone()
+        };
+        assert_eq!(smoothed.projected_burn(), 1_500_000_000);
+
+        // No registrations halves the burn, floored at min_burn
+        let quiet = BurnSchedule {
+            registrations_this_interval: 0,
+            min_burn: 600_000_000,
+            ..schedule
+        };
+        assert_eq!(quiet.projected_burn(), 600_000_000);
+    }
 
     #[test]
     fn test_registration_data_encode() {
@@ -1034,10 +3985,950 @@ mod tests {
             coldkey: AccountId32::new([2u8; 32]),
             burn_amount: 12345,
             block_number: 67890,
+            fee: Some(100),
         };
 
         // Serialization test
         let json = serde_json::to_string(&registration);
         assert!(json.is_ok());
     }
+
+    #[test]
+    fn test_describe_errors() {
+        assert_eq!(
+            describe_dispatch_error(&[3, 8, 6, 0, 0, 0]),
+            "Module(pallet 8, error 6)"
+        );
+        assert_eq!(describe_dispatch_error(&[2]), "BadOrigin");
+        assert_eq!(
+            describe_validity_error(&[0, 3]),
+            "Stale (nonce already used)"
+        );
+        assert_eq!(describe_validity_error(&[0, 7, 12]), "Custom(12)");
+    }
+
+    #[test]
+    fn test_neuron_info_decode() {
+        let mut bytes = vec![1u8]; // Some(..)
+        AccountId32::new([1u8; 32]).encode_to(&mut bytes);
+        AccountId32::new([2u8; 32]).encode_to(&mut bytes);
+        Compact(7u16).encode_to(&mut bytes); // uid
+        Compact(3u16).encode_to(&mut bytes); // netuid
+        true.encode_to(&mut bytes); // active
+        (1u64, 0u32, 0x7f000001u128, 8091u16, 4u8, 0u8, 0u8, 0u8).encode_to(&mut bytes);
+        (0u64, 0u32, 0u128, 0u16, 0u8).encode_to(&mut bytes);
+        vec![(AccountId32::new([2u8; 32]), Compact(5_000_000_000u64))].encode_to(&mut bytes);
+        Compact(10u16).encode_to(&mut bytes); // rank
+        Compact(1_000u64).encode_to(&mut bytes); // emission
+        for value in [20u16, 30, 40, 50, 60] {
+            Compact(value).encode_to(&mut bytes); // incentive .. dividends
+        }
+        Compact(123u64).encode_to(&mut bytes); // last_update
+        false.encode_to(&mut bytes); // validator_permit
+        vec![(Compact(1u16), Compact(65535u16))].encode_to(&mut bytes);
+        Vec::<(Compact<u16>, Compact<u16>)>::new().encode_to(&mut bytes);
+        Compact(9u16).encode_to(&mut bytes); // pruning_score
+
+        let raw = Option::<RawNeuronInfo>::decode(&mut &bytes[..])
+            .unwrap()
+            .unwrap();
+        let neuron = NeuronInfo::from(raw);
+
+        assert_eq!(neuron.uid, 7);
+        assert_eq!(neuron.netuid, 3);
+        assert_eq!(neuron.axon_info.port, 8091);
+        assert_eq!(neuron.stake[0].1, 5_000_000_000);
+        assert_eq!(neuron.emission, 1_000);
+        assert_eq!(neuron.dividends, 60);
+        assert_eq!(neuron.last_update, 123);
+        assert_eq!(neuron.weights, vec![(1, 65535)]);
+        assert_eq!(neuron.pruning_score, 9);
+    }
+
+    // An encoded NeuronInfoLite of subnet 1
+    fn neuron_lite_bytes(uid: u16) -> Vec<u8> {
+        let mut bytes = AccountId32::new([1u8; 32]).encode();
+        AccountId32::new([2u8; 32]).encode_to(&mut bytes);
+        Compact(uid).encode_to(&mut bytes);
+        Compact(1u16).encode_to(&mut bytes); // netuid
+        true.encode_to(&mut bytes); // active
+        (1u64, 0u32, 0x7f000001u128, 8091u16, 4u8, 0u8, 0u8, 0u8).encode_to(&mut bytes);
+        (0u64, 0u32, 0u128, 0u16, 0u8).encode_to(&mut bytes);
+        vec![
+            (AccountId32::new([2u8; 32]), Compact(3_000u64)),
+            (AccountId32::new([3u8; 32]), Compact(2_000u64)),
+        ]
+        .encode_to(&mut bytes);
+        Compact(10u16).encode_to(&mut bytes); // rank
+        Compact(1_000u64).encode_to(&mut bytes); // emission
+        for value in [20u16, 30, 40, 50, 60] {
+            Compact(value).encode_to(&mut bytes); // incentive .. dividends
+        }
+        Compact(123u64).encode_to(&mut bytes); // last_update
+        true.encode_to(&mut bytes); // validator_permit
+        Compact(9u16).encode_to(&mut bytes); // pruning_score
+        bytes
+    }
+
+    #[test]
+    fn test_neurons_lite_decode() {
+        let mut bytes = Compact(1u32).encode(); // one neuron
+        bytes.extend(neuron_lite_bytes(4));
+
+        let neurons = Vec::<RawNeuronInfoLite>::decode(&mut &bytes[..]).unwrap();
+        let neuron = NeuronInfoLite::from(neurons.into_iter().next().unwrap());
+
+        assert_eq!(neuron.uid, 4);
+        assert_eq!(neuron.stake, 5_000);
+        assert_eq!(neuron.trust, 40);
+        assert!(neuron.validator_permit);
+        assert_eq!(neuron.pruning_score, 9);
+    }
+
+    #[test]
+    fn test_registration_events() {
+        let metadata = Metadata::decode(&crate::metadata::runtime::metadata_bytes()).unwrap();
+        let hotkey = AccountId32::new([7u8; 32]);
+        let dispatch_info = (Compact(1_000_000u64), Compact(0u64), 0u8, 0u8).encode();
+        let registered = [&[3, 0, 17, 0][..], &[7u8; 32]].concat();
+        let module_error = [&[3, 7, 3, 0, 0, 0][..], &dispatch_info].concat();
+        let records = [
+            event_record(0, 0, 0, &dispatch_info),
+            event_record(2, 7, 6, &registered),
+            event_record(2, 0, 0, &dispatch_info),
+            event_record(3, 0, 1, &module_error),
+            event_record(4, 0, 1, &[&[2][..], &dispatch_info].concat()),
+        ];
+        let blob = [Compact(records.len() as u32).encode(), records.concat()].concat();
+        let events = metadata.decode_events(&blob).unwrap();
+        assert_eq!(events.len(), 5);
+        assert!(events[1].is("SubtensorModule", "NeuronRegistered"));
+        assert_eq!(events[1].extrinsic_index, Some(2));
+
+        assert_eq!(
+            extrinsic_outcome(&metadata, &events, 2),
+            Some(ExtrinsicOutcome::Success)
+        );
+        assert_eq!(neuron_registered(&events, 2, 3, &hotkey), Some(17));
+        assert_eq!(neuron_registered(&events, 2, 4, &hotkey), None);
+        assert_eq!(neuron_registered(&events, 0, 3, &hotkey), None);
+        assert_eq!(
+            extrinsic_outcome(&metadata, &events, 3),
+            Some(ExtrinsicOutcome::Failed(
+                "dispatch error: Module(SubtensorModule::TooManyRegistrationsThisBlock)"
+                    .to_string()
+            ))
+        );
+        assert_eq!(
+            extrinsic_outcome(&metadata, &events, 4),
+            Some(ExtrinsicOutcome::Failed(
+                "dispatch error: BadOrigin".to_string()
+            ))
+        );
+        assert_eq!(extrinsic_outcome(&metadata, &events, 9), None);
+
+        // A cut off blob or one with trailing bytes doesn't decode
+        assert!(metadata.decode_events(&blob[..blob.len() - 1]).is_err());
+        assert!(metadata.decode_events(&[&blob[..], &[0]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_call_encode() {
+        let hotkey = AccountId32::new([7u8; 32]);
+        let call = encode_subnet_stake_call(constants::REMOVE_STAKE_CALL_INDEX, &hotkey, 3, 5);
+        assert_eq!(&call[..2], &[7, 3]);
+        assert_eq!(&call[2..34], &[7u8; 32]);
+        assert_eq!(&call[34..36], &[3, 0]);
+        assert_eq!(&call[36..], &5u64.to_le_bytes());
+
+        let call = encode_move_stake_call(&StakeMove {
+            origin_hotkey: hotkey.clone(),
+            destination_hotkey: AccountId32::new([9u8; 32]),
+            origin_netuid: 1,
+            destination_netuid: 2,
+            amount: 7,
+        });
+        assert_eq!(&call[..2], &[7, 85]);
+        assert_eq!(&call[34..66], &[9u8; 32]);
+        assert_eq!(&call[66..70], &[1, 0, 2, 0]);
+        assert_eq!(call.len(), 2 + 32 + 32 + 2 + 2 + 8);
+
+        let call = encode_root_register_call(&hotkey);
+        assert_eq!(&call[..2], &[7, 62]);
+        assert_eq!(&call[2..], &[7u8; 32]);
+
+        let call = encode_set_children_call(&hotkey, 1, &[(u64::MAX, AccountId32::new([9u8; 32]))]);
+        assert_eq!(&call[..2], &[7, 67]);
+        assert_eq!(&call[34..37], &[1, 0, 4]); // netuid, Compact(1) children
+        assert_eq!(&call[37..45], &u64::MAX.to_le_bytes());
+        assert_eq!(&call[45..], &[9u8; 32]);
+
+        let identity = ChainIdentity {
+            name: "val".to_string(),
+            discord: "d".to_string(),
+            ..Default::default()
+        };
+        let call = encode_set_identity_call(&identity);
+        assert_eq!(
+            call,
+            vec![7, 68, 12, b'v', b'a', b'l', 0, 0, 0, 4, b'd', 0, 0]
+        );
+        assert_eq!(
+            ChainIdentity::decode_version(2, &call[2..]).unwrap(),
+            identity
+        );
+
+        let call = encode_commit_weights_call(2, H256::repeat_byte(0xab));
+        assert_eq!(&call[..4], &[7, 96, 2, 0]);
+        assert_eq!(&call[4..], &[0xab; 32]);
+        let commit = WeightsCommit {
+            netuid: 2,
+            hotkey: String::new(),
+            uids: vec![1],
+            values: vec![u16::MAX],
+            salt: vec![5, 6],
+            version_key: 3,
+            commit_hash: String::new(),
+            commit_block: 0,
+            committed_at: chrono::Utc::now(),
+        };
+        let call = encode_reveal_weights_call(&commit);
+        assert_eq!(
+            call,
+            [
+                &[7, 97, 2, 0, 4, 1, 0, 4, 0xff, 0xff, 8, 5, 0, 6, 0][..],
+                &3u64.to_le_bytes()
+            ]
+            .concat()
+        );
+
+        let call = encode_transfer_keep_alive_call(&hotkey, 1_000_000_000);
+        assert_eq!(&call[..3], &[5, 3, 0]);
+        assert_eq!(&call[35..], &[0x02, 0x28, 0x6b, 0xee]); // Compact(1e9)
+
+        let axon = AxonInfo {
+            version: 1,
+            ip: 0x7f000001,
+            port: 8091,
+            ip_type: 4,
+            protocol: 4,
+            ..Default::default()
+        };
+        let call = encode_serve_axon_call(3, &axon);
+        assert_eq!(&call[..4], &[7, 4, 3, 0]);
+        assert_eq!(call.len(), 4 + 4 + 16 + 2 + 4);
+    }
+
+    #[test]
+    fn test_signed_extrinsic_layout() {
+        let alice = Sr25519Pair::from_string("//Alice", None).unwrap();
+        let signer = KeyPair::Sr25519(alice.clone());
+        let context = SigningContext {
+            nonce: 5,
+            block_number: 64,
+            spec_version: 263,
+            transaction_version: 1,
+            genesis_hash: H256::repeat_byte(0x11),
+            era: Era::mortal(64, 64),
+            era_block_hash: H256::repeat_byte(0x22),
+            tip: 0,
+        };
+        // SubtensorModule::burned_register(1, 0x0101..)
+        let call = hex::decode(format!("07070100{}", "01".repeat(32))).unwrap();
+
+        let extrinsic = sign_extrinsic(&call, &signer, &context).unwrap();
+
+        // Same layout subxt produces: compact length, version, MultiAddress,
+        // MultiSignature, extra (era, nonce, tip, metadata hash mode) and call
+        let extra = hex::decode("0500140000").unwrap();
+        assert_eq!(&extrinsic[..2], &[0x31, 0x02]); // Compact(140)
+        assert_eq!(extrinsic[2], 0x84);
+        assert_eq!(extrinsic[3], 0x00);
+        assert_eq!(&extrinsic[4..36], &alice.public().0);
+        assert_eq!(extrinsic[36], 0x01);
+        assert_eq!(&extrinsic[101..106], extra.as_slice());
+        assert_eq!(&extrinsic[106..], call.as_slice());
+
+        // The signature covers call ++ extra ++ spec, tx version, genesis, era hash, metadata hash
+        let payload = [
+            call.clone(),
+            extra,
+            hex::decode(format!(
+                "0701000001000000{}{}00",
+                "11".repeat(32),
+                "22".repeat(32)
+            ))
+            .unwrap(),
+        ]
+        .concat();
+        let signature =
+            sp_core::sr25519::Signature::from_raw(extrinsic[37..101].try_into().unwrap());
+        assert!(Sr25519Pair::verify(&signature, &payload, &alice.public()));
+
+        // A tip is a compact right after the nonce
+        let tipped = SigningContext {
+            tip: 1000,
+            ..context
+        };
+        assert_eq!(
+            encode_signed_extra(&tipped),
+            hex::decode("050014a10f00").unwrap()
+        );
+    }
+
+    // Ed25519 signatures are deterministic, so a whole extrinsic can be pinned. The vector was
+    // built outside this crate, by a standalone Python SCALE encoder signing with the
+    // cryptography package, from the RFC 8032 test 1 key and Finney's genesis hash
+    #[test]
+    fn test_signed_extrinsic_vector() {
+        let seed = hex::decode("9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60")
+            .unwrap();
+        let signer = KeyPair::Ed25519(sp_core::ed25519::Pair::from_seed_slice(&seed).unwrap());
+        let context = SigningContext {
+            nonce: 5,
+            block_number: 64,
+            spec_version: 273,
+            transaction_version: 1,
+            genesis_hash: H256::from_str(
+                "2f0555cc76fc2840a25a6ea3b9637146806f1f44b090c175ffde2a7e5ab36c03",
+            )
+            .unwrap(),
+            era: Era::mortal(64, 64),
+            era_block_hash: H256::repeat_byte(0x22),
+            tip: 0,
+        };
+        let call = hex::decode(format!("07070100{}", "01".repeat(32))).unwrap();
+
+        let extrinsic = sign_extrinsic(&call, &signer, &context).unwrap();
+        assert_eq!(
+            hex::encode(extrinsic),
+            concat!(
+                "31028400d75a980182b10ab7d54bfed3c964073a0ee172f3daa62325af021a68f707511a00",
+                "68f22e651dc14efd025dd144493d5ba9cc998e40fae63528cbeaf96b76aead08",
+                "11bf68efe1875fadbdd2f6c96438c8451184030ebdb999ab1179802c40816700",
+                "0500140000",
+                "070701000101010101010101010101010101010101010101010101010101010101010101",
+            )
+        );
+    }
+
+    #[tokio::test]
+    async fn test_alpha_stake() {
+        let chain = MockChain::new(1);
+        let client =
+            BittensorClient::with_chain("mock".to_string(), chain.clone(), Backoff::default());
+        let hotkey = AccountId32::new([1u8; 32]);
+        let coldkey = AccountId32::new([2u8; 32]);
+        let netuid = 3u16.to_le_bytes();
+        let pool = [
+            (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            (StorageHasher::Identity, &netuid[..]),
+        ];
+        // A third of the shares, U64F64 bits, of 900 alpha
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "Alpha",
+                &[
+                    (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+                    (StorageHasher::Blake2_128Concat, coldkey.as_ref()),
+                    (StorageHasher::Identity, &netuid),
+                ],
+            ),
+            100u128 << 64,
+        );
+        chain.insert(
+            &storage_key("SubtensorModule", "TotalHotkeyShares", &pool),
+            300u128 << 64,
+        );
+        chain.insert(
+            &storage_key("SubtensorModule", "TotalHotkeyAlpha", &pool),
+            900u64,
+        );
+
+        assert_eq!(
+            client.get_alpha_stake(&hotkey, &coldkey, 3).await.unwrap(),
+            300
+        );
+        assert_eq!(
+            client.get_alpha_stake(&coldkey, &hotkey, 3).await.unwrap(),
+            0
+        );
+        assert_eq!(alpha_for_shares(1, 3, 10), 3);
+        assert_eq!(alpha_for_shares(5, 0, 10), 0);
+    }
+
+    #[tokio::test]
+    async fn test_rpc_retry_classification() {
+        let chain = MockChain::new(5);
+        let backoff = Backoff {
+            initial_ms: 1,
+            max_ms: 1,
+            ..Backoff::default()
+        };
+        let client = BittensorClient::with_chain("mock".to_string(), chain.clone(), backoff);
+
+        // A dropped connection is replaced and the request sent again
+        chain.drop_connection(2);
+        assert_eq!(client.get_current_block().await.unwrap(), 5);
+        assert_eq!(chain.reconnects(), 2);
+
+        // An error the node answered with is final, pruned state reads as unavailable
+        chain.reject(1);
+        assert_eq!(
+            client.get_timestamp_at(chain.best_hash()).await.unwrap(),
+            None
+        );
+        assert_eq!(chain.requests("storage"), 1);
+        assert_eq!(chain.reconnects(), 2);
+
+        // Once the reconnects run out the error surfaces, classed as a connection failure
+        chain.drop_connection(RPC_RECONNECT_ATTEMPTS + 1);
+        let error = client.get_current_block().await.unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Connection);
+        assert_eq!(chain.reconnects(), 2 + RPC_RECONNECT_ATTEMPTS);
+    }
+
+    #[test]
+    fn test_decode_dispatch_info() {
+        // Weight (compact ref_time and proof_size), DispatchClass::Normal, partial fee
+        let mut bytes = (Compact(125_000_000u64), Compact(4_000u64)).encode();
+        bytes.push(0);
+        bytes.extend(2_048_000u64.encode());
+        let info = RuntimeDispatchInfo::decode_all(&mut &bytes[..]).unwrap();
+        assert_eq!(info.partial_fee, 2_048_000);
+    }
+
+    #[test]
+    fn test_decode_delegates() {
+        let delegate = AccountId32::new([1; 32]);
+        let owner = AccountId32::new([2; 32]);
+        let nominator = AccountId32::new([3; 32]);
+        let tail = |bytes: &mut Vec<u8>| {
+            owner.encode_to(bytes);
+            vec![Compact(1u16), Compact(3u16)].encode_to(bytes);
+            vec![Compact(1u16)].encode_to(bytes);
+            Compact(10u64).encode_to(bytes);
+            Compact(20u64).encode_to(bytes);
+        };
+
+        // Current layout, nominator stake per subnet is summed
+        let mut current = Vec::new();
+        delegate.encode_to(&mut current);
+        Compact(11_796u16).encode_to(&mut current);
+        vec![(
+            nominator.clone(),
+            vec![
+                (Compact(1u16), Compact(100u64)),
+                (Compact(3u16), Compact(50u64)),
+            ],
+        )]
+        .encode_to(&mut current);
+        tail(&mut current);
+        let mut bytes = Compact(1u32).encode();
+        bytes.extend(current);
+        let delegates = decode_delegates(&bytes).unwrap();
+        assert_eq!(delegates.len(), 1);
+        assert_eq!(delegates[0].take, 11_796);
+        assert_eq!(delegates[0].owner, owner);
+        assert_eq!(delegates[0].nominators, vec![(nominator.clone(), 150)]);
+        assert_eq!(delegates[0].registrations, vec![1, 3]);
+        assert_eq!(delegates[0].total_daily_return, 20);
+
+        // Legacy layout, a single stake per nominator
+        let mut legacy = Vec::new();
+        delegate.encode_to(&mut legacy);
+        Compact(11_796u16).encode_to(&mut legacy);
+        vec![(nominator.clone(), Compact(70u64))].encode_to(&mut legacy);
+        tail(&mut legacy);
+        let mut bytes = Compact(1u32).encode();
+        bytes.extend(legacy);
+        let delegates = decode_delegates(&bytes).unwrap();
+        assert_eq!(delegates[0].total_stake(), 70);
+
+        assert!(decode_delegates(&[4, 0]).is_err());
+    }
+
+    #[test]
+    fn test_subnet_pool() {
+        let pool = SubnetPool {
+            netuid: 3,
+            tao_in: 1_000_000_000_000,
+            alpha_in: 4_000_000_000_000,
+            alpha_out: 2_000_000_000_000,
+        };
+        assert_eq!(pool.price(), 0.25);
+        assert_eq!(pool.alpha_out_value(), 500_000_000_000);
+        // 10% of the TAO reserve buys less alpha than the spot price suggests
+        let alpha = pool.alpha_for_tao(100_000_000_000);
+        assert_eq!(alpha, 363_636_363_636);
+        assert!(alpha < (100_000_000_000.0 / pool.price()) as u64);
+        assert_eq!(pool.tao_for_alpha(400_000_000_000), 90_909_090_909);
+        let slippage = slippage_percent(400_000_000_000, alpha);
+        assert!((slippage - 9.09).abs() < 0.01);
+        assert_eq!(slippage_percent(0, 5), 0.0);
+
+        let root = SubnetPool {
+            netuid: 0,
+            ..Default::default()
+        };
+        assert_eq!(root.price(), 1.0);
+        assert_eq!(root.alpha_for_tao(5), 5);
+        let empty = SubnetPool {
+            netuid: 1,
+            ..Default::default()
+        };
+        assert_eq!(empty.alpha_for_tao(5), 0);
+        assert_eq!(empty.price(), 0.0);
+    }
+
+    #[test]
+    fn test_subnet_info_from_storage() {
+        let mut storage = SubnetStorage::new(false);
+        let mut set = |name: &str, value: Vec<u8>| {
+            let item = SUBNET_INFO_STORAGE.iter().position(|n| *n == name).unwrap();
+            storage.values[item] = Some(value);
+        };
+        set("SubnetworkN", 256u16.encode());
+        set("MaxAllowedUids", 256u16.encode());
+        set("Burn", 1_500_000_000u64.encode());
+        let info = subnet_info_from_storage(3, &storage).unwrap();
+        assert_eq!((info.subnetwork_n, info.max_n), (256, 256));
+        assert_eq!(info.burn, 1_500_000_000);
+        assert_eq!(info.tempo, 0); // Unset reads as the default
+        assert!(info.registration_allowed);
+        assert_eq!(info.registered_neurons, info.subnetwork_n);
+
+        // A value that doesn't decode is an error, not a zero
+        let item = SUBNET_INFO_STORAGE
+            .iter()
+            .position(|n| *n == "Tempo")
+            .unwrap();
+        storage.values[item] = Some(vec![0xff]);
+        assert!(subnet_info_from_storage(3, &storage).is_err());
+
+        let empty = SubnetStorage::new(false);
+        assert!(subnet_info_from_storage(3, &empty).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_storage_errors() {
+        let chain = MockChain::new(1);
+        let mut client = mock_client(&chain);
+        let tempo_key = storage_key(
+            "SubtensorModule",
+            "Tempo",
+            &[(StorageHasher::Identity, &3u16.to_le_bytes())],
+        );
+
+        // An absent key is the runtime's default
+        assert_eq!(client.get_bittensor_u16("Tempo", &[3]).await.unwrap(), 0);
+
+        // A failed query and bytes that don't decode are errors, not zeros
+        chain.reject(1);
+        let error = client.get_bittensor_u16("Tempo", &[3]).await.unwrap_err();
+        assert!(format!("{:#}", error).contains("State already discarded"));
+        chain.insert(&tempo_key, 0xffu8);
+        let error = client.get_bittensor_u16("Tempo", &[3]).await.unwrap_err();
+        assert!(error.to_string().contains("Failed to decode Tempo"));
+
+        // --lenient reads both as the default. Set on the client, set_lenient is process-wide
+        client.lenient = true;
+        assert_eq!(client.get_bittensor_u16("Tempo", &[3]).await.unwrap(), 0);
+        chain.reject(1);
+        assert_eq!(client.get_bittensor_u16("Tempo", &[3]).await.unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_tip_escalation() {
+        let alice = crate::key_utils::load_keypair_from_file("//Alice").unwrap();
+        let call = vec![0u8, 0, 0];
+        let escalation = TipEscalation {
+            step: 1_000,
+            max_tip: 1_500,
+            stuck_blocks: 100,
+        };
+        // Every resubmission keeps the context and only raises the tip, capped at max_tip
+        async fn submit(
+            call: &[u8],
+            alice: &KeyPair,
+            drops: u32,
+            escalation: Option<TipEscalation>,
+        ) -> (Result<Inclusion>, Vec<u64>) {
+            let chain = MockChain::new(1);
+            let client = mock_client(&chain);
+            let context = client
+                .fetch_signing_context(&alice.account_id())
+                .await
+                .unwrap();
+            if let Some(escalation) = escalation {
+                client.enable_tip_escalation(escalation);
+            }
+            chain.drop_from_pool(drops);
+            let result = client
+                .submit_signed_call_until_included(call.to_vec(), alice)
+                .await;
+            let tips = chain
+                .submitted()
+                .iter()
+                .map(|extrinsic| {
+                    (0..=escalation.map_or(0, |e| e.max_tip))
+                        .find(|&tip| {
+                            let extra = encode_signed_extra(&SigningContext {
+                                tip,
+                                ..context.clone()
+                            });
+                            extrinsic.ends_with(&[extra, call.to_vec()].concat())
+                        })
+                        .unwrap()
+                })
+                .collect();
+            (result, tips)
+        }
+
+        // Without escalation a dropped transaction is an error
+        let (result, tips) = submit(&call, &alice, 1, None).await;
+        assert!(format!("{:#}", result.unwrap_err()).contains("Transaction dropped"));
+        assert_eq!(tips, [0]);
+
+        let (result, tips) = submit(&call, &alice, 2, Some(escalation)).await;
+        result.unwrap();
+        assert_eq!(tips, [0, 1_000, 1_500]);
+
+        // Dropped at max_tip there is nothing left to outbid with
+        let (result, tips) = submit(&call, &alice, 3, Some(escalation)).await;
+        assert!(format!("{:#}", result.unwrap_err()).contains("Transaction dropped"));
+        assert_eq!(tips, [0, 1_000, 1_500]);
+    }
+
+    #[tokio::test]
+    async fn test_measure_block_time() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let now_key = storage_key("Timestamp", "Now", &[]);
+        assert!(client.measure_block_time(100).await.unwrap().is_none());
+
+        // 100 blocks 1,200 seconds apart
+        chain.insert_at(900, &now_key, 1_700_000_000_000u64);
+        chain.insert(&now_key, 1_700_001_200_000u64);
+        assert_eq!(
+            client.measure_block_time(100).await.unwrap(),
+            Some(Duration::from_secs(12))
+        );
+
+        // Nothing to measure at genesis
+        let client = mock_client(&MockChain::new(0));
+        assert!(client.measure_block_time(100).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_state_at_inclusion() {
+        // The inclusion block already holds the UID a lagging "latest" doesn't show yet
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let bob = crate::key_utils::account_id_from_string("//Bob").unwrap();
+        let uid_key = storage_key(
+            "SubtensorModule",
+            "Uids",
+            &[
+                (StorageHasher::Identity, &1u16.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, bob.as_ref()),
+            ],
+        );
+        chain.insert_at(1_000, &uid_key, 7u16);
+        let inclusion_block = chain.best_hash();
+
+        assert_eq!(
+            client.get_uid_at(1, &bob, inclusion_block).await.unwrap(),
+            Some(7)
+        );
+        assert_eq!(client.get_uid(1, &bob).await.unwrap(), None);
+        // Block n has the hash n + 1 on MockChain
+        assert_eq!(
+            client.get_parent_hash(inclusion_block).await.unwrap(),
+            H256::from_low_u64_be(1_000)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_registration_cooldown() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let bob = crate::key_utils::account_id_from_string("//Bob").unwrap();
+        let subnet_key = |name| {
+            storage_key(
+                "SubtensorModule",
+                name,
+                &[(StorageHasher::Identity, &1u16.to_le_bytes())],
+            )
+        };
+
+        let cooldown = client.get_registration_cooldown(1, &bob).await.unwrap();
+        assert_eq!(cooldown.blocks_remaining(), 0);
+        assert!(cooldown.reason.is_none());
+
+        // A full block frees up on the next one
+        chain.insert(&subnet_key("MaxRegistrationsPerBlock"), 1u16);
+        chain.insert(&subnet_key("RegistrationsThisBlock"), 1u16);
+        let cooldown = client.get_registration_cooldown(1, &bob).await.unwrap();
+        assert_eq!(cooldown.ready_at_block, 1_001);
+
+        // The hotkey's rate limit runs from its last transaction and outlasts that
+        chain.insert(&storage_key("SubtensorModule", "TxRateLimit", &[]), 10u64);
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "LastTxBlock",
+                &[(StorageHasher::Identity, bob.as_ref())],
+            ),
+            995u64,
+        );
+        let cooldown = client.get_registration_cooldown(1, &bob).await.unwrap();
+        assert_eq!(cooldown.blocks_remaining(), 5);
+        assert!(cooldown.reason.unwrap().contains("rate limit"));
+    }
+
+    #[test]
+    fn test_subnet_identity_decode() {
+        let fields = |values: &[&str]| {
+            values
+                .iter()
+                .flat_map(|value| value.as_bytes().to_vec().encode())
+                .collect::<Vec<u8>>()
+        };
+
+        let v1 = SubnetIdentity::decode_version(1, &fields(&["apex", "gh", "ops@x"])).unwrap();
+        assert_eq!(v1.name, "apex");
+        assert_eq!(v1.contact, "ops@x");
+        assert!(v1.description.is_empty());
+
+        let v3 = fields(&[
+            " apex ",
+            "gh",
+            "c",
+            "https://x",
+            "d",
+            "desc",
+            "logo",
+            "more",
+        ]);
+        let v3 = SubnetIdentity::decode_version(3, &v3).unwrap();
+        assert_eq!(v3.name, "apex");
+        assert_eq!(v3.logo_url, "logo");
+        assert_eq!(v3.additional, "more");
+        assert!(SubnetIdentity::decode_version(2, &fields(&["apex"])).is_err());
+
+        let v1 = fields(&["val", "https://v", "img", "disc", "desc", ""]);
+        let v1 = ChainIdentity::decode_version(1, &v1).unwrap();
+        assert_eq!(v1.url, "https://v");
+        assert_eq!(v1.image, "img");
+        assert!(v1.github.is_empty());
+        assert!(v1.validate().is_ok());
+        let long = ChainIdentity {
+            name: "x".repeat(257),
+            ..Default::default()
+        };
+        assert!(long.validate().unwrap_err().to_string().contains("name"));
+    }
+
+    #[tokio::test]
+    async fn test_stream_neurons_lite() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let subnetwork_n = storage_key(
+            "SubtensorModule",
+            "SubnetworkN",
+            &[(StorageHasher::Identity, &1u16.to_le_bytes())],
+        );
+        chain.insert(&subnetwork_n, 3u16);
+        for uid in [0u16, 2] {
+            chain.serve_rpc(
+                "neuronInfo_getNeuronLite",
+                &[1, uid],
+                [&[1u8][..], &neuron_lite_bytes(uid)].concat(), // Some(neuron)
+            );
+        }
+        // A UID freed between reading SubnetworkN and the neuron is skipped
+        chain.serve_rpc("neuronInfo_getNeuronLite", &[1, 1], None::<u8>.encode());
+
+        let neurons: Vec<NeuronInfoLite> = client
+            .stream_neurons_lite(1)
+            .await
+            .unwrap()
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(
+            neurons.iter().map(|n| n.uid).collect::<Vec<_>>(),
+            vec![0, 2]
+        );
+        assert_eq!(chain.requests("neuronInfo_getNeuronLite"), 3);
+
+        // No neurons, no lookups
+        let empty = client.stream_neurons_lite(2).await.unwrap();
+        assert_eq!(empty.count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_cancellation() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let other = mock_client(&chain);
+        assert!(client.ensure_not_cancelled().is_ok());
+
+        let token = client.cancellation_token();
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            token.cancel();
+        });
+        // Cut short long before the hour is up
+        let error = tokio::time::timeout(
+            Duration::from_secs(5),
+            client.pause(Duration::from_secs(3_600)),
+        )
+        .await
+        .expect("pause not cancelled")
+        .unwrap_err();
+        canceller.await.unwrap();
+        assert_eq!(error.to_string(), "Cancelled");
+        assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+        assert!(client.ensure_not_cancelled().is_err());
+
+        // Other clients keep going
+        assert!(other.ensure_not_cancelled().is_ok());
+        assert!(other.pause(Duration::from_millis(1)).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_find_registrations() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let bob = crate::key_utils::account_id_from_string("//Bob").unwrap();
+        for netuid in [0u16, 1, 3] {
+            chain.insert(
+                &storage_key(
+                    "SubtensorModule",
+                    "NetworksAdded",
+                    &[(StorageHasher::Identity, &netuid.to_le_bytes())],
+                ),
+                true,
+            );
+        }
+        let uid_key = |netuid: u16| {
+            storage_key(
+                "SubtensorModule",
+                "Uids",
+                &[
+                    (StorageHasher::Identity, &netuid.to_le_bytes()),
+                    (StorageHasher::Blake2_128Concat, bob.as_ref()),
+                ],
+            )
+        };
+        assert!(client.find_registrations(&bob).await.unwrap().is_empty());
+
+        chain.insert(&uid_key(1), 4u16);
+        chain.insert(&uid_key(3), 9u16);
+        // Left over from a dissolved subnet, not in NetworksAdded
+        chain.insert(&uid_key(2), 5u16);
+        assert_eq!(
+            client.find_registrations(&bob).await.unwrap(),
+            vec![(1, 4), (3, 9)]
+        );
+        // Every subnet read in one batch per lookup
+        assert_eq!(chain.requests("storage_batch"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_owned_hotkeys() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        let alice = crate::key_utils::account_id_from_string("//Alice").unwrap();
+        let bob = crate::key_utils::account_id_from_string("//Bob").unwrap();
+        let charlie = crate::key_utils::account_id_from_string("//Charlie").unwrap();
+        assert!(client.get_owned_hotkeys(&alice).await.unwrap().is_empty());
+        assert_eq!(client.get_hotkey_owner(&bob).await.unwrap(), None);
+
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "OwnedHotkeys",
+                &[(StorageHasher::Blake2_128Concat, alice.as_ref())],
+            ),
+            vec![bob.clone(), charlie.clone()],
+        );
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "Owner",
+                &[(StorageHasher::Blake2_128Concat, bob.as_ref())],
+            ),
+            alice.clone(),
+        );
+        assert_eq!(
+            client.get_owned_hotkeys(&alice).await.unwrap(),
+            vec![bob.clone(), charlie.clone()]
+        );
+        assert_eq!(client.get_hotkey_owner(&bob).await.unwrap(), Some(alice));
+        // Still listed under Alice, but without an Owner record pointing back
+        assert_eq!(client.get_hotkey_owner(&charlie).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_pin_block() {
+        let chain = MockChain::new(1_000);
+        let total_networks = storage_key("SubtensorModule", "TotalNetworks", &[]);
+        chain.insert(&total_networks, 5u16);
+        chain.insert_at(900, &total_networks, 3u16);
+
+        let client = mock_client(&chain);
+        for (block, error) in [
+            ("abc", "Invalid block abc"),
+            ("1,000,001", "Block 1000001 doesn't exist yet"),
+        ] {
+            let e = client.pin_block(block).await.unwrap_err();
+            assert!(e.to_string().starts_with(error), "{}", e);
+        }
+        // Pruned state means a non-archive node
+        chain.prune_before(800);
+        let e = client.pin_block("700").await.unwrap_err();
+        assert!(e.to_string().contains("need an archive node"), "{}", e);
+        assert_eq!(client.pinned_block(), None);
+
+        // Block n has the hash n + 1 on MockChain, by number and by hash alike
+        assert_eq!(
+            client.pin_block("900").await.unwrap(),
+            (900, H256::from_low_u64_be(901))
+        );
+        assert_eq!(client.get_total_networks().await.unwrap(), 3);
+        assert!(client.pin_block("950").await.is_err());
+
+        let client = mock_client(&chain);
+        let hash = format!("{:?}", H256::from_low_u64_be(901));
+        assert_eq!(client.pin_block(&hash).await.unwrap().0, 900);
+        assert_eq!(client.get_total_networks().await.unwrap(), 3);
+        assert_eq!(mock_client(&chain).get_total_networks().await.unwrap(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_get_netuids() {
+        let chain = MockChain::new(1_000);
+        let client = mock_client(&chain);
+        assert!(client.get_netuids().await.unwrap().is_empty());
+
+        // More subnets than one page of keys, with dissolved ones missing
+        let expected: Vec<u16> = (0..1_300).filter(|n| ![5, 600].contains(n)).collect();
+        for netuid in &expected {
+            chain.insert(
+                &storage_key(
+                    "SubtensorModule",
+                    "NetworksAdded",
+                    &[(StorageHasher::Identity, &netuid.to_le_bytes())],
+                ),
+                true,
+            );
+        }
+        assert_eq!(client.get_netuids().await.unwrap(), expected);
+        // The empty listing, then two pages
+        assert_eq!(chain.requests("storage_keys"), 3);
+    }
 }
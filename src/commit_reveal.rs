@@ -0,0 +1,210 @@
+//! Commit-reveal weights: the salted hash committed on chain, the reveal window it opens, and
+//! the weights and salt kept in the data directory until they are revealed
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use codec::Encode;
+use primitive_types::H256;
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::AccountId32;
+use std::path::PathBuf;
+
+use crate::paths;
+use crate::utils;
+
+const COMMITS_FILE: &str = "weight_commits.json";
+
+// Salt length bittensor's own clients use
+const SALT_LEN: usize = 8;
+
+// RevealPeriodEpochs when the subnet leaves it at the runtime default
+pub const DEFAULT_REVEAL_PERIOD_EPOCHS: u64 = 1;
+
+// Weights committed and not yet revealed
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeightsCommit {
+    pub netuid: u16,
+    pub hotkey: String,
+    pub uids: Vec<u16>,
+    pub values: Vec<u16>,
+    pub salt: Vec<u16>,
+    pub version_key: u64,
+    pub commit_hash: String, // 0x-prefixed hex
+    pub commit_block: u64,
+    pub committed_at: DateTime<Utc>,
+}
+
+// Blocks the reveal for a commit is accepted in, both ends included
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RevealWindow {
+    pub first_block: u64,
+    pub last_block: u64,
+}
+
+// "uid:weight" pairs into uids and u16 values scaled so the largest weight is u16::MAX, the
+// normalization the chain expects
+pub fn parse_weights(specs: &[String]) -> Result<(Vec<u16>, Vec<u16>)> {
+    let mut weights = Vec::with_capacity(specs.len());
+    for spec in specs {
+        let (uid, weight) = spec
+            .split_once(':')
+            .ok_or_else(|| anyhow!("Invalid weight {}, expected uid:weight", spec))?;
+        let uid: u16 = uid
+            .trim()
+            .parse()
+            .map_err(|_| anyhow!("Invalid uid in {}", spec))?;
+        let weight: f64 = weight
+            .trim()
+            .parse()
+            .ok()
+            .filter(|weight: &f64| weight.is_finite() && *weight >= 0.0)
+            .ok_or_else(|| anyhow!("Invalid weight in {}", spec))?;
+        if weights.iter().any(|(existing, _)| *existing == uid) {
+            return Err(anyhow!("UID {} is given twice", uid));
+        }
+        weights.push((uid, weight));
+    }
+
+    let max = weights
+        .iter()
+        .map(|(_, weight)| *weight)
+        .fold(0.0, f64::max);
+    if max <= 0.0 {
+        return Err(anyhow!("At least one weight has to be above zero"));
+    }
+    Ok(weights
+        .into_iter()
+        .map(|(uid, weight)| (uid, (weight / max * u16::MAX as f64).round() as u16))
+        .unzip())
+}
+
+pub fn random_salt() -> Result<Vec<u16>> {
+    let mut bytes = [0u8; SALT_LEN * 2];
+    getrandom::getrandom(&mut bytes).map_err(|e| anyhow!("Failed to generate a salt: {}", e))?;
+    Ok(bytes
+        .chunks(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .collect())
+}
+
+// BlakeTwo256 of (hotkey, netuid, uids, values, salt, version_key), what the runtime
+// recomputes from the reveal
+pub fn commit_hash(
+    hotkey: &AccountId32,
+    netuid: u16,
+    uids: &[u16],
+    values: &[u16],
+    salt: &[u16],
+    version_key: u64,
+) -> H256 {
+    let encoded = (hotkey, netuid, uids, values, salt, version_key).encode();
+    H256(sp_core::blake2_256(&encoded))
+}
+
+// Subtensor's epoch index, offset by netuid so subnets don't all step at once
+pub fn epoch_index(block: u64, netuid: u16, tempo: u16) -> u64 {
+    (block + netuid as u64 + 1) / (tempo as u64 + 1)
+}
+
+fn epoch_start(epoch: u64, netuid: u16, tempo: u16) -> u64 {
+    (epoch * (tempo as u64 + 1)).saturating_sub(netuid as u64 + 1)
+}
+
+// A commit is revealed in the epoch reveal_period epochs after the one it landed in
+pub fn reveal_window(
+    commit_block: u64,
+    netuid: u16,
+    tempo: u16,
+    reveal_period: u64,
+) -> RevealWindow {
+    let reveal_epoch = epoch_index(commit_block, netuid, tempo) + reveal_period;
+    RevealWindow {
+        first_block: epoch_start(reveal_epoch, netuid, tempo),
+        last_block: epoch_start(reveal_epoch + 1, netuid, tempo) - 1,
+    }
+}
+
+// Commits waiting for their reveal, one JSON file in the data directory
+pub struct CommitStore {
+    path: PathBuf,
+}
+
+impl CommitStore {
+    pub fn open() -> Result<Self> {
+        let dir = paths::data_dir();
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+        Ok(Self {
+            path: dir.join(COMMITS_FILE),
+        })
+    }
+
+    pub fn load(&self) -> Result<Vec<WeightsCommit>> {
+        match std::fs::read_to_string(&self.path) {
+            Ok(content) => serde_json::from_str(&content)
+                .with_context(|| format!("Failed to parse {}", self.path.display())),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(e).with_context(|| format!("Failed to read {}", self.path.display())),
+        }
+    }
+
+    fn save(&self, commits: &[WeightsCommit]) -> Result<()> {
+        utils::write_atomic(
+            &self.path.to_string_lossy(),
+            &serde_json::to_string_pretty(commits)?,
+        )
+    }
+
+    pub fn add(&self, commit: WeightsCommit) -> Result<()> {
+        let mut commits = self.load()?;
+        commits.push(commit);
+        self.save(&commits)
+    }
+
+    pub fn remove(&self, commit_hash: &str) -> Result<()> {
+        let mut commits = self.load()?;
+        commits.retain(|commit| commit.commit_hash != commit_hash);
+        self.save(&commits)
+    }
+
+    pub fn path(&self) -> &PathBuf {
+        &self.path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_commit_reveal() {
+        let specs = ["0:0.5", "3:1", "7:0"].map(String::from);
+        let (uids, values) = parse_weights(&specs).unwrap();
+        assert_eq!(uids, vec![0, 3, 7]);
+        assert_eq!(values, vec![32768, u16::MAX, 0]);
+        assert!(parse_weights(&["0:0".to_string()]).is_err());
+        assert!(parse_weights(&["1:1".to_string(), "1:2".to_string()]).is_err());
+        assert!(parse_weights(&["x:1".to_string()]).is_err());
+
+        // The hash covers every part of the reveal
+        let hotkey = AccountId32::new([1u8; 32]);
+        let salt = random_salt().unwrap();
+        assert_eq!(salt.len(), SALT_LEN);
+        let hash = commit_hash(&hotkey, 1, &uids, &values, &salt, 0);
+        assert_eq!(hash, commit_hash(&hotkey, 1, &uids, &values, &salt, 0));
+        assert_ne!(hash, commit_hash(&hotkey, 1, &uids, &values, &[0; 8], 0));
+        assert_ne!(hash, commit_hash(&hotkey, 2, &uids, &values, &salt, 0));
+
+        // Tempo 360 on netuid 1: epochs start at 359, 720, 1081...
+        assert_eq!(epoch_index(358, 1, 360), 0);
+        assert_eq!(epoch_index(359, 1, 360), 1);
+        let window = reveal_window(400, 1, 360, 1);
+        assert_eq!(
+            window,
+            RevealWindow {
+                first_block: 720,
+                last_block: 1080
+            }
+        );
+        assert_eq!(reveal_window(400, 1, 360, 2).first_block, 1081);
+    }
+}
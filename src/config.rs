@@ -0,0 +1,423 @@
+//! Tool configuration loaded from a TOML file, optionally encrypted at rest
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::PathBuf, process::Command, sync::OnceLock};
+
+use crate::backoff::Backoff;
+use crate::paths::{self, expand_home};
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+// Encrypted files: header line, then base64(salt(16) + nonce(24) + secretbox)
+const ENCRYPTED_HEADER: &str = "$BTREG_CONFIG;1";
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 24;
+
+// Secret sources for encrypted configs, checked in this order before prompting
+const KEY_COMMAND_ENV: &str = "BTREG_CONFIG_KEY_COMMAND"; // e.g. `secret-tool lookup service btreg`
+const PASSWORD_ENV: &str = "BTREG_CONFIG_PASSWORD";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub price_oracle: PriceOracleConfig,
+    pub records: RecordsConfig,
+    pub latency: LatencyConfig,
+    pub block_time: BlockTimeConfig,
+    pub backoff: Backoff, // Waits between registration retries and RPC reconnects
+    pub default_profile: Option<String>, // Used when --profile is not given
+    pub profiles: BTreeMap<String, Profile>,
+}
+
+// Per-network defaults selected with --profile, e.g. [profiles.test]. Flags still win
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct Profile {
+    pub network: Option<String>, // finney, test or local, see --network
+    pub rpc_url: Option<String>,
+    pub wallet_path: Option<String>,
+    pub ss58_format: Option<u16>, // Prefix for printed addresses, 42 unless set
+    pub max_burn: Option<u64>,    // RAO, for registrations that don't pass --max-burn
+    // What `register` uses when --subnet, --wallet or --hotkey is left out
+    pub subnet: Option<u16>,
+    pub wallet: Option<String>,
+    pub hotkey: Option<String>,
+    pub notify: NotifyConfig,
+}
+
+// Alert channels for watchers, same as the --webhook-url/--telegram-*/--discord-webhook flags
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NotifyConfig {
+    pub webhook_url: Option<String>,
+    pub telegram_token: Option<String>,
+    pub telegram_chat_id: Option<String>,
+    pub discord_webhook: Option<String>,
+}
+
+// Where USD prices come from
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum PriceOracleConfig {
+    #[default]
+    Coingecko,
+    Fixed {
+        usd: f64,
+    },
+    Http {
+        url: String,
+        // JSON pointer to the TAO/USD price in the response, e.g. "/data/price"
+        pointer: String,
+    },
+}
+
+// Where snapshots and registration receipts are persisted
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum RecordsConfig {
+    #[default]
+    None,
+    Sqlite {
+        path: Option<String>, // Default: history.sqlite in the data directory
+    },
+    Jsonl {
+        path: Option<String>, // Default: records.jsonl in the data directory
+    },
+    Http {
+        url: String,
+    },
+}
+
+// Endpoint latency bound checked before snipes and window-aligned registrations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LatencyConfig {
+    pub samples: usize,
+    pub max_p95_ms: u64,
+    pub enforce: bool, // Refuse to start when the bound is exceeded, otherwise only warn
+}
+
+impl Default for LatencyConfig {
+    fn default() -> Self {
+        Self {
+            samples: 20,
+            max_p95_ms: 500,
+            enforce: true,
+        }
+    }
+}
+
+// How long a block takes, for countdowns, verification timeouts and ETAs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum BlockTimeConfig {
+    // Average over the chain's recent block timestamps
+    Measured {
+        #[serde(default = "default_block_time_samples")]
+        sample_blocks: u64,
+    },
+    Fixed {
+        seconds: f64,
+    },
+}
+
+impl Default for BlockTimeConfig {
+    fn default() -> Self {
+        BlockTimeConfig::Measured {
+            sample_blocks: default_block_time_samples(),
+        }
+    }
+}
+
+// Recent enough that non-archive nodes still hold the state
+fn default_block_time_samples() -> u64 {
+    100
+}
+
+impl Config {
+    pub fn from_toml(content: &str) -> Result<Self> {
+        toml::from_str(content).context("Invalid config file")
+    }
+
+    // The named profile, else default_profile, else no overrides at all
+    pub fn profile(&self, name: Option<&str>) -> Result<Profile> {
+        let Some(name) = name.or(self.default_profile.as_deref()) else {
+            return Ok(Profile::default());
+        };
+        self.profiles.get(name).cloned().ok_or_else(|| {
+            let known: Vec<&str> = self.profiles.keys().map(String::as_str).collect();
+            anyhow!(
+                "No profile named {} in the config file (known: {})",
+                name,
+                if known.is_empty() {
+                    "none".to_string()
+                } else {
+                    known.join(", ")
+                }
+            )
+        })
+    }
+}
+
+pub fn config_path(path: Option<&str>) -> PathBuf {
+    path.map(expand_home)
+        .unwrap_or_else(paths::default_config_file)
+}
+
+// Load the config once at startup, a missing default file means defaults
+pub fn load(path: Option<&str>) -> Result<()> {
+    let config_path = config_path(path);
+
+    let config = match std::fs::read_to_string(&config_path) {
+        Ok(content) => {
+            let content = if is_encrypted(&content) {
+                decrypt(&content, &config_secret(false)?)?
+            } else {
+                content
+            };
+            Config::from_toml(&content)
+                .with_context(|| format!("Failed to load {}", config_path.display()))?
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound && path.is_none() => Config::default(),
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", config_path.display()))
+        }
+    };
+
+    let _ = CONFIG.set(config);
+    Ok(())
+}
+
+pub fn current() -> Config {
+    CONFIG.get().cloned().unwrap_or_default()
+}
+
+pub fn is_encrypted(content: &str) -> bool {
+    content.starts_with(ENCRYPTED_HEADER)
+}
+
+// Secret from the key command (OS keychain), the environment, or a prompt
+fn config_secret(confirm: bool) -> Result<String> {
+    if let Ok(command) = std::env::var(KEY_COMMAND_ENV) {
+        let output = Command::new("sh")
+            .arg("-c")
+            .arg(&command)
+            .output()
+            .with_context(|| format!("Failed to run {}", KEY_COMMAND_ENV))?;
+        if !output.status.success() {
+            return Err(anyhow!("{} exited with {}", KEY_COMMAND_ENV, output.status));
+        }
+        let secret = String::from_utf8(output.stdout)
+            .context("Config key is not valid UTF-8")?
+            .trim_end_matches(['\r', '\n'])
+            .to_string();
+        if secret.is_empty() {
+            return Err(anyhow!("{} returned an empty key", KEY_COMMAND_ENV));
+        }
+        return Ok(secret);
+    }
+
+    if let Ok(password) = std::env::var(PASSWORD_ENV) {
+        return Ok(password);
+    }
+
+    let password = rpassword::prompt_password("🔐 Config password: ")
+        .context("Failed to read config password")?;
+    if confirm && rpassword::prompt_password("🔐 Repeat password: ")? != password {
+        return Err(anyhow!("Passwords do not match"));
+    }
+    Ok(password)
+}
+
+fn derive_key(secret: &str, salt: &[u8]) -> Result<[u8; 32]> {
+    let mut key = [0u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(secret.as_bytes(), salt, &mut key)
+        .map_err(|e| anyhow!("Failed to derive config key: {}", e))?;
+    Ok(key)
+}
+
+pub fn encrypt(plaintext: &str, secret: &str) -> Result<String> {
+    use crypto_secretbox::{
+        aead::{Aead, KeyInit},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    let mut salt = [0u8; SALT_LEN];
+    let mut nonce = [0u8; NONCE_LEN];
+    getrandom::getrandom(&mut salt).map_err(|e| anyhow!("No randomness available: {}", e))?;
+    getrandom::getrandom(&mut nonce).map_err(|e| anyhow!("No randomness available: {}", e))?;
+
+    let key = derive_key(secret, &salt)?;
+    let ciphertext = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+        .map_err(|_| anyhow!("Failed to encrypt config"))?;
+
+    let payload = [&salt[..], &nonce[..], &ciphertext].concat();
+    Ok(format!(
+        "{}\n{}\n",
+        ENCRYPTED_HEADER,
+        STANDARD.encode(payload)
+    ))
+}
+
+pub fn decrypt(content: &str, secret: &str) -> Result<String> {
+    use crypto_secretbox::{
+        aead::{Aead, KeyInit},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    let body = content
+        .strip_prefix(ENCRYPTED_HEADER)
+        .ok_or_else(|| anyhow!("Config file is not encrypted"))?;
+    let payload = STANDARD
+        .decode(body.trim())
+        .context("Invalid encrypted config encoding")?;
+    if payload.len() < SALT_LEN + NONCE_LEN {
+        return Err(anyhow!("Encrypted config is truncated"));
+    }
+
+    let (salt, rest) = payload.split_at(SALT_LEN);
+    let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+    let key = derive_key(secret, salt)?;
+    let plaintext = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Invalid config password or corrupted config file"))?;
+
+    String::from_utf8(plaintext).context("Decrypted config is not valid UTF-8")
+}
+
+// Encrypt the config file in place
+pub fn encrypt_file(path: Option<&str>) -> Result<()> {
+    let config_path = config_path(path);
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+    if is_encrypted(&content) {
+        return Err(anyhow!("{} is already encrypted", config_path.display()));
+    }
+    Config::from_toml(&content)?;
+
+    let encrypted = encrypt(&content, &config_secret(true)?)?;
+    std::fs::write(&config_path, encrypted)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    println!("🔒 Encrypted {}", config_path.display());
+    Ok(())
+}
+
+// Decrypt the config file in place
+pub fn decrypt_file(path: Option<&str>) -> Result<()> {
+    let config_path = config_path(path);
+    let content = std::fs::read_to_string(&config_path)
+        .with_context(|| format!("Failed to read {}", config_path.display()))?;
+
+    let decrypted = decrypt(&content, &config_secret(false)?)?;
+    std::fs::write(&config_path, decrypted)
+        .with_context(|| format!("Failed to write {}", config_path.display()))?;
+    println!("🔓 Decrypted {}", config_path.display());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_oracle_config() {
+        assert!(matches!(
+            Config::from_toml("").unwrap().price_oracle,
+            PriceOracleConfig::Coingecko
+        ));
+
+        let config = Config::from_toml(
+            r#"
+            [price_oracle]
+            kind = "http"
+            url = "https://prices.internal/tao"
+            pointer = "/data/usd"
+            "#,
+        )
+        .unwrap();
+        assert!(matches!(
+            config.price_oracle,
+            PriceOracleConfig::Http { .. }
+        ));
+
+        assert!(Config::from_toml("[price_oracle]\nkind = \"fixed\"").is_err());
+
+        let config = Config::from_toml("[records]\nkind = \"jsonl\"").unwrap();
+        assert!(matches!(
+            config.records,
+            RecordsConfig::Jsonl { path: None }
+        ));
+
+        assert!(matches!(
+            Config::from_toml("[block_time]\nkind = \"measured\"")
+                .unwrap()
+                .block_time,
+            BlockTimeConfig::Measured { sample_blocks: 100 }
+        ));
+        assert!(matches!(
+            Config::from_toml("[block_time]\nkind = \"fixed\"\nseconds = 6.0")
+                .unwrap()
+                .block_time,
+            BlockTimeConfig::Fixed { seconds } if seconds == 6.0
+        ));
+
+        let backoff = Config::from_toml("[backoff]\ninitial_ms = 500")
+            .unwrap()
+            .backoff;
+        assert_eq!(backoff.initial_ms, 500);
+        assert_eq!(backoff.max_ms, Backoff::default().max_ms);
+        assert!(Config::from_toml("[backoff]\ninitial = 500").is_err());
+    }
+
+    #[test]
+    fn test_profiles() {
+        let config = Config::from_toml(
+            r#"
+            default_profile = "main"
+
+            [profiles.main]
+            wallet_path = "~/.bittensor/wallets"
+            max_burn = 5000000000
+            subnet = 21
+            hotkey = "miner1"
+
+            [profiles.test]
+            rpc_url = "wss://test.finney.opentensor.ai:443"
+            notify.discord_webhook = "https://discord.com/api/webhooks/1/x"
+            "#,
+        )
+        .unwrap();
+
+        let main = config.profile(None).unwrap();
+        assert_eq!(main.max_burn, Some(5_000_000_000));
+        assert_eq!(main.subnet, Some(21));
+        assert_eq!(main.hotkey.as_deref(), Some("miner1"));
+        assert!(main.wallet.is_none());
+        let test = config.profile(Some("test")).unwrap();
+        assert_eq!(
+            test.rpc_url.as_deref(),
+            Some("wss://test.finney.opentensor.ai:443")
+        );
+        assert!(test.notify.discord_webhook.is_some());
+        assert!(test.max_burn.is_none());
+
+        let error = config.profile(Some("local")).unwrap_err().to_string();
+        assert!(error.contains("known: main, test"));
+        assert!(Config::default().profile(None).unwrap().rpc_url.is_none());
+        assert!(Config::from_toml("[profiles.x]\nrpc = \"ws://x\"").is_err());
+    }
+
+    #[test]
+    fn test_encrypted_config_roundtrip() {
+        let plaintext = "[price_oracle]\nkind = \"fixed\"\nusd = 250.0\n";
+        let encrypted = encrypt(plaintext, "hunter2").unwrap();
+
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("usd"));
+        assert_eq!(decrypt(&encrypted, "hunter2").unwrap(), plaintext);
+        assert!(decrypt(&encrypted, "wrong").is_err());
+    }
+}
@@ -5,8 +5,25 @@ pub const DEFAULT_RPC_ENDPOINTS: &[&str] = &[
     "wss://entrypoint-finney.opentensor.ai:443",
     "wss://archive.chain.opentensor.ai:443",
 ];
-pub const SUBTENSOR_MODULE_INDEX: u8 = 8;
-pub const REGISTER_CALL_INDEX: u8 = 0;
-pub const BURNED_REGISTER_CALL_INDEX: u8 = 1;
+pub const SUBTENSOR_MODULE_INDEX: u8 = 7;
+pub const REGISTER_CALL_INDEX: u8 = 6;
+pub const BURNED_REGISTER_CALL_INDEX: u8 = 7;
+pub const ADD_STAKE_CALL_INDEX: u8 = 2;
+pub const REMOVE_STAKE_CALL_INDEX: u8 = 3;
+pub const SERVE_AXON_CALL_INDEX: u8 = 4;
+pub const FAUCET_CALL_INDEX: u8 = 60; // Only in runtimes built with pow-faucet
+pub const ROOT_REGISTER_CALL_INDEX: u8 = 62;
+pub const SET_CHILDREN_CALL_INDEX: u8 = 67;
+pub const SET_IDENTITY_CALL_INDEX: u8 = 68;
+pub const SWAP_HOTKEY_CALL_INDEX: u8 = 70;
+pub const COMMIT_WEIGHTS_CALL_INDEX: u8 = 96;
+pub const REVEAL_WEIGHTS_CALL_INDEX: u8 = 97;
+pub const MOVE_STAKE_CALL_INDEX: u8 = 85;
+pub const BALANCES_MODULE_INDEX: u8 = 5;
+pub const TRANSFER_KEEP_ALIVE_CALL_INDEX: u8 = 3;
 pub const DEFAULT_BLOCK_TIME: u64 = 12; // seconds
+pub const LIGHT_CLIENT_SYNC_TIMEOUT: u64 = 300; // seconds, from a checkpoint or a saved database
 pub const TAO_DECIMALS: u32 = 9;
+pub const EXISTENTIAL_DEPOSIT: u64 = 500; // RAO, accounts below this are reaped
+pub const ROOT_NETUID: u16 = 0;
+pub const SENATE_MAX_MEMBERS: usize = 12; // SenateMembers pallet's MaxMembers on finney
@@ -0,0 +1,115 @@
+//! Transaction mortality: the CheckMortality era of signed extrinsics, encoded as
+//! sp_runtime's Era, and the period chosen with --era-period or --immortal
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+
+// Blocks a signed extrinsic stays valid for unless --era-period says otherwise
+pub const DEFAULT_PERIOD: u64 = 64;
+
+// Longest period the encoding can express
+pub const MAX_PERIOD: u64 = 1 << 16;
+
+// None for immortal transactions
+static MORTALITY: OnceLock<Option<u64>> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Era {
+    Immortal,
+    Mortal { period: u64, phase: u64 },
+}
+
+impl Era {
+    // Era starting at current, the period rounded up to a power of two between 4 and
+    // MAX_PERIOD and the phase quantized for long periods, as the runtime does
+    pub fn mortal(period: u64, current: u64) -> Self {
+        let period = period
+            .checked_next_power_of_two()
+            .unwrap_or(MAX_PERIOD)
+            .clamp(4, MAX_PERIOD);
+        let quantize_factor = (period >> 12).max(1);
+        let phase = current % period / quantize_factor * quantize_factor;
+        Era::Mortal { period, phase }
+    }
+
+    pub fn period(&self) -> Option<u64> {
+        match self {
+            Era::Immortal => None,
+            Era::Mortal { period, .. } => Some(*period),
+        }
+    }
+
+    // One zero byte when immortal, otherwise the period's exponent in the low four bits and
+    // the quantized phase above it, little endian
+    pub fn encode(&self) -> Vec<u8> {
+        match *self {
+            Era::Immortal => vec![0],
+            Era::Mortal { period, phase } => {
+                let quantize_factor = (period >> 12).max(1);
+                let exponent = (period.trailing_zeros() - 1).clamp(1, 15) as u16;
+                let encoded = exponent | ((phase / quantize_factor) as u16) << 4;
+                encoded.to_le_bytes().to_vec()
+            }
+        }
+    }
+
+    // Block the era started at, its hash is what the signature commits to
+    pub fn birth(&self, current: u64) -> u64 {
+        match *self {
+            Era::Immortal => 0,
+            Era::Mortal { period, phase } => (current.max(phase) - phase) / period * period + phase,
+        }
+    }
+
+    // First block the transaction is no longer valid in
+    pub fn death(&self, current: u64) -> Option<u64> {
+        self.period().map(|period| self.birth(current) + period)
+    }
+}
+
+pub fn set_mortality(period: Option<u64>) {
+    let _ = MORTALITY.set(period);
+}
+
+// The era for a transaction signed at current
+pub fn for_block(current: u64) -> Era {
+    match MORTALITY.get().copied().unwrap_or(Some(DEFAULT_PERIOD)) {
+        Some(period) => Era::mortal(period, current),
+        None => Era::Immortal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_era_encoding() {
+        assert_eq!(Era::Immortal.encode(), vec![0]);
+
+        // Values sp_runtime produces for the same periods and blocks
+        let era = Era::mortal(64, 42);
+        assert_eq!(
+            era,
+            Era::Mortal {
+                period: 64,
+                phase: 42
+            }
+        );
+        assert_eq!(era.encode(), vec![0xa5, 0x02]);
+        assert_eq!(Era::mortal(64, 64).encode(), vec![0x05, 0x00]);
+        assert_eq!(Era::mortal(32768, 20000).encode(), vec![0x4e, 0x9c]);
+
+        // Periods are rounded up to a power of two and clamped
+        assert_eq!(Era::mortal(100, 0).period(), Some(128));
+        assert_eq!(Era::mortal(1, 0).period(), Some(4));
+        assert_eq!(Era::mortal(u64::MAX, 0).period(), Some(MAX_PERIOD));
+
+        // The era is born at the signing block and dies a period later
+        let era = Era::mortal(64, 1000);
+        assert_eq!(era.birth(1000), 1000);
+        assert_eq!(era.birth(1010), 1000);
+        assert_eq!(era.death(1000), Some(1064));
+        assert_eq!(Era::Immortal.death(1000), None);
+    }
+}
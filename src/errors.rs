@@ -0,0 +1,264 @@
+//! Registration failures sorted into classes, so retry loops only retry what can still succeed
+use jsonrpsee::core::client::Error as ClientError;
+use std::{fmt, time::Duration};
+
+use crate::backoff::Backoff;
+use crate::utils;
+
+// Exit codes besides the ones each ErrorKind maps to, see the README
+pub const EXIT_FAILURE: u8 = 1; // Any error without a more specific code
+pub const EXIT_USAGE: u8 = 64; // Invalid arguments, sysexits' EX_USAGE since clap's 2 is taken
+pub const EXIT_CANCELLED: u8 = 130; // Stopped with Ctrl+C, as shells report SIGINT
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    Connection,         // The endpoint dropped, timed out or can't be reached
+    Nonce,              // Outdated nonce or too low a priority, a fresh submission fixes it
+    RegistrationClosed, // Disabled, interval full, rate limited or above the max burn for now
+    Balance,            // Not enough free balance for the burn and fee
+    AlreadyRegistered,
+    Chain, // Rejected or failed on chain for another reason
+    Fatal, // Declined, cancelled, or bad keys and arguments
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorKind::Connection => "connection",
+            ErrorKind::Nonce => "nonce",
+            ErrorKind::RegistrationClosed => "registration closed",
+            ErrorKind::Balance => "balance",
+            ErrorKind::AlreadyRegistered => "already registered",
+            ErrorKind::Chain => "chain error",
+            ErrorKind::Fatal => "fatal",
+        }
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            ErrorKind::Connection
+                | ErrorKind::Nonce
+                | ErrorKind::RegistrationClosed
+                | ErrorKind::Chain
+        )
+    }
+
+    // How long to wait before retry number attempt: the backoff policy, except that a nonce
+    // clash resolves within a block and a closed registration takes a while to reopen
+    pub fn retry_delay(&self, backoff: &Backoff, attempt: u32) -> Duration {
+        match self {
+            ErrorKind::Nonce => backoff.delay(attempt).min(utils::block_time()),
+            ErrorKind::RegistrationClosed => utils::blocks_to_duration(10),
+            _ => backoff.delay(attempt),
+        }
+    }
+
+    // What the process exits with when it fails with this class of error
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            ErrorKind::Fatal => EXIT_FAILURE,
+            ErrorKind::AlreadyRegistered => 2,
+            ErrorKind::Balance => 3,
+            ErrorKind::RegistrationClosed => 4,
+            ErrorKind::Connection => 5,
+            ErrorKind::Chain => 6,
+            ErrorKind::Nonce => 7,
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// An error raised where its class is known, instead of guessed from the message later
+#[derive(Debug)]
+pub struct RegistrationError {
+    pub kind: ErrorKind,
+    message: String,
+}
+
+impl RegistrationError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for RegistrationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for RegistrationError {}
+
+// Context that also settles the class, for failures whose class is known where they happen
+// but whose error comes from elsewhere, e.g. a wallet that doesn't load
+pub trait KindContext<T> {
+    fn kind_context(self, kind: ErrorKind, message: impl Into<String>) -> anyhow::Result<T>;
+}
+
+impl<T, E: Into<anyhow::Error>> KindContext<T> for Result<T, E> {
+    fn kind_context(self, kind: ErrorKind, message: impl Into<String>) -> anyhow::Result<T> {
+        self.map_err(|e| e.into().context(RegistrationError::new(kind, message)))
+    }
+}
+
+// Class of any error out of a registration attempt. Typed errors anywhere in the chain
+// win, then RPC transport failures, then the node's own errors by their message. Anything
+// unrecognized is taken for a chain error
+pub fn classify(error: &anyhow::Error) -> ErrorKind {
+    known_class(error).unwrap_or(ErrorKind::Chain)
+}
+
+// Exit code for the process failing with this error. Errors that aren't recognized as one
+// of the classes, e.g. bad arguments or config, get the generic failure code
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    if utils::shutdown_token().is_cancelled() {
+        return EXIT_CANCELLED;
+    }
+    known_class(error).map_or(EXIT_FAILURE, |kind| kind.exit_code())
+}
+
+fn known_class(error: &anyhow::Error) -> Option<ErrorKind> {
+    // Finds one added with kind_context, under any other context
+    if let Some(error) = error.downcast_ref::<RegistrationError>() {
+        return Some(error.kind);
+    }
+    for cause in error.chain() {
+        if let Some(error) = cause.downcast_ref::<RegistrationError>() {
+            return Some(error.kind);
+        }
+        if let Some(error) = cause.downcast_ref::<ClientError>() {
+            if matches!(
+                error,
+                ClientError::Transport(_)
+                    | ClientError::RestartNeeded(_)
+                    | ClientError::RequestTimeout
+            ) {
+                return Some(ErrorKind::Connection);
+            }
+        }
+    }
+
+    node_error_kind(&format!("{:#}", error))
+}
+
+// Class of an error the node reported, by the names in its message: transaction pool and
+// validity errors, the runtime's dispatch errors and an RPC provider's rate limit. Errors
+// this tool raises itself carry their class as a RegistrationError instead
+pub fn node_error_kind(message: &str) -> Option<ErrorKind> {
+    let contains = |patterns: &[&str]| patterns.iter().any(|p| message.contains(p));
+    Some(
+        if contains(&["Transaction is outdated", "Priority is too low", "Stale"]) {
+            ErrorKind::Nonce
+        } else if contains(&["Payment", "NotEnoughBalance"]) {
+            ErrorKind::Balance
+        } else if contains(&["AlreadyRegistered"]) {
+            ErrorKind::AlreadyRegistered
+        } else if contains(&["TooManyRegistrations", "RegistrationDisabled", "rate limit"]) {
+            ErrorKind::RegistrationClosed
+        } else if contains(&["dispatch error", "Invalid Transaction"]) {
+            ErrorKind::Chain
+        } else {
+            return None;
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::{anyhow, Context};
+
+    #[test]
+    fn test_classify_errors() {
+        // A typed error keeps its class under added context
+        let typed: anyhow::Error =
+            RegistrationError::new(ErrorKind::Balance, "Insufficient").into();
+        let typed = Err::<(), _>(typed).context("Attempt 1").unwrap_err();
+        assert_eq!(classify(&typed), ErrorKind::Balance);
+        assert!(!classify(&typed).is_retryable());
+
+        let timeout = anyhow::Error::new(ClientError::RequestTimeout).context("Failed to get uid");
+        assert_eq!(classify(&timeout), ErrorKind::Connection);
+
+        let nonce = anyhow!("Failed to submit extrinsic: Priority is too low: (1 vs 1)");
+        assert_eq!(classify(&nonce), ErrorKind::Nonce);
+        assert_eq!(
+            classify(&anyhow!(
+                "dispatch error: Module(SubtensorModule::TooManyRegistrationsThisBlock)"
+            )),
+            ErrorKind::RegistrationClosed
+        );
+        assert_eq!(
+            classify(&anyhow!("dispatch error: Module(pallet 7, error 6)")),
+            ErrorKind::Chain
+        );
+
+        // A class settled by context survives more context, wording of our own errors
+        // doesn't decide one
+        let wallet = Err::<(), _>(std::io::Error::other("No such file"))
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")
+            .context("Attempt 2")
+            .unwrap_err();
+        assert_eq!(classify(&wallet), ErrorKind::Fatal);
+        assert!(format!("{:#}", wallet).contains("Failed to load hotkey: No such file"));
+        assert!(ErrorKind::Chain.is_retryable());
+        let backoff = Backoff {
+            jitter: 0.0,
+            ..Backoff::default()
+        };
+        assert_eq!(
+            ErrorKind::Connection.retry_delay(&backoff, 3),
+            Duration::from_secs(4)
+        );
+        assert!(ErrorKind::Nonce.retry_delay(&backoff, 10) <= utils::block_time());
+        assert!(!ErrorKind::AlreadyRegistered.is_retryable());
+        assert_eq!(classify(&anyhow!("--subnet is required")), ErrorKind::Chain);
+    }
+
+    #[test]
+    fn test_exit_codes() {
+        // Documented in the README, scripts branch on them
+        let codes: Vec<u8> = [
+            ErrorKind::Fatal,
+            ErrorKind::AlreadyRegistered,
+            ErrorKind::Balance,
+            ErrorKind::RegistrationClosed,
+            ErrorKind::Connection,
+            ErrorKind::Chain,
+            ErrorKind::Nonce,
+        ]
+        .iter()
+        .map(ErrorKind::exit_code)
+        .collect();
+        assert_eq!(codes, vec![EXIT_FAILURE, 2, 3, 4, 5, 6, 7]);
+
+        // Only recognized failures get their class's exit code, under any context
+        let typed: anyhow::Error =
+            RegistrationError::new(ErrorKind::AlreadyRegistered, "Already registered").into();
+        assert_eq!(exit_code(&typed.context("Attempt 1")), 2);
+        let timeout = anyhow::Error::new(ClientError::RequestTimeout).context("Failed to get uid");
+        assert_eq!(exit_code(&timeout), 5);
+        assert_eq!(
+            exit_code(&anyhow!("Failed to submit extrinsic: Payment")),
+            3
+        );
+        assert_eq!(
+            exit_code(&anyhow!("dispatch error: Module(pallet 7, error 6)")),
+            6
+        );
+        assert_eq!(exit_code(&anyhow!("--subnet is required")), EXIT_FAILURE);
+        assert_eq!(
+            exit_code(&anyhow!("Connection to the wallet cancelled")),
+            EXIT_FAILURE
+        );
+    }
+}
@@ -0,0 +1,88 @@
+//! Proof of work for SubtensorModule.faucet, which funds a coldkey on test and local networks
+use primitive_types::{H256, U256};
+use sp_core::{
+    crypto::AccountId32,
+    hashing::{keccak_256, sha2_256},
+};
+
+// Work the faucet asks for, nodes built with fast-blocks accept far less
+pub const FAUCET_DIFFICULTY: u64 = 1_000_000;
+
+// The work has to be for one of the last few blocks when it is submitted
+pub const MAX_WORK_AGE_BLOCKS: u64 = 2;
+
+#[derive(Debug, Clone)]
+pub struct FaucetWork {
+    pub block_number: u64,
+    pub nonce: u64,
+    pub seal: H256,
+}
+
+// keccak(block hash ++ account), the part of the seal that doesn't change with the nonce
+pub fn hash_block_with_account(block_hash: &H256, account: &AccountId32) -> [u8; 32] {
+    let account: &[u8; 32] = account.as_ref();
+    keccak_256(&[block_hash.as_bytes(), &account[..]].concat())
+}
+
+// keccak(sha256(nonce as little endian ++ block and account hash)), as the chain computes it
+pub fn seal_hash(block_and_account: &[u8; 32], nonce: u64) -> H256 {
+    let pre_seal = [&nonce.to_le_bytes()[..], &block_and_account[..]].concat();
+    H256(keccak_256(&sha2_256(&pre_seal)))
+}
+
+// A seal meets the difficulty when seal * difficulty still fits in 256 bits
+pub fn meets_difficulty(seal: &H256, difficulty: U256) -> bool {
+    !U256::from_big_endian(seal.as_bytes())
+        .overflowing_mul(difficulty)
+        .1
+}
+
+// Search nonces from start, giving up after attempts so a new block can be picked up
+pub fn solve(
+    block_number: u64,
+    block_hash: &H256,
+    account: &AccountId32,
+    difficulty: U256,
+    start: u64,
+    attempts: u64,
+) -> Option<FaucetWork> {
+    let block_and_account = hash_block_with_account(block_hash, account);
+    (start..start.saturating_add(attempts)).find_map(|nonce| {
+        let seal = seal_hash(&block_and_account, nonce);
+        meets_difficulty(&seal, difficulty).then_some(FaucetWork {
+            block_number,
+            nonce,
+            seal,
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_faucet_work() {
+        let account = AccountId32::new([7; 32]);
+        let block_hash = H256::repeat_byte(0x42);
+
+        // Any seal meets difficulty 1, the maximum seal only difficulty 1
+        assert!(meets_difficulty(&H256::repeat_byte(0xff), U256::one()));
+        assert!(!meets_difficulty(&H256::repeat_byte(0xff), U256::from(2)));
+        assert!(meets_difficulty(&H256::zero(), U256::MAX));
+
+        let difficulty = U256::from(1_000);
+        let work = solve(10, &block_hash, &account, difficulty, 0, 1_000_000).unwrap();
+        assert_eq!(work.block_number, 10);
+        assert_eq!(
+            work.seal,
+            seal_hash(&hash_block_with_account(&block_hash, &account), work.nonce)
+        );
+        assert!(meets_difficulty(&work.seal, difficulty));
+        // The first nonce meeting the difficulty is the one found
+        assert!((0..work.nonce).all(|nonce| !meets_difficulty(
+            &seal_hash(&hash_block_with_account(&block_hash, &account), nonce),
+            difficulty
+        )));
+    }
+}
@@ -0,0 +1,145 @@
+//! Funding plan for a registration campaign: what each coldkey still needs and the
+//! transfers from a treasury wallet that cover it
+use serde::Serialize;
+
+use crate::constants::EXISTENTIAL_DEPOSIT;
+
+// Set aside per extrinsic for fees, in RAO. Generous, fees are a fraction of this
+pub const FEE_RESERVE: u64 = 1_000_000;
+
+#[derive(Debug, Clone)]
+pub struct FundPlanOptions {
+    pub margin_percent: u64, // Headroom on top of current burns, they may rise before registering
+    pub output: Option<String>, // Write the plan as JSON here
+    pub execute: bool,
+    pub assume_yes: bool,
+}
+
+// One coldkey taking part in the campaign
+#[derive(Debug, Clone, Serialize)]
+pub struct FundingNeed {
+    pub wallet: String,
+    pub address: String,
+    pub subnets: Vec<u16>,
+    pub required: u64, // Burns with margin plus fee reserves, in RAO
+    pub balance: u64,
+}
+
+impl FundingNeed {
+    pub fn shortfall(&self) -> u64 {
+        self.required.saturating_sub(self.balance)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PlannedTransfer {
+    pub wallet: String,
+    pub to: String,
+    pub amount: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FundPlan {
+    pub treasury: String,
+    pub treasury_balance: u64,
+    pub needs: Vec<FundingNeed>,
+    pub transfers: Vec<PlannedTransfer>,
+    pub treasury_required: u64, // Transfers, their fees and the treasury's own registrations
+}
+
+impl FundPlan {
+    pub fn new(treasury: String, treasury_balance: u64, needs: Vec<FundingNeed>) -> Self {
+        let transfers = plan_transfers(&needs, &treasury);
+        let own_need = needs
+            .iter()
+            .filter(|need| need.address == treasury)
+            .map(|need| need.required)
+            .sum::<u64>();
+        let treasury_required = transfers
+            .iter()
+            .map(|transfer| transfer.amount.saturating_add(FEE_RESERVE))
+            .sum::<u64>()
+            .saturating_add(own_need);
+
+        Self {
+            treasury,
+            treasury_balance,
+            needs,
+            transfers,
+            treasury_required,
+        }
+    }
+
+    pub fn treasury_shortfall(&self) -> u64 {
+        self.treasury_required.saturating_sub(self.treasury_balance)
+    }
+}
+
+// Cost of one registration at the given burn, with margin and a fee reserve
+pub fn registration_cost(burn: u64, margin_percent: u64) -> u64 {
+    burn.saturating_add(burn.saturating_mul(margin_percent) / 100)
+        .saturating_add(FEE_RESERVE)
+}
+
+// One transfer per coldkey that is short, for exactly the shortfall. An empty account needs
+// at least the existential deposit or transfer_keep_alive to it would fail
+pub fn plan_transfers(needs: &[FundingNeed], treasury: &str) -> Vec<PlannedTransfer> {
+    needs
+        .iter()
+        .filter(|need| need.address != treasury && need.shortfall() > 0)
+        .map(|need| PlannedTransfer {
+            wallet: need.wallet.clone(),
+            to: need.address.clone(),
+            amount: if need.balance < EXISTENTIAL_DEPOSIT {
+                need.shortfall().max(EXISTENTIAL_DEPOSIT - need.balance)
+            } else {
+                need.shortfall()
+            },
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fund_plan() {
+        let need = |address: &str, required: u64, balance: u64| FundingNeed {
+            wallet: address.to_string(),
+            address: address.to_string(),
+            subnets: vec![1],
+            required,
+            balance,
+        };
+        let plan = FundPlan::new(
+            "treasury".to_string(),
+            10_000_000,
+            vec![
+                need("funded", 2_000, 5_000),
+                need("short", 5_000, 1_000),
+                need("empty", 100, 0),
+                need("treasury", 3_000, 10_000_000),
+            ],
+        );
+
+        // Only short coldkeys get a transfer, the treasury pays its own registrations
+        assert_eq!(
+            plan.transfers
+                .iter()
+                .map(|t| (t.to.as_str(), t.amount))
+                .collect::<Vec<_>>(),
+            vec![("short", 4_000), ("empty", EXISTENTIAL_DEPOSIT)]
+        );
+        assert_eq!(
+            plan.treasury_required,
+            4_000 + EXISTENTIAL_DEPOSIT + 2 * FEE_RESERVE + 3_000
+        );
+        assert_eq!(plan.treasury_shortfall(), 0);
+
+        assert_eq!(
+            registration_cost(1_000_000_000, 10),
+            1_100_000_000 + FEE_RESERVE
+        );
+    }
+}
@@ -0,0 +1,314 @@
+//! Subnet metrics history kept in a local SQLite database (--record, History)
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Duration, NaiveDate, SecondsFormat, Utc};
+use colored::*;
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::client::SubnetInfo;
+use crate::records::Receipt;
+use crate::utils;
+
+// Timestamps are stored as fixed-width RFC 3339 text so they compare in time order
+fn format_timestamp(time: DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Secs, true)
+}
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS subnet_snapshots (
+    id INTEGER PRIMARY KEY,
+    recorded_at TEXT NOT NULL,
+    netuid INTEGER NOT NULL,
+    burn INTEGER NOT NULL,
+    neurons INTEGER NOT NULL,
+    max_neurons INTEGER NOT NULL,
+    emission INTEGER NOT NULL,
+    registrations_this_interval INTEGER NOT NULL
+);
+CREATE INDEX IF NOT EXISTS subnet_snapshots_netuid_time
+    ON subnet_snapshots (netuid, recorded_at);
+CREATE TABLE IF NOT EXISTS receipts (
+    id INTEGER PRIMARY KEY,
+    recorded_at TEXT NOT NULL,
+    netuid INTEGER NOT NULL,
+    hotkey TEXT NOT NULL,
+    coldkey TEXT NOT NULL,
+    burn INTEGER NOT NULL,
+    fee INTEGER,
+    tx_hash TEXT NOT NULL
+);
+";
+
+// One observation of a subnet
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SubnetSnapshot {
+    pub recorded_at: DateTime<Utc>,
+    pub netuid: u16,
+    pub burn: u64,
+    pub neurons: u16,
+    pub max_neurons: u16,
+    pub emission: u64,
+    pub registrations_this_interval: u16,
+}
+
+impl SubnetSnapshot {
+    pub fn from_info(info: &SubnetInfo, recorded_at: DateTime<Utc>) -> Self {
+        Self {
+            recorded_at,
+            netuid: info.netuid,
+            burn: info.burn,
+            neurons: info.subnetwork_n,
+            max_neurons: info.max_n,
+            emission: info.emission_value,
+            registrations_this_interval: info.registrations_this_interval,
+        }
+    }
+}
+
+// Per-subnet, per-day aggregate of recorded snapshots
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DailySummary {
+    pub netuid: u16,
+    pub day: NaiveDate,
+    pub samples: usize,
+    pub min_burn: u64,
+    pub max_burn: u64,
+    pub avg_burn: u64,
+    pub min_neurons: u16,
+    pub max_neurons: u16,
+    pub avg_emission: u64,
+    pub registrations: u64, // Estimated from the interval counter between samples
+}
+
+pub struct HistoryStore {
+    conn: Connection,
+}
+
+impl HistoryStore {
+    pub fn open(path: &str) -> Result<Self> {
+        let conn = Connection::open(path)
+            .with_context(|| format!("Failed to open history database {}", path))?;
+        Self::init(conn)
+    }
+
+    fn init(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)
+            .context("Failed to create history schema")?;
+        Ok(Self { conn })
+    }
+
+    pub fn record(&self, snapshot: &SubnetSnapshot) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO subnet_snapshots
+                    (recorded_at, netuid, burn, neurons, max_neurons, emission, registrations_this_interval)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    format_timestamp(snapshot.recorded_at),
+                    snapshot.netuid,
+                    snapshot.burn as i64,
+                    snapshot.neurons,
+                    snapshot.max_neurons,
+                    snapshot.emission as i64,
+                    snapshot.registrations_this_interval,
+                ],
+            )
+            .context("Failed to record subnet snapshot")?;
+        Ok(())
+    }
+
+    pub fn record_receipt(&self, receipt: &Receipt) -> Result<()> {
+        self.conn
+            .execute(
+                "INSERT INTO receipts (recorded_at, netuid, hotkey, coldkey, burn, fee, tx_hash)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+                params![
+                    format_timestamp(receipt.recorded_at),
+                    receipt.netuid,
+                    receipt.hotkey,
+                    receipt.coldkey,
+                    receipt.burn as i64,
+                    receipt.fee.map(|fee| fee as i64),
+                    receipt.tx_hash,
+                ],
+            )
+            .context("Failed to record registration receipt")?;
+        Ok(())
+    }
+
+    // Snapshots since a point in time, oldest first, optionally for one subnet
+    pub fn snapshots(
+        &self,
+        netuid: Option<u16>,
+        since: DateTime<Utc>,
+    ) -> Result<Vec<SubnetSnapshot>> {
+        let mut statement = self.conn.prepare(
+            "SELECT recorded_at, netuid, burn, neurons, max_neurons, emission, registrations_this_interval
+             FROM subnet_snapshots
+             WHERE recorded_at >= ?1 AND (?2 IS NULL OR netuid = ?2)
+             ORDER BY netuid, recorded_at",
+        )?;
+
+        let rows = statement.query_map(params![format_timestamp(since), netuid], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                SubnetSnapshot {
+                    recorded_at: DateTime::<Utc>::MIN_UTC,
+                    netuid: row.get(1)?,
+                    burn: row.get::<_, i64>(2)? as u64,
+                    neurons: row.get(3)?,
+                    max_neurons: row.get(4)?,
+                    emission: row.get::<_, i64>(5)? as u64,
+                    registrations_this_interval: row.get(6)?,
+                },
+            ))
+        })?;
+
+        rows.map(|row| {
+            let (recorded_at, mut snapshot) = row?;
+            snapshot.recorded_at = DateTime::parse_from_rfc3339(&recorded_at)
+                .map_err(|e| anyhow!("Invalid timestamp {} in history: {}", recorded_at, e))?
+                .with_timezone(&Utc);
+            Ok(snapshot)
+        })
+        .collect()
+    }
+}
+
+// Group snapshots (ordered by subnet then time) into daily summaries
+pub fn summarize_daily(snapshots: &[SubnetSnapshot]) -> Vec<DailySummary> {
+    let mut days: BTreeMap<(u16, NaiveDate), Vec<&SubnetSnapshot>> = BTreeMap::new();
+    for snapshot in snapshots {
+        days.entry((snapshot.netuid, snapshot.recorded_at.date_naive()))
+            .or_default()
+            .push(snapshot);
+    }
+
+    let mut summaries = Vec::with_capacity(days.len());
+    let mut previous: BTreeMap<u16, u16> = BTreeMap::new();
+    for ((netuid, day), samples) in days {
+        let mut registrations = 0u64;
+        for snapshot in &samples {
+            let count = snapshot.registrations_this_interval;
+            // The counter resets at each burn adjustment
+            registrations += match previous.insert(netuid, count) {
+                Some(last) if count >= last => (count - last) as u64,
+                Some(_) => count as u64,
+                None => 0,
+            };
+        }
+
+        let burns = samples.iter().map(|s| s.burn);
+        summaries.push(DailySummary {
+            netuid,
+            day,
+            samples: samples.len(),
+            min_burn: burns.clone().min().unwrap_or(0),
+            max_burn: burns.clone().max().unwrap_or(0),
+            avg_burn: (burns.map(u128::from).sum::<u128>() / samples.len() as u128) as u64,
+            min_neurons: samples.iter().map(|s| s.neurons).min().unwrap_or(0),
+            max_neurons: samples.iter().map(|s| s.neurons).max().unwrap_or(0),
+            avg_emission: (samples.iter().map(|s| s.emission as u128).sum::<u128>()
+                / samples.len() as u128) as u64,
+            registrations,
+        });
+    }
+
+    summaries
+}
+
+pub fn show_history(path: &str, netuid: Option<u16>, days: i64, format: &str) -> Result<()> {
+    let store = HistoryStore::open(path)?;
+    let since = Utc::now() - Duration::days(days);
+    let summaries = summarize_daily(&store.snapshots(netuid, since)?);
+
+    match format {
+        "json" => println!("{}", serde_json::to_string_pretty(&summaries)?),
+        "text" => {
+            println!("{}", "📚 Subnet History".bright_cyan().bold());
+            println!("═══════════════════════════════════════");
+            if summaries.is_empty() {
+                println!("   No snapshots recorded in the last {} day(s)", days);
+                return Ok(());
+            }
+            println!(
+                "   {:>6} {:<10} {:>7} {:>14} {:>14} {:>14} {:>9} {:>6}",
+                "Subnet", "Day", "Samples", "Min Burn", "Avg Burn", "Max Burn", "Neurons", "Regs"
+            );
+            for summary in &summaries {
+                println!(
+                    "   {:>6} {:<10} {:>7} {:>14} {:>14} {:>14} {:>9} {:>6}",
+                    summary.netuid,
+                    summary.day,
+                    summary.samples,
+                    utils::format_tao(summary.min_burn as u128),
+                    utils::format_tao(summary.avg_burn as u128),
+                    utils::format_tao(summary.max_burn as u128),
+                    format!("{}-{}", summary.min_neurons, summary.max_neurons),
+                    summary.registrations
+                );
+            }
+        }
+        other => {
+            return Err(anyhow!(
+                "Unknown output format: {}. Use text or json",
+                other
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_history_daily_summary() {
+        let store = HistoryStore::init(Connection::open_in_memory().unwrap()).unwrap();
+        let start = DateTime::parse_from_rfc3339("2025-06-01T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        for (hours, burn, neurons, registrations) in [
+            (0, 100, 250, 1),
+            (2, 300, 252, 3),
+            (4, 200, 252, 1),
+            (24, 400, 256, 2),
+        ] {
+            store
+                .record(&SubnetSnapshot {
+                    recorded_at: start + Duration::hours(hours),
+                    netuid: 1,
+                    burn,
+                    neurons,
+                    max_neurons: 256,
+                    emission: 10,
+                    registrations_this_interval: registrations,
+                })
+                .unwrap();
+        }
+
+        let snapshots = store.snapshots(Some(1), start).unwrap();
+        assert_eq!(snapshots.len(), 4);
+        assert!(store.snapshots(Some(2), start).unwrap().is_empty());
+
+        let summaries = summarize_daily(&snapshots);
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(
+            (
+                summaries[0].min_burn,
+                summaries[0].avg_burn,
+                summaries[0].max_burn
+            ),
+            (100, 200, 300)
+        );
+        assert_eq!(
+            (summaries[0].min_neurons, summaries[0].max_neurons),
+            (250, 252)
+        );
+        assert_eq!(summaries[0].registrations, 3);
+        assert_eq!(summaries[1].registrations, 1);
+    }
+}
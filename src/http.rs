@@ -0,0 +1,113 @@
+//! Minimal HTTPS client for REST endpoints such as price feeds and webhooks, and the
+//! connection loop behind the local API and the metrics endpoint
+use anyhow::{anyhow, Context, Result};
+use http_body_util::{BodyExt, Full};
+use hyper::{
+    body::{Bytes, Incoming},
+    server::conn::http1,
+    service::service_fn,
+    Method, Request, Response,
+};
+use hyper_util::{
+    client::legacy::Client,
+    rt::{TokioExecutor, TokioIo},
+};
+use std::{future::Future, time::Duration};
+use tokio::net::TcpListener;
+
+use crate::utils;
+
+const HTTP_TIMEOUT: Duration = Duration::from_secs(15);
+
+async fn send(request: Request<Full<Bytes>>) -> Result<Bytes> {
+    let connector = hyper_rustls::HttpsConnectorBuilder::new()
+        .with_native_roots()
+        .context("Failed to load native TLS roots")?
+        .https_or_http()
+        .enable_http1()
+        .build();
+    let client: Client<_, Full<Bytes>> = Client::builder(TokioExecutor::new()).build(connector);
+
+    let uri = request.uri().clone();
+    let response = tokio::time::timeout(HTTP_TIMEOUT, client.request(request))
+        .await
+        .map_err(|_| anyhow!("Request to {} timed out", uri))?
+        .with_context(|| format!("Request to {} failed", uri))?;
+
+    let status = response.status();
+    let body = response
+        .into_body()
+        .collect()
+        .await
+        .with_context(|| format!("Failed to read response from {}", uri))?
+        .to_bytes();
+
+    if !status.is_success() {
+        return Err(anyhow!(
+            "{} returned {}: {}",
+            uri,
+            status,
+            String::from_utf8_lossy(&body)
+        ));
+    }
+
+    Ok(body)
+}
+
+pub async fn get_json(url: &str) -> Result<serde_json::Value> {
+    let request = Request::builder()
+        .method(Method::GET)
+        .uri(url)
+        .header("accept", "application/json")
+        .header("user-agent", concat!("btreg/", env!("CARGO_PKG_VERSION")))
+        .body(Full::new(Bytes::new()))?;
+
+    let body = send(request).await?;
+    serde_json::from_slice(&body).with_context(|| format!("Invalid JSON from {}", url))
+}
+
+pub async fn post_json(url: &str, body: &serde_json::Value) -> Result<()> {
+    let request = Request::builder()
+        .method(Method::POST)
+        .uri(url)
+        .header("content-type", "application/json")
+        .header("user-agent", concat!("btreg/", env!("CARGO_PKG_VERSION")))
+        .body(Full::new(Bytes::from(serde_json::to_vec(body)?)))?;
+
+    send(request).await?;
+    Ok(())
+}
+
+// Answer each connection with handle on its own task, until Ctrl+C
+pub async fn serve<F, Fut>(listener: TcpListener, handle: F)
+where
+    F: Fn(Request<Incoming>) -> Fut + Clone + Send + 'static,
+    Fut: Future<Output = Response<Full<Bytes>>> + Send + 'static,
+{
+    let shutdown = utils::shutdown_token();
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    log::debug!("Failed to accept a connection: {}", e);
+                    continue;
+                }
+            },
+            _ = shutdown.cancelled() => return,
+        };
+        let handle = handle.clone();
+        tokio::spawn(async move {
+            let service = service_fn(move |request| {
+                let response = handle(request);
+                async move { Ok::<_, std::convert::Infallible>(response.await) }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                log::debug!("Connection from {} failed: {}", peer, e);
+            }
+        });
+    }
+}
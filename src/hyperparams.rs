@@ -0,0 +1,101 @@
+//! Subnet hyperparameters tracked between monitor cycles, reported field by field when a
+//! subnet owner changes them
+use serde::Serialize;
+
+use crate::client::SubnetInfo;
+use crate::utils;
+
+// The owner-tunable values of one subnet, formatted for display
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hyperparameters {
+    pub netuid: u16,
+    values: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ParamChange {
+    pub netuid: u16,
+    pub name: &'static str,
+    pub old: String,
+    pub new: String,
+}
+
+impl Hyperparameters {
+    pub fn from_info(info: &SubnetInfo) -> Self {
+        let tao = |rao: u64| utils::format_tao(rao as u128);
+        Self {
+            netuid: info.netuid,
+            values: vec![
+                ("burn", tao(info.burn)),
+                ("min_burn", tao(info.min_burn)),
+                ("max_burn", tao(info.max_burn)),
+                ("tempo", info.tempo.to_string()),
+                (
+                    "registration_allowed",
+                    info.registration_allowed.to_string(),
+                ),
+                ("immunity_period", info.immunity_period.to_string()),
+                ("max_n", info.max_n.to_string()),
+                (
+                    "max_registrations_per_block",
+                    info.max_registrations_per_block.to_string(),
+                ),
+                (
+                    "target_registrations_per_interval",
+                    info.target_registrations_per_interval.to_string(),
+                ),
+            ],
+        }
+    }
+
+    // Values that differ from an earlier snapshot of the same subnet
+    pub fn changes_since(&self, earlier: &Hyperparameters) -> Vec<ParamChange> {
+        self.values
+            .iter()
+            .zip(&earlier.values)
+            .filter(|((_, new), (_, old))| new != old)
+            .map(|((name, new), (_, old))| ParamChange {
+                netuid: self.netuid,
+                name,
+                old: old.clone(),
+                new: new.clone(),
+            })
+            .collect()
+    }
+}
+
+impl ParamChange {
+    pub fn describe(&self) -> String {
+        format!("{}: {} → {}", self.name, self.old, self.new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn params(burn: &str, tempo: &str) -> Hyperparameters {
+        Hyperparameters {
+            netuid: 3,
+            values: vec![("burn", burn.into()), ("tempo", tempo.into())],
+        }
+    }
+
+    #[test]
+    fn test_hyperparameter_changes() {
+        let before = params("1.000 TAO", "360");
+        assert!(before.changes_since(&before).is_empty());
+
+        let changes = params("1.000 TAO", "99").changes_since(&before);
+        assert_eq!(
+            changes,
+            vec![ParamChange {
+                netuid: 3,
+                name: "tempo",
+                old: "360".into(),
+                new: "99".into(),
+            }]
+        );
+        assert_eq!(changes[0].describe(), "tempo: 360 → 99");
+    }
+}
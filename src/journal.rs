@@ -0,0 +1,179 @@
+//! Per-operation state of a batch run, kept next to its config so an interrupted run can resume
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::utils;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntryState {
+    Pending, // Not run yet, or interrupted while running
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalEntry {
+    pub operation: String,
+    pub subnet: Option<u16>,
+    pub hotkey: String,
+    pub state: EntryState,
+    pub tx_hash: Option<String>,
+    pub error: Option<String>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchJournal {
+    #[serde(skip)]
+    path: String,
+    config_hash: String, // The journal only applies to the exact config it was written for
+    pub entries: Vec<JournalEntry>,
+}
+
+impl BatchJournal {
+    pub fn path_for(config_path: &str) -> String {
+        format!("{}.journal", config_path)
+    }
+
+    // Fresh journal with every operation pending, refusing to drop an interrupted run's state
+    pub fn start(
+        config_path: &str,
+        config_content: &str,
+        operations: impl IntoIterator<Item = (String, Option<u16>, String)>,
+    ) -> Result<Self> {
+        let path = Self::path_for(config_path);
+        if let Ok(previous) = Self::read(&path) {
+            let unfinished = previous.count(EntryState::Pending);
+            if unfinished > 0 {
+                return Err(anyhow!(
+                    "{} has {} unfinished operation(s) from an interrupted run. \
+                     Pass --resume to continue it, or delete the journal to start over",
+                    path,
+                    unfinished
+                ));
+            }
+        }
+
+        let journal = Self {
+            path,
+            config_hash: config_hash(config_content),
+            entries: operations
+                .into_iter()
+                .map(|(operation, subnet, hotkey)| JournalEntry {
+                    operation,
+                    subnet,
+                    hotkey,
+                    state: EntryState::Pending,
+                    tx_hash: None,
+                    error: None,
+                    updated_at: None,
+                })
+                .collect(),
+        };
+        journal.save()?;
+        Ok(journal)
+    }
+
+    // Journal of an earlier run of this exact config
+    pub fn resume(config_path: &str, config_content: &str) -> Result<Self> {
+        let path = Self::path_for(config_path);
+        let journal = Self::read(&path)?;
+        if journal.config_hash != config_hash(config_content) {
+            return Err(anyhow!(
+                "{} changed since {} was written, resuming could skip or repeat the wrong operations",
+                config_path,
+                path
+            ));
+        }
+        Ok(journal)
+    }
+
+    fn read(path: &str) -> Result<Self> {
+        let content = std::fs::read_to_string(path)
+            .with_context(|| format!("No batch journal at {}", path))?;
+        let mut journal: Self = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid batch journal {}", path))?;
+        journal.path = path.to_string();
+        Ok(journal)
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    pub fn is_succeeded(&self, index: usize) -> bool {
+        self.entries
+            .get(index)
+            .is_some_and(|entry| entry.state == EntryState::Succeeded)
+    }
+
+    pub fn count(&self, state: EntryState) -> usize {
+        self.entries
+            .iter()
+            .filter(|entry| entry.state == state)
+            .count()
+    }
+
+    // Record how an operation ended, written out immediately so a crash loses nothing
+    pub fn record(
+        &mut self,
+        index: usize,
+        state: EntryState,
+        tx_hash: Option<String>,
+        error: Option<String>,
+    ) -> Result<()> {
+        let entry = self
+            .entries
+            .get_mut(index)
+            .ok_or_else(|| anyhow!("No operation {} in the batch journal", index + 1))?;
+        entry.state = state;
+        entry.tx_hash = tx_hash.or(entry.tx_hash.take());
+        entry.error = error;
+        entry.updated_at = Some(Utc::now());
+        self.save()
+    }
+
+    fn save(&self) -> Result<()> {
+        utils::write_atomic(&self.path, &serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn config_hash(config_content: &str) -> String {
+    hex::encode(sp_core::blake2_256(config_content.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_batch_journal_resume() {
+        let dir = std::env::temp_dir().join(format!("btreg-journal-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("batch.json").to_string_lossy().to_string();
+        let config = r#"{"operations": []}"#;
+        let operations = || {
+            vec![
+                ("register".to_string(), Some(1), "default".to_string()),
+                ("register".to_string(), Some(2), "default".to_string()),
+            ]
+        };
+
+        let mut journal = BatchJournal::start(&config_path, config, operations()).unwrap();
+        journal
+            .record(0, EntryState::Succeeded, Some("0xabc".into()), None)
+            .unwrap();
+
+        // Interrupted halfway: a plain rerun refuses, resume picks up the state
+        assert!(BatchJournal::start(&config_path, config, operations()).is_err());
+        let journal = BatchJournal::resume(&config_path, config).unwrap();
+        assert!(journal.is_succeeded(0));
+        assert!(!journal.is_succeeded(1));
+        assert_eq!(journal.entries[0].tx_hash.as_deref(), Some("0xabc"));
+        assert!(BatchJournal::resume(&config_path, "{}").is_err());
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -1,73 +1,768 @@
 //! Key Utility functions for Bittensor registration
 use anyhow::{Context, Result, *};
-use serde::Deserialize;
 use sp_core::{
     crypto::{AccountId32, Ss58Codec},
-    sr25519::Pair as Sr25519Pair,
+    ecdsa, ed25519,
+    sr25519::{self, Pair as Sr25519Pair},
     Pair,
 };
 
-use std::fs;
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::errors::{ErrorKind, RegistrationError};
+use crate::keyfile;
+use crate::paths;
+pub use crate::paths::expand_home;
+
+// File holding the password for encrypted key files, prompts interactively when unset
+static PASSWORD_FILE: OnceLock<PathBuf> = OnceLock::new();
+
+// Root of the btcli wallet layout: <wallets>/<wallet>/coldkey, <wallets>/<wallet>/hotkeys/<hotkey>
+static WALLETS_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// Remember key file passwords in the OS keyring (--use-keyring)
+static USE_KEYRING: OnceLock<bool> = OnceLock::new();
+
+const KEYRING_SERVICE: &str = "btreg";
+
+// Scheme for keys that do not carry one (--scheme), sr25519 unless set
+static DEFAULT_SCHEME: OnceLock<KeyScheme> = OnceLock::new();
+
+pub fn set_password_file(path: &str) {
+    let _ = PASSWORD_FILE.set(expand_home(path));
+}
+
+// Whether encrypted key files unlock without asking on the terminal
+pub fn password_file_set() -> bool {
+    PASSWORD_FILE.get().is_some()
+}
+
+pub fn set_wallets_dir(path: &str) {
+    let _ = WALLETS_DIR.set(expand_home(path));
+}
+
+pub fn wallets_dir() -> PathBuf {
+    WALLETS_DIR
+        .get()
+        .cloned()
+        .unwrap_or_else(paths::default_wallets_dir)
+}
+
+pub fn set_use_keyring(enabled: bool) {
+    let _ = USE_KEYRING.set(enabled);
+}
+
+fn keyring_enabled() -> bool {
+    USE_KEYRING.get().copied().unwrap_or(false)
+}
+
+// Keyring entry name: the wallet name for <wallets>/<wallet>/coldkey, else the path
+fn keyring_account(key_path: &str) -> String {
+    keyring_account_in(&wallets_dir(), key_path)
+}
+
+fn keyring_account_in(wallets: &Path, key_path: &str) -> String {
+    let path = expand_home(key_path);
+    match path.strip_prefix(wallets) {
+        Result::Ok(relative) if relative.file_name().is_some_and(|f| f == "coldkey") => relative
+            .parent()
+            .map(|wallet| wallet.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+        Result::Ok(relative) => relative.to_string_lossy().into_owned(),
+        Err(_) => path.to_string_lossy().into_owned(),
+    }
+}
+
+fn keyring_entry(key_path: &str) -> Result<keyring::Entry> {
+    keyring::Entry::new(KEYRING_SERVICE, &keyring_account(key_path))
+        .context("Failed to open the OS keyring")
+}
+
+// Stored password for a key file, None when there is none or the keyring is unavailable
+fn keyring_password(key_path: &str) -> Option<String> {
+    match keyring_entry(key_path).and_then(|entry| Ok(entry.get_password()?)) {
+        Result::Ok(password) => Some(password),
+        Err(e) => {
+            if !matches!(e.downcast_ref(), Some(keyring::Error::NoEntry)) {
+                println!("⚠️ Keyring lookup failed: {}", e);
+            }
+            None
+        }
+    }
+}
+
+// Save a password that just unlocked a key file, failures only warn
+pub fn remember_password(key_path: &str, password: &str) {
+    if !keyring_enabled() {
+        return;
+    }
+    match keyring_entry(key_path).and_then(|entry| Ok(entry.set_password(password)?)) {
+        Result::Ok(()) => println!(
+            "🔐 Saved password for {} in the OS keyring",
+            keyring_account(key_path)
+        ),
+        Err(e) => println!("⚠️ Could not save password to the OS keyring: {}", e),
+    }
+}
+
+// Remove a stored password, returns false when none was stored
+pub fn forget_password(key_path: &str) -> Result<bool> {
+    match keyring_entry(key_path)?.delete_credential() {
+        Result::Ok(()) => Ok(true),
+        Err(keyring::Error::NoEntry) => Ok(false),
+        Err(e) => Err(anyhow!(
+            "Failed to remove password from the OS keyring: {}",
+            e
+        )),
+    }
+}
+
+pub fn set_default_scheme(scheme: &str) -> Result<()> {
+    let _ = DEFAULT_SCHEME.set(scheme.parse()?);
+    Ok(())
+}
+
+pub fn default_scheme() -> KeyScheme {
+    DEFAULT_SCHEME.get().copied().unwrap_or_default()
+}
+
+// Signature schemes an account can be controlled by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum KeyScheme {
+    #[default]
+    Sr25519,
+    Ed25519,
+    Ecdsa,
+}
+
+impl KeyScheme {
+    pub const ALL: [KeyScheme; 3] = [KeyScheme::Sr25519, KeyScheme::Ed25519, KeyScheme::Ecdsa];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyScheme::Sr25519 => "sr25519",
+            KeyScheme::Ed25519 => "ed25519",
+            KeyScheme::Ecdsa => "ecdsa",
+        }
+    }
+}
+
+impl FromStr for KeyScheme {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        KeyScheme::ALL
+            .into_iter()
+            .find(|scheme| scheme.as_str().eq_ignore_ascii_case(value))
+            .ok_or_else(|| {
+                anyhow!(
+                    "Unknown key scheme: {}. Use sr25519, ed25519 or ecdsa",
+                    value
+                )
+            })
+    }
+}
+
+impl fmt::Display for KeyScheme {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// A signing key of any supported scheme
+#[derive(Clone)]
+#[allow(clippy::large_enum_variant)]
+pub enum KeyPair {
+    Sr25519(sr25519::Pair),
+    Ed25519(ed25519::Pair),
+    Ecdsa(ecdsa::Pair),
+}
+
+impl KeyPair {
+    // Dev key, seed or phrase (with optional derivation path) under a scheme
+    pub fn from_string(secret: &str, scheme: KeyScheme) -> Result<Self> {
+        let invalid = |e| anyhow!("invalid {} secret: {:?}", scheme, e);
+        Ok(match scheme {
+            KeyScheme::Sr25519 => {
+                KeyPair::Sr25519(sr25519::Pair::from_string(secret, None).map_err(invalid)?)
+            }
+            KeyScheme::Ed25519 => {
+                KeyPair::Ed25519(ed25519::Pair::from_string(secret, None).map_err(invalid)?)
+            }
+            KeyScheme::Ecdsa => {
+                KeyPair::Ecdsa(ecdsa::Pair::from_string(secret, None).map_err(invalid)?)
+            }
+        })
+    }
+
+    pub fn scheme(&self) -> KeyScheme {
+        match self {
+            KeyPair::Sr25519(_) => KeyScheme::Sr25519,
+            KeyPair::Ed25519(_) => KeyScheme::Ed25519,
+            KeyPair::Ecdsa(_) => KeyScheme::Ecdsa,
+        }
+    }
+
+    // Raw public key: 32 bytes, or 33 (compressed) for ecdsa
+    pub fn public_bytes(&self) -> Vec<u8> {
+        match self {
+            KeyPair::Sr25519(pair) => pair.public().0.to_vec(),
+            KeyPair::Ed25519(pair) => pair.public().0.to_vec(),
+            KeyPair::Ecdsa(pair) => AsRef::<[u8]>::as_ref(&pair.public()).to_vec(),
+        }
+    }
+
+    // On-chain account, ecdsa accounts are the blake2 hash of the public key
+    pub fn account_id(&self) -> AccountId32 {
+        match self {
+            KeyPair::Sr25519(pair) => AccountId32::from(pair.public().0),
+            KeyPair::Ed25519(pair) => AccountId32::from(pair.public().0),
+            KeyPair::Ecdsa(_) => AccountId32::from(sp_core::blake2_256(&self.public_bytes())),
+        }
+    }
+
+    // SCALE-encoded MultiSignature over a payload: variant index then signature bytes
+    pub fn sign_multi(&self, payload: &[u8]) -> Vec<u8> {
+        let (variant, signature) = match self {
+            KeyPair::Ed25519(pair) => (0x00u8, AsRef::<[u8]>::as_ref(&pair.sign(payload)).to_vec()),
+            KeyPair::Sr25519(pair) => (0x01u8, AsRef::<[u8]>::as_ref(&pair.sign(payload)).to_vec()),
+            KeyPair::Ecdsa(pair) => (0x02u8, AsRef::<[u8]>::as_ref(&pair.sign(payload)).to_vec()),
+        };
+        [vec![variant], signature].concat()
+    }
+}
+
+// Dev keys and existing files are used as given, never resolved as wallet names
+fn is_explicit_key(key: &str) -> bool {
+    key.starts_with("//") || expand_home(key).exists()
+}
+
+// Map a wallet name to its coldkey file, other inputs pass through unchanged
+pub fn resolve_wallet(wallet: &str) -> String {
+    resolve_wallet_in(&wallets_dir(), wallet)
+}
+
+// The same under a given wallets directory
+pub fn resolve_wallet_in(wallets: &Path, wallet: &str) -> String {
+    if is_explicit_key(wallet) {
+        return wallet.to_string();
+    }
+
+    let wallet_dir = wallets.join(wallet);
+    // A wallet holding only coldkeypub.txt is view-only, good for every read
+    let coldkey = ["coldkey", "coldkeypub.txt"]
+        .iter()
+        .map(|name| wallet_dir.join(name))
+        .find(|path| path.is_file());
+    match coldkey {
+        Some(coldkey) => coldkey.to_string_lossy().into_owned(),
+        None => wallet.to_string(),
+    }
+}
+
+// Map a hotkey name to its file, using the wallet name or "wallet/hotkey" form
+pub fn resolve_hotkey(wallet: Option<&str>, hotkey: &str) -> String {
+    resolve_hotkey_in(&wallets_dir(), wallet, hotkey)
+}
+
+pub fn resolve_hotkey_in(wallets: &Path, wallet: Option<&str>, hotkey: &str) -> String {
+    if is_explicit_key(hotkey) {
+        return hotkey.to_string();
+    }
+
+    let wallet_dir = match wallet {
+        // Coldkey given as a file inside a wallet directory
+        Some(wallet) if expand_home(wallet).is_file() => {
+            expand_home(wallet).parent().map(|dir| dir.to_path_buf())
+        }
+        Some(wallet) if !wallet.starts_with("//") => Some(wallets.join(wallet)),
+        _ => None,
+    };
+
+    let candidates = wallet_dir
+        .map(|dir| dir.join("hotkeys").join(hotkey))
+        .into_iter()
+        .chain(
+            hotkey
+                .split_once('/')
+                .map(|(wallet, name)| wallets.join(wallet).join("hotkeys").join(name)),
+        );
+
+    for candidate in candidates {
+        if candidate.is_file() {
+            return candidate.to_string_lossy().into_owned();
+        }
+    }
+
+    hotkey.to_string()
+}
+
+// Every hotkey file in a directory, or in a wallet's hotkeys directory when given its name,
+// sorted by name. Hidden files such as editor backups are left out
+pub fn hotkey_files(dir: &str) -> Result<Vec<String>> {
+    hotkey_files_in(&wallets_dir(), dir)
+}
+
+fn hotkey_files_in(wallets: &Path, dir: &str) -> Result<Vec<String>> {
+    let path = expand_home(dir);
+    let path = if path.is_dir() {
+        path
+    } else {
+        wallets.join(dir).join("hotkeys")
+    };
+    let mut hotkeys: Vec<String> = fs::read_dir(&path)
+        .with_context(|| format!("Failed to read hotkey directory {}", path.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.is_file())
+        .filter(|path| {
+            path.file_name()
+                .is_some_and(|name| !name.to_string_lossy().starts_with('.'))
+        })
+        .map(|path| path.to_string_lossy().into_owned())
+        .collect();
+    if hotkeys.is_empty() {
+        return Err(anyhow!("No hotkeys found in {}", path.display()));
+    }
+    hotkeys.sort();
+    Ok(hotkeys)
+}
+
+fn read_key_password(key_path: &str) -> Result<String> {
+    if let Some(password_file) = PASSWORD_FILE.get() {
+        let password = fs::read_to_string(password_file).context(format!(
+            "Failed to read password file: {}",
+            password_file.display()
+        ))?;
+        return Ok(password.trim_end_matches(['\r', '\n']).to_string());
+    }
+
+    rpassword::prompt_password(format!("🔐 Enter password to unlock {}: ", key_path))
+        .context("Failed to read password")
+}
+
+// Password for a new encrypted key file, entered twice unless read from --password-file
+pub fn read_new_password(key_name: &str) -> Result<String> {
+    if PASSWORD_FILE.get().is_some() {
+        return read_key_password(key_name);
+    }
+
+    let password = rpassword::prompt_password(format!("🔐 Choose a password for {}: ", key_name))
+        .context("Failed to read password")?;
+    let confirmation = rpassword::prompt_password("🔐 Repeat the password: ")
+        .context("Failed to read password")?;
+    if password != confirmation {
+        return Err(anyhow!("Passwords do not match"));
+    }
+    if password.is_empty() {
+        return Err(anyhow!("Password must not be empty"));
+    }
+    Ok(password)
+}
+
+// Try the keyring first, then ask, saving a password that worked when --use-keyring is set
+fn decrypt_key_file(path: &str, raw: &[u8]) -> Result<Vec<u8>> {
+    if keyring_enabled() && PASSWORD_FILE.get().is_none() {
+        if let Some(password) = keyring_password(path) {
+            match keyfile::decrypt(raw, &password) {
+                Result::Ok(decrypted) => return Ok(decrypted),
+                Err(_) => println!(
+                    "⚠️ Keyring password for {} no longer unlocks it",
+                    keyring_account(path)
+                ),
+            }
+        }
+    }
+
+    let password = read_key_password(path)?;
+    let decrypted = keyfile::decrypt(raw, &password)
+        .context(format!("Failed to decrypt key file: {}", path))?;
+    if PASSWORD_FILE.get().is_none() {
+        remember_password(path, &password);
+    }
+    Ok(decrypted)
+}
+
+// Read a key file, decrypting btcli encrypted formats
+fn read_key_file(path: &str) -> Result<String> {
+    let raw = fs::read(expand_home(path)).context(format!("Failed to read key file: {}", path))?;
+
+    if keyfile::is_encrypted(&raw) {
+        let decrypted = decrypt_key_file(path, &raw)?;
+        String::from_utf8(decrypted).context("Decrypted key file is not valid UTF-8")
+    } else {
+        String::from_utf8(raw).context(format!("Key file is not valid UTF-8: {}", path))
+    }
+}
+
+// JSON key file layouts we accept, tried in order
+struct JsonKeyFormat {
+    name: &'static str,
+    required: &'static [&'static str], // Each entry needs one of its "|" alternatives
+    allowed: &'static [&'static str],
+}
+
+const JSON_KEY_FORMATS: &[JsonKeyFormat] = &[
+    JsonKeyFormat {
+        name: "btcli keyfile",
+        required: &["secretSeed|secretPhrase"],
+        allowed: &[
+            "accountId",
+            "publicKey",
+            "privateKey",
+            "secretPhrase",
+            "secretSeed",
+            "ss58Address",
+            "scheme",
+        ],
+    },
+    JsonKeyFormat {
+        name: "subkey export",
+        required: &["secretSeed|secretPhrase", "ss58Address"],
+        allowed: &[
+            "accountId",
+            "networkId",
+            "publicKey",
+            "secretPhrase",
+            "secretSeed",
+            "ss58Address",
+            "ss58PublicKey",
+            "scheme",
+        ],
+    },
+    JsonKeyFormat {
+        name: "raw seed",
+        required: &["seed|phrase|secret_seed|secret_phrase"],
+        allowed: &["seed", "phrase", "secret_seed", "secret_phrase", "scheme"],
+    },
+];
+
+impl JsonKeyFormat {
+    fn parse(&self, fields: &serde_json::Map<String, serde_json::Value>) -> Result<KeyPair> {
+        if let Some(unexpected) = fields.keys().find(|k| !self.allowed.contains(&k.as_str())) {
+            return Err(anyhow!("unexpected field \"{}\"", unexpected));
+        }
+        for required in self.required {
+            if !required.split('|').any(|field| fields.contains_key(field)) {
+                return Err(anyhow!("missing {}", required.replace('|', " or ")));
+            }
+        }
+
+        let text = |field: &str| text_field(fields, field);
+
+        let secret = if let Some(seed) = text("secretSeed")?
+            .or(text("seed")?)
+            .or(text("secret_seed")?)
+        {
+            let hex_seed = seed.trim_start_matches("0x");
+            if hex_seed.len() != 64 || !hex_seed.chars().all(|c| c.is_ascii_hexdigit()) {
+                return Err(anyhow!("seed must be 32 bytes of hex (0x + 64 hex digits)"));
+            }
+            seed
+        } else {
+            let phrase = text("secretPhrase")?
+                .or(text("phrase")?)
+                .or(text("secret_phrase")?)
+                .unwrap_or_default();
+            if phrase.trim().is_empty() {
+                return Err(anyhow!("secret phrase is empty"));
+            }
+            phrase
+        };
+
+        // An explicit scheme wins, otherwise the default is tried first and the
+        // public parts of the file pick between schemes
+        let schemes = match text("scheme")? {
+            Some(scheme) => vec![scheme.parse()?],
+            None => {
+                let default = default_scheme();
+                std::iter::once(default)
+                    .chain(KeyScheme::ALL.into_iter().filter(|s| *s != default))
+                    .collect()
+            }
+        };
+
+        let mut first_problem = None;
+        for scheme in schemes {
+            let pair = KeyPair::from_string(secret, scheme)?;
+            match check_public_fields(&pair, fields) {
+                Result::Ok(()) => return Ok(pair),
+                Err(problem) => {
+                    first_problem.get_or_insert(problem);
+                }
+            }
+        }
+        Err(first_problem.unwrap_or_else(|| anyhow!("no key scheme matched")))
+    }
+}
+
+fn text_field<'a>(
+    fields: &'a serde_json::Map<String, serde_json::Value>,
+    field: &str,
+) -> Result<Option<&'a str>> {
+    match fields.get(field) {
+        None => Ok(None),
+        Some(serde_json::Value::String(value)) => Ok(Some(value.as_str())),
+        Some(other) => Err(anyhow!("\"{}\" must be a string, found {}", field, other)),
+    }
+}
+
+// Public parts, when present, must belong to the secret
+fn check_public_fields(
+    pair: &KeyPair,
+    fields: &serde_json::Map<String, serde_json::Value>,
+) -> Result<()> {
+    let text = |field: &str| text_field(fields, field);
+    let account = pair.account_id();
+    let public_hex = format!("0x{}", hex::encode(pair.public_bytes()));
+    let account_hex = format!("0x{}", hex::encode(&account));
+    for (field, expected) in [("publicKey", &public_hex), ("accountId", &account_hex)] {
+        if let Some(value) = text(field)? {
+            if !value.eq_ignore_ascii_case(expected) {
+                return Err(anyhow!(
+                    "{} does not match the {} secret ({})",
+                    field,
+                    pair.scheme(),
+                    expected
+                ));
+            }
+        }
+    }
+    if let Some(address) = text("ss58Address")? {
+        let expected = AccountId32::from_ss58check(address)
+            .map_err(|_| anyhow!("ss58Address {} is not a valid address", address))?;
+        if expected != account {
+            return Err(anyhow!(
+                "ss58Address does not match the {} secret",
+                pair.scheme()
+            ));
+        }
+    }
+    Ok(())
+}
+
+// btcli keyfile contents for a key generated from a mnemonic
+pub fn keyfile_json(pair: &Sr25519Pair, phrase: &str, seed: &[u8]) -> serde_json::Value {
+    let public_hex = format!("0x{}", hex::encode(pair.public().0));
+    serde_json::json!({
+        "accountId": public_hex,
+        "publicKey": public_hex,
+        "privateKey": format!("0x{}", hex::encode(pair.to_raw_vec())),
+        "secretPhrase": phrase,
+        "secretSeed": format!("0x{}", hex::encode(seed)),
+        "ss58Address": AccountId32::from(pair.public().0).to_ss58check(),
+    })
+}
+
+// btcli coldkeypub.txt contents, the public half only
+pub fn public_keyfile_json(pair: &Sr25519Pair) -> serde_json::Value {
+    let public_hex = format!("0x{}", hex::encode(pair.public().0));
+    serde_json::json!({
+        "accountId": public_hex,
+        "publicKey": public_hex,
+        "privateKey": null,
+        "secretPhrase": null,
+        "secretSeed": null,
+        "ss58Address": AccountId32::from(pair.public().0).to_ss58check(),
+    })
+}
+
+// Parse a JSON key file, listing what was wrong with each format when none fits
+fn parse_json_key_file(contents: &str) -> Result<KeyPair> {
+    let value: serde_json::Value = serde_json::from_str(contents).map_err(|e| {
+        anyhow!(
+            "not valid JSON at line {}, column {}: {}",
+            e.line(),
+            e.column(),
+            e
+        )
+    })?;
+    let fields = value
+        .as_object()
+        .ok_or_else(|| anyhow!("expected a JSON object"))?;
+
+    let mut problems = Vec::new();
+    for format in JSON_KEY_FORMATS {
+        match format.parse(fields) {
+            Result::Ok(pair) => return Ok(pair),
+            Err(problem) => problems.push(format!("  - {}: {}", format.name, problem)),
+        }
+    }
+
+    Err(anyhow!(
+        "no recognized key format matched. Tried:\n{}",
+        problems.join("\n")
+    ))
+}
+
+// A coldkey as given on the command line: key material to sign with, or only its address.
+// Reads work with either, signing with a view-only coldkey fails up front
+#[allow(clippy::large_enum_variant)]
+pub enum Coldkey {
+    Signing(KeyPair),
+    ViewOnly(AccountId32),
+}
+
+impl Coldkey {
+    pub fn load(path: &str) -> Result<Self> {
+        match view_only_address(path)? {
+            Some(account) => Ok(Coldkey::ViewOnly(account)),
+            None => load_keypair_from_file(path).map(Coldkey::Signing),
+        }
+    }
+
+    pub fn account_id(&self) -> AccountId32 {
+        match self {
+            Coldkey::Signing(pair) => pair.account_id(),
+            Coldkey::ViewOnly(account) => account.clone(),
+        }
+    }
+
+    pub fn is_view_only(&self) -> bool {
+        matches!(self, Coldkey::ViewOnly(_))
+    }
+
+    pub fn signer(&self, action: &str) -> Result<&KeyPair> {
+        match self {
+            Coldkey::Signing(pair) => Ok(pair),
+            Coldkey::ViewOnly(account) => Err(view_only_error(account, action)),
+        }
+    }
+}
+
+fn view_only_error(account: &AccountId32, action: &str) -> Error {
+    RegistrationError::new(
+        ErrorKind::Fatal,
+        format!(
+            "Cannot {} with view-only coldkey {}: only its address is known. \
+             Pass the wallet's coldkey file or seed to sign",
+            action,
+            account.to_ss58check()
+        ),
+    )
+    .into()
+}
+
+// JSON holding a public key and nothing secret, like btcli's coldkeypub.txt
+fn parse_public_key_file(contents: &str) -> Option<Result<AccountId32>> {
+    let json: serde_json::Value = serde_json::from_str(contents).ok()?;
+    let fields = json.as_object()?;
+    let secret = ["secretSeed", "secretPhrase", "privateKey"]
+        .iter()
+        .any(|field| fields.contains_key(*field));
+    let address = fields.get("ss58Address")?.as_str()?;
+    (!secret).then(|| parse_address(address))
+}
+
+// The address of a key given without a secret: an SS58 address or a public key file
+pub fn view_only_address(key: &str) -> Result<Option<AccountId32>> {
+    if key.starts_with("//") {
+        return Ok(None);
+    }
+    let path = expand_home(key);
+    if path.is_file() {
+        // Encrypted and binary key files are never public key files
+        return match fs::read_to_string(&path) {
+            Result::Ok(contents) => parse_public_key_file(&contents)
+                .transpose()
+                .with_context(|| format!("Invalid public key file {}", key)),
+            Err(_) => Ok(None),
+        };
+    }
+    Ok(AccountId32::from_ss58check(key.trim()).ok())
+}
+
+pub fn load_keypair_from_file(path: &str) -> Result<KeyPair> {
+    if let Some(account) = view_only_address(path)? {
+        return Err(view_only_error(&account, "sign"));
+    }
 
-pub fn load_keypair_from_file(path: &str) -> Result<Sr25519Pair> {
     if path.starts_with("//") {
         // Dev key (//Alice, //Bob, etc.)
         println!("🔑 Using dev key: {}", path);
-        Ok(Sr25519Pair::from_string(path, None)?)
-    } else if std::path::Path::new(path).exists() {
+        KeyPair::from_string(path, default_scheme())
+    } else if expand_home(path).exists() {
         // File path
-        let contents =
-            fs::read_to_string(path).context(format!("Failed to read key file: {}", path))?;
+        let contents = read_key_file(path)?;
 
         // Try different formats
         if contents.trim().starts_with('{') {
-            // JSON format
-            #[derive(Deserialize)]
-            struct KeyFile {
-                #[serde(alias = "secretSeed", alias = "seed")]
-                secret_seed: Option<String>,
-                #[serde(alias = "secretPhrase", alias = "phrase")]
-                secret_phrase: Option<String>,
-            }
-
-            let key_data: KeyFile =
-                serde_json::from_str(&contents).context("Invalid JSON key file format")?;
-
-            if let Some(seed) = key_data.secret_seed {
-                Ok(Sr25519Pair::from_string(&seed, None)?)
-            } else if let Some(phrase) = key_data.secret_phrase {
-                Ok(Sr25519Pair::from_string(&phrase, None)?)
-            } else {
-                Err(anyhow!("Key file missing secretSeed or secretPhrase"))
-            }
+            parse_json_key_file(&contents).context(format!("Invalid JSON key file: {}", path))
         } else {
             // Raw seed/phrase format
             let seed = contents.trim();
-            Ok(Sr25519Pair::from_string(seed, None)?)
+            KeyPair::from_string(seed, default_scheme())
         }
     } else {
         // Direct seed/phrase
         println!("🔑 Using provided seed/phrase");
-        Ok(Sr25519Pair::from_string(path, None)?)
+        KeyPair::from_string(path, default_scheme())
+    }
+}
+
+// A wallet's coldkey address, read from coldkeypub.txt next to the coldkey when there is
+// one so encrypted coldkeys don't have to be unlocked just to be looked up
+pub fn coldkey_address(wallet_path: &str) -> Result<AccountId32> {
+    let public_file = expand_home(wallet_path).with_file_name("coldkeypub.txt");
+    if expand_home(wallet_path).is_file() && public_file.is_file() {
+        let contents = fs::read_to_string(&public_file)
+            .with_context(|| format!("Failed to read {}", public_file.display()))?;
+        let json: serde_json::Value = serde_json::from_str(&contents)
+            .with_context(|| format!("Invalid public key file {}", public_file.display()))?;
+        let address = json["ss58Address"]
+            .as_str()
+            .ok_or_else(|| anyhow!("No ss58Address in {}", public_file.display()))?;
+        return parse_address(address);
     }
+    account_id_from_string(wallet_path)
 }
 
+// 0x-prefixed 32-byte public key, the form logs and explorers often show
+fn is_hex_public_key(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 64 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// An address given as SS58 or as a hex public key
+pub fn parse_address(address: &str) -> Result<AccountId32> {
+    let address = address.trim();
+    if is_hex_public_key(address) {
+        let bytes: [u8; 32] = hex::decode(&address[2..])?
+            .try_into()
+            .map_err(|_| anyhow!("Public key must be 32 bytes"))?;
+        return Ok(AccountId32::from(bytes));
+    }
+    AccountId32::from_ss58check(address).map_err(|e| {
+        anyhow!(
+            "Invalid address {}: {:?}. Use SS58 or a 0x hex public key",
+            address,
+            e
+        )
+    })
+}
+
+// Accounts given as addresses are never treated as secrets, so a 0x + 64 hex digit
+// input is a public key here even though a seed has the same shape
 pub fn account_id_from_string(account: &str) -> Result<AccountId32> {
     if account.starts_with("//") {
         // Dev key
-        let pair = Sr25519Pair::from_string(account, None)?;
-        Ok(AccountId32::from(pair.public().0))
-    } else if std::path::Path::new(account).exists() {
-        // File path - load public key from file
-        let pair = load_keypair_from_file(account)?;
-        Ok(AccountId32::from(pair.public().0))
-    } else if account.len() == 48 && account.chars().all(|c| c.is_ascii_alphanumeric()) {
-        // SS58 address
-        AccountId32::from_ss58check(account).map_err(|_| anyhow!("Invalid SS58 address"))
-    } else if account.len() != 0 {
+        Ok(KeyPair::from_string(account, default_scheme())?.account_id())
+    } else if expand_home(account).exists() {
+        // File path - public key files are read as they are, key files are loaded
+        Ok(Coldkey::load(account)?.account_id())
+    } else if is_hex_public_key(account)
+        || (account.len() == 48 && account.chars().all(|c| c.is_ascii_alphanumeric()))
+    {
+        // SS58 address or hex public key
+        parse_address(account)
+    } else if !account.is_empty() {
         // Try as raw seed/phrase to get public
-        let pair = Sr25519Pair::from_string(account, None)?;
-        Ok(AccountId32::from(pair.public().0))
+        Ok(KeyPair::from_string(account, default_scheme())?.account_id())
     } else {
         Err(anyhow!("Empty account string provided"))
     }
@@ -88,6 +783,182 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_hex_public_keys() {
+        // Hex public keys and SS58 addresses name the same account
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let alice_hex = "0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+        assert_eq!(
+            account_id_from_string(alice_hex).unwrap(),
+            account_id_from_string(alice).unwrap()
+        );
+        assert_eq!(
+            parse_address(&alice_hex.to_uppercase().replace("0X", "0x")).unwrap(),
+            parse_address(alice).unwrap()
+        );
+        assert!(parse_address("0x1234").is_err());
+    }
+
+    #[test]
+    fn test_json_key_file_errors() {
+        let alice = Sr25519Pair::from_string("//Alice", None).unwrap();
+        let seed = "0xe5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a";
+        let pair = parse_json_key_file(&format!(r#"{{"secretSeed": "{}"}}"#, seed)).unwrap();
+        assert_eq!(pair.public_bytes(), alice.public().0.to_vec());
+
+        let error = parse_json_key_file(r#"{"secretSeed": "0x1234", "foo": 1}"#)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("btcli keyfile: unexpected field \"foo\""));
+        assert!(error.contains("subkey export: unexpected field \"foo\""));
+        assert!(error.contains("raw seed: unexpected field"));
+
+        let error = parse_json_key_file(r#"{"seed": "0x1234"}"#)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("raw seed: seed must be 32 bytes of hex"));
+
+        assert!(parse_json_key_file("{\"secretSeed\": ")
+            .err()
+            .unwrap()
+            .to_string()
+            .contains("line 1"));
+    }
+
+    #[test]
+    fn test_key_schemes() {
+        let seed = "0xe5be9a5092b81bca64be81d212e7f2f9eba183bb7a90954f7b76361f6edb5c0a";
+        let ed = KeyPair::from_string(seed, KeyScheme::Ed25519).unwrap();
+        let ecdsa = KeyPair::from_string(seed, KeyScheme::Ecdsa).unwrap();
+        assert_eq!(ecdsa.public_bytes().len(), 33);
+        assert_eq!(
+            ecdsa.account_id(),
+            AccountId32::from(sp_core::blake2_256(&ecdsa.public_bytes()))
+        );
+
+        // The file's address picks the scheme when none is named
+        let detected = parse_json_key_file(&format!(
+            r#"{{"secretSeed": "{}", "ss58Address": "{}"}}"#,
+            seed,
+            ed.account_id().to_ss58check()
+        ))
+        .unwrap();
+        assert_eq!(detected.scheme(), KeyScheme::Ed25519);
+        let named = parse_json_key_file(&format!(
+            r#"{{"secretSeed": "{}", "scheme": "ecdsa"}}"#,
+            seed
+        ))
+        .unwrap();
+        assert_eq!(named.account_id(), ecdsa.account_id());
+
+        // MultiSignature variants: 0 ed25519, 1 sr25519, 2 ecdsa
+        assert_eq!(ed.sign_multi(b"payload")[0], 0x00);
+        assert_eq!(ed.sign_multi(b"payload").len(), 65);
+        assert_eq!(ecdsa.sign_multi(b"payload")[0], 0x02);
+        assert_eq!(ecdsa.sign_multi(b"payload").len(), 66);
+        assert!("bls".parse::<KeyScheme>().is_err());
+    }
+
+    #[test]
+    fn test_wallet_resolution() {
+        let wallets = std::env::temp_dir().join(format!("btreg-wallets-{}", std::process::id()));
+        fs::create_dir_all(wallets.join("miner").join("hotkeys")).unwrap();
+        fs::write(wallets.join("miner").join("coldkey"), "//Alice").unwrap();
+        fs::write(
+            wallets.join("miner").join("hotkeys").join("default"),
+            "//Bob",
+        )
+        .unwrap();
+
+        let coldkey = resolve_wallet_in(&wallets, "miner");
+        assert!(coldkey.ends_with("coldkey"));
+        assert!(resolve_hotkey_in(&wallets, Some("miner"), "default").ends_with("default"));
+        assert_eq!(
+            resolve_hotkey_in(&wallets, Some(&coldkey), "default"),
+            resolve_hotkey_in(&wallets, None, "miner/default")
+        );
+
+        // Dev keys and unknown names pass through untouched
+        assert_eq!(resolve_wallet_in(&wallets, "//Alice"), "//Alice");
+        assert_eq!(
+            resolve_hotkey_in(&wallets, Some("miner"), "missing"),
+            "missing"
+        );
+
+        // A fleet is listed from the wallet's hotkeys directory
+        fs::write(wallets.join("miner").join("hotkeys").join("m2"), "//Eve").unwrap();
+        let fleet = hotkey_files_in(&wallets, "miner").unwrap();
+        assert_eq!(fleet.len(), 2);
+        assert!(fleet[0].ends_with("default") && fleet[1].ends_with("m2"));
+        assert!(hotkey_files_in(&wallets, "analyst").is_err());
+
+        // A wallet with only coldkeypub.txt reads fine but refuses to sign
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        fs::create_dir_all(wallets.join("analyst")).unwrap();
+        fs::write(
+            wallets.join("analyst").join("coldkeypub.txt"),
+            format!(
+                r#"{{"accountId": "0x00", "publicKey": "0x00", "ss58Address": "{}"}}"#,
+                alice
+            ),
+        )
+        .unwrap();
+        let view_only = resolve_wallet_in(&wallets, "analyst");
+        assert!(view_only.ends_with("coldkeypub.txt"));
+        assert_eq!(
+            account_id_from_string(&view_only).unwrap().to_ss58check(),
+            alice
+        );
+        assert!(Coldkey::load(alice).unwrap().is_view_only());
+        assert!(!Coldkey::load(&coldkey).unwrap().is_view_only());
+        let error = load_keypair_from_file(&view_only)
+            .err()
+            .unwrap()
+            .to_string();
+        assert!(error.contains("view-only coldkey"));
+
+        fs::remove_dir_all(wallets).unwrap();
+    }
+
+    #[test]
+    fn test_hotkey_files() {
+        // A plain directory lists its key files in name order, hidden files left out
+        let dir = std::env::temp_dir().join(format!("btreg-fleet-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        for (name, key) in [("m2", "//Eve"), ("m1", "//Dave"), (".m3.swp", "")] {
+            fs::write(dir.join(name), key).unwrap();
+        }
+        let fleet = hotkey_files_in(Path::new("/wallets"), dir.to_str().unwrap()).unwrap();
+        assert_eq!(fleet.len(), 2);
+        assert!(fleet[0].ends_with("m1") && fleet[1].ends_with("m2"));
+
+        fs::remove_file(dir.join("m1")).unwrap();
+        fs::remove_file(dir.join("m2")).unwrap();
+        let error = hotkey_files_in(Path::new("/wallets"), dir.to_str().unwrap()).unwrap_err();
+        assert!(error.to_string().starts_with("No hotkeys found"));
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_keyring_account() {
+        // Wallet coldkeys are stored under the wallet name, other keys under their path
+        let wallets = Path::new("/wallets");
+        assert_eq!(
+            keyring_account_in(wallets, "/wallets/miner/coldkey"),
+            "miner"
+        );
+        assert_eq!(
+            keyring_account_in(wallets, "/wallets/miner/hotkeys/default"),
+            "miner/hotkeys/default"
+        );
+        assert_eq!(
+            keyring_account_in(wallets, "/elsewhere/key"),
+            "/elsewhere/key"
+        );
+    }
+
     #[tokio::test]
     async fn test_key_loading() {
         // Load keys from seed phrase
@@ -0,0 +1,279 @@
+//! Encryption and decryption of btcli keyfiles ($NACL, ansible-vault and legacy Fernet formats)
+use anyhow::{anyhow, Context, Result};
+use base64::{engine::general_purpose::URL_SAFE, Engine};
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+const NACL_PREFIX: &[u8] = b"$NACL";
+const ANSIBLE_VAULT_PREFIX: &[u8] = b"$ANSIBLE_VAULT";
+const LEGACY_PREFIX: &[u8] = b"gAAAAA"; // Fernet token with version byte 0x80
+
+// Salts and work factors used by the Python bittensor wallet
+const NACL_SALT: &[u8; 16] = b"\x13q\x83\xdf\xf1Z\t\xbc\x9c\x90\xb5Q\x879\xe9\xb1";
+const NACL_OPSLIMIT: u32 = 8; // argon2i OPSLIMIT_SENSITIVE
+const NACL_MEMLIMIT_KIB: u32 = 524_288; // argon2i MEMLIMIT_SENSITIVE (512 MiB)
+const LEGACY_SALT: &[u8] = b"Iguesscyborgslikemyselfhaveatendencytobeparanoidaboutourorigins";
+const LEGACY_ITERATIONS: u32 = 10_000_000;
+const ANSIBLE_VAULT_ITERATIONS: u32 = 10_000;
+
+pub fn is_encrypted(data: &[u8]) -> bool {
+    data.starts_with(NACL_PREFIX)
+        || data.starts_with(ANSIBLE_VAULT_PREFIX)
+        || data.starts_with(LEGACY_PREFIX)
+}
+
+pub fn decrypt(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    if let Some(encrypted) = data.strip_prefix(NACL_PREFIX) {
+        decrypt_nacl(encrypted, password)
+    } else if data.starts_with(ANSIBLE_VAULT_PREFIX) {
+        decrypt_ansible_vault(data, password)
+    } else if data.starts_with(LEGACY_PREFIX) {
+        decrypt_legacy(data, password)
+    } else {
+        Err(anyhow!("Key file is not in a known encrypted format"))
+    }
+}
+
+// Encrypt in the $NACL format current btcli writes
+pub fn encrypt(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    use crypto_secretbox::{
+        aead::{Aead, KeyInit},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    let key = nacl_key(password)?;
+    let mut nonce = [0u8; 24];
+    getrandom::getrandom(&mut nonce).map_err(|e| anyhow!("Failed to generate nonce: {}", e))?;
+    let ciphertext = XSalsa20Poly1305::new(Key::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), data)
+        .map_err(|_| anyhow!("Failed to encrypt key file"))?;
+
+    Ok([NACL_PREFIX, &nonce[..], &ciphertext].concat())
+}
+
+// SecretBox key derived from the password with argon2i
+fn nacl_key(password: &str) -> Result<[u8; 32]> {
+    argon2i_key(password, NACL_MEMLIMIT_KIB, NACL_OPSLIMIT)
+}
+
+// libsodium's crypto_pwhash_argon2i: one lane, version 0x13
+fn argon2i_key(password: &str, memlimit_kib: u32, opslimit: u32) -> Result<[u8; 32]> {
+    use argon2::{Algorithm, Argon2, Params, Version};
+
+    let params = Params::new(memlimit_kib, opslimit, 1, Some(32))
+        .map_err(|e| anyhow!("Invalid argon2 parameters: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2i, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), NACL_SALT, &mut key)
+        .map_err(|e| anyhow!("Failed to derive key from password: {}", e))?;
+    Ok(key)
+}
+
+// NaCl SecretBox keyed with argon2i(password), data is nonce(24) + box
+fn decrypt_nacl(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    if data.len() < 24 {
+        return Err(anyhow!("NaCl key file is truncated"));
+    }
+    open_secretbox(data, &nacl_key(password)?)
+}
+
+fn open_secretbox(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
+    use crypto_secretbox::{
+        aead::{Aead, KeyInit},
+        Key, Nonce, XSalsa20Poly1305,
+    };
+
+    if data.len() < 24 {
+        return Err(anyhow!("NaCl key file is truncated"));
+    }
+    let (nonce, ciphertext) = data.split_at(24);
+    XSalsa20Poly1305::new(Key::from_slice(key))
+        .decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map_err(|_| anyhow!("Invalid password or corrupted key file"))
+}
+
+// Ansible vault 1.1: PBKDF2-SHA256 derived AES-256-CTR key, HMAC-SHA256 key and IV
+fn decrypt_ansible_vault(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    use aes::Aes256;
+    use ctr::cipher::{KeyIvInit, StreamCipher};
+
+    let text = std::str::from_utf8(data).context("Ansible vault key file is not valid UTF-8")?;
+    let body: String = text.lines().skip(1).map(str::trim).collect();
+    let body = hex::decode(body).context("Invalid ansible vault body")?;
+    let body = String::from_utf8(body).context("Invalid ansible vault body")?;
+
+    let parts: Vec<&str> = body.lines().collect();
+    if parts.len() != 3 {
+        return Err(anyhow!("Invalid ansible vault body"));
+    }
+    let salt = hex::decode(parts[0]).context("Invalid ansible vault salt")?;
+    let expected_hmac = hex::decode(parts[1]).context("Invalid ansible vault HMAC")?;
+    let mut ciphertext = hex::decode(parts[2]).context("Invalid ansible vault ciphertext")?;
+
+    let mut derived = [0u8; 80];
+    pbkdf2::pbkdf2_hmac::<Sha256>(
+        password.as_bytes(),
+        &salt,
+        ANSIBLE_VAULT_ITERATIONS,
+        &mut derived,
+    );
+    let (cipher_key, rest) = derived.split_at(32);
+    let (hmac_key, iv) = rest.split_at(32);
+
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(hmac_key).map_err(|e| anyhow!("Invalid HMAC key: {}", e))?;
+    mac.update(&ciphertext);
+    mac.verify_slice(&expected_hmac)
+        .map_err(|_| anyhow!("Invalid password or corrupted key file"))?;
+
+    ctr::Ctr128BE::<Aes256>::new(cipher_key.into(), iv.into()).apply_keystream(&mut ciphertext);
+
+    // PKCS7 padding
+    let pad = *ciphertext
+        .last()
+        .ok_or_else(|| anyhow!("Empty ansible vault"))? as usize;
+    if pad == 0 || pad > 16 || pad > ciphertext.len() {
+        return Err(anyhow!("Invalid ansible vault padding"));
+    }
+    ciphertext.truncate(ciphertext.len() - pad);
+
+    Ok(ciphertext)
+}
+
+// Legacy Fernet token keyed with PBKDF2-SHA256(password)
+fn decrypt_legacy(data: &[u8], password: &str) -> Result<Vec<u8>> {
+    let token = URL_SAFE
+        .decode(data.trim_ascii())
+        .context("Invalid legacy key file encoding")?;
+    if !is_fernet_token(&token) {
+        return Err(anyhow!("Invalid legacy key file token"));
+    }
+    open_fernet(&token, &legacy_key(password, LEGACY_ITERATIONS))
+}
+
+// version(1) + timestamp(8) + iv(16) + ciphertext + hmac(32)
+fn is_fernet_token(token: &[u8]) -> bool {
+    token.len() >= 1 + 8 + 16 + 32 && token[0] == 0x80
+}
+
+// The Fernet key, signing half first
+fn legacy_key(password: &str, iterations: u32) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    pbkdf2::pbkdf2_hmac::<Sha256>(password.as_bytes(), LEGACY_SALT, iterations, &mut key);
+    key
+}
+
+fn open_fernet(token: &[u8], key: &[u8; 32]) -> Result<Vec<u8>> {
+    use aes::Aes128;
+    use cbc::cipher::{block_padding::Pkcs7, BlockDecryptMut, KeyIvInit};
+
+    if !is_fernet_token(token) {
+        return Err(anyhow!("Invalid legacy key file token"));
+    }
+    let (signing_key, encryption_key) = key.split_at(16);
+
+    let (signed, expected_hmac) = token.split_at(token.len() - 32);
+    let mut mac = Hmac::<Sha256>::new_from_slice(signing_key)
+        .map_err(|e| anyhow!("Invalid HMAC key: {}", e))?;
+    mac.update(signed);
+    mac.verify_slice(expected_hmac)
+        .map_err(|_| anyhow!("Invalid password or corrupted key file"))?;
+
+    let iv = &signed[9..25];
+    cbc::Decryptor::<Aes128>::new(encryption_key.into(), iv.into())
+        .decrypt_padded_vec_mut::<Pkcs7>(&signed[25..])
+        .map_err(|_| anyhow!("Invalid legacy key file padding"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const VAULT: &str = "$ANSIBLE_VAULT;1.1;AES256
+30303031303230333034303530363037303830393061306230633064306530663130313131323133
+3134313531363137313831393161316231633164316531660a346465356531613334353465343933
+64323663623139313064363736646261303365343335376264633461383036613663613364623033
+3933316430346463340a313363363634653865323865306332383536303338376165326238383932
+32333031373838306138353635613237626264633464636562353066663233343164383136613730
+62393835386438323937653530666339646664323162663236613937366337353365383737653738
+30366139353636396233613737633362623166373631373533656134313437346565383137363739
+63326533393362353932666234613861633864393666666639386266313739643834336537363639
+6364";
+
+    #[test]
+    fn test_ansible_vault_decryption() {
+        assert!(is_encrypted(VAULT.as_bytes()));
+        assert!(!is_encrypted(b"{\"secretPhrase\": \"...\"}"));
+
+        let decrypted = decrypt(VAULT.as_bytes(), "hunter2").unwrap();
+        assert!(String::from_utf8(decrypted)
+            .unwrap()
+            .contains("bottom drive obey lake"));
+
+        assert!(decrypt(VAULT.as_bytes(), "wrong").is_err());
+    }
+
+    // The secretbox vector from "Cryptography in NaCl" (Bernstein), section 10
+    const NACL_KEY: &str = "1b27556473e985d462cd51197a9a46c76009549eac6474f206c4ee0844f68389";
+    const NACL_NONCE: &str = "69696ee955b62b73cd62bda875fc73d68219e0036b7a0b37";
+    const NACL_MESSAGE: &str = "be075fc53c81f2d5cf141316ebeb0c7b5228c52a4c62cbd44b66849b64244ffc\
+        e5ecbaaf33bd751a1ac728d45e6c61296cdc3c01233561f41db66cce314adb310e3be8250c46f06dceea3a7fa134\
+        8057e2f6556ad6b1318a024a838f21af1fde048977eb48f59ffd4924ca1c60902e52f0a089bc76897040e082f93\
+        7763848645e0705";
+    const NACL_BOX: &str = "f3ffc7703f9400e52a7dfb4b3d3305d98e993b9f48681273c29650ba32fc76ce\
+        48332ea7164d96a4476fb8c531a1186ac0dfc17c98dce87b4da7f011ec48c97271d2c20f9b928fe2270d6fb863d5\
+        1738b48eeee314a7cc8ab932164548e526ae90224368517acfeabd6bb3732bc0e9da99832b61ca01b6de56244a9e\
+        88d5f9b37973f622a43d14a6599b1f654cb45a74e355a5";
+
+    #[test]
+    fn test_nacl_decryption() {
+        let key: [u8; 32] = hex::decode(NACL_KEY).unwrap().try_into().unwrap();
+        let file = [
+            NACL_PREFIX,
+            &hex::decode(NACL_NONCE).unwrap(),
+            &hex::decode(NACL_BOX).unwrap(),
+        ]
+        .concat();
+        assert!(is_encrypted(&file));
+        let sealed = file.strip_prefix(NACL_PREFIX).unwrap();
+        assert_eq!(
+            open_secretbox(sealed, &key).unwrap(),
+            hex::decode(NACL_MESSAGE).unwrap()
+        );
+
+        let mut tampered = sealed.to_vec();
+        tampered[30] ^= 1;
+        assert!(open_secretbox(&tampered, &key).is_err());
+        assert!(decrypt_nacl(&sealed[..20], "hunter2").is_err());
+
+        // argon2i with btcli's salt, from `openssl kdf ... -kdfopt iter:2 -kdfopt memcost:64
+        // -kdfopt lanes:1 ARGON2I` (the real work factors take 512 MiB)
+        assert_eq!(
+            hex::encode(argon2i_key("hunter2", 64, 2).unwrap()),
+            "c46265a91a79bde34d8d378d03ec10805842e94d46c8ce4eb5808cc99e1eca04"
+        );
+    }
+
+    // Made with Python's cryptography: Fernet(urlsafe_b64encode(key))._encrypt_from_parts(
+    // data, 1700000000, bytes(range(16))), the key being PBKDF2 of "hunter2" at 1000 rounds
+    const FERNET_TOKEN: &str =
+        "gAAAAABlU_EAAAECAwQFBgcICQoLDA0OD6oHB5sTiIyHLD_WWkqqRcTX9Ta9Bf61xLqXPjZQ\
+        Pd1sFbn4jNlMkMzMl64XkSz99vd2nRwELcvrEb3wIP-AUGfAd1R9cyPRJGcZclI90rT1RdAbiFturJCPMG4YwenQWmk\
+        MCAwpk7G0hhZTojc_RFyirvX64Jga7FdJLw_iPPp9";
+
+    #[test]
+    fn test_legacy_decryption() {
+        assert!(is_encrypted(FERNET_TOKEN.as_bytes()));
+        let key = legacy_key("hunter2", 1000);
+        assert_eq!(
+            hex::encode(key),
+            "fc071d993017f374fbeac96541082aa709e9219f4bd4856676d14edc3136022b"
+        );
+
+        let token = URL_SAFE.decode(FERNET_TOKEN).unwrap();
+        let decrypted = String::from_utf8(open_fernet(&token, &key).unwrap()).unwrap();
+        assert!(decrypted.starts_with(r#"{"secretPhrase": "bottom drive obey lake"#));
+
+        assert!(open_fernet(&token, &legacy_key("wrong", 1000)).is_err());
+    }
+}
@@ -0,0 +1,148 @@
+//! Endpoint latency benchmark and SLA check run before timing-sensitive submissions
+use colored::*;
+use std::time::Duration;
+
+use crate::config::LatencyConfig;
+use crate::utils;
+
+const HISTOGRAM_BUCKETS: usize = 6;
+const HISTOGRAM_WIDTH: usize = 30;
+
+// Bound a timed operation's endpoint must meet
+#[derive(Debug, Clone, Copy)]
+pub struct LatencySla {
+    pub samples: usize,
+    pub max_p95: Duration,
+    pub enforce: bool, // Refuse to start when exceeded, otherwise only warn
+}
+
+impl LatencySla {
+    // Configured SLA with command-line overrides applied
+    pub fn from_config(config: &LatencyConfig, max_p95_ms: Option<u64>, warn_only: bool) -> Self {
+        Self {
+            samples: config.samples.max(1),
+            max_p95: Duration::from_millis(max_p95_ms.unwrap_or(config.max_p95_ms)),
+            enforce: config.enforce && !warn_only,
+        }
+    }
+}
+
+// Round-trip times of a benchmark, sorted fastest first
+#[derive(Debug, Clone)]
+pub struct LatencyReport {
+    samples: Vec<Duration>,
+}
+
+impl LatencyReport {
+    pub fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort();
+        Self { samples }
+    }
+
+    // Nearest-rank percentile, p in 0..=100
+    pub fn percentile(&self, p: f64) -> Duration {
+        if self.samples.is_empty() {
+            return Duration::ZERO;
+        }
+        let rank = ((p / 100.0) * self.samples.len() as f64).ceil() as usize;
+        self.samples[rank.clamp(1, self.samples.len()) - 1]
+    }
+
+    pub fn p50(&self) -> Duration {
+        self.percentile(50.0)
+    }
+
+    pub fn p95(&self) -> Duration {
+        self.percentile(95.0)
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    // Standard deviation of the samples
+    pub fn jitter(&self) -> Duration {
+        if self.samples.len() < 2 {
+            return Duration::ZERO;
+        }
+        let millis: Vec<f64> = self
+            .samples
+            .iter()
+            .map(|s| s.as_secs_f64() * 1000.0)
+            .collect();
+        let mean = millis.iter().sum::<f64>() / millis.len() as f64;
+        let variance = millis.iter().map(|m| (m - mean).powi(2)).sum::<f64>() / millis.len() as f64;
+        Duration::from_secs_f64(variance.sqrt() / 1000.0)
+    }
+
+    // Sample counts in equal-width buckets up to the slowest sample, as (upper bound, count)
+    pub fn histogram(&self, buckets: usize) -> Vec<(Duration, usize)> {
+        if self.samples.is_empty() || buckets == 0 {
+            return Vec::new();
+        }
+        let width = (self.max() / buckets as u32).max(Duration::from_micros(1));
+        let mut counts = vec![0usize; buckets];
+        for sample in &self.samples {
+            let bucket = (sample.as_nanos() / width.as_nanos()) as usize;
+            counts[bucket.min(buckets - 1)] += 1;
+        }
+        counts
+            .into_iter()
+            .enumerate()
+            .map(|(i, count)| (width * (i as u32 + 1), count))
+            .collect()
+    }
+
+    pub fn meets(&self, sla: &LatencySla) -> bool {
+        self.p95() <= sla.max_p95
+    }
+
+    pub fn print(&self) {
+        println!(
+            "   p50 {:.0?} | p95 {:.0?} | max {:.0?} | jitter {:.0?} ({} samples)",
+            self.p50(),
+            self.p95(),
+            self.max(),
+            self.jitter(),
+            self.samples.len()
+        );
+        let histogram = self.histogram(HISTOGRAM_BUCKETS);
+        let peak = histogram.iter().map(|(_, count)| *count).max().unwrap_or(0);
+        for (upper, count) in histogram {
+            println!(
+                "   ≤ {:>8} {} {}",
+                format!("{:.0?}", upper),
+                utils::format_bar(count as u64, peak as u64, HISTOGRAM_WIDTH).bright_blue(),
+                count
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_latency_report() {
+        let report = LatencyReport::new(
+            (1..=20)
+                .rev()
+                .map(|ms| Duration::from_millis(ms * 10))
+                .collect(),
+        );
+        assert_eq!(report.p50(), Duration::from_millis(100));
+        assert_eq!(report.p95(), Duration::from_millis(190));
+        assert_eq!(report.max(), Duration::from_millis(200));
+
+        let histogram = report.histogram(4);
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram.iter().map(|(_, count)| count).sum::<usize>(), 20);
+        assert_eq!(histogram[3].0, Duration::from_millis(200));
+
+        let sla = LatencySla::from_config(&LatencyConfig::default(), Some(150), false);
+        assert!(!report.meets(&sla));
+        assert!(sla.enforce);
+        assert!(!LatencySla::from_config(&LatencyConfig::default(), None, true).enforce);
+    }
+}
@@ -1,65 +1,442 @@
 use anyhow::{anyhow, Result};
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use colored::*;
-use std::time::Duration;
-use tokio::time::sleep;
+use sp_core::crypto::Ss58Codec;
+use std::{process::ExitCode, time::Duration};
 
+pub mod analysis;
+pub mod api;
+pub mod backoff;
+pub mod batch;
+pub mod browse;
+pub mod btcli;
+pub mod cache;
+pub mod campaign;
+pub mod capabilities;
+pub mod chain;
+pub mod children;
 pub mod client;
+pub mod commit_reveal;
+pub mod config;
 pub mod constants;
+pub mod era;
+pub mod errors;
+pub mod faucet;
+pub mod funding;
+pub mod history;
+pub mod http;
+pub mod hyperparams;
+pub mod journal;
 pub mod key_utils;
+pub mod keyfile;
+pub mod latency;
+pub mod metadata;
+pub mod network;
+pub mod notify;
+pub mod offline;
+pub mod paths;
+pub mod price;
+pub mod progress;
+pub mod prom;
+pub mod records;
 pub mod register;
+pub mod rpc_stats;
+pub mod session;
+pub mod status;
+pub mod transport;
 pub mod utils;
+pub mod wallet;
+pub mod warnings;
 
+use crate::errors::{ErrorKind, KindContext, RegistrationError};
+use crate::funding::FundPlanOptions;
+use crate::latency::LatencySla;
 use crate::register::*;
 
 #[derive(Parser)]
 #[command(name = "bittensor-quick-register")]
 #[command(about = "Quick registration tool for Bittensor network")]
+#[command(
+    after_help = "btcli command names work when run as btcli or with --btcli first, e.g. --btcli subnets register --netuid 1 --wallet.name miner --wallet.hotkey default"
+)]
 struct Cli {
-    /// RPC endpoint URL
+    /// RPC endpoint URL (default: the network's or profile's, else wss://entrypoint-finney.opentensor.ai:443)
+    #[arg(short = 'r', long)]
+    rpc_url: Option<String>,
+
+    /// Network: finney, test or local. Picks its endpoint, checks the chain is really that
+    /// network, and skips confirmation prompts off mainnet
+    #[arg(long, global = true, env = "BTREG_NETWORK")]
+    network: Option<String>,
+
+    /// Named profile from the config file supplying defaults for the flags below
+    #[arg(long, global = true, env = "BTREG_PROFILE")]
+    profile: Option<String>,
+
+    /// File containing the password for encrypted key files (prompts when omitted)
+    #[arg(long, global = true)]
+    password_file: Option<String>,
+
+    /// Read key file passwords from the OS keyring, saving them there after the first unlock
+    #[arg(long, global = true, env = "BTREG_USE_KEYRING")]
+    use_keyring: bool,
+
+    /// Directory holding btcli wallets, used to resolve wallet and hotkey names (default: ~/.bittensor/wallets)
+    #[arg(long, global = true, env = "BTREG_WALLETS_DIR")]
+    wallet_path: Option<String>,
+
+    /// Key scheme for keys that do not name one: sr25519, ed25519 or ecdsa (JSON key files are detected)
+    #[arg(long, global = true, default_value = "sr25519")]
+    scheme: String,
+
+    /// Config file (default: config.toml in the platform config directory)
+    #[arg(long, global = true, env = "BTREG_CONFIG")]
+    config: Option<String>,
+
+    /// Directory for cached chain data (default: the platform cache directory)
+    #[arg(long, global = true, env = "BTREG_CACHE_DIR")]
+    cache_dir: Option<String>,
+
+    /// Directory for receipts and history (default: the platform data directory)
+    #[arg(long, global = true, env = "BTREG_DATA_DIR")]
+    data_dir: Option<String>,
+
+    /// Submit extrinsics even when the chain runs a runtime this tool wasn't verified against
+    #[arg(long, global = true)]
+    force: bool,
+
+    /// Blocks a signed transaction stays valid for, rounded up to a power of two
+    #[arg(long, global = true, default_value_t = era::DEFAULT_PERIOD,
+          value_parser = clap::value_parser!(u64).range(4..=era::MAX_PERIOD))]
+    era_period: u64,
+
+    /// Show wall-clock times in UTC instead of the system timezone
+    #[arg(long, global = true)]
+    utc: bool,
+
+    /// Never draw progress bars or spinners, only print lines (implied when output isn't a terminal)
+    #[arg(long, global = true, env = "BTREG_PLAIN")]
+    plain: bool,
+
+    /// Tip in RAO paid on every transaction to raise its priority
+    #[arg(long, global = true, default_value_t = 0)]
+    tip: u64,
+
+    /// Sign transactions that never expire. They can be replayed if the account is ever reaped
+    #[arg(long, global = true, conflicts_with = "era_period")]
+    immortal: bool,
+
+    /// Report RPC calls, bytes transferred, retries and latency on exit
+    #[arg(long, global = true)]
+    rpc_stats: bool,
+
+    /// Warning output: text, or json lines on stderr for automation
+    #[arg(long, global = true, default_value = "text")]
+    warnings: String,
+
+    /// Read storage that fails to query or decode as zero with a warning, instead of failing
+    #[arg(long, global = true)]
+    lenient: bool,
+
+    /// Sync an embedded light client instead of querying --rpc-url, from this chain spec or,
+    /// without one, from the spec the endpoint's node serves (cached after the first fetch).
+    /// system_dryRun, neuronInfo_* and delegateInfo_* are unavailable through it (needs a
+    /// build with --features light-client)
     #[arg(
-        short = 'r',
         long,
-        default_value = "wss://entrypoint-finney.opentensor.ai:443"
+        global = true,
+        env = "BTREG_LIGHT_CLIENT",
+        value_name = "CHAIN_SPEC"
     )]
-    rpc_url: String,
+    light_client: Option<Option<String>>,
 
     #[command(subcommand)]
     command: Commands,
 }
 
+// Alert channels shared by the long-running commands, defaults from the profile's [notify]
+#[derive(Args)]
+struct NotifyArgs {
+    #[arg(long, help = "POST alerts as JSON to this URL")]
+    webhook_url: Option<String>,
+    #[arg(long, requires = "telegram_chat_id")]
+    telegram_token: Option<String>,
+    #[arg(long, requires = "telegram_token")]
+    telegram_chat_id: Option<String>,
+    #[arg(long)]
+    discord_webhook: Option<String>,
+    #[arg(
+        long,
+        default_value = "900",
+        help = "Minimum seconds between repeats of the same alert"
+    )]
+    cooldown: u64,
+}
+
+impl NotifyArgs {
+    fn notifications(self, defaults: config::NotifyConfig) -> notify::Notifications {
+        let mut channels: Vec<Box<dyn notify::Notifier>> = vec![Box::new(notify::ConsoleNotifier)];
+        if let Some(url) = self.webhook_url.or(defaults.webhook_url) {
+            channels.push(Box::new(notify::WebhookNotifier { url }));
+        }
+        if let (Some(bot_token), Some(chat_id)) = (
+            self.telegram_token.or(defaults.telegram_token),
+            self.telegram_chat_id.or(defaults.telegram_chat_id),
+        ) {
+            channels.push(Box::new(notify::TelegramNotifier { bot_token, chat_id }));
+        }
+        if let Some(webhook_url) = self.discord_webhook.or(defaults.discord_webhook) {
+            channels.push(Box::new(notify::DiscordNotifier { webhook_url }));
+        }
+        notify::Notifications::new(channels, Duration::from_secs(self.cooldown))
+    }
+}
+
+// A single subnet, by netuid or by the name in its on-chain identity
+#[derive(Args)]
+struct SubnetArgs {
+    #[arg(short, long, required_unless_present = "subnet_name")]
+    subnet: Option<u16>,
+    #[arg(
+        long,
+        conflicts_with = "subnet",
+        help = "Subnet by its on-chain name instead of netuid"
+    )]
+    subnet_name: Option<String>,
+}
+
+impl SubnetArgs {
+    async fn netuid(&self, register_client: &QuickRegister) -> Result<u16> {
+        match (self.subnet, &self.subnet_name) {
+            (Some(netuid), _) => Ok(netuid),
+            (None, Some(name)) => register_client.resolve_subnet_name(name).await,
+            (None, None) => Err(anyhow!("--subnet or --subnet-name is required")),
+        }
+    }
+}
+
 #[derive(Subcommand)]
 enum Commands {
     /// Register to a subnet using burn registration
     Register {
+        #[arg(
+            short,
+            long,
+            help = "Subnet to register into (default: the profile's subnet)"
+        )]
+        subnet: Option<u16>,
+        #[arg(
+            long,
+            conflicts_with = "subnet",
+            help = "Subnet by its on-chain name instead of netuid"
+        )]
+        subnet_name: Option<String>,
+        #[arg(short, long, help = "Coldkey wallet (default: the profile's wallet)")]
+        wallet: Option<String>,
+        #[arg(
+            short = 'H',
+            long,
+            help = "Hotkey to register (default: the profile's hotkey)"
+        )]
+        hotkey: Option<String>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            conflicts_with = "hotkey",
+            help = "Hotkeys to register together, e.g. m1,m2,m3"
+        )]
+        hotkeys: Vec<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["hotkey", "hotkeys"],
+            help = "Register every hotkey in this directory, or in this wallet's hotkeys directory"
+        )]
+        hotkey_dir: Option<String>,
+        #[arg(
+            long,
+            default_value = "1",
+            help = "Hotkeys registered at once with --hotkeys or --hotkey-dir"
+        )]
+        concurrency: usize,
+        #[arg(long)]
+        burn_amount: Option<u64>,
+        /// Raise burn_amount to the current burn when it is too low
+        #[arg(long)]
+        auto_adjust: bool,
+        #[arg(
+            long,
+            help = "Maximum burn to pay, in RAO (default: the profile's max_burn)"
+        )]
+        max_burn: Option<u64>,
+        /// Wait for a full registration interval to reopen instead of failing
+        #[arg(long)]
+        wait_for_window: bool,
+        /// Register even when the hotkey is owned by a different coldkey
+        #[arg(long)]
+        allow_foreign_hotkey: bool,
+        /// Build, sign and simulate the registration without submitting it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
         #[arg(short, long)]
-        subnet: u16,
+        yes: bool,
+        /// Fail when the endpoint's p95 latency exceeds this (default from config: 500ms)
+        #[arg(long)]
+        max_p95_ms: Option<u64>,
+        /// Only warn when the endpoint misses its latency bound
+        #[arg(long)]
+        latency_warn_only: bool,
+        /// Skip the endpoint latency benchmark
+        #[arg(long)]
+        skip_latency_check: bool,
+    },
+
+    /// Register a hotkey on the root network (netuid 0) with root_register
+    RegisterRoot {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        /// Register even when the hotkey is owned by a different coldkey
+        #[arg(long)]
+        allow_foreign_hotkey: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Wait until the burn cost drops to a threshold, then register instantly
+    Snipe {
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            help = "Subnets to watch, e.g. 1,3,8"
+        )]
+        subnet: Vec<u16>,
         #[arg(short, long)]
         wallet: String,
         #[arg(short = 'H', long)]
         hotkey: String,
+        #[arg(long, help = "Maximum burn to pay, in RAO")]
+        max_burn: u64,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Per-subnet max burn overrides, format: subnet:rao"
+        )]
+        target: Vec<String>,
+        #[arg(long, help = "Total TAO the snipe may spend, in RAO")]
+        budget: Option<u64>,
+        /// Fail when the endpoint's p95 latency exceeds this (default from config: 500ms)
+        #[arg(long)]
+        max_p95_ms: Option<u64>,
+        /// Only warn when the endpoint misses its latency bound
+        #[arg(long)]
+        latency_warn_only: bool,
+        /// Skip the endpoint latency benchmark
+        #[arg(long)]
+        skip_latency_check: bool,
+    },
+
+    /// Build an unsigned registration for a coldkey kept offline (sign it with SignTx)
+    BuildTx {
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short, long, help = "Coldkey SS58 address, the secret stays offline")]
+        coldkey: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
         #[arg(long)]
         burn_amount: Option<u64>,
+        #[arg(
+            long,
+            help = "Maximum burn to pay, in RAO (default: the profile's max_burn)"
+        )]
+        max_burn: Option<u64>,
+        #[arg(short, long, help = "Write the unsigned transaction to this file")]
+        output: Option<String>,
+        /// Also show the unsigned transaction as a QR code
+        #[arg(long)]
+        qr: bool,
+    },
+
+    /// Sign a BuildTx transaction with the coldkey, needs no network access
+    SignTx {
+        #[arg(short, long, help = "Unsigned transaction file from BuildTx")]
+        input: String,
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short, long, help = "Write the signed extrinsic hex to this file")]
+        output: Option<String>,
+        /// Also show the signed extrinsic as a QR code
+        #[arg(long)]
+        qr: bool,
+    },
+
+    /// Broadcast a pre-signed extrinsic
+    SubmitTx {
+        #[arg(help = "Signed extrinsic as hex, or a file containing it")]
+        extrinsic: String,
     },
 
     /// Check registration status of a hotkey
     Status {
-        #[arg(short, long)]
-        subnet: u16,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        /// Read state at this block (number or 0x hash) instead of the latest, needs an archive node
+        #[arg(long, value_name = "BLOCK")]
+        at_block: Option<String>,
+    },
+
+    /// List every subnet a hotkey is registered in
+    Registrations {
         #[arg(short = 'H', long)]
         hotkey: String,
+        #[arg(long, default_value = "text", help = "text or csv")]
+        format: String,
+        #[arg(short, long, help = "Write to this file instead of stdout")]
+        output: Option<String>,
     },
 
+    /// Run the post-registration checklist for a hotkey
+    PostRegister {
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+    },
+
+    /// Browse subnets interactively, then estimate cost or register from the selection
+    Browse,
+
     /// Show detailed subnet information
     SubnetInfo {
-        #[arg(short, long)]
-        subnet: u16,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(long, default_value = "text", help = "text or csv")]
+        format: String,
+        #[arg(short, long, help = "Write to this file instead of stdout")]
+        output: Option<String>,
+        /// Read state at this block (number or 0x hash) instead of the latest, needs an archive node
+        #[arg(long, value_name = "BLOCK")]
+        at_block: Option<String>,
     },
 
     /// Estimate registration costs and time
     EstimateCost {
-        #[arg(short, long)]
-        subnet: u16,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(
+            long,
+            default_value = "8",
+            help = "Adjustment intervals of burn history to chart, 0 to skip"
+        )]
+        intervals: usize,
+        #[arg(long, help = "Chart burn history from this --record database")]
+        history: Option<String>,
     },
 
     /// Monitor multiple neurons across subnets
@@ -68,138 +445,1870 @@ enum Commands {
         neurons: Vec<String>,
         #[arg(long, default_value = "60")]
         interval: u64,
+        #[arg(long, help = "Keep a JSON status file updated after every check")]
+        status_file: Option<String>,
+        #[arg(
+            long,
+            help = "Write gauges for the neurons and their subnets to this node_exporter textfile (.prom)"
+        )]
+        prom_file: Option<String>,
+        /// Serve the same gauges plus block height and RPC latency for Prometheus to scrape
+        /// on this port, at /metrics
+        #[arg(long)]
+        metrics_port: Option<u16>,
+        /// Address the metrics port listens on
+        #[arg(long, default_value = "127.0.0.1", requires = "metrics_port")]
+        metrics_listen: std::net::IpAddr,
+        /// Report pruning rank, immunity remaining and the lowest scores
+        #[arg(long)]
+        risk: bool,
+        #[arg(long, help = "Alert when ranked among the N neurons next to be pruned")]
+        danger_zone: Option<usize>,
+        /// Alert when burn, tempo, registration_allowed or other hyperparameters of the
+        /// monitored subnets change between checks
+        #[arg(long)]
+        track_params: bool,
+        #[command(flatten)]
+        notify: NotifyArgs,
+        #[arg(
+            long,
+            num_args = 0..=1,
+            value_name = "DB_PATH",
+            help = "Record subnet snapshots, into this SQLite database or the configured store"
+        )]
+        record: Option<Option<String>>,
     },
 
     /// Auto-register with retry logic
     AutoRegister {
+        #[arg(short, long, required_unless_present_any = ["subnets", "subnet_name"])]
+        subnet: Option<u16>,
+        #[arg(
+            long,
+            conflicts_with_all = ["subnet", "subnets"],
+            help = "Subnet by its on-chain name instead of netuid"
+        )]
+        subnet_name: Option<String>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            conflicts_with = "subnet",
+            help = "Candidate subnets, registers into the cheapest one, e.g. 1,3,8,21"
+        )]
+        subnets: Vec<u16>,
         #[arg(short, long)]
-        subnet: u16,
+        wallet: String,
+        #[arg(short = 'H', long, required_unless_present_any = ["hotkeys", "hotkey_dir"])]
+        hotkey: Option<String>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            conflicts_with_all = ["hotkey", "subnets", "hotkey_pool"],
+            help = "Hotkeys to register together, e.g. m1,m2,m3"
+        )]
+        hotkeys: Vec<String>,
+        #[arg(
+            long,
+            conflicts_with_all = ["hotkey", "hotkeys", "subnets", "hotkey_pool"],
+            help = "Register every hotkey in this directory, or in this wallet's hotkeys directory"
+        )]
+        hotkey_dir: Option<String>,
+        #[arg(
+            long,
+            default_value = "1",
+            help = "Hotkeys registered at once with --hotkeys or --hotkey-dir"
+        )]
+        concurrency: usize,
+        #[arg(long, default_value = "3")]
+        max_retries: usize,
+        #[arg(
+            long,
+            help = "Maximum burn to pay per subnet, in RAO (default: the profile's max_burn)"
+        )]
+        max_burn: Option<u64>,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            conflicts_with = "subnets",
+            help = "Spare hotkeys to try in turn when the hotkey cannot register"
+        )]
+        hotkey_pool: Vec<String>,
+        /// Wait for a full registration interval to reopen instead of failing
+        #[arg(long)]
+        wait_for_window: bool,
+        /// Register even when the hotkey is owned by a different coldkey
+        #[arg(long)]
+        allow_foreign_hotkey: bool,
+        /// Build, sign and simulate the registration without submitting it
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+        #[arg(
+            long,
+            requires = "max_tip",
+            help = "RAO to raise the tip by when the registration is dropped or stuck in the pool"
+        )]
+        escalate_tip: Option<u64>,
+        #[arg(long, help = "Highest tip to escalate to, in RAO")]
+        max_tip: Option<u64>,
+        #[arg(
+            long,
+            default_value = "3",
+            help = "Blocks a pending registration may wait before it is resubmitted with a higher tip"
+        )]
+        stuck_blocks: u64,
+    },
+
+    /// Guard a hotkey and re-register it automatically after deregistration
+    Guard {
+        #[command(flatten)]
+        subnet: SubnetArgs,
         #[arg(short, long)]
         wallet: String,
         #[arg(short = 'H', long)]
         hotkey: String,
-        #[arg(long, default_value = "3")]
-        max_retries: usize,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Spare hotkeys to rotate to after deregistration or failed registration"
+        )]
+        hotkey_pool: Vec<String>,
+        #[arg(long, help = "Maximum burn per registration, in RAO")]
+        max_burn: u64,
+        #[arg(long, help = "Total burn budget for all re-registrations, in RAO")]
+        budget: u64,
+        #[arg(long, default_value = "60")]
+        interval: u64,
+        /// Write the session summary as JSON on exit
+        #[arg(long)]
+        summary: Option<String>,
+        #[arg(long, help = "Keep a JSON status file updated after every check")]
+        status_file: Option<String>,
+    },
+
+    /// Register into candidate subnets over time within a budget and deadline
+    Campaign {
+        #[arg(
+            long,
+            value_delimiter = ',',
+            required = true,
+            help = "Candidate subnets, e.g. 1,3,8,21"
+        )]
+        subnets: Vec<u16>,
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(long, help = "Number of new registrations to make")]
+        target: usize,
+        #[arg(long, help = "Total burn budget, in RAO")]
+        budget: u64,
+        #[arg(long, help = "RFC 3339 time or duration from now, e.g. 6h or 2d")]
+        deadline: String,
+        #[arg(
+            long,
+            help = "Maximum burn per registration, in RAO (default: the profile's max_burn)"
+        )]
+        max_burn: Option<u64>,
+        #[arg(long, default_value = "60")]
+        interval: u64,
+        /// Append every decision to this file as JSON lines
+        #[arg(long)]
+        log: Option<String>,
+        /// Write the session summary as JSON on exit
+        #[arg(long)]
+        summary: Option<String>,
+    },
+
+    /// Watch subnets and send alerts when registration conditions are met
+    Watch {
+        #[arg(short, long, value_delimiter = ',', required = true)]
+        subnet: Vec<u16>,
+        #[arg(long, help = "Alert while the burn is below this, in RAO")]
+        burn_below: Option<u64>,
+        /// Alert while a subnet has free UID slots
+        #[arg(long)]
+        open_slots: bool,
+        /// Alert when registration is re-enabled on a subnet
+        #[arg(long)]
+        registration_opens: bool,
+        #[arg(
+            short = 'H',
+            long,
+            value_delimiter = ',',
+            help = "Alert when any of these hotkeys is deregistered"
+        )]
+        hotkey: Vec<String>,
+        #[arg(long, default_value = "60")]
+        interval: u64,
+        #[command(flatten)]
+        notify: NotifyArgs,
+        #[arg(
+            long,
+            num_args = 0..=1,
+            value_name = "DB_PATH",
+            help = "Record subnet snapshots, into this SQLite database or the configured store"
+        )]
+        record: Option<Option<String>>,
+        /// Serve subnet burn, watched hotkeys' registration, stake, incentive, emission and
+        /// pruning rank, block height and RPC latency gauges for Prometheus to scrape on this
+        /// port, at /metrics
+        #[arg(long)]
+        metrics_port: Option<u16>,
+        /// Address the metrics port listens on
+        #[arg(long, default_value = "127.0.0.1", requires = "metrics_port")]
+        metrics_listen: std::net::IpAddr,
+    },
+
+    /// Summarize subnet snapshots recorded with --record
+    History {
+        #[arg(help = "SQLite database written by Monitor/Watch --record")]
+        db: String,
+        #[arg(short, long)]
+        subnet: Option<u16>,
+        #[arg(long, default_value = "7")]
+        days: i64,
+        #[arg(long, default_value = "text", help = "text or json")]
+        format: String,
     },
 
     /// Show network statistics
-    NetworkStats,
+    NetworkStats {
+        #[arg(long, default_value = "text", help = "text or csv")]
+        format: String,
+        #[arg(short, long, help = "Write to this file instead of stdout")]
+        output: Option<String>,
+        /// List every subnet instead of the top ones
+        #[arg(long, conflicts_with = "top")]
+        all: bool,
+        /// How many subnets to list, those with the highest emission first
+        #[arg(long, default_value_t = 20)]
+        top: usize,
+    },
+
+    /// Analyze validator stake concentration in a subnet
+    StakeDistribution {
+        #[command(flatten)]
+        subnet: SubnetArgs,
+    },
+
+    /// Show every neuron of a subnet
+    Metagraph {
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(
+            long,
+            default_value = "uid",
+            help = "uid, stake, rank, trust, consensus, incentive, dividends or emission"
+        )]
+        sort_by: String,
+        #[arg(long, default_value = "text", help = "text, json or csv")]
+        format: String,
+        #[arg(short, long, help = "Write to this file instead of stdout")]
+        output: Option<String>,
+        /// Fetch and print neurons a few at a time instead of all at once, for very large
+        /// subnets on low-memory machines (UID order only)
+        #[arg(long)]
+        stream: bool,
+        /// Read state at this block (number or 0x hash) instead of the latest, needs an archive node
+        #[arg(long, value_name = "BLOCK")]
+        at_block: Option<String>,
+    },
 
     /// Export subnet configuration
     ExportConfig {
-        #[arg(short, long)]
-        subnet: u16,
+        #[command(flatten)]
+        subnet: SubnetArgs,
         #[arg(short, long, default_value = "subnet_config.json")]
         output: String,
     },
 
-    /// Batch operations from config file
+    /// Batch operations from a config file (JSON, YAML or TOML)
     Batch {
         #[arg(short, long)]
         config: String,
+        /// Write the session summary as JSON on exit
+        #[arg(long)]
+        summary: Option<String>,
+        /// Continue an interrupted run from its journal, skipping completed operations
+        #[arg(long)]
+        resume: bool,
     },
 
-    /// Check account balance
-    Balance {
+    /// Replace a hotkey with a new one across all subnets
+    SwapHotkey {
         #[arg(short, long)]
-        account: String,
+        wallet: String,
+        #[arg(long, help = "Hotkey being replaced")]
+        old_hotkey: String,
+        #[arg(long, help = "Hotkey taking over its registrations")]
+        new_hotkey: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
     },
-}
-
-#[tokio::main]
-async fn main() -> Result<()> {
-    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
-        .format_timestamp(None)
-        .format_module_path(false)
-        .init();
-
-    print_banner();
 
-    let cli = Cli::parse();
+    /// Publish the axon IP and port for a registered hotkey
+    ServeAxon {
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(long, help = "Public IPv4 or IPv6 address")]
+        ip: String,
+        #[arg(short, long)]
+        port: u16,
+        #[arg(long, default_value = "4")]
+        protocol: u8,
+        #[arg(long, default_value = "0", help = "Axon version reported on chain")]
+        version: u32,
+    },
 
-    match cli.command {
-        Commands::Register {
-            subnet,
-            wallet,
-            hotkey,
-            burn_amount,
-        } => {
-            let register_client: QuickRegister = QuickRegister::new(cli.rpc_url).await?;
-            register_client
-                .register_to_subnet(subnet, &wallet, &hotkey, burn_amount)
-                .await?;
-        }
+    /// Transfer TAO to another account (transfer_keep_alive)
+    Transfer {
+        #[arg(short, long, help = "Wallet (coldkey) to send from")]
+        from: String,
+        #[arg(short, long, help = "Destination SS58 address")]
+        to: String,
+        #[arg(short, long, help = "Amount to transfer, in RAO")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::Status { subnet, hotkey } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.check_status(subnet, &hotkey).await?;
-        }
+    /// Plan (and optionally send) treasury transfers that fund a registration campaign
+    FundPlan {
+        #[arg(long, help = "Wallet (coldkey) the funds come from")]
+        treasury: String,
+        #[arg(
+            short,
+            long,
+            value_delimiter = ',',
+            help = "Registrations to fund, format: wallet:subnet"
+        )]
+        target: Vec<String>,
+        #[arg(short, long, help = "Fund the register operations of a batch config")]
+        config: Option<String>,
+        /// Headroom on top of current burns, in percent
+        #[arg(long, default_value = "10")]
+        margin: u64,
+        /// Write the plan as JSON
+        #[arg(short, long)]
+        output: Option<String>,
+        /// Send the planned transfers
+        #[arg(long)]
+        execute: bool,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::SubnetInfo { subnet } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.show_subnet_info(subnet).await?;
-        }
+    /// Stake TAO from a coldkey to a hotkey, on the root network unless a subnet is given
+    Stake {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(short, long, default_value_t = constants::ROOT_NETUID, help = "Subnet to stake into (default: root)")]
+        subnet: u16,
+        #[arg(short, long, help = "Amount to stake, in RAO")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::EstimateCost { subnet } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.estimate_registration_cost(subnet).await?;
-        }
+    /// Unstake from a hotkey back to its coldkey, on the root network unless a subnet is given
+    Unstake {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(short, long, default_value_t = constants::ROOT_NETUID, help = "Subnet to unstake from (default: root)")]
+        subnet: u16,
+        #[arg(short, long, help = "Amount to unstake, in RAO (alpha on a subnet)")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::Monitor { neurons, interval } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            let parsed_neurons: Result<Vec<(u16, String)>> = neurons
-                .iter()
-                .map(|s| {
-                    let parts: Vec<&str> = s.split(':').collect();
-                    if parts.len() == 2 {
-                        Ok((parts[0].parse::<u16>()?, parts[1].to_string()))
-                    } else {
-                        Err(anyhow!("Invalid format: {}. Use subnet:hotkey", s))
-                    }
-                })
-                .collect();
+    /// Stake TAO into a subnet's alpha on a hotkey, previewing the pool swap first
+    StakeAlpha {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short, long, help = "TAO to stake, in RAO")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-            let parsed_neurons = parsed_neurons?;
+    /// Unstake a hotkey's alpha in a subnet, swapped back to TAO
+    UnstakeAlpha {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(short, long, help = "Alpha to unstake, in its smallest unit")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-            loop {
-                register_client
-                    .monitor_multiple_neurons(parsed_neurons.clone())
-                    .await?;
-                println!("\n⏳ Waiting {}s before next check...", interval);
-                sleep(Duration::from_secs(interval)).await;
-            }
-        }
+    /// Move alpha stake to another hotkey or subnet
+    MoveStake {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(long, help = "Hotkey to move the stake to (default: the same hotkey)")]
+        destination_hotkey: Option<String>,
+        #[arg(long)]
+        origin_subnet: u16,
+        #[arg(long)]
+        destination_subnet: u16,
+        #[arg(short, long, help = "Alpha to move, in its smallest unit")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::AutoRegister {
-            subnet,
-            wallet,
-            hotkey,
-            max_retries,
-        } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client
-                .auto_register_with_retry(subnet, &wallet, &hotkey, max_retries)
-                .await?;
-        }
+    /// Set a hotkey's child hotkeys in a subnet, replacing the current ones
+    SetChildren {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long, help = "Parent hotkey")]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            help = "Children as hotkey:proportion, e.g. child1:0.6,child2:0.4. None revokes all"
+        )]
+        child: Vec<String>,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::NetworkStats => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.show_network_statistics().await?;
-        }
+    /// Show a hotkey's children, parents and childkey takes in a subnet
+    ChildInfo {
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+    },
 
-        Commands::ExportConfig { subnet, output } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.export_config(subnet, &output).await?;
-        }
+    /// Commit weights in a subnet with commit-reveal, keeping the salt for the reveal
+    CommitWeights {
+        #[arg(short, long, help = "Wallet holding the hotkey")]
+        wallet: Option<String>,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        #[arg(
+            long,
+            value_delimiter = ',',
+            required = true,
+            help = "Weights as uid:weight, e.g. 0:0.5,3:1"
+        )]
+        weights: Vec<String>,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
 
-        Commands::Batch { config } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.execute_batch_operations(&config).await?;
-        }
+    /// Reveal the weights of the oldest pending commit once its window opens
+    RevealWeights {
+        #[arg(short, long, help = "Wallet holding the hotkey")]
+        wallet: Option<String>,
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[command(flatten)]
+        subnet: SubnetArgs,
+        /// Wait for the reveal window instead of failing before it opens
+        #[arg(long)]
+        wait: bool,
+    },
 
-        Commands::Balance { account } => {
-            let register_client = QuickRegister::new(cli.rpc_url).await?;
-            register_client.check_account_balance(&account).await?;
+    /// Set the wallet coldkey's on-chain identity, replacing any existing one
+    SetIdentity {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(long)]
+        name: String,
+        #[arg(long, default_value = "")]
+        url: String,
+        #[arg(long, default_value = "", help = "GitHub repository")]
+        github: String,
+        #[arg(long, default_value = "", help = "Image URL")]
+        image: String,
+        #[arg(long, default_value = "")]
+        discord: String,
+        #[arg(long, default_value = "")]
+        description: String,
+        #[arg(long, default_value = "")]
+        additional: String,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Show the on-chain identity of a coldkey, or of a hotkey's owner
+    Identity {
+        #[arg(help = "Wallet name, key file or SS58 address")]
+        account: String,
+    },
+
+    /// List delegates with their take, total stake and nominators
+    Delegates {
+        #[arg(short, long, help = "Show only the top delegates by stake")]
+        limit: Option<usize>,
+        #[arg(long, default_value = "text", help = "text or csv")]
+        format: String,
+        #[arg(short, long, help = "Write to this file instead of stdout")]
+        output: Option<String>,
+    },
+
+    /// Nominate a delegate: stake TAO to its hotkey
+    Delegate {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long, help = "Delegate hotkey to stake to")]
+        hotkey: String,
+        #[arg(short, long, help = "Amount to stake, in RAO")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Take nominated stake back from a delegate
+    Undelegate {
+        #[arg(short, long)]
+        wallet: String,
+        #[arg(short = 'H', long, help = "Delegate hotkey to unstake from")]
+        hotkey: String,
+        #[arg(short, long, help = "Amount to unstake, in RAO")]
+        amount: u64,
+        /// Skip the confirmation prompt
+        #[arg(short, long)]
+        yes: bool,
+    },
+
+    /// Show total stake on a hotkey in a subnet and a coldkey's share of it
+    StakeInfo {
+        #[arg(short = 'H', long)]
+        hotkey: String,
+        #[arg(short, long, default_value_t = constants::ROOT_NETUID, help = "Subnet (default: root)")]
+        subnet: u16,
+        #[arg(short, long)]
+        coldkey: Option<String>,
+    },
+
+    /// List the hotkeys a coldkey owns with their registrations, stake and the coldkey's total
+    Overview {
+        #[arg(short, long, help = "Wallet name, coldkey file or SS58 address")]
+        wallet: String,
+    },
+
+    /// Check account balance
+    Balance {
+        #[arg(short, long, help = "SS58 address, 0x hex public key or wallet name")]
+        account: String,
+        /// Read state at this block (number or 0x hash) instead of the latest, needs an archive node
+        #[arg(long, value_name = "BLOCK")]
+        at_block: Option<String>,
+    },
+
+    /// Generate a new wallet (coldkey and hotkey) from fresh mnemonics
+    NewKey {
+        #[arg(short, long, help = "Wallet name under --wallet-path")]
+        wallet: String,
+        #[arg(short = 'H', long, default_value = "default")]
+        hotkey: String,
+        /// Only add a hotkey to an existing wallet
+        #[arg(long)]
+        hotkey_only: bool,
+        /// Encrypt the coldkey with a password, as btcli does
+        #[arg(long)]
+        encrypt: bool,
+        /// Replace existing key files
+        #[arg(long)]
+        overwrite: bool,
+    },
+
+    /// Claim test TAO for a coldkey by proof of work (--network test or local only)
+    Faucet {
+        #[arg(short, long)]
+        wallet: String,
+    },
+
+    /// Remove a wallet's key file password from the OS keyring
+    ForgetPassword {
+        #[arg(short, long)]
+        wallet: String,
+    },
+
+    /// Encrypt the config file at rest
+    EncryptConfig,
+
+    /// Decrypt the config file back to plain TOML
+    DecryptConfig,
+
+    /// Report version, supported calls, features and networks
+    Capabilities {
+        #[arg(
+            short,
+            long,
+            default_value = "text",
+            help = "Output format: text or json"
+        )]
+        output: String,
+    },
+
+    /// Check the call indices, storage hashers and signed extensions this build encodes
+    /// against the connected runtime's metadata
+    CheckMetadata {
+        /// Also save the metadata, SCALE encoded as state_getMetadata returns it
+        #[arg(long)]
+        save: Option<String>,
+    },
+
+    /// Keep one chain connection open and serve a local REST/JSON API: GET /subnet/{netuid},
+    /// GET /status/{netuid}/{hotkey} and POST /register
+    Serve {
+        /// Address to listen on
+        #[arg(long, default_value = "127.0.0.1:8737")]
+        listen: std::net::SocketAddr,
+        /// Bearer token clients send in the Authorization header, at least 16 characters
+        #[arg(long, env = "BTREG_API_TOKEN", hide_env_values = true)]
+        token: String,
+        /// Requests handled at once, more are answered with 429
+        #[arg(long, default_value = "8")]
+        max_concurrent: usize,
+        /// Refuse registration requests whose max_burn is above this, in RAO (default: the
+        /// profile's max_burn)
+        #[arg(long)]
+        max_burn: Option<u64>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    match start().await {
+        Ok(()) => ExitCode::SUCCESS,
+        // Reported as returning the error from main would, with a code scripts can branch on
+        Err(e) => {
+            eprintln!("Error: {:?}", e);
+            ExitCode::from(errors::exit_code(&e))
+        }
+    }
+}
+
+async fn start() -> Result<()> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info"))
+        .format_timestamp(None)
+        .format_module_path(false)
+        .init();
+
+    let cli = match Cli::try_parse_from(btcli::translate(std::env::args().collect())?) {
+        Ok(cli) => cli,
+        // --help and --version come through here too and exit with 0
+        Err(e) if e.use_stderr() => {
+            let _ = e.print();
+            std::process::exit(errors::EXIT_USAGE.into());
+        }
+        Err(e) => e.exit(),
+    };
+    warnings::set_format(&cli.warnings)?;
+
+    // Keep machine-readable output clean
+    if !writes_machine_output(&cli.command) {
+        print_banner();
+    }
+
+    // The config file may still be encrypted while it is being encrypted or decrypted
+    let profile = if matches!(
+        cli.command,
+        Commands::EncryptConfig | Commands::DecryptConfig
+    ) {
+        config::Profile::default()
+    } else {
+        config::load(cli.config.as_deref())?;
+        config::current().profile(cli.profile.as_deref())?
+    };
+
+    key_utils::set_default_scheme(&cli.scheme)?;
+    key_utils::set_use_keyring(cli.use_keyring);
+    client::set_allow_unverified_runtime(cli.force);
+    era::set_mortality((!cli.immortal).then_some(cli.era_period));
+    client::set_default_tip(cli.tip);
+    client::set_lenient(cli.lenient);
+    utils::set_utc_times(cli.utc);
+    progress::set_plain(cli.plain || writes_machine_output(&cli.command));
+    if let Some(password_file) = &cli.password_file {
+        key_utils::set_password_file(password_file);
+    }
+    if let Some(wallet_path) = cli.wallet_path.as_ref().or(profile.wallet_path.as_ref()) {
+        key_utils::set_wallets_dir(wallet_path);
+    }
+    if let Some(format) = profile.ss58_format {
+        sp_core::crypto::set_default_ss58_version(format.into());
+    }
+    if let Some(cache_dir) = &cli.cache_dir {
+        paths::set_cache_dir(cache_dir);
+    }
+    if let Some(data_dir) = &cli.data_dir {
+        paths::set_data_dir(data_dir);
+    }
+    if let Some(chain_spec) = &cli.light_client {
+        transport::set_light_client(chain_spec.as_deref());
+    }
+
+    // First Ctrl+C cancels whatever is running so it can wind down, a second one exits
+    tokio::spawn(async {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            println!("\n🛑 Cancelling, press Ctrl+C again to exit immediately");
+            utils::shutdown_token().cancel();
+            if tokio::signal::ctrl_c().await.is_ok() {
+                std::process::exit(errors::EXIT_CANCELLED.into());
+            }
+        }
+    });
+
+    let show_rpc_stats = cli.rpc_stats;
+    let result = run(cli, profile).await;
+    if show_rpc_stats {
+        rpc_stats::print_report();
+    }
+
+    result
+}
+
+// Hotkeys of a multi-hotkey registration resolved to their files, None for a single hotkey
+fn fleet_hotkeys(
+    wallet: &str,
+    hotkeys: &[String],
+    hotkey_dir: Option<&str>,
+) -> Result<Option<Vec<String>>> {
+    if let Some(dir) = hotkey_dir {
+        return key_utils::hotkey_files(dir).map(Some);
+    }
+    if hotkeys.is_empty() {
+        return Ok(None);
+    }
+    Ok(Some(
+        hotkeys
+            .iter()
+            .map(|hotkey| key_utils::resolve_hotkey(Some(wallet), hotkey))
+            .collect(),
+    ))
+}
+
+// A flag's value, else the profile's default for it, noting which names came from the profile
+fn flag_or_profile<T>(
+    flag: Option<T>,
+    default: Option<T>,
+    name: &'static str,
+    from_profile: &mut Vec<&'static str>,
+) -> Result<T> {
+    if let Some(value) = flag {
+        return Ok(value);
+    }
+    let value = default.ok_or_else(|| {
+        anyhow!(
+            "--{} is required, or set {} in the config profile",
+            name,
+            name
+        )
+    })?;
+    from_profile.push(name);
+    Ok(value)
+}
+
+// Keep machine-readable output clean of the banner
+fn writes_machine_output(command: &Commands) -> bool {
+    match command {
+        Commands::Capabilities { output } => output != "text",
+        Commands::History { format, .. } => format != "text",
+        Commands::Metagraph { format, .. }
+        | Commands::SubnetInfo { format, .. }
+        | Commands::NetworkStats { format, .. }
+        | Commands::Registrations { format, .. }
+        | Commands::Delegates { format, .. } => format != "text",
+        _ => false,
+    }
+}
+
+// Flags before the profile, an explicit endpoint before a network's default one
+fn resolve_rpc_url(
+    cli_rpc_url: Option<String>,
+    cli_network: Option<network::Network>,
+    profile_rpc_url: Option<String>,
+    profile_network: Option<network::Network>,
+) -> String {
+    cli_rpc_url
+        .or_else(|| cli_network.map(|network| network.endpoint().to_string()))
+        .or(profile_rpc_url)
+        .or_else(|| profile_network.map(|network| network.endpoint().to_string()))
+        .unwrap_or_else(|| constants::DEFAULT_RPC_ENDPOINTS[0].to_string())
+}
+
+async fn run(cli: Cli, profile: config::Profile) -> Result<()> {
+    let cli_network = cli.network.as_deref().map(str::parse).transpose()?;
+    let profile_network = profile.network.as_deref().map(str::parse).transpose()?;
+    if let Some(network) = cli_network.or(profile_network) {
+        network::set_current(network);
+    }
+    let rpc_url = resolve_rpc_url(cli.rpc_url, cli_network, profile.rpc_url, profile_network);
+
+    match cli.command {
+        Commands::Register {
+            subnet,
+            subnet_name,
+            wallet,
+            hotkey,
+            hotkeys,
+            hotkey_dir,
+            concurrency,
+            burn_amount,
+            auto_adjust,
+            max_burn,
+            wait_for_window,
+            allow_foreign_hotkey,
+            dry_run,
+            yes,
+            max_p95_ms,
+            latency_warn_only,
+            skip_latency_check,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let mut from_profile = Vec::new();
+            let subnet = match subnet_name {
+                Some(name) => register_client.resolve_subnet_name(&name).await?,
+                None => flag_or_profile(subnet, profile.subnet, "subnet", &mut from_profile)?,
+            };
+            let wallet = flag_or_profile(wallet, profile.wallet, "wallet", &mut from_profile)?;
+            // Only registrations timed to a window reopening are latency sensitive
+            let latency_sla = (wait_for_window && !dry_run && !skip_latency_check).then(|| {
+                LatencySla::from_config(&config::current().latency, max_p95_ms, latency_warn_only)
+            });
+            let options = RegistrationOptions {
+                burn_amount,
+                auto_adjust,
+                max_burn: max_burn.or(profile.max_burn),
+                dry_run,
+                assume_yes: yes,
+                wait_for_window,
+                latency_sla,
+                allow_foreign_hotkey,
+                ..Default::default()
+            };
+
+            let fleet = fleet_hotkeys(&wallet, &hotkeys, hotkey_dir.as_deref())?;
+            let hotkey = match &fleet {
+                Some(_) => None,
+                None => Some(flag_or_profile(
+                    hotkey,
+                    profile.hotkey,
+                    "hotkey",
+                    &mut from_profile,
+                )?),
+            };
+            // Defaults are easy to forget about, so what will be registered is always spelled out
+            // and still has to be confirmed unless --yes
+            println!(
+                "📋 Registering {} of wallet {} into subnet {}",
+                match (&hotkey, &fleet) {
+                    (Some(hotkey), _) => format!("hotkey {}", hotkey.bright_white()),
+                    (None, fleet) => format!("{} hotkeys", fleet.as_ref().map_or(0, Vec::len)),
+                },
+                wallet.bright_white(),
+                subnet
+            );
+            if !from_profile.is_empty() {
+                println!("   {} from the profile", from_profile.join(", "));
+            }
+
+            let hotkey = hotkey.map(|hotkey| key_utils::resolve_hotkey(Some(&wallet), &hotkey));
+            let wallet = key_utils::resolve_wallet(&wallet);
+            match (hotkey, fleet) {
+                (Some(hotkey), _) => {
+                    let outcome = register_client
+                        .register_to_subnet(subnet, &wallet, &hotkey, &options)
+                        .await?;
+                    // Nothing to do is still its own exit code, for scripts to tell apart
+                    if outcome.tx_hash.is_none() && !dry_run {
+                        return Err(RegistrationError::new(
+                            ErrorKind::AlreadyRegistered,
+                            format!("{} is already registered in subnet {}", hotkey, subnet),
+                        )
+                        .into());
+                    }
+                }
+                (None, fleet) => {
+                    register_client
+                        .register_hotkeys(
+                            subnet,
+                            &wallet,
+                            &fleet.unwrap_or_default(),
+                            None,
+                            concurrency,
+                            &options,
+                        )
+                        .await?;
+                }
+            }
+        }
+
+        Commands::Snipe {
+            subnet,
+            wallet,
+            hotkey,
+            max_burn,
+            target,
+            budget,
+            max_p95_ms,
+            latency_warn_only,
+            skip_latency_check,
+        } => {
+            let overrides: Result<Vec<(u16, u64)>> = target
+                .iter()
+                .map(|s| match s.split_once(':') {
+                    Some((netuid, burn)) => Ok((netuid.parse()?, burn.parse()?)),
+                    None => Err(anyhow!("Invalid target: {}. Use subnet:rao", s)),
+                })
+                .collect();
+            let overrides = overrides?;
+            if let Some((netuid, _)) = overrides.iter().find(|(n, _)| !subnet.contains(n)) {
+                return Err(anyhow!(
+                    "Target for subnet {} which is not being watched",
+                    netuid
+                ));
+            }
+            let targets: Vec<SnipeTarget> = subnet
+                .iter()
+                .map(|&netuid| SnipeTarget {
+                    netuid,
+                    max_burn: overrides
+                        .iter()
+                        .find(|(n, _)| *n == netuid)
+                        .map_or(max_burn, |(_, burn)| *burn),
+                })
+                .collect();
+
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let latency_sla = (!skip_latency_check).then(|| {
+                LatencySla::from_config(&config::current().latency, max_p95_ms, latency_warn_only)
+            });
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .snipe_registration(&targets, &wallet, &hotkey, budget, latency_sla.as_ref())
+                .await?;
+        }
+
+        Commands::BuildTx {
+            subnet,
+            coldkey,
+            hotkey,
+            burn_amount,
+            max_burn,
+            output,
+            qr,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            let unsigned = register_client
+                .build_registration_tx(
+                    subnet,
+                    &coldkey,
+                    &hotkey,
+                    &RegistrationOptions {
+                        burn_amount,
+                        max_burn: max_burn.or(profile.max_burn),
+                        ..Default::default()
+                    },
+                )
+                .await?;
+            offline::write_output(
+                &serde_json::to_string_pretty(&unsigned)?,
+                output.as_deref(),
+                qr,
+            )?;
+        }
+
+        Commands::SignTx {
+            input,
+            wallet,
+            output,
+            qr,
+        } => {
+            let wallet = key_utils::resolve_wallet(&wallet);
+            offline::sign_tx(&input, &wallet, output.as_deref(), qr)?;
+        }
+
+        Commands::SubmitTx { extrinsic } => {
+            let extrinsic = offline::read_extrinsic(&extrinsic)?;
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.submit_signed_tx(&extrinsic).await?;
+        }
+
+        Commands::Status {
+            subnet,
+            hotkey,
+            at_block,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            if let Some(block) = &at_block {
+                register_client.pin_block(block).await?;
+            }
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client.check_status(subnet, &hotkey).await?;
+        }
+
+        Commands::Registrations {
+            hotkey,
+            format,
+            output,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .show_registrations(&hotkey, &format, output.as_deref())
+                .await?;
+        }
+
+        Commands::PostRegister { subnet, hotkey } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .run_post_register_checklist(subnet, &hotkey)
+                .await?;
+        }
+
+        Commands::Browse => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.browse_subnets().await?;
+        }
+
+        Commands::SubnetInfo {
+            subnet,
+            format,
+            output,
+            at_block,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            if let Some(block) = &at_block {
+                register_client.pin_block(block).await?;
+            }
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .show_subnet_info(subnet, &format, output.as_deref())
+                .await?;
+        }
+
+        Commands::EstimateCost {
+            subnet,
+            intervals,
+            history,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .estimate_registration_cost(subnet, intervals, history.as_deref())
+                .await?;
+        }
+
+        Commands::Monitor {
+            neurons,
+            interval,
+            status_file,
+            prom_file,
+            metrics_port,
+            metrics_listen,
+            risk,
+            danger_zone,
+            track_params,
+            notify,
+            record,
+        } => {
+            let mut notifications = notify.notifications(profile.notify);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let exporter = match metrics_port {
+                Some(port) => Some(prom::Exporter::serve((metrics_listen, port).into()).await?),
+                None => None,
+            };
+            let parsed_neurons: Result<Vec<(u16, String)>> = neurons
+                .iter()
+                .map(|s| {
+                    let parts: Vec<&str> = s.split(':').collect();
+                    if parts.len() == 2 {
+                        Ok((
+                            parts[0].parse::<u16>()?,
+                            key_utils::resolve_hotkey(None, parts[1]),
+                        ))
+                    } else {
+                        Err(anyhow!("Invalid format: {}. Use subnet:hotkey", s))
+                    }
+                })
+                .collect();
+
+            let parsed_neurons = parsed_neurons?;
+            let records = records::for_recording(record, &config::current().records)?;
+            let mut subnets: Vec<u16> = parsed_neurons.iter().map(|(netuid, _)| *netuid).collect();
+            subnets.sort_unstable();
+            subnets.dedup();
+
+            let mut status = status::DaemonStatus::new("monitor");
+            let mut params = std::collections::HashMap::new();
+            let watched = parsed_neurons
+                .iter()
+                .map(|(netuid, hotkey)| Ok((*netuid, key_utils::account_id_from_string(hotkey)?)))
+                .collect::<Result<Vec<_>>>()?;
+            let mut changes = register_client
+                .start_storage_cache(&subnets, &watched)
+                .await;
+            loop {
+                let neurons = register_client
+                    .monitor_multiple_neurons(
+                        parsed_neurons.clone(),
+                        &MonitorOptions {
+                            show_risk: risk,
+                            danger_zone,
+                        },
+                    )
+                    .await?;
+                if prom_file.is_some() || exporter.is_some() {
+                    match register_client.prometheus_metrics(&subnets, &neurons).await {
+                        Ok(metrics) => {
+                            if let Some(path) = &prom_file {
+                                if let Err(e) = metrics.write(path) {
+                                    println!("⚠️ {:#}", e);
+                                }
+                            }
+                            if let Some(exporter) = &exporter {
+                                exporter.publish(&metrics);
+                            }
+                        }
+                        Err(e) => println!("⚠️ {:#}", e),
+                    }
+                }
+                status.record_check(neurons);
+                if track_params {
+                    status.record_param_changes(
+                        register_client
+                            .check_hyperparameters(&subnets, &mut params, &mut notifications)
+                            .await,
+                    );
+                }
+                status.write_or_warn(status_file.as_deref());
+                if let Some(records) = &records {
+                    register_client
+                        .record_subnet_snapshots(records.as_ref(), &subnets)
+                        .await;
+                }
+                println!(
+                    "\n⏳ Waiting up to {}s before next check, sooner on chain changes...",
+                    interval
+                );
+                if !register_client
+                    .wait_for_next_check(interval, &mut changes)
+                    .await
+                {
+                    println!("🛑 Monitor stopped");
+                    break;
+                }
+            }
+        }
+
+        Commands::AutoRegister {
+            subnet,
+            subnet_name,
+            subnets,
+            wallet,
+            hotkey,
+            hotkeys,
+            hotkey_dir,
+            concurrency,
+            max_retries,
+            max_burn,
+            hotkey_pool,
+            wait_for_window,
+            allow_foreign_hotkey,
+            dry_run,
+            yes,
+            escalate_tip,
+            max_tip,
+            stuck_blocks,
+        } => {
+            let fleet = fleet_hotkeys(&wallet, &hotkeys, hotkey_dir.as_deref())?;
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey.unwrap_or_default());
+            let hotkey_pool: Vec<String> = hotkey_pool
+                .iter()
+                .map(|h| key_utils::resolve_hotkey(Some(&wallet), h))
+                .collect();
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let options = RegistrationOptions {
+                max_burn: max_burn.or(profile.max_burn),
+                dry_run,
+                assume_yes: yes,
+                wait_for_window,
+                allow_foreign_hotkey,
+                ..Default::default()
+            };
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = match subnet_name {
+                Some(name) => Some(register_client.resolve_subnet_name(&name).await?),
+                None => subnet,
+            };
+            if let (Some(step), Some(max_tip)) = (escalate_tip, max_tip) {
+                register_client.enable_tip_escalation(client::TipEscalation {
+                    step,
+                    max_tip,
+                    stuck_blocks,
+                });
+            }
+            match (subnet, fleet) {
+                (Some(subnet), Some(fleet)) => {
+                    register_client
+                        .register_hotkeys(
+                            subnet,
+                            &wallet,
+                            &fleet,
+                            Some(max_retries),
+                            concurrency,
+                            &options,
+                        )
+                        .await?;
+                }
+                (Some(subnet), None) if !hotkey_pool.is_empty() => {
+                    let pool =
+                        HotkeyPool::new(std::iter::once(hotkey).chain(hotkey_pool).collect())?;
+                    register_client
+                        .auto_register_with_pool(subnet, &wallet, &pool, max_retries, &options)
+                        .await?;
+                }
+                (Some(subnet), None) => {
+                    register_client
+                        .auto_register_with_retry(subnet, &wallet, &hotkey, max_retries, &options)
+                        .await?;
+                }
+                (None, _) => {
+                    register_client
+                        .register_cheapest(&subnets, &wallet, &hotkey, max_retries, &options)
+                        .await?;
+                }
+            }
+        }
+
+        Commands::Guard {
+            subnet,
+            wallet,
+            hotkey,
+            hotkey_pool,
+            max_burn,
+            budget,
+            interval,
+            summary,
+            status_file,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let hotkey_pool: Vec<String> = hotkey_pool
+                .iter()
+                .map(|h| key_utils::resolve_hotkey(Some(&wallet), h))
+                .collect();
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .guard_registration(
+                    subnet,
+                    &wallet,
+                    &hotkey,
+                    &GuardOptions {
+                        hotkey_pool,
+                        max_burn,
+                        budget,
+                        interval,
+                        summary_path: summary,
+                        status_path: status_file,
+                    },
+                )
+                .await?;
+        }
+
+        Commands::Campaign {
+            subnets,
+            wallet,
+            hotkey,
+            target,
+            budget,
+            deadline,
+            max_burn,
+            interval,
+            log,
+            summary,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let options = campaign::CampaignOptions {
+                target,
+                budget,
+                deadline: utils::parse_deadline(&deadline, chrono::Utc::now())?,
+                max_burn: max_burn.or(profile.max_burn),
+                interval,
+                log_path: log,
+                summary_path: summary,
+            };
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .run_campaign(&subnets, &wallet, &hotkey, &options)
+                .await?;
+        }
+
+        Commands::Watch {
+            subnet,
+            burn_below,
+            open_slots,
+            registration_opens,
+            hotkey,
+            interval,
+            notify,
+            record,
+            metrics_port,
+            metrics_listen,
+        } => {
+            let mut notifications = notify.notifications(profile.notify);
+
+            let options = WatchOptions {
+                burn_below,
+                open_slots,
+                registration_opens,
+                hotkeys: hotkey
+                    .iter()
+                    .map(|h| key_utils::resolve_hotkey(None, h))
+                    .collect(),
+                interval,
+            };
+            let records = records::for_recording(record, &config::current().records)?;
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let exporter = match metrics_port {
+                Some(port) => Some(prom::Exporter::serve((metrics_listen, port).into()).await?),
+                None => None,
+            };
+            register_client
+                .watch_subnets(
+                    &subnet,
+                    &options,
+                    &mut notifications,
+                    records.as_deref(),
+                    exporter.as_ref(),
+                )
+                .await?;
+        }
+
+        Commands::History {
+            db,
+            subnet,
+            days,
+            format,
+        } => {
+            history::show_history(&db, subnet, days, &format)?;
+        }
+
+        Commands::NetworkStats {
+            format,
+            output,
+            all,
+            top,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .show_network_statistics(&format, output.as_deref(), (!all).then_some(top))
+                .await?;
+        }
+
+        Commands::StakeDistribution { subnet } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client.show_stake_distribution(subnet).await?;
+        }
+
+        Commands::Metagraph {
+            subnet,
+            sort_by,
+            format,
+            output,
+            stream,
+            at_block,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            if let Some(block) = &at_block {
+                register_client.pin_block(block).await?;
+            }
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .show_metagraph(subnet, &sort_by, &format, output.as_deref(), stream)
+                .await?;
+        }
+
+        Commands::ExportConfig { subnet, output } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client.export_config(subnet, &output).await?;
+        }
+
+        Commands::Batch {
+            config,
+            summary,
+            resume,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .execute_batch_operations(&config, summary.as_deref(), resume)
+                .await?;
+        }
+
+        Commands::SwapHotkey {
+            wallet,
+            old_hotkey,
+            new_hotkey,
+            yes,
+        } => {
+            let old_hotkey = key_utils::resolve_hotkey(Some(&wallet), &old_hotkey);
+            let new_hotkey = key_utils::resolve_hotkey(Some(&wallet), &new_hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .swap_hotkey(&wallet, &old_hotkey, &new_hotkey, yes)
+                .await?;
+        }
+
+        Commands::ServeAxon {
+            subnet,
+            wallet,
+            hotkey,
+            ip,
+            port,
+            protocol,
+            version,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .serve_axon(subnet, &hotkey, &ip, port, protocol, version)
+                .await?;
+        }
+
+        Commands::Transfer {
+            from,
+            to,
+            amount,
+            yes,
+        } => {
+            let wallet = key_utils::resolve_wallet(&from);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.transfer(&wallet, &to, amount, yes).await?;
+        }
+
+        Commands::FundPlan {
+            treasury,
+            target,
+            config,
+            margin,
+            output,
+            execute,
+            yes,
+        } => {
+            let mut targets = target
+                .iter()
+                .map(|s| match s.rsplit_once(':') {
+                    Some((wallet, netuid)) => {
+                        Ok((key_utils::resolve_wallet(wallet), netuid.parse()?))
+                    }
+                    None => Err(anyhow!("Invalid target: {}. Use wallet:subnet", s)),
+                })
+                .collect::<Result<Vec<(String, u16)>>>()?;
+            if let Some(config) = config {
+                targets.extend(register::batch_registration_targets(&config)?);
+            }
+
+            let treasury = key_utils::resolve_wallet(&treasury);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .fund_plan(
+                    &treasury,
+                    &targets,
+                    &FundPlanOptions {
+                        margin_percent: margin,
+                        output,
+                        execute,
+                        assume_yes: yes,
+                    },
+                )
+                .await?;
+        }
+
+        Commands::Stake {
+            wallet,
+            hotkey,
+            subnet,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .add_stake_alpha(&wallet, &hotkey, subnet, amount, yes)
+                .await?;
+        }
+
+        Commands::Unstake {
+            wallet,
+            hotkey,
+            subnet,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .remove_stake_alpha(&wallet, &hotkey, subnet, amount, yes)
+                .await?;
+        }
+
+        Commands::RegisterRoot {
+            wallet,
+            hotkey,
+            allow_foreign_hotkey,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .register_root(&wallet, &hotkey, allow_foreign_hotkey, yes)
+                .await?;
+        }
+
+        Commands::StakeAlpha {
+            wallet,
+            hotkey,
+            subnet,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .add_stake_alpha(&wallet, &hotkey, subnet, amount, yes)
+                .await?;
+        }
+
+        Commands::UnstakeAlpha {
+            wallet,
+            hotkey,
+            subnet,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .remove_stake_alpha(&wallet, &hotkey, subnet, amount, yes)
+                .await?;
+        }
+
+        Commands::MoveStake {
+            wallet,
+            hotkey,
+            destination_hotkey,
+            origin_subnet,
+            destination_subnet,
+            amount,
+            yes,
+        } => {
+            let origin_hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let destination_hotkey = destination_hotkey
+                .map(|destination| key_utils::resolve_hotkey(Some(&wallet), &destination))
+                .unwrap_or_else(|| origin_hotkey.clone());
+            let stake_move = client::StakeMove {
+                origin_hotkey: key_utils::account_id_from_string(&origin_hotkey)
+                    .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?,
+                destination_hotkey: key_utils::account_id_from_string(&destination_hotkey)
+                    .kind_context(ErrorKind::Fatal, "Failed to load destination hotkey")?,
+                origin_netuid: origin_subnet,
+                destination_netuid: destination_subnet,
+                amount,
+            };
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .move_stake(&wallet, &stake_move, yes)
+                .await?;
+        }
+
+        Commands::SetChildren {
+            wallet,
+            hotkey,
+            subnet,
+            child,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let new_children = child
+                .iter()
+                .map(|spec| {
+                    let (child, parts) = children::parse_child(spec)?;
+                    Ok((key_utils::resolve_hotkey(Some(&wallet), &child), parts))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .set_children(&wallet, &hotkey, subnet, &new_children, yes)
+                .await?;
+        }
+
+        Commands::ChildInfo { hotkey, subnet } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client.show_child_info(&hotkey, subnet).await?;
+        }
+
+        Commands::CommitWeights {
+            wallet,
+            hotkey,
+            subnet,
+            weights,
+            yes,
+        } => {
+            let (uids, values) = commit_reveal::parse_weights(&weights)?;
+            let hotkey = key_utils::resolve_hotkey(wallet.as_deref(), &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .commit_weights(&hotkey, subnet, uids, values, yes)
+                .await?;
+        }
+
+        Commands::RevealWeights {
+            wallet,
+            hotkey,
+            subnet,
+            wait,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(wallet.as_deref(), &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            let subnet = subnet.netuid(&register_client).await?;
+            register_client
+                .reveal_weights(&hotkey, subnet, wait)
+                .await?;
+        }
+
+        Commands::SetIdentity {
+            wallet,
+            name,
+            url,
+            github,
+            image,
+            discord,
+            description,
+            additional,
+            yes,
+        } => {
+            let identity = client::ChainIdentity {
+                name,
+                url,
+                github,
+                image,
+                discord,
+                description,
+                additional,
+            };
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .set_identity(&wallet, &identity, yes)
+                .await?;
+        }
+
+        Commands::Identity { account } => {
+            let account = key_utils::resolve_wallet(&account);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.show_identity(&account).await?;
+        }
+
+        Commands::Delegates {
+            limit,
+            format,
+            output,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .show_delegates(limit, &format, output.as_deref())
+                .await?;
+        }
+
+        Commands::Delegate {
+            wallet,
+            hotkey,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .delegate(&wallet, &hotkey, amount, yes)
+                .await?;
+        }
+
+        Commands::Undelegate {
+            wallet,
+            hotkey,
+            amount,
+            yes,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .undelegate(&wallet, &hotkey, amount, yes)
+                .await?;
+        }
+
+        Commands::StakeInfo {
+            hotkey,
+            subnet,
+            coldkey,
+        } => {
+            let hotkey = key_utils::resolve_hotkey(None, &hotkey);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client
+                .show_stake_info(&hotkey, subnet, coldkey.as_deref())
+                .await?;
+        }
+
+        Commands::Overview { wallet } => {
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.show_wallet_overview(&wallet).await?;
+        }
+
+        Commands::Balance { account, at_block } => {
+            // A wallet name shows its coldkey's balance, as `btcli wallet balance` does
+            let wallet = key_utils::resolve_wallet(&account);
+            let account = if wallet != account {
+                key_utils::account_id_from_string(&wallet)?.to_ss58check()
+            } else {
+                account
+            };
+            let register_client = QuickRegister::new(rpc_url).await?;
+            if let Some(block) = &at_block {
+                register_client.pin_block(block).await?;
+            }
+            register_client.check_account_balance(&account).await?;
+        }
+
+        Commands::Faucet { wallet } => {
+            let wallet = key_utils::resolve_wallet(&wallet);
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.faucet(&wallet).await?;
+        }
+
+        Commands::EncryptConfig => {
+            config::encrypt_file(cli.config.as_deref())?;
+        }
+
+        Commands::DecryptConfig => {
+            config::decrypt_file(cli.config.as_deref())?;
+        }
+
+        Commands::NewKey {
+            wallet,
+            hotkey,
+            hotkey_only,
+            encrypt,
+            overwrite,
+        } => {
+            wallet::create_keys(
+                &key_utils::wallets_dir(),
+                &wallet::NewKeyOptions {
+                    wallet,
+                    hotkey,
+                    hotkey_only,
+                    encrypt,
+                    overwrite,
+                },
+            )?;
+        }
+
+        Commands::ForgetPassword { wallet } => {
+            let coldkey = key_utils::resolve_wallet(&wallet);
+            if key_utils::forget_password(&coldkey)? {
+                println!("🗑️ Removed the keyring password for {}", wallet);
+            } else {
+                println!("ℹ️ No keyring password stored for {}", wallet);
+            }
+        }
+
+        Commands::Capabilities { output } => {
+            capabilities::show_capabilities(&output)?;
+        }
+
+        Commands::CheckMetadata { save } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            register_client.check_metadata(save.as_deref()).await?;
+        }
+
+        Commands::Serve {
+            listen,
+            token,
+            max_concurrent,
+            max_burn,
+        } => {
+            let register_client = QuickRegister::new(rpc_url).await?;
+            api::serve(
+                register_client,
+                api::ApiOptions {
+                    listen,
+                    token,
+                    max_concurrent,
+                    max_burn: max_burn.or(profile.max_burn),
+                },
+            )
+            .await?;
         }
     }
 
@@ -220,3 +2329,66 @@ fn print_banner() {
         .bright_cyan()
     );
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use network::Network;
+
+    #[test]
+    fn test_flag_or_profile() {
+        let mut from_profile = Vec::new();
+        assert_eq!(
+            flag_or_profile(Some(3u16), Some(7), "subnet", &mut from_profile).unwrap(),
+            3
+        );
+        assert!(from_profile.is_empty());
+
+        assert_eq!(
+            flag_or_profile(None, Some(7u16), "subnet", &mut from_profile).unwrap(),
+            7
+        );
+        assert_eq!(from_profile, ["subnet"]);
+
+        let error = flag_or_profile::<u16>(None, None, "wallet", &mut from_profile).unwrap_err();
+        assert!(error.to_string().starts_with("--wallet is required"));
+        assert_eq!(from_profile, ["subnet"]);
+    }
+
+    #[test]
+    fn test_fleet_hotkeys() {
+        // One hotkey is not a fleet, a list is resolved key by key
+        assert!(fleet_hotkeys("//Alice", &[], None).unwrap().is_none());
+        let hotkeys = ["//Bob", "//Charlie"].map(String::from);
+        assert_eq!(
+            fleet_hotkeys("//Alice", &hotkeys, None).unwrap().unwrap(),
+            hotkeys
+        );
+        assert!(fleet_hotkeys("//Alice", &hotkeys, Some("/nonexistent/hotkeys")).is_err());
+    }
+
+    #[test]
+    fn test_rpc_url_precedence() {
+        let url = |s: &str| Some(s.to_string());
+        assert_eq!(
+            resolve_rpc_url(url("ws://flag"), None, url("ws://profile"), None),
+            "ws://flag"
+        );
+        assert_eq!(
+            resolve_rpc_url(None, Some(Network::Local), url("ws://profile"), None),
+            Network::Local.endpoint()
+        );
+        assert_eq!(
+            resolve_rpc_url(None, None, url("ws://profile"), Some(Network::Test)),
+            "ws://profile"
+        );
+        assert_eq!(
+            resolve_rpc_url(None, None, None, Some(Network::Test)),
+            Network::Test.endpoint()
+        );
+        assert_eq!(
+            resolve_rpc_url(None, None, None, None),
+            constants::DEFAULT_RPC_ENDPOINTS[0]
+        );
+    }
+}
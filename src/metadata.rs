@@ -0,0 +1,1292 @@
+//! Runtime metadata (V14, what state_getMetadata serves) decoded just far enough to check the
+//! call indices, storage hashers and signed extensions this tool hardcodes against the ones a
+//! runtime declares, and to read a block's events by pallet and event name
+use anyhow::{anyhow, Result};
+use codec::{Compact, Decode, Encode};
+use scale_info::{form::PortableForm, PortableRegistry, TypeDef, TypeDefPrimitive, Variant};
+
+use crate::capabilities::Capabilities;
+use crate::client::SUBNET_INFO_STORAGE;
+
+// "meta", little endian
+const METADATA_MAGIC: u32 = 0x6174_656d;
+const METADATA_VERSION: u8 = 14;
+
+// Hashers as the metadata names them, a superset of the ones this tool keys with
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Encode, Decode)]
+pub enum Hasher {
+    Blake2_128,
+    Blake2_256,
+    Blake2_128Concat,
+    Twox128,
+    Twox256,
+    Twox64Concat,
+    Identity,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct RuntimeMetadataV14 {
+    types: PortableRegistry,
+    pallets: Vec<PalletMetadata>,
+    extrinsic: ExtrinsicMetadata,
+    ty: Compact<u32>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct PalletMetadata {
+    name: String,
+    storage: Option<PalletStorageMetadata>,
+    calls: Option<Compact<u32>>,
+    event: Option<Compact<u32>>,
+    constants: Vec<PalletConstantMetadata>,
+    error: Option<Compact<u32>>,
+    index: u8,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct PalletStorageMetadata {
+    prefix: String,
+    entries: Vec<StorageEntryMetadata>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct StorageEntryMetadata {
+    name: String,
+    modifier: u8, // Optional or Default
+    ty: StorageEntryType,
+    default: Vec<u8>,
+    docs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+enum StorageEntryType {
+    Plain(Compact<u32>),
+    Map {
+        hashers: Vec<Hasher>,
+        key: Compact<u32>,
+        value: Compact<u32>,
+    },
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct PalletConstantMetadata {
+    name: String,
+    ty: Compact<u32>,
+    value: Vec<u8>,
+    docs: Vec<String>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct ExtrinsicMetadata {
+    ty: Compact<u32>,
+    version: u8,
+    signed_extensions: Vec<SignedExtensionMetadata>,
+}
+
+#[derive(Debug, Clone, Encode, Decode)]
+struct SignedExtensionMetadata {
+    identifier: String,
+    ty: Compact<u32>,
+    additional_signed: Compact<u32>,
+}
+
+// Storage items as this tool keys them, besides the netuid-keyed ones in SUBNET_INFO_STORAGE
+// and NETUID_STORAGE
+const STORAGE_LAYOUTS: &[(&str, &str, &[Hasher])] = &[
+    ("System", "Account", &[Hasher::Blake2_128Concat]),
+    ("System", "Events", &[]),
+    ("Timestamp", "Now", &[]),
+    ("SenateMembers", "Members", &[]),
+    (
+        "SubtensorModule",
+        "Uids",
+        &[Hasher::Identity, Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "Keys",
+        &[Hasher::Identity, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "BlockAtRegistration",
+        &[Hasher::Identity, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "Axons",
+        &[Hasher::Identity, Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "Prometheus",
+        &[Hasher::Identity, Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "Weights",
+        &[Hasher::Identity, Hasher::Identity],
+    ),
+    ("SubtensorModule", "Owner", &[Hasher::Blake2_128Concat]),
+    (
+        "SubtensorModule",
+        "OwnedHotkeys",
+        &[Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "Alpha",
+        &[
+            Hasher::Blake2_128Concat,
+            Hasher::Blake2_128Concat,
+            Hasher::Identity,
+        ],
+    ),
+    (
+        "SubtensorModule",
+        "TotalHotkeyAlpha",
+        &[Hasher::Blake2_128Concat, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "TotalHotkeyShares",
+        &[Hasher::Blake2_128Concat, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "ChildKeys",
+        &[Hasher::Blake2_128Concat, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "ParentKeys",
+        &[Hasher::Blake2_128Concat, Hasher::Identity],
+    ),
+    (
+        "SubtensorModule",
+        "ChildkeyTake",
+        &[Hasher::Blake2_128Concat, Hasher::Identity],
+    ),
+    ("SubtensorModule", "LastTxBlock", &[Hasher::Identity]),
+    ("SubtensorModule", "NetworksAdded", &[Hasher::Identity]),
+    (
+        "SubtensorModule",
+        "SubnetIdentitiesV3",
+        &[Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "IdentitiesV2",
+        &[Hasher::Blake2_128Concat],
+    ),
+    ("SubtensorModule", "TotalNetworks", &[]),
+    ("SubtensorModule", "SubnetOwnerCut", &[]),
+    ("SubtensorModule", "TxRateLimit", &[]),
+];
+
+// SubtensorModule items keyed by netuid alone (Identity) outside SUBNET_INFO_STORAGE
+const NETUID_STORAGE: &[&str] = &[
+    "PruningScores",
+    "ValidatorPermit",
+    "Emission",
+    "CommitRevealWeightsEnabled",
+    "RevealPeriodEpochs",
+    "WeightsVersionKey",
+    "SubnetTAO",
+    "SubnetAlphaIn",
+    "SubnetAlphaOut",
+    "AdjustmentInterval",
+    "LastAdjustmentBlock",
+    "AdjustmentAlpha",
+    "RegistrationsThisBlock",
+];
+
+// Older identity versions, read only as fallbacks. Checked when a runtime still has them
+const LEGACY_STORAGE_LAYOUTS: &[(&str, &str, &[Hasher])] = &[
+    (
+        "SubtensorModule",
+        "SubnetIdentitiesV2",
+        &[Hasher::Blake2_128Concat],
+    ),
+    (
+        "SubtensorModule",
+        "SubnetIdentities",
+        &[Hasher::Blake2_128Concat],
+    ),
+    ("SubtensorModule", "Identities", &[Hasher::Blake2_128Concat]),
+];
+
+// Events this tool reads from blocks, found by name
+const EVENTS: &[(&str, &str)] = &[
+    ("System", "ExtrinsicSuccess"),
+    ("System", "ExtrinsicFailed"),
+    ("SubtensorModule", "NeuronRegistered"),
+];
+
+// One record of System::Events, its event named the way the runtime declares it
+#[derive(Debug, Clone)]
+pub struct EventRecord {
+    pub extrinsic_index: Option<u32>, // From the ApplyExtrinsic phase
+    pub pallet: String,
+    pub name: String,
+    pub fields: Vec<EventField>,
+}
+
+#[derive(Debug, Clone)]
+pub struct EventField {
+    pub name: Option<String>,
+    pub ty: u32,
+    pub bytes: Vec<u8>, // SCALE encoded
+}
+
+impl EventRecord {
+    pub fn is(&self, pallet: &str, name: &str) -> bool {
+        self.pallet == pallet && self.name == name
+    }
+
+    pub fn field(&self, name: &str) -> Option<&EventField> {
+        self.fields
+            .iter()
+            .find(|field| field.name.as_deref() == Some(name))
+    }
+}
+
+pub struct Metadata {
+    inner: RuntimeMetadataV14,
+}
+
+impl Metadata {
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let mut input = bytes;
+        let magic = u32::decode(&mut input).map_err(|_| anyhow!("Metadata is empty"))?;
+        if magic != METADATA_MAGIC {
+            return Err(anyhow!("Not runtime metadata, the magic number is missing"));
+        }
+        match u8::decode(&mut input) {
+            Ok(METADATA_VERSION) => {}
+            Ok(version) => {
+                return Err(anyhow!(
+                    "Metadata V{} is not supported, only V{}",
+                    version,
+                    METADATA_VERSION
+                ))
+            }
+            Err(_) => return Err(anyhow!("Metadata version missing")),
+        }
+        let inner = RuntimeMetadataV14::decode(&mut input)
+            .map_err(|e| anyhow!("Failed to decode metadata: {:?}", e))?;
+        Ok(Self { inner })
+    }
+
+    fn pallet(&self, name: &str) -> Option<&PalletMetadata> {
+        self.inner.pallets.iter().find(|pallet| pallet.name == name)
+    }
+
+    // Pallet and call index of a call, None when the runtime has no such call
+    pub fn call_index(&self, pallet: &str, call: &str) -> Option<(u8, u8)> {
+        let pallet = self.pallet(pallet)?;
+        let calls = self.inner.types.resolve(pallet.calls?.0)?;
+        let TypeDef::Variant(variants) = &calls.type_def else {
+            return None;
+        };
+        variants
+            .variants
+            .iter()
+            .find(|variant| variant.name == call)
+            .map(|variant| (pallet.index, variant.index))
+    }
+
+    // Encodings of a call's arguments, in order, None when the runtime has no such call
+    pub fn call_args(&self, pallet: &str, call: &str) -> Option<Vec<String>> {
+        let calls = self.inner.types.resolve(self.pallet(pallet)?.calls?.0)?;
+        let TypeDef::Variant(variants) = &calls.type_def else {
+            return None;
+        };
+        let variant = variants
+            .variants
+            .iter()
+            .find(|variant| variant.name == call)?;
+        Some(
+            variant
+                .fields
+                .iter()
+                .map(|field| self.shape(field.ty.id))
+                .collect(),
+        )
+    }
+
+    // How a type encodes, in Rust syntax: newtypes by what they wrap (AccountId32 and H256 are
+    // both [u8; 32]), structs as tuples and enums by name
+    fn shape(&self, id: u32) -> String {
+        let Some(ty) = self.inner.types.resolve(id) else {
+            return format!("?{}", id);
+        };
+        let list = |ids: Vec<u32>| {
+            ids.into_iter()
+                .map(|id| self.shape(id))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        match &ty.type_def {
+            TypeDef::Primitive(primitive) => format!("{:?}", primitive).to_lowercase(),
+            TypeDef::Compact(compact) => format!("Compact<{}>", self.shape(compact.type_param.id)),
+            TypeDef::Sequence(sequence) => format!("Vec<{}>", self.shape(sequence.type_param.id)),
+            TypeDef::Array(array) => {
+                format!("[{}; {}]", self.shape(array.type_param.id), array.len)
+            }
+            TypeDef::Tuple(tuple) => {
+                format!("({})", list(tuple.fields.iter().map(|f| f.id).collect()))
+            }
+            TypeDef::Composite(composite) if composite.fields.len() == 1 => {
+                self.shape(composite.fields[0].ty.id)
+            }
+            TypeDef::Composite(composite) => {
+                format!(
+                    "({})",
+                    list(composite.fields.iter().map(|f| f.ty.id).collect())
+                )
+            }
+            TypeDef::Variant(_) => ty.path.segments.last().cloned().unwrap_or_default(),
+            TypeDef::BitSequence(_) => "BitVec".to_string(),
+        }
+    }
+
+    // Hashers of a storage item, empty for a plain value, None when there is no such item
+    pub fn storage_hashers(&self, pallet: &str, name: &str) -> Option<Vec<Hasher>> {
+        let entry = self
+            .pallet(pallet)?
+            .storage
+            .as_ref()?
+            .entries
+            .iter()
+            .find(|entry| entry.name == name)?;
+        Some(match &entry.ty {
+            StorageEntryType::Plain(_) => Vec::new(),
+            StorageEntryType::Map { hashers, .. } => hashers.clone(),
+        })
+    }
+
+    // Whether a pallet declares an event of this name
+    pub fn has_event(&self, pallet: &str, name: &str) -> bool {
+        self.pallet(pallet)
+            .and_then(|pallet| pallet.event)
+            .and_then(|event| self.variants(event.0))
+            .is_some_and(|variants| variants.iter().any(|variant| variant.name == name))
+    }
+
+    fn variants(&self, id: u32) -> Option<&[Variant<PortableForm>]> {
+        match &self.inner.types.resolve(id)?.type_def {
+            TypeDef::Variant(def) => Some(&def.variants),
+            _ => None,
+        }
+    }
+
+    // Records of an encoded System::Events value, Vec<EventRecord<RuntimeEvent, H256>>
+    pub fn decode_events(&self, bytes: &[u8]) -> Result<Vec<EventRecord>> {
+        let entry = self
+            .pallet("System")
+            .and_then(|system| system.storage.as_ref())
+            .and_then(|storage| storage.entries.iter().find(|entry| entry.name == "Events"))
+            .ok_or_else(|| anyhow!("Runtime has no System.Events"))?;
+        let StorageEntryType::Plain(events) = &entry.ty else {
+            return Err(anyhow!("System.Events is not a plain value"));
+        };
+        let record = match self.inner.types.resolve(events.0).map(|ty| &ty.type_def) {
+            Some(TypeDef::Sequence(sequence)) => sequence.type_param.id,
+            _ => return Err(anyhow!("System.Events is not a list of records")),
+        };
+
+        let mut input = bytes;
+        let count = Compact::<u32>::decode(&mut input)
+            .map_err(|e| anyhow!("Failed to decode events: {:?}", e))?
+            .0;
+        let records = (0..count)
+            .map(|_| self.decode_event_record(record, &mut input))
+            .collect::<Result<Vec<_>>>()?;
+        if !input.is_empty() {
+            return Err(anyhow!("{} bytes left after the events", input.len()));
+        }
+        Ok(records)
+    }
+
+    // EventRecord { phase, event, topics }
+    fn decode_event_record(&self, id: u32, input: &mut &[u8]) -> Result<EventRecord> {
+        let fields = match self.inner.types.resolve(id).map(|ty| &ty.type_def) {
+            Some(TypeDef::Composite(record)) => &record.fields,
+            _ => return Err(anyhow!("Event record type {} is not a struct", id)),
+        };
+        let mut extrinsic_index = None;
+        let mut event = None;
+        for field in fields {
+            match field.name.as_deref() {
+                Some("phase") => {
+                    let phase = self.variant_at(field.ty.id, input)?;
+                    if phase.name == "ApplyExtrinsic" {
+                        extrinsic_index = Some(
+                            u32::decode(input)
+                                .map_err(|e| anyhow!("Failed to decode phase: {:?}", e))?,
+                        );
+                    } else {
+                        self.skip_fields(phase, input)?;
+                    }
+                }
+                Some("event") => event = Some(self.decode_event(field.ty.id, input)?),
+                _ => self.skip(field.ty.id, input)?,
+            }
+        }
+        let (pallet, name, fields) = event.ok_or_else(|| anyhow!("Event record has no event"))?;
+        Ok(EventRecord {
+            extrinsic_index,
+            pallet,
+            name,
+            fields,
+        })
+    }
+
+    // RuntimeEvent: a variant per pallet, named after it, wrapping that pallet's event enum
+    fn decode_event(
+        &self,
+        id: u32,
+        input: &mut &[u8],
+    ) -> Result<(String, String, Vec<EventField>)> {
+        let pallet = self.variant_at(id, input)?;
+        let [inner] = &pallet.fields[..] else {
+            return Err(anyhow!(
+                "{} events are not wrapped in one enum",
+                pallet.name
+            ));
+        };
+        let event = self.variant_at(inner.ty.id, input)?;
+        let fields = event
+            .fields
+            .iter()
+            .map(|field| {
+                let start = *input;
+                self.skip(field.ty.id, input)?;
+                Ok(EventField {
+                    name: field.name.clone(),
+                    ty: field.ty.id,
+                    bytes: start[..start.len() - input.len()].to_vec(),
+                })
+            })
+            .collect::<Result<_>>()?;
+        Ok((pallet.name.clone(), event.name.clone(), fields))
+    }
+
+    // A DispatchError by name, "Module(SubtensorModule::TooManyRegistrationsThisBlock)" for
+    // one a pallet raised. Hex when it doesn't decode
+    pub fn describe_dispatch_error(&self, ty: u32, bytes: &[u8]) -> String {
+        let mut input = bytes;
+        let Ok(variant) = self.variant_at(ty, &mut input) else {
+            return format!("0x{}", hex::encode(bytes));
+        };
+        // ModuleError { index, error: [u8; 4] }, the first error byte is the variant
+        if let ("Module", [pallet, error, ..]) = (variant.name.as_str(), input) {
+            return match self.pallet_error(*pallet, *error) {
+                Some(name) => format!("Module({})", name),
+                None => format!("Module(pallet {}, error {})", pallet, error),
+            };
+        }
+        match variant.fields.first() {
+            Some(field) => match self.variant_at(field.ty.id, &mut input) {
+                Ok(inner) => format!("{}({})", variant.name, inner.name),
+                Err(_) => variant.name.clone(),
+            },
+            None => variant.name.clone(),
+        }
+    }
+
+    // "Pallet::Error" for an error index of the pallet at this index
+    fn pallet_error(&self, pallet: u8, error: u8) -> Option<String> {
+        let pallet = self.inner.pallets.iter().find(|p| p.index == pallet)?;
+        let variant = self
+            .variants(pallet.error?.0)?
+            .iter()
+            .find(|variant| variant.index == error)?;
+        Some(format!("{}::{}", pallet.name, variant.name))
+    }
+
+    // Reads an enum's variant index and returns the variant
+    fn variant_at(&self, id: u32, input: &mut &[u8]) -> Result<&Variant<PortableForm>> {
+        let variants = self
+            .variants(id)
+            .ok_or_else(|| anyhow!("Type {} is not an enum", id))?;
+        let index = u8::decode(input).map_err(|_| anyhow!("Enum of type {} is cut off", id))?;
+        variants
+            .iter()
+            .find(|variant| variant.index == index)
+            .ok_or_else(|| anyhow!("Type {} has no variant {}", id, index))
+    }
+
+    fn skip_fields(&self, variant: &Variant<PortableForm>, input: &mut &[u8]) -> Result<()> {
+        variant
+            .fields
+            .iter()
+            .try_for_each(|field| self.skip(field.ty.id, input))
+    }
+
+    // Moves input past one encoded value of a type
+    fn skip(&self, id: u32, input: &mut &[u8]) -> Result<()> {
+        let ty = self
+            .inner
+            .types
+            .resolve(id)
+            .ok_or_else(|| anyhow!("Type {} not in the registry", id))?;
+        let length = |input: &mut &[u8]| {
+            Compact::<u32>::decode(input)
+                .map(|len| len.0 as usize)
+                .map_err(|_| anyhow!("Length of type {} is cut off", id))
+        };
+        let advance = |input: &mut &[u8], len: usize| {
+            if input.len() < len {
+                return Err(anyhow!("Value of type {} is cut off", id));
+            }
+            *input = &input[len..];
+            Ok(())
+        };
+        match &ty.type_def {
+            TypeDef::Primitive(primitive) => {
+                let len = match primitive {
+                    TypeDefPrimitive::Str => length(input)?,
+                    primitive => primitive_width(primitive),
+                };
+                advance(input, len)
+            }
+            TypeDef::Compact(_) => Compact::<u128>::decode(input)
+                .map(|_| ())
+                .map_err(|_| anyhow!("Compact of type {} is cut off", id)),
+            TypeDef::Sequence(sequence) => {
+                (0..length(input)?).try_for_each(|_| self.skip(sequence.type_param.id, input))
+            }
+            TypeDef::Array(array) => {
+                (0..array.len).try_for_each(|_| self.skip(array.type_param.id, input))
+            }
+            TypeDef::Tuple(tuple) => tuple
+                .fields
+                .iter()
+                .try_for_each(|field| self.skip(field.id, input)),
+            TypeDef::Composite(composite) => composite
+                .fields
+                .iter()
+                .try_for_each(|field| self.skip(field.ty.id, input)),
+            TypeDef::Variant(_) => {
+                let variant = self.variant_at(id, input)?;
+                self.skip_fields(variant, input)
+            }
+            TypeDef::BitSequence(bits) => {
+                let store = match self
+                    .inner
+                    .types
+                    .resolve(bits.bit_store_type.id)
+                    .map(|ty| &ty.type_def)
+                {
+                    Some(TypeDef::Primitive(primitive)) => primitive_width(primitive).max(1),
+                    _ => 1,
+                };
+                let bits = length(input)?;
+                advance(input, bits.div_ceil(store * 8) * store)
+            }
+        }
+    }
+
+    pub fn extrinsic_version(&self) -> u8 {
+        self.inner.extrinsic.version
+    }
+
+    // Signed extensions that put bytes in the extrinsic or the signed payload, in order.
+    // Those that add neither (CheckWeight and the like) don't affect the layout
+    pub fn payload_signed_extensions(&self) -> Vec<&str> {
+        self.inner
+            .extrinsic
+            .signed_extensions
+            .iter()
+            .filter(|extension| {
+                !self.is_empty_type(extension.ty.0)
+                    || !self.is_empty_type(extension.additional_signed.0)
+            })
+            .map(|extension| extension.identifier.as_str())
+            .collect()
+    }
+
+    // Whether values of a type always encode to nothing: unit structs, empty tuples and the like
+    fn is_empty_type(&self, id: u32) -> bool {
+        let Some(ty) = self.inner.types.resolve(id) else {
+            return false;
+        };
+        match &ty.type_def {
+            TypeDef::Composite(composite) => composite
+                .fields
+                .iter()
+                .all(|field| self.is_empty_type(field.ty.id)),
+            TypeDef::Tuple(tuple) => tuple
+                .fields
+                .iter()
+                .all(|field| self.is_empty_type(field.id)),
+            TypeDef::Array(array) => array.len == 0 || self.is_empty_type(array.type_param.id),
+            _ => false,
+        }
+    }
+
+    // Every way this build's hardcoded encoding disagrees with the runtime, empty when none
+    pub fn drift(&self, capabilities: &Capabilities) -> Vec<String> {
+        let mut drift = Vec::new();
+
+        for call in &capabilities.calls {
+            let Some((pallet, name)) = call.name.split_once('.') else {
+                continue;
+            };
+            match self.call_index(pallet, name) {
+                None => {
+                    drift.push(format!("Call {} not found", call.name));
+                    continue;
+                }
+                Some(index) if index != (call.pallet_index, call.call_index) => {
+                    drift.push(format!(
+                        "Call {} is at {}:{}, this tool encodes {}:{}",
+                        call.name, index.0, index.1, call.pallet_index, call.call_index
+                    ))
+                }
+                Some(_) => {}
+            }
+            let args = self.call_args(pallet, name).unwrap_or_default();
+            if args != call.args {
+                drift.push(format!(
+                    "Call {} takes ({}), this tool encodes ({})",
+                    call.name,
+                    args.join(", "),
+                    call.args.join(", ")
+                ));
+            }
+        }
+
+        let netuid_items = SUBNET_INFO_STORAGE
+            .iter()
+            .chain(NETUID_STORAGE)
+            .map(|name| ("SubtensorModule", *name, &[Hasher::Identity][..]));
+        for (pallet, name, expected) in STORAGE_LAYOUTS.iter().copied().chain(netuid_items) {
+            match self.storage_hashers(pallet, name) {
+                None => drift.push(format!("Storage {}.{} not found", pallet, name)),
+                Some(hashers) if hashers != expected => drift.push(format!(
+                    "Storage {}.{} is keyed with {:?}, this tool uses {:?}",
+                    pallet, name, hashers, expected
+                )),
+                Some(_) => {}
+            }
+        }
+        for &(pallet, name, expected) in LEGACY_STORAGE_LAYOUTS {
+            if let Some(hashers) = self.storage_hashers(pallet, name) {
+                if hashers != expected {
+                    drift.push(format!(
+                        "Storage {}.{} is keyed with {:?}, this tool uses {:?}",
+                        pallet, name, hashers, expected
+                    ));
+                }
+            }
+        }
+
+        for &(pallet, name) in EVENTS {
+            if !self.has_event(pallet, name) {
+                drift.push(format!("Event {}.{} not found", pallet, name));
+            }
+        }
+
+        if self.extrinsic_version() != capabilities.extrinsic_version {
+            drift.push(format!(
+                "Extrinsic version is {}, this tool builds {}",
+                self.extrinsic_version(),
+                capabilities.extrinsic_version
+            ));
+        }
+        let extensions = self.payload_signed_extensions();
+        if extensions != capabilities.signed_extensions {
+            drift.push(format!(
+                "Signed extensions are [{}], this tool signs [{}]",
+                extensions.join(", "),
+                capabilities.signed_extensions.join(", ")
+            ));
+        }
+        drift
+    }
+}
+
+// Bytes a fixed-width primitive encodes to
+fn primitive_width(primitive: &TypeDefPrimitive) -> usize {
+    match primitive {
+        TypeDefPrimitive::Bool | TypeDefPrimitive::U8 | TypeDefPrimitive::I8 => 1,
+        TypeDefPrimitive::U16 | TypeDefPrimitive::I16 => 2,
+        TypeDefPrimitive::Char | TypeDefPrimitive::U32 | TypeDefPrimitive::I32 => 4,
+        TypeDefPrimitive::U64 | TypeDefPrimitive::I64 => 8,
+        TypeDefPrimitive::U128 | TypeDefPrimitive::I128 => 16,
+        TypeDefPrimitive::U256 | TypeDefPrimitive::I256 => 32,
+        TypeDefPrimitive::Str => 0,
+    }
+}
+
+// A runtime described the way Subtensor's pallets declare it, for tests to check the hardcoded
+// encodings against and for MockChain to serve as its metadata
+#[cfg(test)]
+pub mod runtime {
+    use super::*;
+    use scale_info::{
+        build::{Fields, Variants},
+        Path, PortableRegistryBuilder, Type, TypeDefArray, TypeDefCompact, TypeDefSequence,
+        TypeDefTuple,
+    };
+
+    // Subtensor's calls as its pallets declare them: pallet and index, call and index, argument
+    // types. Written out from the runtime source, not from constants.rs or Capabilities, so a
+    // wrong index or argument in either shows up as drift
+    #[rustfmt::skip]
+    const RUNTIME_CALLS: &[(&str, u8, &str, u8, &[&str])] = &[
+        ("SubtensorModule", 7, "register", 6, &["NetUid", "u64", "u64", "Vec<u8>", "AccountId", "AccountId"]),
+        ("SubtensorModule", 7, "burned_register", 7, &["NetUid", "AccountId"]),
+        ("SubtensorModule", 7, "add_stake", 2, &["AccountId", "NetUid", "TaoCurrency"]),
+        ("SubtensorModule", 7, "remove_stake", 3, &["AccountId", "NetUid", "AlphaCurrency"]),
+        ("SubtensorModule", 7, "serve_axon", 4, &["NetUid", "u32", "u128", "u16", "u8", "u8", "u8", "u8"]),
+        ("SubtensorModule", 7, "move_stake", 85, &["AccountId", "AccountId", "NetUid", "NetUid", "AlphaCurrency"]),
+        ("SubtensorModule", 7, "set_children", 67, &["AccountId", "NetUid", "Vec<(u64, AccountId)>"]),
+        ("SubtensorModule", 7, "set_identity", 68, &["Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>", "Vec<u8>"]),
+        ("SubtensorModule", 7, "swap_hotkey", 70, &["AccountId", "AccountId"]),
+        ("SubtensorModule", 7, "root_register", 62, &["AccountId"]),
+        ("SubtensorModule", 7, "commit_weights", 96, &["NetUid", "H256"]),
+        ("SubtensorModule", 7, "reveal_weights", 97, &["NetUid", "Vec<u16>", "Vec<u16>", "Vec<u16>", "u64"]),
+        ("SubtensorModule", 7, "faucet", 60, &["u64", "u64", "Vec<u8>"]),
+        ("Balances", 5, "transfer_keep_alive", 3, &["AccountIdLookupOf", "Compact<Balance>"]),
+    ];
+
+    // Storage maps as the runtime declares them. The plain netuid-keyed items are the names in
+    // SUBNET_INFO_STORAGE and NETUID_STORAGE, each an Identity map
+    #[rustfmt::skip]
+    const RUNTIME_STORAGE: &[(&str, &str, &[Hasher])] = {
+        use Hasher::*;
+        &[
+            ("System", "Account", &[Blake2_128Concat]),
+            ("System", "Events", &[]),
+            ("Timestamp", "Now", &[]),
+            ("SenateMembers", "Members", &[]),
+            ("SubtensorModule", "Uids", &[Identity, Blake2_128Concat]),
+            ("SubtensorModule", "Keys", &[Identity, Identity]),
+            ("SubtensorModule", "BlockAtRegistration", &[Identity, Identity]),
+            ("SubtensorModule", "Axons", &[Identity, Blake2_128Concat]),
+            ("SubtensorModule", "Prometheus", &[Identity, Blake2_128Concat]),
+            ("SubtensorModule", "Weights", &[Identity, Identity]),
+            ("SubtensorModule", "Owner", &[Blake2_128Concat]),
+            ("SubtensorModule", "OwnedHotkeys", &[Blake2_128Concat]),
+            ("SubtensorModule", "Alpha", &[Blake2_128Concat, Blake2_128Concat, Identity]),
+            ("SubtensorModule", "TotalHotkeyAlpha", &[Blake2_128Concat, Identity]),
+            ("SubtensorModule", "TotalHotkeyShares", &[Blake2_128Concat, Identity]),
+            ("SubtensorModule", "ChildKeys", &[Blake2_128Concat, Identity]),
+            ("SubtensorModule", "ParentKeys", &[Blake2_128Concat, Identity]),
+            ("SubtensorModule", "ChildkeyTake", &[Blake2_128Concat, Identity]),
+            ("SubtensorModule", "LastTxBlock", &[Identity]),
+            ("SubtensorModule", "NetworksAdded", &[Identity]),
+            ("SubtensorModule", "SubnetIdentitiesV3", &[Blake2_128Concat]),
+            ("SubtensorModule", "IdentitiesV2", &[Blake2_128Concat]),
+            ("SubtensorModule", "TotalNetworks", &[]),
+            ("SubtensorModule", "SubnetOwnerCut", &[]),
+            ("SubtensorModule", "TxRateLimit", &[]),
+        ]
+    };
+
+    fn type_of(def: impl Into<TypeDef<PortableForm>>) -> Type<PortableForm> {
+        Type::new(
+            Path::from_segments_unchecked(Vec::<String>::new()),
+            Vec::new(),
+            def,
+            Vec::new(),
+        )
+    }
+
+    // Name, index and (field name, type) of an enum variant
+    type VariantDef<'a> = (&'a str, u8, &'a [(Option<&'a str>, u32)]);
+
+    struct RuntimeTypes(PortableRegistryBuilder);
+
+    impl RuntimeTypes {
+        fn register(&mut self, ty: impl Into<Type<PortableForm>>) -> u32 {
+            self.0.register_type(ty.into())
+        }
+
+        fn primitive(&mut self, primitive: TypeDefPrimitive) -> u32 {
+            self.register(type_of(primitive))
+        }
+
+        // A single-field struct, the way newtypes like NetUid and AccountId32 appear
+        fn newtype(&mut self, path: &str, inner: u32) -> u32 {
+            self.register(
+                Type::builder_portable()
+                    .path(Path::from_segments_unchecked(
+                        path.split("::").map(String::from).collect::<Vec<_>>(),
+                    ))
+                    .composite(Fields::unnamed().field_portable(|f| f.ty(inner))),
+            )
+        }
+
+        fn account_id(&mut self) -> u32 {
+            let byte = self.primitive(TypeDefPrimitive::U8);
+            let bytes = self.register(type_of(TypeDefArray::<PortableForm>::new(32, byte.into())));
+            self.newtype("sp_core::crypto::AccountId32", bytes)
+        }
+
+        fn structure(&mut self, path: &str, fields: &[(&str, u32)]) -> u32 {
+            let fields = fields.iter().fold(Fields::named(), |fields, (name, ty)| {
+                fields.field_portable(|f| f.name(name.to_string()).ty(*ty))
+            });
+            self.register(
+                Type::builder_portable()
+                    .path(path_of(path))
+                    .composite(fields),
+            )
+        }
+
+        // An enum of variants, fields named or not
+        fn enumeration(&mut self, path: &str, variants: &[VariantDef]) -> u32 {
+            let variants = variants.iter().fold(
+                Variants::<PortableForm>::new(),
+                |variants, (name, index, fields)| {
+                    variants.variant(name.to_string(), |v| {
+                        let v = v.index(*index);
+                        if fields.iter().all(|(name, _)| name.is_some()) {
+                            v.fields(fields.iter().fold(Fields::named(), |fields, (name, ty)| {
+                                fields.field_portable(|f| f.name(name.unwrap().to_string()).ty(*ty))
+                            }))
+                        } else {
+                            v.fields(fields.iter().fold(Fields::unnamed(), |fields, (_, ty)| {
+                                fields.field_portable(|f| f.ty(*ty))
+                            }))
+                        }
+                    })
+                },
+            );
+            self.register(
+                Type::builder_portable()
+                    .path(path_of(path))
+                    .variant(variants),
+            )
+        }
+
+        fn vec_of(&mut self, inner: u32) -> u32 {
+            self.register(type_of(TypeDefSequence::<PortableForm>::new(inner.into())))
+        }
+
+        // Type ids for the argument types named in RUNTIME_CALLS
+        fn arg(&mut self, name: &str) -> u32 {
+            match name {
+                "u8" => self.primitive(TypeDefPrimitive::U8),
+                "u16" => self.primitive(TypeDefPrimitive::U16),
+                "u32" => self.primitive(TypeDefPrimitive::U32),
+                "u64" => self.primitive(TypeDefPrimitive::U64),
+                "u128" => self.primitive(TypeDefPrimitive::U128),
+                "NetUid" => {
+                    let inner = self.arg("u16");
+                    self.newtype("subtensor_runtime_common::NetUid", inner)
+                }
+                "TaoCurrency" | "AlphaCurrency" => {
+                    let inner = self.arg("u64");
+                    self.newtype(&format!("subtensor_runtime_common::{}", name), inner)
+                }
+                "AccountId" => self.account_id(),
+                "H256" => {
+                    let byte = self.arg("u8");
+                    let bytes =
+                        self.register(type_of(TypeDefArray::<PortableForm>::new(32, byte.into())));
+                    self.newtype("primitive_types::H256", bytes)
+                }
+                "Vec<u8>" | "Vec<u16>" => {
+                    let inner = self.arg(&name[4..name.len() - 1]);
+                    self.vec_of(inner)
+                }
+                "Vec<(u64, AccountId)>" => {
+                    let fields = vec![self.arg("u64").into(), self.account_id().into()];
+                    let pair = self.register(type_of(TypeDefTuple::new_portable(fields)));
+                    self.vec_of(pair)
+                }
+                "AccountIdLookupOf" => {
+                    let account = self.account_id();
+                    let address = Type::builder_portable()
+                        .path(Path::from_segments_unchecked(vec![
+                            "sp_runtime".to_string(),
+                            "multiaddress".to_string(),
+                            "MultiAddress".to_string(),
+                        ]))
+                        .variant(Variants::new().variant("Id".to_string(), |v| {
+                            v.index(0)
+                                .fields(Fields::unnamed().field_portable(|f| f.ty(account)))
+                        }));
+                    self.register(address)
+                }
+                "Compact<Balance>" => {
+                    let balance = self.arg("u64");
+                    self.register(type_of(TypeDefCompact::<PortableForm>::new(balance.into())))
+                }
+                other => panic!("No runtime type for {}", other),
+            }
+        }
+    }
+
+    fn path_of(path: &str) -> Path<PortableForm> {
+        Path::from_segments_unchecked(path.split("::").map(String::from).collect::<Vec<_>>())
+    }
+
+    // A few of Subtensor's errors. Tests refer to them by name, so their indices don't matter
+    const RUNTIME_ERRORS: &[&str] = &[
+        "HotKeyNotRegisteredInSubNet",
+        "NotEnoughBalanceToStake",
+        "HotKeyAlreadyRegisteredInSubNet",
+        "TooManyRegistrationsThisBlock",
+        "SubNetRegistrationDisabled",
+    ];
+
+    // System::Events as Vec<EventRecord<RuntimeEvent, H256>>, and the event and error enums
+    // of System and SubtensorModule
+    struct RuntimeEvents {
+        events: u32,
+        system_event: u32,
+        system_error: u32,
+        subtensor_event: u32,
+        subtensor_error: u32,
+    }
+
+    fn runtime_events(types: &mut RuntimeTypes) -> RuntimeEvents {
+        let byte = types.arg("u8");
+        let uid = types.arg("u16");
+        let word = types.arg("u32");
+        let netuid = types.arg("NetUid");
+        let account = types.arg("AccountId");
+        let hash = types.arg("H256");
+        let gas = types.arg("u64");
+        let gas = types.register(type_of(TypeDefCompact::<PortableForm>::new(gas.into())));
+
+        let weight = types.structure(
+            "sp_weights::weight_v2::Weight",
+            &[("ref_time", gas), ("proof_size", gas)],
+        );
+        let class = types.enumeration(
+            "frame_support::dispatch::DispatchClass",
+            &[
+                ("Normal", 0, &[]),
+                ("Operational", 1, &[]),
+                ("Mandatory", 2, &[]),
+            ],
+        );
+        let pays = types.enumeration(
+            "frame_support::dispatch::Pays",
+            &[("Yes", 0, &[]), ("No", 1, &[])],
+        );
+        let dispatch_info = types.structure(
+            "frame_support::dispatch::DispatchInfo",
+            &[("weight", weight), ("class", class), ("pays_fee", pays)],
+        );
+        let error_bytes =
+            types.register(type_of(TypeDefArray::<PortableForm>::new(4, byte.into())));
+        let module_error = types.structure(
+            "sp_runtime::ModuleError",
+            &[("index", byte), ("error", error_bytes)],
+        );
+        let token_error = types.enumeration(
+            "sp_runtime::TokenError",
+            &[
+                ("FundsUnavailable", 0, &[]),
+                ("OnlyProvider", 1, &[]),
+                ("BelowMinimum", 2, &[]),
+                ("CannotCreate", 3, &[]),
+            ],
+        );
+        let dispatch_error = types.enumeration(
+            "sp_runtime::DispatchError",
+            &[
+                ("Other", 0, &[]),
+                ("CannotLookup", 1, &[]),
+                ("BadOrigin", 2, &[]),
+                ("Module", 3, &[(None, module_error)]),
+                ("ConsumerRemaining", 4, &[]),
+                ("NoProviders", 5, &[]),
+                ("TooManyConsumers", 6, &[]),
+                ("Token", 7, &[(None, token_error)]),
+            ],
+        );
+
+        let system_event = types.enumeration(
+            "frame_system::pallet::Event",
+            &[
+                (
+                    "ExtrinsicSuccess",
+                    0,
+                    &[(Some("dispatch_info"), dispatch_info)],
+                ),
+                (
+                    "ExtrinsicFailed",
+                    1,
+                    &[
+                        (Some("dispatch_error"), dispatch_error),
+                        (Some("dispatch_info"), dispatch_info),
+                    ],
+                ),
+                ("CodeUpdated", 2, &[]),
+            ],
+        );
+        let system_error = types.enumeration(
+            "frame_system::pallet::Error",
+            &[("InvalidSpecName", 0, &[]), ("NonDefaultComposite", 1, &[])],
+        );
+        let subtensor_event = types.enumeration(
+            "pallet_subtensor::pallet::Event",
+            &[
+                ("NetworkAdded", 0, &[(None, netuid), (None, uid)]),
+                (
+                    "StakeAdded",
+                    2,
+                    &[(None, account), (None, account), (None, gas)],
+                ),
+                (
+                    "NeuronRegistered",
+                    6,
+                    &[(None, netuid), (None, uid), (None, account)],
+                ),
+            ],
+        );
+        let errors: Vec<VariantDef> = RUNTIME_ERRORS
+            .iter()
+            .enumerate()
+            .map(|(index, name)| (*name, index as u8, &[][..]))
+            .collect();
+        let subtensor_error = types.enumeration("pallet_subtensor::pallet::Error", &errors);
+
+        let event = types.enumeration(
+            "node_subtensor_runtime::RuntimeEvent",
+            &[
+                ("System", 0, &[(None, system_event)]),
+                ("SubtensorModule", 7, &[(None, subtensor_event)]),
+            ],
+        );
+        let phase = types.enumeration(
+            "frame_system::Phase",
+            &[
+                ("ApplyExtrinsic", 0, &[(None, word)]),
+                ("Finalization", 1, &[]),
+                ("Initialization", 2, &[]),
+            ],
+        );
+        let topics = types.vec_of(hash);
+        let record = types.structure(
+            "frame_system::EventRecord",
+            &[("phase", phase), ("event", event), ("topics", topics)],
+        );
+        RuntimeEvents {
+            events: types.vec_of(record),
+            system_event,
+            system_error,
+            subtensor_event,
+            subtensor_error,
+        }
+    }
+
+    pub(super) fn runtime_metadata() -> RuntimeMetadataV14 {
+        let mut types = RuntimeTypes(PortableRegistryBuilder::new());
+        let unit = types.register(type_of(TypeDefTuple::new_portable(Vec::new())));
+        let word = types.arg("u32");
+        let events = runtime_events(&mut types);
+
+        let storage: Vec<(&str, &str, Vec<Hasher>)> = RUNTIME_STORAGE
+            .iter()
+            .map(|(pallet, name, hashers)| (*pallet, *name, hashers.to_vec()))
+            .chain(
+                SUBNET_INFO_STORAGE
+                    .iter()
+                    .chain(NETUID_STORAGE)
+                    .map(|name| ("SubtensorModule", *name, vec![Hasher::Identity])),
+            )
+            .collect();
+        let pallet_names = RUNTIME_CALLS
+            .iter()
+            .map(|(pallet, index, ..)| (*pallet, *index))
+            .chain([("System", 0)])
+            .chain(storage.iter().map(|(pallet, ..)| (*pallet, 200)));
+        let mut pallets: Vec<PalletMetadata> = Vec::new();
+        for (name, index) in pallet_names {
+            if pallets.iter().all(|pallet| pallet.name != name) {
+                pallets.push(PalletMetadata {
+                    name: name.to_string(),
+                    storage: None,
+                    calls: None,
+                    event: None,
+                    constants: Vec::new(),
+                    error: None,
+                    index,
+                });
+            }
+        }
+        for pallet in &mut pallets {
+            let mut variants = Variants::<PortableForm>::new();
+            for (pallet_name, _, call, index, args) in RUNTIME_CALLS {
+                if *pallet_name != pallet.name {
+                    continue;
+                }
+                let arg_types: Vec<u32> = args.iter().map(|arg| types.arg(arg)).collect();
+                variants = variants.variant(call.to_string(), |v| {
+                    let fields =
+                        args.iter()
+                            .zip(&arg_types)
+                            .fold(Fields::unnamed(), |fields, (arg, ty)| {
+                                fields.field_portable(|f| f.ty(*ty).type_name(arg.to_string()))
+                            });
+                    v.index(*index).fields(fields)
+                });
+            }
+            let calls = Type::builder_portable()
+                .path(Path::from_segments_unchecked(vec![format!(
+                    "{}Call",
+                    pallet.name
+                )]))
+                .variant(variants);
+            pallet.calls = Some(Compact(types.register(calls)));
+
+            let entries: Vec<StorageEntryMetadata> = storage
+                .iter()
+                .filter(|(pallet_name, ..)| *pallet_name == pallet.name)
+                .map(|(_, name, hashers)| StorageEntryMetadata {
+                    name: name.to_string(),
+                    modifier: 0,
+                    ty: if *name == "Events" {
+                        StorageEntryType::Plain(Compact(events.events))
+                    } else if hashers.is_empty() {
+                        StorageEntryType::Plain(Compact(word))
+                    } else {
+                        StorageEntryType::Map {
+                            hashers: hashers.clone(),
+                            key: Compact(word),
+                            value: Compact(word),
+                        }
+                    },
+                    default: Vec::new(),
+                    docs: Vec::new(),
+                })
+                .collect();
+            pallet.storage = Some(PalletStorageMetadata {
+                prefix: pallet.name.clone(),
+                entries,
+            });
+
+            let (event, error) = match pallet.name.as_str() {
+                "System" => (events.system_event, events.system_error),
+                "SubtensorModule" => (events.subtensor_event, events.subtensor_error),
+                _ => continue,
+            };
+            pallet.event = Some(Compact(event));
+            pallet.error = Some(Compact(error));
+        }
+
+        // Extensions with neither extra nor implicit data are left out of the payload
+        let extension = |identifier: &str, ty: u32| SignedExtensionMetadata {
+            identifier: identifier.to_string(),
+            ty: Compact(ty),
+            additional_signed: Compact(ty),
+        };
+        let signed_extensions = vec![
+            extension("CheckNonZeroSender", unit),
+            extension("CheckSpecVersion", word),
+            extension("CheckTxVersion", word),
+            extension("CheckGenesis", word),
+            extension("CheckMortality", word),
+            extension("CheckNonce", word),
+            extension("CheckWeight", unit),
+            extension("ChargeTransactionPayment", word),
+            extension("CheckMetadataHash", word),
+        ];
+
+        RuntimeMetadataV14 {
+            types: types.0.finish(),
+            pallets,
+            extrinsic: ExtrinsicMetadata {
+                ty: Compact(unit),
+                version: 4,
+                signed_extensions,
+            },
+            ty: Compact(unit),
+        }
+    }
+
+    pub(super) fn prefixed(metadata: &RuntimeMetadataV14) -> Vec<u8> {
+        (METADATA_MAGIC, METADATA_VERSION, metadata).encode()
+    }
+
+    // Encoded the way state_getMetadata serves it
+    pub fn metadata_bytes() -> Vec<u8> {
+        prefixed(&runtime_metadata())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::runtime::{prefixed, runtime_metadata};
+    use super::*;
+
+    fn subtensor(runtime: &mut RuntimeMetadataV14) -> &mut PalletMetadata {
+        runtime
+            .pallets
+            .iter_mut()
+            .find(|pallet| pallet.name == "SubtensorModule")
+            .unwrap()
+    }
+
+    #[test]
+    fn test_metadata_drift() {
+        let capabilities = Capabilities::current();
+        let mut runtime = runtime_metadata();
+        let metadata = Metadata::decode(&prefixed(&runtime)).unwrap();
+        assert_eq!(metadata.drift(&capabilities), Vec::<String>::new());
+        assert_eq!(
+            metadata.call_index("SubtensorModule", "burned_register"),
+            Some((7, 7))
+        );
+        assert_eq!(
+            metadata.call_args("SubtensorModule", "burned_register"),
+            Some(vec!["u16".to_string(), "[u8; 32]".to_string()])
+        );
+        assert_eq!(
+            metadata.call_args("Balances", "transfer_keep_alive"),
+            Some(vec!["MultiAddress".to_string(), "Compact<u64>".to_string()])
+        );
+        assert_eq!(metadata.storage_hashers("Timestamp", "Now"), Some(vec![]));
+
+        // A moved pallet, a rehashed map and an extension with a payload all show up
+        subtensor(&mut runtime).index += 1;
+        let uids = subtensor(&mut runtime)
+            .storage
+            .as_mut()
+            .unwrap()
+            .entries
+            .iter_mut()
+            .find(|entry| entry.name == "Uids")
+            .unwrap();
+        uids.ty = StorageEntryType::Map {
+            hashers: vec![Hasher::Identity, Hasher::Twox64Concat],
+            key: Compact(0),
+            value: Compact(0),
+        };
+        let word = runtime.extrinsic.signed_extensions[1].ty;
+        runtime.extrinsic.signed_extensions[0].ty = word;
+        let drift = Metadata::decode(&prefixed(&runtime))
+            .unwrap()
+            .drift(&capabilities);
+        assert!(drift
+            .iter()
+            .any(|d| d.starts_with("Call SubtensorModule.burned_register is at 8:7")));
+        assert!(drift
+            .iter()
+            .any(|d| d.starts_with("Storage SubtensorModule.Uids is keyed")));
+        assert!(drift.iter().any(|d| d.contains("CheckNonZeroSender")));
+
+        // The pre-dTAO add_stake(hotkey, amount) no longer matches
+        let mut capabilities = Capabilities::current();
+        capabilities.calls[2].args = vec!["[u8; 32]", "u64"];
+        let drift = metadata.drift(&capabilities);
+        assert_eq!(
+            drift,
+            vec!["Call SubtensorModule.add_stake takes ([u8; 32], u16, u64), this tool encodes ([u8; 32], u64)"]
+        );
+
+        assert!(Metadata::decode(b"junk").is_err());
+        assert!(Metadata::decode(&(METADATA_MAGIC, 15u8).encode()).is_err());
+    }
+}
@@ -0,0 +1,107 @@
+//! Networks selectable with --network: their endpoints, the chain each must be, and how
+//! careful to be with confirmations on it
+use anyhow::{anyhow, Result};
+use primitive_types::H256;
+use std::{fmt, str::FromStr, sync::OnceLock};
+
+use crate::constants;
+
+static NETWORK: OnceLock<Network> = OnceLock::new();
+
+const FINNEY_GENESIS: &str = "2f0555cc76fc2840a25a6ea3b9637146806f1f44b090c175ffde2a7e5ab36c03";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Finney,
+    Test,
+    Local, // A subtensor node started on this machine, e.g. localnet.sh
+}
+
+impl Network {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Network::Finney => "finney",
+            Network::Test => "test",
+            Network::Local => "local",
+        }
+    }
+
+    pub fn endpoint(&self) -> &'static str {
+        match self {
+            Network::Finney => constants::DEFAULT_RPC_ENDPOINTS[0],
+            Network::Test => "wss://test.finney.opentensor.ai:443",
+            Network::Local => "ws://127.0.0.1:9944",
+        }
+    }
+
+    // Only finney TAO is worth anything, elsewhere prompts are skipped and the faucet works
+    pub fn is_mainnet(&self) -> bool {
+        *self == Network::Finney
+    }
+
+    // Catch an endpoint serving a different chain than asked for, above all mainnet when a
+    // test network was meant
+    pub fn check_genesis(&self, genesis_hash: H256) -> Result<()> {
+        let is_finney = hex::encode(genesis_hash.as_bytes()) == FINNEY_GENESIS;
+        if self.is_mainnet() != is_finney {
+            return Err(anyhow!(
+                "The endpoint serves {} (genesis {:?}), not the {} network. Check --rpc-url",
+                if is_finney { "finney" } else { "another chain" },
+                genesis_hash,
+                self
+            ));
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Network {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Network {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "finney" | "main" | "mainnet" => Ok(Network::Finney),
+            "test" | "testnet" => Ok(Network::Test),
+            "local" | "localnet" => Ok(Network::Local),
+            other => Err(anyhow!(
+                "Unknown network: {}. Use finney, test or local",
+                other
+            )),
+        }
+    }
+}
+
+pub fn set_current(network: Network) {
+    let _ = NETWORK.set(network);
+}
+
+// None when only an endpoint was given, nothing is assumed about its chain then
+pub fn current() -> Option<Network> {
+    NETWORK.get().copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_genesis_check() {
+        let finney = H256::from_str(FINNEY_GENESIS).unwrap();
+        let other = H256::repeat_byte(0x11);
+
+        assert!(Network::Finney.check_genesis(finney).is_ok());
+        assert!(Network::Finney.check_genesis(other).is_err());
+        // A test network that turns out to be mainnet must never go unnoticed
+        assert!(Network::Test.check_genesis(finney).is_err());
+        assert!(Network::Local.check_genesis(other).is_ok());
+
+        assert_eq!("testnet".parse::<Network>().unwrap(), Network::Test);
+        assert!("devnet".parse::<Network>().is_err());
+    }
+}
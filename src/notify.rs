@@ -0,0 +1,175 @@
+//! Alert delivery for long-running watchers (console, webhook, Telegram, Discord)
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use colored::*;
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    time::{Duration, Instant},
+};
+
+use crate::http;
+
+#[async_trait]
+#[allow(clippy::double_must_use)]
+pub trait Notifier: Send + Sync {
+    fn name(&self) -> &'static str;
+    async fn send(&self, title: &str, message: &str) -> Result<()>;
+}
+
+pub struct ConsoleNotifier;
+
+#[async_trait]
+impl Notifier for ConsoleNotifier {
+    fn name(&self) -> &'static str {
+        "console"
+    }
+
+    async fn send(&self, title: &str, message: &str) -> Result<()> {
+        println!(
+            "{}",
+            format!("🔔 {}: {}", title, message).bright_yellow().bold()
+        );
+        Ok(())
+    }
+}
+
+// Generic JSON webhook: {"title", "message", "timestamp"}
+pub struct WebhookNotifier {
+    pub url: String,
+}
+
+#[async_trait]
+impl Notifier for WebhookNotifier {
+    fn name(&self) -> &'static str {
+        "webhook"
+    }
+
+    async fn send(&self, title: &str, message: &str) -> Result<()> {
+        http::post_json(
+            &self.url,
+            &json!({
+                "title": title,
+                "message": message,
+                "timestamp": Utc::now().to_rfc3339(),
+            }),
+        )
+        .await
+    }
+}
+
+pub struct TelegramNotifier {
+    pub bot_token: String,
+    pub chat_id: String,
+}
+
+#[async_trait]
+impl Notifier for TelegramNotifier {
+    fn name(&self) -> &'static str {
+        "telegram"
+    }
+
+    async fn send(&self, title: &str, message: &str) -> Result<()> {
+        http::post_json(
+            &format!("https://api.telegram.org/bot{}/sendMessage", self.bot_token),
+            &json!({
+                "chat_id": self.chat_id,
+                "text": format!("{}\n{}", title, message),
+            }),
+        )
+        .await
+    }
+}
+
+pub struct DiscordNotifier {
+    pub webhook_url: String,
+}
+
+#[async_trait]
+impl Notifier for DiscordNotifier {
+    fn name(&self) -> &'static str {
+        "discord"
+    }
+
+    async fn send(&self, title: &str, message: &str) -> Result<()> {
+        http::post_json(
+            &self.webhook_url,
+            &json!({ "content": format!("**{}**\n{}", title, message) }),
+        )
+        .await
+    }
+}
+
+// Allows each alert key through at most once per cooldown
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    cooldown: Duration,
+    last_sent: HashMap<String, Instant>,
+}
+
+impl RateLimiter {
+    pub fn new(cooldown: Duration) -> Self {
+        Self {
+            cooldown,
+            last_sent: HashMap::new(),
+        }
+    }
+
+    pub fn allow(&mut self, key: &str, now: Instant) -> bool {
+        match self.last_sent.get(key) {
+            Some(last) if now.duration_since(*last) < self.cooldown => false,
+            _ => {
+                self.last_sent.insert(key.to_string(), now);
+                true
+            }
+        }
+    }
+}
+
+pub struct Notifications {
+    channels: Vec<Box<dyn Notifier>>,
+    limiter: RateLimiter,
+}
+
+impl Notifications {
+    pub fn new(channels: Vec<Box<dyn Notifier>>, cooldown: Duration) -> Self {
+        Self {
+            channels,
+            limiter: RateLimiter::new(cooldown),
+        }
+    }
+
+    pub fn channel_names(&self) -> Vec<&'static str> {
+        self.channels.iter().map(|c| c.name()).collect()
+    }
+
+    // Deliver an alert to every channel unless the same alert fired recently
+    pub async fn notify(&mut self, key: &str, title: &str, message: &str) {
+        if !self.limiter.allow(key, Instant::now()) {
+            return;
+        }
+
+        for channel in &self.channels {
+            if let Err(e) = channel.send(title, message).await {
+                println!("⚠️ Failed to send {} notification: {}", channel.name(), e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter() {
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(limiter.allow("burn:1", start));
+        assert!(!limiter.allow("burn:1", start + Duration::from_secs(30)));
+        assert!(limiter.allow("burn:3", start + Duration::from_secs(30)));
+        assert!(limiter.allow("burn:1", start + Duration::from_secs(61)));
+    }
+}
@@ -0,0 +1,199 @@
+//! Offline signing: transactions built online, signed on an air-gapped machine, submitted online
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use primitive_types::H256;
+use qrcode::{render::unicode, QrCode};
+use serde::{Deserialize, Serialize};
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::str::FromStr;
+
+use crate::client::{self, SigningContext};
+use crate::era::Era;
+use crate::errors::{ErrorKind, KindContext};
+use crate::key_utils::{self, expand_home};
+
+// Everything the offline signer needs, written by BuildTx and read by SignTx
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UnsignedTx {
+    pub description: String,
+    pub signer: String, // SS58 account that has to sign
+    pub call: String,   // 0x-prefixed SCALE call data
+    pub nonce: u64,
+    pub block_number: u64,
+    pub era: Era,
+    #[serde(default)]
+    pub tip: u64, // RAO
+    pub valid_until_block: Option<u64>, // Where the era ends, None for immortal transactions
+    pub spec_version: u32,
+    pub transaction_version: u32,
+    pub genesis_hash: String,
+    pub era_block_hash: String,
+    pub signing_payload: String, // What gets signed, checked again before signing
+}
+
+impl UnsignedTx {
+    pub fn new(
+        description: impl Into<String>,
+        signer: &AccountId32,
+        call: &[u8],
+        context: &SigningContext,
+    ) -> Self {
+        Self {
+            description: description.into(),
+            signer: signer.to_ss58check(),
+            call: format!("0x{}", hex::encode(call)),
+            nonce: context.nonce,
+            block_number: context.block_number,
+            era: context.era,
+            tip: context.tip,
+            valid_until_block: context.era.death(context.block_number),
+            spec_version: context.spec_version,
+            transaction_version: context.transaction_version,
+            genesis_hash: format!("{:?}", context.genesis_hash),
+            era_block_hash: format!("{:?}", context.era_block_hash),
+            signing_payload: format!("0x{}", hex::encode(client::signing_payload(call, context))),
+        }
+    }
+
+    pub fn context(&self) -> Result<SigningContext> {
+        let hash = |value: &str| {
+            H256::from_str(value.trim_start_matches("0x"))
+                .map_err(|e| anyhow!("Invalid block hash {}: {}", value, e))
+        };
+        Ok(SigningContext {
+            nonce: self.nonce,
+            block_number: self.block_number,
+            spec_version: self.spec_version,
+            transaction_version: self.transaction_version,
+            genesis_hash: hash(&self.genesis_hash)?,
+            era: self.era,
+            era_block_hash: hash(&self.era_block_hash)?,
+            tip: self.tip,
+        })
+    }
+
+    // Signed extrinsic, refusing a different key or a payload that no longer matches the call
+    pub fn sign(&self, signer: &key_utils::KeyPair) -> Result<Vec<u8>> {
+        let expected = AccountId32::from_ss58check(&self.signer)
+            .map_err(|_| anyhow!("Invalid signer address {}", self.signer))?;
+        if signer.account_id() != expected {
+            return Err(anyhow!(
+                "Transaction must be signed by {}, the loaded key is {}",
+                self.signer,
+                signer.account_id().to_ss58check()
+            ));
+        }
+
+        let call = decode_hex(&self.call)?;
+        let context = self.context()?;
+        let payload = client::signing_payload(&call, &context);
+        if decode_hex(&self.signing_payload)? != payload {
+            return Err(anyhow!(
+                "Signing payload does not match the call and context, the file was modified"
+            ));
+        }
+
+        client::sign_extrinsic(&call, signer, &context)
+    }
+}
+
+pub fn decode_hex(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim().trim_start_matches("0x")).context("Invalid hex data")
+}
+
+// Terminal QR code for moving data across an air gap with a camera
+pub fn render_qr(data: &str) -> Result<String> {
+    let code = QrCode::new(data.as_bytes()).context("Data does not fit in a QR code")?;
+    Ok(code.render::<unicode::Dense1x2>().quiet_zone(true).build())
+}
+
+// Write to a file or stdout, with an optional QR code on the terminal
+pub fn write_output(contents: &str, output: Option<&str>, qr: bool) -> Result<()> {
+    match output {
+        Some(path) => {
+            std::fs::write(expand_home(path), contents)
+                .with_context(|| format!("Failed to write {}", path))?;
+            println!("💾 Written to {}", path);
+        }
+        None => println!("{}", contents),
+    }
+    if qr {
+        println!("{}", render_qr(contents)?);
+    }
+    Ok(())
+}
+
+// Hex extrinsic given directly or in a file
+pub fn read_extrinsic(input: &str) -> Result<Vec<u8>> {
+    let path = expand_home(input);
+    let hex = if path.is_file() {
+        std::fs::read_to_string(&path).with_context(|| format!("Failed to read {}", input))?
+    } else {
+        input.to_string()
+    };
+    decode_hex(&hex)
+}
+
+// SignTx: runs without any network access
+pub fn sign_tx(input: &str, wallet_path: &str, output: Option<&str>, qr: bool) -> Result<()> {
+    println!("{}", "✍️ Signing Offline".bright_cyan().bold());
+    println!("═══════════════════════════════════════");
+
+    let contents = std::fs::read_to_string(expand_home(input))
+        .with_context(|| format!("Failed to read {}", input))?;
+    let unsigned: UnsignedTx = serde_json::from_str(&contents)
+        .with_context(|| format!("Invalid unsigned transaction {}", input))?;
+
+    println!("   Transaction: {}", unsigned.description);
+    println!("   Signer: {}", unsigned.signer);
+    match unsigned.valid_until_block {
+        Some(block) => println!("   Valid until block: {} (submit before then)", block),
+        None => println!("   Valid until: never (immortal)"),
+    }
+
+    let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+        .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+    let extrinsic = unsigned.sign(&coldkey_pair)?;
+
+    println!("✅ Signed, submit it online with SubmitTx");
+    write_output(&format!("0x{}", hex::encode(extrinsic)), output, qr)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::key_utils::{KeyPair, KeyScheme};
+
+    #[test]
+    fn test_offline_signing_roundtrip() {
+        let alice = KeyPair::from_string("//Alice", KeyScheme::Sr25519).unwrap();
+        let context = SigningContext {
+            nonce: 1,
+            block_number: 100,
+            spec_version: 263,
+            transaction_version: 1,
+            genesis_hash: H256::repeat_byte(0x11),
+            era: Era::mortal(64, 100),
+            era_block_hash: H256::repeat_byte(0x22),
+            tip: 0,
+        };
+        let call = vec![7, 1, 2, 3];
+        let unsigned = UnsignedTx::new("test", &alice.account_id(), &call, &context);
+        assert_eq!(unsigned.valid_until_block, Some(164));
+
+        // Survives the trip through a file
+        let unsigned: UnsignedTx =
+            serde_json::from_str(&serde_json::to_string(&unsigned).unwrap()).unwrap();
+        let extrinsic = unsigned.sign(&alice).unwrap();
+        assert!(extrinsic.ends_with(&call));
+
+        let bob = KeyPair::from_string("//Bob", KeyScheme::Sr25519).unwrap();
+        assert!(unsigned.sign(&bob).is_err());
+
+        let mut tampered = unsigned.clone();
+        tampered.call = "0x07010204".into();
+        assert!(tampered.sign(&alice).is_err());
+
+        assert!(render_qr(&unsigned.call).unwrap().contains('█'));
+    }
+}
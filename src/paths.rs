@@ -0,0 +1,96 @@
+//! Platform-aware file locations (config, wallets, cache, data) with flag/env overrides
+use directories::{BaseDirs, ProjectDirs};
+use std::{path::PathBuf, sync::OnceLock};
+
+const APP_NAME: &str = "bittensor-quick-register";
+
+// Pre-existing per-user directory, still honored when present
+const LEGACY_DIR: &str = ".bittensor-quick-register";
+
+static CACHE_DIR: OnceLock<PathBuf> = OnceLock::new();
+static DATA_DIR: OnceLock<PathBuf> = OnceLock::new();
+
+// None in scratch containers without a home directory
+pub fn home_dir() -> Option<PathBuf> {
+    BaseDirs::new().map(|dirs| dirs.home_dir().to_path_buf())
+}
+
+// Expand a leading "~/" (or "~\" on Windows) to the user's home directory
+pub fn expand_home(path: &str) -> PathBuf {
+    let rest = path.strip_prefix("~/").or_else(|| path.strip_prefix("~\\"));
+    match (rest, home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", APP_NAME)
+}
+
+fn legacy_dir() -> Option<PathBuf> {
+    home_dir()
+        .map(|home| home.join(LEGACY_DIR))
+        .filter(|dir| dir.is_dir())
+}
+
+// Without a home directory everything lives under the working directory
+fn fallback_dir() -> PathBuf {
+    PathBuf::from(LEGACY_DIR)
+}
+
+pub fn default_config_file() -> PathBuf {
+    legacy_dir()
+        .or_else(|| project_dirs().map(|dirs| dirs.config_dir().to_path_buf()))
+        .unwrap_or_else(fallback_dir)
+        .join("config.toml")
+}
+
+// btcli keeps wallets under ~/.bittensor/wallets on every platform
+pub fn default_wallets_dir() -> PathBuf {
+    home_dir()
+        .unwrap_or_default()
+        .join(".bittensor")
+        .join("wallets")
+}
+
+pub fn set_cache_dir(path: &str) {
+    let _ = CACHE_DIR.set(expand_home(path));
+}
+
+pub fn cache_dir() -> PathBuf {
+    CACHE_DIR.get().cloned().unwrap_or_else(|| {
+        legacy_dir()
+            .map(|dir| dir.join("cache"))
+            .or_else(|| project_dirs().map(|dirs| dirs.cache_dir().to_path_buf()))
+            .unwrap_or_else(|| fallback_dir().join("cache"))
+    })
+}
+
+pub fn set_data_dir(path: &str) {
+    let _ = DATA_DIR.set(expand_home(path));
+}
+
+// Receipts, history and other state worth keeping across runs
+pub fn data_dir() -> PathBuf {
+    DATA_DIR.get().cloned().unwrap_or_else(|| {
+        legacy_dir()
+            .or_else(|| project_dirs().map(|dirs| dirs.data_dir().to_path_buf()))
+            .unwrap_or_else(fallback_dir)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand_home() {
+        assert_eq!(expand_home("/tmp/wallet"), PathBuf::from("/tmp/wallet"));
+        assert_eq!(expand_home("wallet"), PathBuf::from("wallet"));
+        if let Some(home) = home_dir() {
+            assert_eq!(expand_home("~/wallets"), home.join("wallets"));
+        }
+        assert!(default_config_file().ends_with("config.toml"));
+    }
+}
@@ -0,0 +1,100 @@
+//! TAO/USD price oracles used for cost estimates
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+
+use crate::config::PriceOracleConfig;
+use crate::http;
+
+const COINGECKO_URL: &str =
+    "https://api.coingecko.com/api/v3/simple/price?ids=bittensor&vs_currencies=usd";
+
+#[async_trait]
+#[allow(clippy::double_must_use)]
+pub trait PriceOracle: Send + Sync {
+    fn name(&self) -> String;
+    async fn tao_usd(&self) -> Result<f64>;
+}
+
+pub struct CoinGeckoOracle;
+
+#[async_trait]
+impl PriceOracle for CoinGeckoOracle {
+    fn name(&self) -> String {
+        "CoinGecko".to_string()
+    }
+
+    async fn tao_usd(&self) -> Result<f64> {
+        let response = http::get_json(COINGECKO_URL).await?;
+        price_at(&response, "/bittensor/usd")
+    }
+}
+
+pub struct FixedPriceOracle {
+    pub usd: f64,
+}
+
+#[async_trait]
+impl PriceOracle for FixedPriceOracle {
+    fn name(&self) -> String {
+        "fixed price".to_string()
+    }
+
+    async fn tao_usd(&self) -> Result<f64> {
+        Ok(self.usd)
+    }
+}
+
+// Any JSON endpoint, e.g. an internal pricing service
+pub struct HttpPriceOracle {
+    pub url: String,
+    pub pointer: String,
+}
+
+#[async_trait]
+impl PriceOracle for HttpPriceOracle {
+    fn name(&self) -> String {
+        self.url.clone()
+    }
+
+    async fn tao_usd(&self) -> Result<f64> {
+        let response = http::get_json(&self.url).await?;
+        price_at(&response, &self.pointer)
+    }
+}
+
+pub fn from_config(config: &PriceOracleConfig) -> Box<dyn PriceOracle> {
+    match config {
+        PriceOracleConfig::Coingecko => Box::new(CoinGeckoOracle),
+        PriceOracleConfig::Fixed { usd } => Box::new(FixedPriceOracle { usd: *usd }),
+        PriceOracleConfig::Http { url, pointer } => Box::new(HttpPriceOracle {
+            url: url.clone(),
+            pointer: pointer.clone(),
+        }),
+    }
+}
+
+// Read a price that may be a JSON number or a numeric string
+fn price_at(response: &serde_json::Value, pointer: &str) -> Result<f64> {
+    let value = response
+        .pointer(pointer)
+        .ok_or_else(|| anyhow!("No price at {} in response", pointer))?;
+
+    value
+        .as_f64()
+        .or_else(|| value.as_str().and_then(|s| s.parse().ok()))
+        .filter(|price| *price > 0.0)
+        .ok_or_else(|| anyhow!("Invalid price at {}: {}", pointer, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_at() {
+        let response = serde_json::json!({"bittensor": {"usd": 412.5}, "data": {"price": "399.1"}});
+        assert_eq!(price_at(&response, "/bittensor/usd").unwrap(), 412.5);
+        assert_eq!(price_at(&response, "/data/price").unwrap(), 399.1);
+        assert!(price_at(&response, "/missing").is_err());
+    }
+}
@@ -0,0 +1,179 @@
+//! Progress bars and spinners for the long stretches: blocks waited, verification attempts,
+//! proof of work and batch items. Drawn only on an interactive terminal without --plain,
+//! otherwise nothing is added and the lines they stand in for are printed as before.
+//! Batch items print as they run, so a bar kept at the bottom would be torn by their output;
+//! theirs is printed as a line after each item instead
+use indicatif::{ProgressBar, ProgressStyle};
+use std::{io::IsTerminal, sync::OnceLock, time::Duration};
+
+use crate::utils;
+
+static PLAIN: OnceLock<bool> = OnceLock::new();
+
+// Never draw, for logs and scripts (--plain, machine-readable output)
+pub fn set_plain(plain: bool) {
+    let _ = PLAIN.set(plain);
+}
+
+fn interactive() -> bool {
+    !PLAIN.get().copied().unwrap_or(false)
+        && std::io::stdout().is_terminal()
+        && std::io::stderr().is_terminal()
+}
+
+const BAR_TEMPLATE: &str = "{spinner} {msg} [{bar:30}] {pos}/{len} ({eta} left)";
+const SPINNER_TEMPLATE: &str = "{spinner} {msg} ({elapsed})";
+const BAR_WIDTH: u64 = 30;
+
+enum Display {
+    Plain,
+    Drawn(ProgressBar),
+    // Counted without drawing, rendered as a line on each step
+    Lines(ProgressBar),
+}
+
+// A bar or spinner, or nothing at all when output is plain. Cleared when dropped
+pub struct Progress {
+    display: Display,
+}
+
+impl Progress {
+    // Counts up to len, with the ETA its rate so far gives
+    pub fn bar(len: u64, message: impl Into<String>) -> Self {
+        Self::drawn(ProgressBar::new(len), BAR_TEMPLATE, message.into())
+    }
+
+    // For waits without a known length
+    pub fn spinner(message: impl Into<String>) -> Self {
+        Self::drawn(ProgressBar::new_spinner(), SPINNER_TEMPLATE, message.into())
+    }
+
+    // Items done out of len, for work that prints as it goes
+    pub fn items(len: u64, message: impl Into<String>) -> Self {
+        if !interactive() {
+            return Self {
+                display: Display::Plain,
+            };
+        }
+        let bar = ProgressBar::hidden();
+        bar.set_length(len);
+        bar.set_message(message.into());
+        Self {
+            display: Display::Lines(bar),
+        }
+    }
+
+    fn drawn(bar: ProgressBar, template: &str, message: String) -> Self {
+        if !interactive() {
+            return Self {
+                display: Display::Plain,
+            };
+        }
+        bar.set_style(
+            ProgressStyle::with_template(template)
+                .expect("progress templates are valid")
+                .progress_chars("=> "),
+        );
+        bar.set_message(message);
+        bar.enable_steady_tick(Duration::from_millis(120));
+        Self {
+            display: Display::Drawn(bar),
+        }
+    }
+
+    pub fn is_drawn(&self) -> bool {
+        !matches!(self.display, Display::Plain)
+    }
+
+    pub fn set_message(&self, message: impl Into<String>) {
+        if let Display::Drawn(bar) = &self.display {
+            bar.set_message(message.into());
+        }
+    }
+
+    pub fn inc(&self, delta: u64) {
+        match &self.display {
+            Display::Plain => {}
+            Display::Drawn(bar) => bar.inc(delta),
+            Display::Lines(bar) => {
+                bar.inc(delta);
+                println!(
+                    "📊 {}",
+                    render_line(&bar.message(), bar.position(), bar.length(), bar.eta())
+                );
+            }
+        }
+    }
+
+    // Printed above the bar while it is drawn, as a plain line otherwise
+    pub fn println(&self, line: impl AsRef<str>) {
+        match &self.display {
+            Display::Drawn(bar) => bar.println(line.as_ref()),
+            _ => println!("{}", line.as_ref()),
+        }
+    }
+
+    // Only printed when nothing is drawn, the bar already shows it
+    pub fn plain_println(&self, line: impl AsRef<str>) {
+        if !self.is_drawn() {
+            println!("{}", line.as_ref());
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if let Display::Drawn(bar) = &self.display {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+// "Registering [=========>          ] 3/10 (~2m 10s left)"
+fn render_line(message: &str, position: u64, len: Option<u64>, eta: Duration) -> String {
+    let len = len.unwrap_or(position).max(1);
+    let filled = (position.min(len) * BAR_WIDTH / len) as usize;
+    let mut bar = "=".repeat(filled);
+    if filled < BAR_WIDTH as usize {
+        bar.push('>');
+    }
+    let left = if position >= len {
+        "done".to_string()
+    } else {
+        format!("{} left", utils::format_duration(eta))
+    };
+    format!(
+        "{} [{:<width$}] {}/{} ({})",
+        message,
+        bar,
+        position,
+        len,
+        left,
+        width = BAR_WIDTH as usize
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_lines() {
+        assert_eq!(
+            render_line("Registering", 3, Some(10), Duration::from_secs(130)),
+            format!("Registering [{:<30}] 3/10 (~2m 10s left)", "=========>")
+        );
+        assert_eq!(
+            render_line("Batch", 4, Some(4), Duration::ZERO),
+            format!("Batch [{}] 4/4 (done)", "=".repeat(30))
+        );
+
+        // Built directly, --plain is process-wide and would stick for every other test
+        let progress = Progress {
+            display: Display::Plain,
+        };
+        assert!(!progress.is_drawn());
+        progress.set_message("Waiting");
+        progress.inc(1);
+    }
+}
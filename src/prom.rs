@@ -0,0 +1,360 @@
+//! Prometheus text format metrics, written as a node_exporter textfile collector file
+//! (--prom-file) or served for scraping (--metrics-port, on --metrics-listen)
+use anyhow::{Context, Result};
+use http_body_util::Full;
+use hyper::{
+    body::{Bytes, Incoming},
+    header, Request, Response, StatusCode,
+};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+use tokio::net::TcpListener;
+
+use crate::client::{NeuronInfoLite, SubnetInfo};
+use crate::http;
+use crate::status::NeuronStatus;
+use crate::utils;
+
+// Samples of one metric, kept together as the format requires
+struct Family {
+    name: &'static str,
+    help: &'static str,
+    samples: Vec<String>,
+}
+
+#[derive(Default)]
+pub struct Textfile {
+    families: Vec<Family>,
+}
+
+impl Textfile {
+    pub fn gauge(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        labels: &[(&str, String)],
+        value: f64,
+    ) {
+        let labels: Vec<String> = labels
+            .iter()
+            .map(|(key, value)| format!("{}=\"{}\"", key, escape_label(value)))
+            .collect();
+        let sample = if labels.is_empty() {
+            format!("{} {}", name, value)
+        } else {
+            format!("{}{{{}}} {}", name, labels.join(","), value)
+        };
+
+        match self.families.iter_mut().find(|family| family.name == name) {
+            Some(family) => family.samples.push(sample),
+            None => self.families.push(Family {
+                name,
+                help,
+                samples: vec![sample],
+            }),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        for family in &self.families {
+            out.push_str(&format!("# HELP {} {}\n", family.name, family.help));
+            out.push_str(&format!("# TYPE {} gauge\n", family.name));
+            for sample in &family.samples {
+                out.push_str(sample);
+                out.push('\n');
+            }
+        }
+        out
+    }
+
+    // Replaced atomically, the collector may read it at any moment
+    pub fn write(&self, path: &str) -> Result<()> {
+        utils::write_atomic(path, &self.render()).context("Failed to write Prometheus textfile")
+    }
+
+    // Registration state of the monitored hotkeys
+    pub fn add_monitored(&mut self, statuses: &[NeuronStatus]) {
+        for status in statuses {
+            let labels = [
+                ("netuid", status.netuid.to_string()),
+                ("hotkey", status.hotkey.clone()),
+            ];
+            self.gauge(
+                "btreg_monitored_registered",
+                "Whether a monitored hotkey is registered (1) or not (0)",
+                &labels,
+                if status.registered { 1.0 } else { 0.0 },
+            );
+            if let Some(rank) = status.pruning_rank {
+                self.gauge(
+                    "btreg_monitored_pruning_rank",
+                    "Position in the pruning queue, 1 is pruned next",
+                    &labels,
+                    rank as f64,
+                );
+            }
+        }
+    }
+
+    // Standing of a watched hotkey's neuron, scores u16-normalized to 0..1
+    pub fn add_watched_neuron(&mut self, neuron: &NeuronInfoLite) {
+        let labels = [
+            ("netuid", neuron.netuid.to_string()),
+            ("hotkey", neuron.hotkey.to_string()),
+        ];
+        self.gauge(
+            "btreg_monitored_stake_rao",
+            "Total stake of a monitored hotkey in RAO",
+            &labels,
+            neuron.stake as f64,
+        );
+        self.gauge(
+            "btreg_monitored_incentive",
+            "Incentive of a monitored hotkey (0-1)",
+            &labels,
+            neuron.incentive as f64 / u16::MAX as f64,
+        );
+        self.gauge(
+            "btreg_monitored_emission_rao",
+            "Emission per tempo of a monitored hotkey in RAO",
+            &labels,
+            neuron.emission as f64,
+        );
+    }
+
+    pub fn add_chain(&mut self, block: u64, rpc_latency: Duration) {
+        self.gauge("btreg_block_height", "Best block number", &[], block as f64);
+        self.gauge(
+            "btreg_rpc_latency_seconds",
+            "Round trip of a cheap RPC call to the endpoint",
+            &[],
+            rpc_latency.as_secs_f64(),
+        );
+    }
+
+    pub fn add_subnet(&mut self, info: &SubnetInfo) {
+        let labels = [("netuid", info.netuid.to_string())];
+        self.gauge(
+            "btreg_subnet_burn_rao",
+            "Current burn registration cost in RAO",
+            &labels,
+            info.burn as f64,
+        );
+        self.gauge(
+            "btreg_subnet_neurons",
+            "Registered neurons",
+            &labels,
+            info.subnetwork_n as f64,
+        );
+        self.gauge(
+            "btreg_subnet_max_neurons",
+            "UID slots",
+            &labels,
+            info.max_n as f64,
+        );
+        self.gauge(
+            "btreg_subnet_registrations_this_interval",
+            "Registrations since the last burn adjustment",
+            &labels,
+            info.registrations_this_interval as f64,
+        );
+    }
+
+    // Every neuron of a subnet, u16-normalized scores exported as 0..1
+    pub fn add_neurons(&mut self, neurons: &[NeuronInfoLite]) {
+        let ratio = |value: u16| value as f64 / u16::MAX as f64;
+        for neuron in neurons {
+            let labels = [
+                ("netuid", neuron.netuid.to_string()),
+                ("uid", neuron.uid.to_string()),
+                ("hotkey", neuron.hotkey.to_string()),
+            ];
+            self.gauge(
+                "btreg_neuron_stake_rao",
+                "Total stake in RAO",
+                &labels,
+                neuron.stake as f64,
+            );
+            self.gauge(
+                "btreg_neuron_emission_rao",
+                "Emission per tempo in RAO",
+                &labels,
+                neuron.emission as f64,
+            );
+            self.gauge(
+                "btreg_neuron_incentive",
+                "Incentive (0-1)",
+                &labels,
+                ratio(neuron.incentive),
+            );
+            self.gauge(
+                "btreg_neuron_trust",
+                "Trust (0-1)",
+                &labels,
+                ratio(neuron.trust),
+            );
+            self.gauge(
+                "btreg_neuron_consensus",
+                "Consensus (0-1)",
+                &labels,
+                ratio(neuron.consensus),
+            );
+            self.gauge(
+                "btreg_neuron_dividends",
+                "Dividends (0-1)",
+                &labels,
+                ratio(neuron.dividends),
+            );
+            self.gauge(
+                "btreg_neuron_pruning_score",
+                "Pruning score, the lowest non-immune neuron is replaced next",
+                &labels,
+                neuron.pruning_score as f64,
+            );
+            self.gauge(
+                "btreg_neuron_validator_permit",
+                "Whether the neuron holds a validator permit",
+                &labels,
+                if neuron.validator_permit { 1.0 } else { 0.0 },
+            );
+            self.gauge(
+                "btreg_neuron_last_update",
+                "Block of the neuron's last weight update",
+                &labels,
+                neuron.last_update as f64,
+            );
+        }
+    }
+}
+
+// The latest metrics, served on /metrics until the process exits
+#[derive(Clone)]
+pub struct Exporter {
+    metrics: Arc<Mutex<String>>,
+}
+
+impl Exporter {
+    pub async fn serve(listen: SocketAddr) -> Result<Self> {
+        let listener = TcpListener::bind(listen)
+            .await
+            .with_context(|| format!("Failed to listen on {}", listen))?;
+        println!("📊 Serving metrics on http://{}/metrics", listen);
+
+        let exporter = Self {
+            metrics: Arc::default(),
+        };
+        let metrics = exporter.metrics.clone();
+        tokio::spawn(http::serve(listener, move |request| {
+            let response = scrape(&metrics, &request);
+            async move { response }
+        }));
+        Ok(exporter)
+    }
+
+    // Replace what scrapes return, after each check
+    pub fn publish(&self, textfile: &Textfile) {
+        *self.metrics.lock().unwrap_or_else(|e| e.into_inner()) = textfile.render();
+    }
+}
+
+fn scrape(metrics: &Mutex<String>, request: &Request<Incoming>) -> Response<Full<Bytes>> {
+    let (status, body) = if request.uri().path() == "/metrics" {
+        let metrics = metrics.lock().unwrap_or_else(|e| e.into_inner()).clone();
+        (StatusCode::OK, metrics)
+    } else {
+        (
+            StatusCode::NOT_FOUND,
+            "Metrics are on /metrics\n".to_string(),
+        )
+    };
+    let mut response = Response::new(Full::new(Bytes::from(body)));
+    *response.status_mut() = status;
+    response.headers_mut().insert(
+        header::CONTENT_TYPE,
+        header::HeaderValue::from_static("text/plain; version=0.0.4"),
+    );
+    response
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_textfile_render() {
+        let mut textfile = Textfile::default();
+        textfile.gauge("btreg_a", "First", &[("netuid", "1".into())], 1.0);
+        textfile.gauge("btreg_b", "Second", &[("name", "x\"y".into())], 0.5);
+        textfile.gauge("btreg_a", "First", &[("netuid", "2".into())], 250.0);
+
+        // Samples of a metric stay grouped under a single HELP/TYPE header
+        assert_eq!(
+            textfile.render(),
+            "# HELP btreg_a First\n# TYPE btreg_a gauge\nbtreg_a{netuid=\"1\"} 1\nbtreg_a{netuid=\"2\"} 250\n\
+             # HELP btreg_b Second\n# TYPE btreg_b gauge\nbtreg_b{name=\"x\\\"y\"} 0.5\n"
+        );
+
+        let mut textfile = Textfile::default();
+        textfile.add_chain(4_000_000, Duration::from_millis(250));
+        let rendered = textfile.render();
+        assert!(rendered.contains("\nbtreg_block_height 4000000\n"));
+        assert!(rendered.contains("\nbtreg_rpc_latency_seconds 0.25\n"));
+    }
+
+    #[test]
+    fn test_watched_gauges() {
+        let hotkey = sp_core::crypto::AccountId32::new([1; 32]);
+        let neuron = NeuronInfoLite {
+            hotkey: hotkey.clone(),
+            coldkey: hotkey.clone(),
+            uid: 7,
+            netuid: 1,
+            active: true,
+            axon_info: Default::default(),
+            stake: 2_500_000_000,
+            rank: 0,
+            emission: 40_000,
+            incentive: u16::MAX,
+            consensus: 0,
+            trust: 0,
+            validator_trust: 0,
+            dividends: 0,
+            last_update: 0,
+            validator_permit: false,
+            pruning_score: 0,
+        };
+        let mut textfile = Textfile::default();
+        textfile.add_watched_neuron(&neuron);
+        textfile.add_monitored(&[NeuronStatus {
+            netuid: 1,
+            hotkey: hotkey.to_string(),
+            registered: true,
+            uid: Some(7),
+            pruning_rank: Some(3),
+            error: None,
+        }]);
+
+        let labels = format!("{{netuid=\"1\",hotkey=\"{}\"}}", hotkey);
+        let rendered = textfile.render();
+        for sample in [
+            format!("btreg_monitored_stake_rao{} 2500000000", labels),
+            format!("btreg_monitored_incentive{} 1", labels),
+            format!("btreg_monitored_emission_rao{} 40000", labels),
+            format!("btreg_monitored_registered{} 1", labels),
+            format!("btreg_monitored_pruning_rank{} 3", labels),
+        ] {
+            assert!(rendered.contains(&format!("\n{}\n", sample)), "{}", sample);
+        }
+    }
+}
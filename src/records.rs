@@ -0,0 +1,194 @@
+//! Persistence of subnet snapshots and registration receipts (SQLite, JSONL, HTTP)
+use anyhow::{anyhow, Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::{io::Write, path::PathBuf, sync::Mutex};
+
+use crate::config::RecordsConfig;
+use crate::history::{HistoryStore, SubnetSnapshot};
+use crate::http;
+use crate::paths::{self, expand_home};
+
+const DEFAULT_SQLITE_FILE: &str = "history.sqlite";
+const DEFAULT_JSONL_FILE: &str = "records.jsonl";
+
+// A completed registration
+#[derive(Debug, Clone, Serialize)]
+pub struct Receipt {
+    pub recorded_at: DateTime<Utc>,
+    pub netuid: u16,
+    pub hotkey: String,
+    pub coldkey: String,
+    pub burn: u64,
+    pub fee: Option<u128>,
+    pub tx_hash: String,
+}
+
+// Anything a store persists, tagged with "type" when serialized
+#[derive(Debug, Clone, Copy, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Record<'a> {
+    Snapshot(&'a SubnetSnapshot),
+    Receipt(&'a Receipt),
+}
+
+#[async_trait]
+#[allow(clippy::double_must_use)]
+pub trait RecordStore: Send + Sync {
+    fn name(&self) -> String;
+    async fn write(&self, record: Record<'_>) -> Result<()>;
+}
+
+// Local SQLite database, the one History reads from
+pub struct SqliteStore {
+    path: String,
+    history: Mutex<HistoryStore>,
+}
+
+impl SqliteStore {
+    pub fn open(path: &str) -> Result<Self> {
+        Ok(Self {
+            path: path.to_string(),
+            history: Mutex::new(HistoryStore::open(path)?),
+        })
+    }
+}
+
+#[async_trait]
+impl RecordStore for SqliteStore {
+    fn name(&self) -> String {
+        format!("sqlite ({})", self.path)
+    }
+
+    async fn write(&self, record: Record<'_>) -> Result<()> {
+        let history = self
+            .history
+            .lock()
+            .map_err(|_| anyhow!("History database lock poisoned"))?;
+        match record {
+            Record::Snapshot(snapshot) => history.record(snapshot),
+            Record::Receipt(receipt) => history.record_receipt(receipt),
+        }
+    }
+}
+
+// Append-only JSON lines file, easy to ship with log collectors
+pub struct JsonlStore {
+    pub path: PathBuf,
+}
+
+#[async_trait]
+impl RecordStore for JsonlStore {
+    fn name(&self) -> String {
+        format!("jsonl ({})", self.path.display())
+    }
+
+    async fn write(&self, record: Record<'_>) -> Result<()> {
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("Failed to open {}", self.path.display()))?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)
+            .with_context(|| format!("Failed to write to {}", self.path.display()))
+    }
+}
+
+// POST every record as JSON to a central service
+pub struct HttpStore {
+    pub url: String,
+}
+
+#[async_trait]
+impl RecordStore for HttpStore {
+    fn name(&self) -> String {
+        format!("http ({})", self.url)
+    }
+
+    async fn write(&self, record: Record<'_>) -> Result<()> {
+        http::post_json(&self.url, &serde_json::to_value(record)?).await
+    }
+}
+
+// File in the data directory unless a path is configured
+fn data_file(path: Option<&str>, default_name: &str) -> Result<PathBuf> {
+    match path {
+        Some(path) => Ok(expand_home(path)),
+        None => {
+            let dir = paths::data_dir();
+            std::fs::create_dir_all(&dir)
+                .with_context(|| format!("Failed to create {}", dir.display()))?;
+            Ok(dir.join(default_name))
+        }
+    }
+}
+
+// Configured store, None when recording is not set up
+pub fn from_config(config: &RecordsConfig) -> Result<Option<Box<dyn RecordStore>>> {
+    let store: Box<dyn RecordStore> = match config {
+        RecordsConfig::None => return Ok(None),
+        RecordsConfig::Sqlite { path } => {
+            let path = data_file(path.as_deref(), DEFAULT_SQLITE_FILE)?;
+            Box::new(SqliteStore::open(&path.to_string_lossy())?)
+        }
+        RecordsConfig::Jsonl { path } => Box::new(JsonlStore {
+            path: data_file(path.as_deref(), DEFAULT_JSONL_FILE)?,
+        }),
+        RecordsConfig::Http { url } => Box::new(HttpStore { url: url.clone() }),
+    };
+    Ok(Some(store))
+}
+
+// Store for --record: a SQLite path when given, else the configured store,
+// else SQLite in the data directory
+pub fn for_recording(
+    record: Option<Option<String>>,
+    config: &RecordsConfig,
+) -> Result<Option<Box<dyn RecordStore>>> {
+    let store = match record {
+        None => return Ok(None),
+        Some(Some(path)) => Box::new(SqliteStore::open(&path)?),
+        Some(None) => match from_config(config)? {
+            Some(store) => store,
+            None => from_config(&RecordsConfig::Sqlite { path: None })?
+                .ok_or_else(|| anyhow!("No record store available"))?,
+        },
+    };
+    println!("💾 Recording to {}", store.name());
+    Ok(Some(store))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_jsonl_store() {
+        let path = std::env::temp_dir().join(format!("btreg-records-{}.jsonl", std::process::id()));
+        let store = JsonlStore { path: path.clone() };
+        let receipt = Receipt {
+            recorded_at: Utc::now(),
+            netuid: 3,
+            hotkey: "5Hotkey".into(),
+            coldkey: "5Coldkey".into(),
+            burn: 1_000,
+            fee: Some(10),
+            tx_hash: "0xabc".into(),
+        };
+        store.write(Record::Receipt(&receipt)).await.unwrap();
+        store.write(Record::Receipt(&receipt)).await.unwrap();
+
+        let lines: Vec<serde_json::Value> = std::fs::read_to_string(&path)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["type"], "receipt");
+        assert_eq!(lines[0]["netuid"], 3);
+
+        assert!(from_config(&RecordsConfig::None).unwrap().is_none());
+        std::fs::remove_file(path).unwrap();
+    }
+}
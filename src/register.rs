@@ -1,41 +1,278 @@
 use anyhow::{anyhow, Context, Result};
 use colored::*;
-use serde::{Deserialize, Serialize};
-use sp_core::{
-    crypto::{AccountId32, Ss58Codec},
-    Pair,
-};
-use std::time::Duration;
+use futures::stream::{self, StreamExt};
+use primitive_types::H256;
+use sp_core::crypto::{AccountId32, Ss58Codec};
+use std::{collections::HashMap, io::Write, sync::Mutex, time::Duration};
 use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
 
+use crate::analysis;
+use crate::backoff::Backoff;
+use crate::batch::{BatchConfig, BatchOperation};
+use crate::browse::{self, SubnetAction, SubnetRow};
+use crate::campaign::{self, CampaignOptions, Candidate, Decision};
+use crate::capabilities;
+use crate::chain::{ChainApi, WsChain};
+use crate::children;
 use crate::client::*;
-use crate::key_utils;
+use crate::commit_reveal::{self, CommitStore, WeightsCommit};
+use crate::config::{self, BlockTimeConfig, Config};
+use crate::constants;
+use crate::errors::{self, ErrorKind, KindContext, RegistrationError};
+use crate::faucet;
+use crate::funding::{self, FundPlan, FundPlanOptions, FundingNeed};
+use crate::history::{HistoryStore, SubnetSnapshot};
+use crate::hyperparams::{Hyperparameters, ParamChange};
+use crate::journal::{BatchJournal, EntryState};
+use crate::key_utils::{self, KeyPair};
+use crate::latency::{LatencyReport, LatencySla};
+use crate::metadata::Metadata;
+use crate::network;
+use crate::notify::Notifications;
+use crate::offline::UnsignedTx;
+use crate::price::{self, PriceOracle};
+use crate::progress::Progress;
+use crate::prom;
+use crate::records::{self, Receipt, Record, RecordStore};
+use crate::session::{OperationReport, OperationStatus, SessionSummary};
+use crate::status::{DaemonStatus, NeuronStatus};
 use crate::utils;
+use crate::warnings::{self, WarningCode};
 
-// Batch operations configuration
-#[derive(Serialize, Deserialize)]
-struct BatchConfig {
-    operations: Vec<BatchOperation>,
+// Finalized blocks to wait for batch registrations before giving up
+const BATCH_VERIFY_BLOCKS: usize = 10;
+
+// Lowest pruning scores listed in the deregistration risk report
+const PRUNING_LOWEST_SHOWN: usize = 5;
+
+// Upper bound on cached subnet storage age, chain changes invalidate it sooner
+const STORAGE_CACHE_TTL: Duration = Duration::from_secs(600);
+
+// Burn history shown when estimating from the browser, same as EstimateCost's default
+const BROWSE_BURN_INTERVALS: usize = 8;
+
+// Pause between operations when a batch runs one at a time
+const BATCH_OPERATION_DELAY: Duration = Duration::from_secs(5);
+
+// Registrations listed in a batch config, as (wallet, subnet)
+pub fn batch_registration_targets(config_path: &str) -> Result<Vec<(String, u16)>> {
+    let (batch_config, _) = BatchConfig::load(config_path)?;
+
+    Ok(batch_config
+        .operations
+        .into_iter()
+        .filter(BatchOperation::is_registration)
+        .filter_map(|operation| {
+            let wallet = key_utils::resolve_wallet(operation.wallet.as_deref()?);
+            Some((wallet, operation.subnet?))
+        })
+        .collect())
+}
+
+// What a batch operation ended with
+enum BatchOutcome {
+    Registered(RegistrationOutcome),
+    Completed(&'static str),
+    Skipped(String),
+}
+
+// Single step of the post-registration checklist
+struct ChecklistItem {
+    name: &'static str,
+    passed: Option<bool>, // None when skipped because an earlier step failed
+    detail: String,
+}
+
+impl ChecklistItem {
+    // Warning raised when this step fails
+    fn warning_code(&self) -> Option<WarningCode> {
+        match self.name {
+            "Axon served" => Some(WarningCode::AxonNotServed),
+            "Prometheus served" => Some(WarningCode::PrometheusNotServed),
+            "Weight received" => Some(WarningCode::NoWeights),
+            "Nonzero emission" => Some(WarningCode::NoEmission),
+            _ => None,
+        }
+    }
+}
+
+// User choices that shape a single registration
+#[derive(Debug, Clone, Default)]
+pub struct RegistrationOptions {
+    pub burn_amount: Option<u64>, // Explicit burn in RAO, live burn when None
+    pub auto_adjust: bool,        // Raise a too-low burn_amount to the live burn
+    pub max_burn: Option<u64>,    // Never pay more than this, in RAO
+    pub dry_run: bool,            // Build, sign and simulate without submitting
+    pub assume_yes: bool,         // Skip the confirmation prompt before spending TAO
+    pub wait_for_window: bool,    // Wait out a full registration interval instead of failing
+    pub skip_verify: bool,        // Leave verification to the caller, e.g. a whole batch at once
+    pub latency_sla: Option<LatencySla>, // Benchmark the endpoint before starting
+    pub allow_foreign_hotkey: bool, // Register a hotkey another coldkey owns, warning only
+}
+
+// Limits for the registration guard
+#[derive(Debug, Clone, Default)]
+pub struct GuardOptions {
+    pub hotkey_pool: Vec<String>, // Spare hotkeys to rotate to after the primary one
+    pub max_burn: u64,            // Maximum burn per registration, in RAO
+    pub budget: u64,              // Total burn budget for all re-registrations, in RAO
+    pub interval: u64,            // Seconds between checks
+    pub summary_path: Option<String>, // Write the session summary here on exit
+    pub status_path: Option<String>, // Keep a JSON status file updated after every check
+}
+
+// Extra checks run by the monitor for each registered neuron
+#[derive(Debug, Clone, Default)]
+pub struct MonitorOptions {
+    pub show_risk: bool,            // Report pruning rank and immunity
+    pub danger_zone: Option<usize>, // Alert when ranked among the N next to be pruned
+}
+
+// Conditions the watcher alerts on
+#[derive(Debug, Clone, Default)]
+pub struct WatchOptions {
+    pub burn_below: Option<u64>, // Alert while the burn is below this, in RAO
+    pub open_slots: bool,        // Alert while a subnet has free UID slots
+    pub registration_opens: bool, // Alert when registration is re-enabled
+    pub hotkeys: Vec<String>,    // Alert when one of these hotkeys is deregistered
+    pub interval: u64,           // Seconds between checks
+}
+
+// Hotkeys used in turn, moving on when one is deregistered or cannot register
+#[derive(Debug, Clone)]
+pub struct HotkeyPool {
+    hotkeys: Vec<String>,
+    current: usize,
+}
+
+impl HotkeyPool {
+    pub fn new(hotkeys: Vec<String>) -> Result<Self> {
+        if hotkeys.is_empty() {
+            return Err(anyhow!("Hotkey pool is empty"));
+        }
+        Ok(Self {
+            hotkeys,
+            current: 0,
+        })
+    }
+
+    pub fn current(&self) -> &str {
+        &self.hotkeys[self.current]
+    }
+
+    pub fn len(&self) -> usize {
+        self.hotkeys.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hotkeys.is_empty()
+    }
+
+    // Move to the next hotkey, wrapping around to the first
+    pub fn rotate(&mut self) -> &str {
+        self.current = (self.current + 1) % self.hotkeys.len();
+        self.current()
+    }
+}
+
+// Subnet to snipe and the most it may cost
+#[derive(Debug, Clone, Copy)]
+pub struct SnipeTarget {
+    pub netuid: u16,
+    pub max_burn: u64, // In RAO
 }
 
-#[derive(Serialize, Deserialize)]
-struct BatchOperation {
-    operation: String, // "register", "check_status", "monitor"
-    subnet: u16,
-    wallet: Option<String>,
-    hotkey: String,
-    max_retries: Option<usize>,
+// Per-subnet snipe state kept pre-signed between blocks
+struct SnipeWatcher {
+    max_burn: u64,
+    schedule: BurnSchedule,
+    registration_data: RegistrationData,
+    extrinsic: Vec<u8>,
 }
 
-pub struct QuickRegister {
-    client: BittensorClient,
+// What a registration attempt actually did on chain
+#[derive(Debug, Clone, Default)]
+pub struct RegistrationOutcome {
+    pub tx_hash: Option<H256>, // None when nothing was submitted
+    pub burn: u64,             // Burn paid in RAO
+    pub fee: Option<u128>,     // Estimated fee in RAO
+}
+
+pub struct QuickRegister<C: ChainApi = WsChain> {
+    client: BittensorClient<C>,
+    price_oracle: Box<dyn PriceOracle>,
+    receipts: Option<Box<dyn RecordStore>>,
+    backoff: Backoff, // Waits between registration attempts
 }
 
 impl QuickRegister {
+    // Connected to endpoint, set up from the loaded config
     pub async fn new(endpoint: String) -> Result<Self> {
-        let client = BittensorClient::new(endpoint).await?;
+        let config = config::current();
+        let client = BittensorClient::new(endpoint, config.backoff).await?;
+        if let Some(network) = network::current() {
+            network.check_genesis(client.get_genesis_hash().await?)?;
+            println!("🌐 Network: {}", network);
+        }
+        client.check_runtime_compatibility().await?;
+        utils::set_block_time(resolve_block_time(&client, &config.block_time).await);
+        Self::with_client(client, &config)
+    }
+}
+
+impl<C: ChainApi> QuickRegister<C> {
+    // Around an already connected client, without the network and runtime checks
+    pub fn with_client(client: BittensorClient<C>, config: &Config) -> Result<Self> {
+        let price_oracle = price::from_config(&config.price_oracle);
+        let receipts = records::from_config(&config.records)?;
+
+        Ok(Self {
+            client,
+            price_oracle,
+            receipts,
+            backoff: config.backoff,
+        })
+    }
+
+    // Outbid competing registrations in congested blocks, see TipEscalation
+    pub fn enable_tip_escalation(&self, escalation: TipEscalation) {
+        println!(
+            "📈 Tip escalation: +{} per resubmission after {} blocks stuck, up to {}",
+            utils::format_tao(escalation.step as u128),
+            escalation.stuck_blocks,
+            utils::format_tao(escalation.max_tip as u128)
+        );
+        self.client.enable_tip_escalation(escalation);
+    }
+
+    // Answer every following query from the state at a past block (number or hash)
+    pub async fn pin_block(&self, block: &str) -> Result<()> {
+        let (number, block_hash) = self.client.pin_block(block).await?;
+        println!(
+            "⏪ Reading state at block {} ({:?})",
+            utils::format_thousands(number),
+            block_hash
+        );
+        Ok(())
+    }
+
+    // Cancels this instance's waits, watches and solving, for callers that outlive an operation
+    pub fn cancellation_token(&self) -> CancellationToken {
+        self.client.cancellation_token()
+    }
 
-        Ok(Self { client })
+    // USD value of an amount in RAO, or why it is unavailable
+    async fn format_usd(&self, rao: u64) -> String {
+        match self.price_oracle.tao_usd().await {
+            Ok(price) => format!(
+                "~${:.2} (TAO at ${:.2} via {})",
+                rao as f64 / 1e9 * price,
+                price,
+                self.price_oracle.name()
+            ),
+            Err(e) => format!("unavailable ({})", e),
+        }
     }
 
     pub async fn register_to_subnet(
@@ -43,25 +280,48 @@ impl QuickRegister {
         netuid: u16,
         wallet_path: &str,
         hotkey_path: &str,
-        burn_amount: Option<u64>,
-    ) -> Result<()> {
+        options: &RegistrationOptions,
+    ) -> Result<RegistrationOutcome> {
+        // burned_register can't express the root network
+        if netuid == constants::ROOT_NETUID {
+            return Err(RegistrationError::new(
+                ErrorKind::Fatal,
+                "The root network takes root_register, use register-root",
+            )
+            .into());
+        }
         println!(
             "{}",
             "🚀 Starting Bittensor Registration".bright_cyan().bold()
         );
         println!("═══════════════════════════════════════");
 
-        // 1. Loading keys
-        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
-            .context("Failed to load wallet/coldkey")?;
-        let hotkey_account =
-            key_utils::account_id_from_string(hotkey_path).context("Failed to load hotkey")?;
-        let coldkey_account = AccountId32::from(coldkey_pair.public().0);
+        // 1. Loading keys, a view-only coldkey is enough for a dry run
+        let coldkey = key_utils::Coldkey::load(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        if !options.dry_run {
+            coldkey.signer("register")?;
+        }
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let coldkey_account = coldkey.account_id();
 
         println!("🔑 Keys loaded:");
-        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!(
+            "   Coldkey: {}{}",
+            coldkey_account.to_ss58check(),
+            if coldkey.is_view_only() {
+                " (view-only)"
+            } else {
+                ""
+            }
+        );
         println!("   Hotkey: {}", hotkey_account.to_ss58check());
 
+        if let Some(sla) = &options.latency_sla {
+            self.check_endpoint_latency(sla).await?;
+        }
+
         // 2. Checking if already registered
         if let Some(neuron) = self
             .client
@@ -72,494 +332,6184 @@ impl QuickRegister {
                 "✅ Already registered in subnet {} with UID: {}",
                 netuid, neuron.uid
             );
-            return Ok(());
+            return Ok(RegistrationOutcome::default());
         }
 
-        // 3. Getting subnet information
+        // Burning for a hotkey that belongs to someone else's coldkey is a costly mix-up
+        let owner = self.client.get_hotkey_owner(&hotkey_account).await?;
+        check_hotkey_owner(
+            &hotkey_account,
+            owner.as_ref(),
+            &coldkey_account,
+            options.allow_foreign_hotkey,
+        )?;
+
+        // 3. Getting subnet information, making sure registration is open
         let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+        let subnet_info = self
+            .wait_for_registration_window(subnet_info, options.wait_for_window)
+            .await?;
 
         // 4. Getting the current block number
         let current_block = self.client.get_current_block().await?;
-        println!("📦 Current block: {}", current_block);
+        println!(
+            "📦 Current block: {}",
+            utils::format_thousands(current_block)
+        );
 
         // 6. Performing registration using the selected method
-        let burn_cost = burn_amount.unwrap_or(subnet_info.burn);
         let registration_data = self
             .perform_burn_registration(
-                netuid,
+                &subnet_info,
                 &hotkey_account,
                 &coldkey_account,
                 current_block,
-                burn_cost,
+                options,
             )
             .await?;
 
+        if options.dry_run {
+            match &coldkey {
+                key_utils::Coldkey::Signing(coldkey_pair) => {
+                    self.report_dry_run(&registration_data, coldkey_pair)
+                        .await?
+                }
+                // Without a signature the node can't dry-run the extrinsic itself
+                key_utils::Coldkey::ViewOnly(_) => {
+                    println!("\n🔒 View-only coldkey: subnet, burn and balance checks passed");
+                    println!(
+                        "ℹ️ The signed dry run needs the coldkey's secret, nothing was submitted"
+                    );
+                }
+            }
+            return Ok(RegistrationOutcome::default());
+        }
+        let coldkey_pair = coldkey.signer("register")?;
+
+        let fee = registration_data.fee;
+        if !options.assume_yes && !self.confirm_registration(&registration_data, fee).await? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Registration cancelled").into());
+        }
+        // The prompt can sit for any time and the chain burns whatever the burn is at inclusion
+        if let Some(max_burn) = options.max_burn {
+            self.check_live_burn(netuid, max_burn).await?;
+        }
+
         // 7. Sending registration
-        let tx_hash = self
+        let inclusion = self
             .client
-            .submit_burned_registration(&registration_data, &coldkey_pair)
+            .submit_burned_registration(&registration_data, coldkey_pair)
             .await?;
+        let tx_hash = inclusion.tx_hash;
+        let registered_at = chrono::Utc::now();
 
         println!("\n🎉 Registration completed successfully!");
-        println!("   Transaction hash: {}", tx_hash);
+        println!("   Transaction hash: {:?}", tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+        println!("   Time: {}", utils::format_time(registered_at));
         println!("   Subnet: {}", netuid);
         println!("   Hotkey: {}", hotkey_account.to_ss58check());
         println!("   Coldkey: {}", coldkey_account.to_ss58check());
 
-        // 8. Verifying final registration
-        self.verify_registration(netuid, &hotkey_account).await?;
-
-        Ok(())
-    }
-
-    // Burn registration
-    async fn perform_burn_registration(
-        &self,
-        netuid: u16,
-        hotkey_account: &AccountId32,
-        coldkey_account: &AccountId32,
-        current_block: u64,
-        burn_amount: u64,
-    ) -> Result<RegistrationData> {
-        println!("\n🔥 Preparing burn registration...");
-        println!("   Burn amount: {}", utils::format_tao(burn_amount as u128));
+        self.record_receipt(Receipt {
+            recorded_at: registered_at,
+            netuid,
+            hotkey: hotkey_account.to_ss58check(),
+            coldkey: coldkey_account.to_ss58check(),
+            burn: registration_data.burn_amount,
+            fee,
+            tx_hash: format!("{:?}", tx_hash),
+        })
+        .await;
 
-        // Checking balance
-        let balance = self.client.get_account_balance(coldkey_account).await?;
-        if balance < burn_amount {
-            return Err(anyhow!(
-                "Insufficient balance. Required: {}, Available: {}",
-                utils::format_tao(burn_amount as u128),
-                utils::format_tao(balance as u128)
-            ));
+        // 8. Verifying final registration, reading the state of the inclusion block itself
+        self.report_balance_change(&coldkey_account, inclusion.block_hash)
+            .await;
+        if !options.skip_verify {
+            self.verify_registration_at(netuid, &hotkey_account, &inclusion)
+                .await?;
         }
 
-        println!("✅ Sufficient balance confirmed");
-
-        Ok(RegistrationData {
-            subnet_id: netuid,
-            hotkey: hotkey_account.clone(),
-            coldkey: coldkey_account.clone(),
-            burn_amount: burn_amount,
-            block_number: current_block,
+        Ok(RegistrationOutcome {
+            tx_hash: Some(tx_hash),
+            burn: registration_data.burn_amount,
+            fee,
         })
     }
 
-    // Verification of registration success
-    async fn verify_registration(&self, netuid: u16, hotkey_account: &AccountId32) -> Result<()> {
-        println!("\n🔍 Verifying registration...");
+    // Browse: pick a subnet from a searchable list, inspect it, then estimate or register
+    pub async fn browse_subnets(&self) -> Result<()> {
+        println!("{}", "🧭 Subnet Browser".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
 
-        for attempt in 1..=5 {
-            println!("   Attempt {}/5...", attempt);
-            sleep(Duration::from_secs(12)).await;
+        // Listing every subnet is slow, keep the answers for the session
+        self.client.enable_storage_cache(STORAGE_CACHE_TTL);
+        let total_networks = self.client.get_total_networks().await?;
+        println!("🔍 Loading {} subnets...", total_networks);
 
-            match self
-                .client
-                .check_registration(netuid, hotkey_account)
-                .await?
-            {
-                Some(neuron) => {
-                    println!("✅ Registration verified! Assigned UID: {}", neuron.uid);
-                    println!("   Active: {}", if neuron.active { "Yes" } else { "No" });
-                    println!(
-                        "   Stake: {}",
-                        utils::format_tao(neuron.stake.iter().map(|(_, s)| s).sum::<u64>() as u128)
-                    );
-                    return Ok(());
-                }
-                None => {
-                    if attempt < 5 {
-                        println!("   Still processing, waiting for next block...");
+        let mut rows = Vec::new();
+        for netuid in 0..total_networks {
+            // Dissolved netuids have nothing behind them
+            let Ok(info) = self.client.get_subnet_info(netuid, false).await else {
+                continue;
+            };
+            let name = self.client.get_subnet_name(netuid).await.unwrap_or(None);
+            rows.push(SubnetRow::from_info(&info, name));
+        }
+        if rows.is_empty() {
+            return Err(anyhow!("No subnets found"));
+        }
+
+        let mut selected = 0;
+        while let Some(index) = browse::pick_subnet(&rows, selected)? {
+            selected = index;
+            let netuid = rows[index].netuid;
+
+            // Drilling in always shows live values
+            self.client.invalidate_subnet(netuid);
+            let result = match self.show_subnet_info(netuid, "text", None).await {
+                Ok(()) => match browse::pick_action(netuid)? {
+                    SubnetAction::EstimateCost => {
+                        self.estimate_registration_cost(netuid, BROWSE_BURN_INTERVALS, None)
+                            .await
                     }
-                }
+                    SubnetAction::Register => {
+                        let (wallet, hotkey) = browse::prompt_keys()?;
+                        let hotkey = key_utils::resolve_hotkey(Some(&wallet), &hotkey);
+                        let wallet = key_utils::resolve_wallet(&wallet);
+                        self.client.invalidate_subnet(netuid);
+                        self.register_to_subnet(
+                            netuid,
+                            &wallet,
+                            &hotkey,
+                            &RegistrationOptions::default(),
+                        )
+                        .await
+                        .map(|_| ())
+                    }
+                    SubnetAction::Back => Ok(()),
+                    SubnetAction::Quit => break,
+                },
+                Err(e) => Err(e),
+            };
+            // A failed step returns to the list instead of ending the session
+            if let Err(e) = result {
+                println!("❌ {:#}", e);
             }
         }
 
-        println!(
-            "⚠️ Registration may still be processing. Check status manually in a few minutes."
-        );
+        self.client.disable_storage_cache();
         Ok(())
     }
 
-    pub async fn estimate_registration_cost(&self, netuid: u16) -> Result<()> {
-        println!("💰 Estimating registration costs for subnet {}...", netuid);
-        println!("═══════════════════════════════════════════════════");
+    // BuildTx: an unsigned burned registration for a coldkey that stays offline
+    pub async fn build_registration_tx(
+        &self,
+        netuid: u16,
+        coldkey: &str,
+        hotkey_path: &str,
+        options: &RegistrationOptions,
+    ) -> Result<UnsignedTx> {
+        println!(
+            "{}",
+            "🧾 Building Unsigned Registration".bright_cyan().bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let coldkey_account = key_utils::account_id_from_string(coldkey)
+            .kind_context(ErrorKind::Fatal, "Failed to load coldkey address")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+
+        if let Some(uid) = self.client.get_uid(netuid, &hotkey_account).await? {
+            return Err(anyhow!(
+                "Hotkey is already registered in subnet {} with UID {}",
+                netuid,
+                uid
+            ));
+        }
 
         let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+        let subnet_info = self
+            .wait_for_registration_window(subnet_info, false)
+            .await?;
+        let context = self.client.fetch_signing_context(&coldkey_account).await?;
+        let registration_data = self
+            .perform_burn_registration(
+                &subnet_info,
+                &hotkey_account,
+                &coldkey_account,
+                context.block_number,
+                options,
+            )
+            .await?;
 
-        println!("\n📊 Cost Analysis:");
-        println!("┌─ Burn Registration (Instant)");
-        println!(
-            "│  ├─ Cost: {}",
-            utils::format_tao(subnet_info.burn as u128)
-        );
+        let call = self
+            .client
+            .encode_burned_register_call(netuid, hotkey_account.clone())?;
+        if let Some(death) = context.era.death(context.block_number) {
+            println!(
+                "⏳ Sign and submit before block {} (around {}) or the transaction expires",
+                utils::format_thousands(death),
+                utils::format_time(utils::block_eta(death - context.block_number))
+            );
+        }
+
+        Ok(UnsignedTx::new(
+            format!(
+                "burned_register(netuid {}, hotkey {}, burn {})",
+                netuid,
+                hotkey_account.to_ss58check(),
+                utils::format_tao(registration_data.burn_amount as u128)
+            ),
+            &coldkey_account,
+            &call,
+            &context,
+        ))
+    }
+
+    // SubmitTx: broadcast an extrinsic signed elsewhere
+    pub async fn submit_signed_tx(&self, extrinsic: &[u8]) -> Result<H256> {
         println!(
-            "│  ├─ USD equivalent: ~${:.2} (assuming $200/TAO)",
-            subnet_info.burn as f64 / 1e9 * 200.0
+            "📡 Submitting pre-signed extrinsic ({} bytes)...",
+            extrinsic.len()
         );
-        println!("│  └─ Processing time: 1-2 blocks (~12-24s)");
-
-        Ok(())
+        let tx_hash = self.client.submit_signed_extrinsic(extrinsic).await?;
+        println!("✅ Submitted, transaction hash: {:?}", tx_hash);
+        Ok(tx_hash)
     }
 
-    pub async fn check_status(&self, netuid: u16, hotkey_path: &str) -> Result<()> {
-        println!("🔍 Checking registration status...");
+    // Watch several subnets at once and register into the first whose burn drops to its target
+    pub async fn snipe_registration(
+        &self,
+        targets: &[SnipeTarget],
+        wallet_path: &str,
+        hotkey_path: &str,
+        budget: Option<u64>,
+        latency_sla: Option<&LatencySla>,
+    ) -> Result<()> {
+        println!("{}", "🎯 Starting Snipe Mode".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
 
-        let hotkey_account =
-            key_utils::account_id_from_string(hotkey_path).context("Failed to load hotkey")?;
+        if targets.is_empty() {
+            return Err(anyhow!("No subnets to snipe"));
+        }
 
-        match self
-            .client
-            .check_registration(netuid, &hotkey_account)
-            .await?
-        {
-            Some(neuron) => {
-                println!("✅ Neuron is registered in subnet {}!", netuid);
-                println!("\n📊 Neuron Details:");
-                println!("   UID: {}", neuron.uid);
-                println!("   Hotkey: {}", neuron.hotkey.to_ss58check());
-                println!("   Coldkey: {}", neuron.coldkey.to_ss58check());
-                println!("   Active: {}", if neuron.active { "Yes" } else { "No" });
-                println!(
-                    "   Stake: {}",
-                    utils::format_tao(neuron.stake.iter().map(|(_, s)| s).sum::<u64>() as u128)
-                );
-                println!("   Emission: {}", neuron.emission);
-                println!("   Last update: block {}", neuron.last_update);
-                println!("   Validator permit: {}", neuron.validator_permit);
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let coldkey_account = coldkey_pair.account_id();
 
-                // Show additional statistics
-                let subnet_info = self.client.get_subnet_info(netuid, false).await?;
-                println!("\n📈 Subnet Statistics:");
-                println!(
-                    "   Total neurons: {}/{}",
-                    subnet_info.registered_neurons, subnet_info.max_allowed_uids
-                );
-                println!("   Registration difficulty: {}", subnet_info.difficulty);
-                println!(
-                    "   Burn cost: {}",
-                    utils::format_tao(subnet_info.burn as u128)
-                );
-            }
-            None => {
-                println!(
-                    "❌ Hotkey {} is NOT registered in subnet {}",
-                    hotkey_account.to_ss58check(),
-                    netuid
-                );
+        println!("🔑 Keys loaded:");
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        for target in targets {
+            println!(
+                "   Subnet {}: max burn {}",
+                target.netuid,
+                utils::format_tao(target.max_burn as u128)
+            );
+        }
+        if let Some(budget) = budget {
+            println!("   Shared budget: {}", utils::format_tao(budget as u128));
+        }
+        if let Some(sla) = latency_sla {
+            self.check_endpoint_latency(sla).await?;
+        }
 
-                // Show possible registration information
-                let subnet_info = self.client.get_subnet_info(netuid, false).await?;
-                println!("\n💡 Registration options:");
+        for target in targets {
+            if let Some(uid) = self.client.get_uid(target.netuid, &hotkey_account).await? {
                 println!(
-                    "   Burn cost: {}",
-                    utils::format_tao(subnet_info.burn as u128)
+                    "✅ Already registered in subnet {} with UID: {}",
+                    target.netuid, uid
                 );
+                return Ok(());
             }
         }
 
-        Ok(())
-    }
+        // Never spend more than the budget or the balance, whichever is lower
+        let balance = self.client.get_account_balance(&coldkey_account).await?;
+        let spend_limit = budget.map_or(balance, |budget| budget.min(balance));
+        if targets.iter().any(|target| target.max_burn > spend_limit) {
+            warnings::emit(
+                WarningCode::SpendLimitBelowTarget,
+                format!(
+                    "Spend limit {} is below some targets, those subnets fire only if burn drops below it",
+                    utils::format_tao(spend_limit as u128)
+                ),
+            );
+        }
 
-    pub async fn show_subnet_info(&self, netuid: u16) -> Result<()> {
-        println!("📋 Fetching subnet {} information...", netuid);
+        let mut context = self.client.fetch_signing_context(&coldkey_account).await?;
+        let mut watchers = Vec::with_capacity(targets.len());
+        for target in targets {
+            let schedule = self.client.get_burn_schedule(target.netuid).await?;
+            let registration_data = RegistrationData {
+                subnet_id: target.netuid,
+                hotkey: hotkey_account.clone(),
+                coldkey: coldkey_account.clone(),
+                burn_amount: schedule.burn,
+                block_number: context.block_number,
+                fee: None,
+            };
+            let extrinsic = self.client.build_burned_registration(
+                &registration_data,
+                &coldkey_pair,
+                &context,
+            )?;
 
-        let subnet_info = self.client.get_subnet_info(netuid, true).await?;
+            println!(
+                "📦 Subnet {} | burn {} | next adjustment at block {}",
+                target.netuid,
+                utils::format_tao(schedule.burn as u128),
+                utils::format_thousands(schedule.next_adjustment_block())
+            );
+            watchers.push(SnipeWatcher {
+                max_burn: target.max_burn.min(spend_limit),
+                schedule,
+                registration_data,
+                extrinsic,
+            });
+        }
 
-        println!("\n📊 Subnet {} Details:", netuid);
-        println!("═══════════════════════════════════════");
-        println!(
-            "   Registered neurons: {}/{}",
-            subnet_info.subnetwork_n, subnet_info.max_n
-        );
-        println!("   Registration difficulty: {}", subnet_info.difficulty);
+        // One head subscription drives every watcher, so they all see each block together
+        let mut heads = self.client.subscribe_new_heads().await?;
+        let mut block_number = context.block_number;
+
+        loop {
+            let ready = watchers
+                .iter()
+                .enumerate()
+                .filter(|(_, watcher)| watcher.schedule.burn <= watcher.max_burn)
+                .min_by_key(|(_, watcher)| watcher.schedule.burn)
+                .map(|(i, _)| i);
+
+            if let Some(i) = ready {
+                let watcher = watchers.swap_remove(i);
+                let netuid = watcher.registration_data.subnet_id;
+                println!(
+                    "\n🔥 Subnet {} burn {} is within limit, firing pre-signed registration at block {}",
+                    netuid,
+                    utils::format_tao(watcher.schedule.burn as u128),
+                    block_number
+                );
+
+                match self
+                    .client
+                    .submit_signed_extrinsic(&watcher.extrinsic)
+                    .await
+                {
+                    Ok(tx_hash) => {
+                        // First success wins, the remaining watchers are dropped here
+                        println!("\n🎉 Registration submitted!");
+                        println!("   Transaction hash: {}", tx_hash);
+                        println!("   Subnet: {}", netuid);
+                        println!(
+                            "   Burn: {}",
+                            utils::format_tao(watcher.schedule.burn as u128)
+                        );
+                        if !watchers.is_empty() {
+                            println!("   Stopped watching {} other subnet(s)", watchers.len());
+                        }
+
+                        return self.verify_registration(netuid, &hotkey_account).await;
+                    }
+                    Err(e) => {
+                        println!("❌ Subnet {} submission failed: {}", netuid, e);
+                        if watchers.is_empty() {
+                            return Err(anyhow!("All snipe targets failed"));
+                        }
+                        continue;
+                    }
+                }
+            }
+
+            self.client.ensure_not_cancelled()?;
+            let header = tokio::select! {
+                header = heads.next() => header,
+                _ = self.cancellation_token().cancelled_owned() => {
+                    return Err(RegistrationError::new(ErrorKind::Fatal, "Cancelled").into())
+                }
+            };
+            let header = header
+                .ok_or_else(|| {
+                    RegistrationError::new(ErrorKind::Connection, "New heads subscription closed")
+                })?
+                .context("Invalid block header in subscription")?;
+            block_number = header.number;
+
+            // Keep the signing context fresh so the pre-signed extrinsics never expire,
+            // re-signing halfway through the era
+            let refresh = context
+                .era
+                .period()
+                .is_some_and(|period| block_number >= context.block_number + period / 2);
+            if refresh {
+                context = self.client.fetch_signing_context(&coldkey_account).await?;
+            }
+
+            for watcher in &mut watchers {
+                let netuid = watcher.registration_data.subnet_id;
+                let mut resign = refresh;
+
+                if block_number >= watcher.schedule.next_adjustment_block() {
+                    // Adjustment boundary reached, recompute the effective burn
+                    let previous_burn = watcher.schedule.burn;
+                    watcher.schedule = self.client.get_burn_schedule(netuid).await?;
+                    println!(
+                        "⚖️ Subnet {} | block {} | burn adjusted {} → {} | next adjustment at block {}",
+                        netuid,
+                        utils::format_thousands(block_number),
+                        utils::format_tao(previous_burn as u128),
+                        utils::format_tao(watcher.schedule.burn as u128),
+                        utils::format_thousands(watcher.schedule.next_adjustment_block())
+                    );
+                    resign |= watcher.schedule.burn != previous_burn;
+                } else {
+                    println!(
+                        "   Subnet {} | block {} | burn {} | adjustment in {}",
+                        netuid,
+                        utils::format_thousands(block_number),
+                        utils::format_tao(watcher.schedule.burn as u128),
+                        utils::format_countdown(
+                            watcher.schedule.next_adjustment_block() - block_number
+                        )
+                    );
+                }
+
+                if resign {
+                    // Re-signing is local, no extra round trips before submission
+                    watcher.registration_data.burn_amount = watcher.schedule.burn;
+                    watcher.registration_data.block_number = context.block_number;
+                    watcher.extrinsic = self.client.build_burned_registration(
+                        &watcher.registration_data,
+                        &coldkey_pair,
+                        &context,
+                    )?;
+                }
+            }
+        }
+    }
+
+    // Sleep until the chain's rate limits allow registering this hotkey
+    async fn wait_for_registration_cooldown(
+        &self,
+        netuid: u16,
+        hotkey_account: &AccountId32,
+    ) -> Result<()> {
+        loop {
+            let cooldown = self
+                .client
+                .get_registration_cooldown(netuid, hotkey_account)
+                .await?;
+            let blocks = cooldown.blocks_remaining();
+            if blocks == 0 {
+                return Ok(());
+            }
+
+            println!(
+                "⏳ Registration cooldown: {} ({}, until block {})",
+                cooldown.reason.as_deref().unwrap_or("rate limited"),
+                utils::format_countdown(blocks),
+                utils::format_thousands(cooldown.ready_at_block)
+            );
+            self.wait_blocks(blocks, "Registration cooldown").await?;
+        }
+    }
+
+    // Pause for a number of blocks, counted on a bar when drawn
+    async fn wait_blocks(&self, blocks: u64, message: &str) -> Result<()> {
+        let progress = Progress::bar(blocks, message);
+        if !progress.is_drawn() {
+            return self.client.pause(utils::blocks_to_duration(blocks)).await;
+        }
+        for _ in 0..blocks {
+            self.client.pause(utils::block_time()).await?;
+            progress.inc(1);
+        }
+        Ok(())
+    }
+
+    // Fail fast when registration is closed, or wait for the next interval if allowed to
+    async fn wait_for_registration_window(
+        &self,
+        mut subnet_info: SubnetInfo,
+        wait: bool,
+    ) -> Result<SubnetInfo> {
+        loop {
+            match subnet_info.registration_window() {
+                RegistrationWindow::Open => return Ok(subnet_info),
+                RegistrationWindow::Disabled => {
+                    return Err(RegistrationError::new(
+                        ErrorKind::RegistrationClosed,
+                        format!("Registration is disabled on subnet {}", subnet_info.netuid),
+                    )
+                    .into())
+                }
+                RegistrationWindow::IntervalFull {
+                    registrations,
+                    limit,
+                } => {
+                    let schedule = self.client.get_burn_schedule(subnet_info.netuid).await?;
+                    let current_block = self.client.get_current_block().await?;
+                    let reopens_at = schedule.next_adjustment_block();
+                    if !wait {
+                        return Err(RegistrationError::new(
+                            ErrorKind::RegistrationClosed,
+                            format!(
+                                "Subnet {} registration interval is full ({}/{}), reopens at block {}. Use --wait-for-window to wait",
+                                subnet_info.netuid,
+                                registrations,
+                                limit,
+                                utils::format_thousands(reopens_at)
+                            ),
+                        )
+                        .into());
+                    }
+
+                    let blocks = reopens_at.saturating_sub(current_block).max(1);
+                    println!(
+                        "⏳ Registration interval full ({}/{}), waiting {} until block {}",
+                        registrations,
+                        limit,
+                        utils::format_countdown(blocks),
+                        utils::format_thousands(reopens_at)
+                    );
+                    self.wait_blocks(blocks, "Waiting for the registration window")
+                        .await?;
+                    subnet_info = self
+                        .client
+                        .get_subnet_info(subnet_info.netuid, false)
+                        .await?;
+                }
+            }
+        }
+    }
+
+    // Benchmark the endpoint and refuse (or warn) when its p95 latency is over the SLA,
+    // a late submission from a slow endpoint burns TAO for nothing
+    pub async fn check_endpoint_latency(&self, sla: &LatencySla) -> Result<()> {
+        println!("⏱️ Measuring endpoint latency...");
+        let report = LatencyReport::new(self.client.measure_latency(sla.samples).await?);
+        report.print();
+
+        if report.meets(sla) {
+            println!("✅ p95 latency within {:.0?}", sla.max_p95);
+            return Ok(());
+        }
+        let message = format!(
+            "Endpoint p95 latency {:.0?} exceeds {:.0?}",
+            report.p95(),
+            sla.max_p95
+        );
+        if sla.enforce {
+            return Err(anyhow!(
+                "{}. Use a closer endpoint, raise --max-p95-ms or pass --latency-warn-only",
+                message
+            ));
+        }
+        warnings::emit(WarningCode::SlowEndpoint, message);
+        Ok(())
+    }
+
+    // Burn registration
+    async fn perform_burn_registration(
+        &self,
+        subnet_info: &SubnetInfo,
+        hotkey_account: &AccountId32,
+        coldkey_account: &AccountId32,
+        current_block: u64,
+        options: &RegistrationOptions,
+    ) -> Result<RegistrationData> {
+        println!("\n🔥 Preparing burn registration...");
+
+        // A burn below the live cost is guaranteed to fail after fees
+        let live_burn = subnet_info.burn;
+        let burn_amount = match options.burn_amount {
+            Some(amount) if amount < live_burn && !options.auto_adjust => {
+                return Err(anyhow!(
+                    "Burn amount {} is below the current burn {}. Use --burn-amount {} or --auto-adjust",
+                    utils::format_tao(amount as u128),
+                    utils::format_tao(live_burn as u128),
+                    live_burn
+                ));
+            }
+            Some(amount) if amount < live_burn => {
+                warnings::emit(
+                    WarningCode::BurnAdjusted,
+                    format!(
+                        "Adjusting burn amount {} → {} (current burn)",
+                        utils::format_tao(amount as u128),
+                        utils::format_tao(live_burn as u128)
+                    ),
+                );
+                live_burn
+            }
+            Some(amount) => amount,
+            None => live_burn,
+        };
+        // The burn can spike between estimate and submit, never pay past the cap
+        if let Some(max_burn) = options.max_burn.filter(|max| burn_amount > *max) {
+            return Err(burn_over_max(burn_amount, max_burn).into());
+        }
+        subnet_info.check_burn_bounds(burn_amount)?;
+
+        println!("   Burn amount: {}", utils::format_tao(burn_amount as u128));
+
+        // The fee comes on top of the burn, an account holding just the burn fails at submit
+        let call = self
+            .client
+            .encode_burned_register_call(subnet_info.netuid, hotkey_account.clone())?;
+        let fee = match self.client.estimate_call_fee(&call, coldkey_account).await {
+            Ok(fee) => {
+                println!("   Fee: {}", utils::format_tao(fee));
+                Some(fee)
+            }
+            Err(e) => {
+                warnings::emit(
+                    WarningCode::FeeUnknown,
+                    format!("Fee estimation failed: {}", e),
+                );
+                None
+            }
+        };
+        let required = burn_amount as u128 + fee.unwrap_or(0);
+
+        // Checking balance
+        let balance = self.client.get_account_balance(coldkey_account).await? as u128;
+        if balance < required {
+            return Err(RegistrationError::new(
+                ErrorKind::Balance,
+                format!(
+                    "Insufficient balance. Required: {}{}, Available: {}",
+                    utils::format_tao(required),
+                    if fee.is_some() { " (burn + fee)" } else { "" },
+                    utils::format_tao(balance)
+                ),
+            )
+            .into());
+        }
+
+        println!("✅ Sufficient balance confirmed");
+
+        Ok(RegistrationData {
+            subnet_id: subnet_info.netuid,
+            hotkey: hotkey_account.clone(),
+            coldkey: coldkey_account.clone(),
+            burn_amount,
+            block_number: current_block,
+            fee,
+        })
+    }
+
+    // Reading the burn afresh, past the subnet cache, and refusing one above max_burn
+    async fn check_live_burn(&self, netuid: u16, max_burn: u64) -> Result<()> {
+        self.client.invalidate_subnet(netuid);
+        let burn = self.client.get_subnet_info(netuid, false).await?.burn;
+        if burn > max_burn {
+            return Err(burn_over_max(burn, max_burn).into());
+        }
+        Ok(())
+    }
+
+    // Show burn, fee and resulting balance, then ask before spending
+    async fn confirm_registration(
+        &self,
+        registration_data: &RegistrationData,
+        fee: Option<u128>,
+    ) -> Result<bool> {
+        let balance = self
+            .client
+            .get_account_balance(&registration_data.coldkey)
+            .await? as u128;
+        let burn = registration_data.burn_amount as u128;
+        let total = burn + fee.unwrap_or(0);
+
+        println!("\n💸 About to spend:");
+        println!("   Burn: {}", utils::format_tao(burn));
+        match fee {
+            Some(fee) => println!("   Fee: {}", utils::format_tao(fee)),
+            None => println!("   Fee: unknown"),
+        }
+        if fee.is_some() {
+            println!("   Total: {}", utils::format_tao(total));
+        }
+        println!("   Balance: {}", utils::format_tao(balance));
         println!(
-            "   Burn cost: {}",
+            "   Balance after: {}",
+            utils::format_tao(balance.saturating_sub(total))
+        );
+
+        utils::confirm("Proceed with registration?")
+    }
+
+    // Simulate the signed registration and report what would happen
+    async fn report_dry_run(
+        &self,
+        registration_data: &RegistrationData,
+        coldkey_pair: &KeyPair,
+    ) -> Result<()> {
+        println!("\n🧪 Dry run: building and signing registration extrinsic...");
+
+        let context = self
+            .client
+            .fetch_signing_context(&registration_data.coldkey)
+            .await?;
+        let extrinsic =
+            self.client
+                .build_burned_registration(registration_data, coldkey_pair, &context)?;
+
+        let fee = match self.client.get_extrinsic_fee(&extrinsic).await {
+            Ok(fee) => Some(fee),
+            Err(e) => {
+                warnings::emit(
+                    WarningCode::FeeUnknown,
+                    format!("Fee estimation failed: {}", e),
+                );
+                None
+            }
+        };
+        let outcome = self.client.dry_run_extrinsic(&extrinsic).await?;
+
+        println!("\n🧪 Dry Run Report:");
+        println!("   Extrinsic size: {} bytes", extrinsic.len());
+        println!("   Nonce: {}", context.nonce);
+        println!(
+            "   Burn: {}",
+            utils::format_tao(registration_data.burn_amount as u128)
+        );
+        match fee {
+            Some(fee) => {
+                println!("   Fee: {}", utils::format_tao(fee));
+                println!(
+                    "   Total cost: {}",
+                    utils::format_tao(registration_data.burn_amount as u128 + fee)
+                );
+            }
+            None => println!("   Fee: unknown"),
+        }
+
+        if outcome.would_succeed {
+            println!("   Result: ✅ would succeed ({})", outcome.detail);
+            println!("\nℹ️ Dry run only, nothing was submitted");
+            Ok(())
+        } else {
+            println!("   Result: ❌ would fail ({})", outcome.detail);
+            // The detail is the runtime's dispatch or validity error, named by the node
+            Err(RegistrationError::new(
+                errors::node_error_kind(&outcome.detail).unwrap_or(ErrorKind::Chain),
+                format!("Dry run failed: {}", outcome.detail),
+            )
+            .into())
+        }
+    }
+
+    // Verification against the block the registration was included in. Only a node that has
+    // that block answers, so a lagging one behind a load balancer can't report stale state.
+    // The block's events settle it in one read, the Uids storage is the fallback
+    async fn verify_registration_at(
+        &self,
+        netuid: u16,
+        hotkey_account: &AccountId32,
+        inclusion: &Inclusion,
+    ) -> Result<()> {
+        let block_hash = inclusion.block_hash;
+        println!("\n🔍 Verifying registration at block {:?}...", block_hash);
+
+        match self
+            .client
+            .get_registration_events(inclusion, netuid, hotkey_account)
+            .await
+        {
+            Ok(Some(events)) => {
+                if let Some(uid) = events.uid {
+                    println!(
+                        "✅ Registration confirmed by its NeuronRegistered event! Assigned UID: {}",
+                        uid
+                    );
+                    return Ok(());
+                }
+                if let Some(ExtrinsicOutcome::Failed(error)) = events.outcome {
+                    return Err(RegistrationError::new(
+                        errors::node_error_kind(&error).unwrap_or(ErrorKind::Chain),
+                        format!(
+                            "Registration was included in block {:?} but failed on chain: {}",
+                            block_hash, error
+                        ),
+                    )
+                    .into());
+                }
+            }
+            Ok(None) => println!("   Extrinsic not found in the block, checking storage"),
+            Err(e) => println!("   Block events unavailable ({}), checking storage", e),
+        }
+
+        let progress = Progress::spinner("Reading the block");
+        for attempt in 1..=5 {
+            match self
+                .client
+                .get_uid_at(netuid, hotkey_account, block_hash)
+                .await
+            {
+                Ok(Some(uid)) => {
+                    progress.println(format!("✅ Registration verified! Assigned UID: {}", uid));
+                    return Ok(());
+                }
+                Ok(None) => {
+                    // Included but not registered: the call failed on chain
+                    return Err(RegistrationError::new(
+                        ErrorKind::Chain,
+                        format!(
+                            "Registration was included in block {:?} but the hotkey has no UID in subnet {}, the call failed on chain",
+                            block_hash, netuid
+                        ),
+                    )
+                    .into());
+                }
+                Err(e) => {
+                    progress.set_message(format!("Attempt {}/5: block not available yet", attempt));
+                    progress.plain_println(format!(
+                        "   Attempt {}/5: block not available yet ({})",
+                        attempt, e
+                    ));
+                    self.client.pause(utils::block_time() / 4).await?;
+                }
+            }
+        }
+
+        warnings::emit(
+            WarningCode::RegistrationUnverified,
+            format!(
+                "Could not read block {:?} to verify the registration in subnet {}",
+                block_hash, netuid
+            ),
+        );
+        Ok(())
+    }
+
+    // Balance change caused by a block, read at the block and its parent so both sides are
+    // consistent. Informational, failures are only reported
+    async fn report_balance_change(&self, account: &AccountId32, block_hash: H256) {
+        let balances = async {
+            let parent_hash = self.client.get_parent_hash(block_hash).await?;
+            let before = self
+                .client
+                .get_account_balance_at(account, parent_hash)
+                .await?;
+            let after = self
+                .client
+                .get_account_balance_at(account, block_hash)
+                .await?;
+            Ok::<_, anyhow::Error>((before, after))
+        };
+        match balances.await {
+            Ok((before, after)) => println!(
+                "   Balance: {} → {} ({}{})",
+                utils::format_tao(before as u128),
+                utils::format_tao(after as u128),
+                if after >= before { "+" } else { "-" },
+                utils::format_tao(after.abs_diff(before) as u128)
+            ),
+            Err(e) => println!("   Balance change unavailable: {}", e),
+        }
+    }
+
+    // Verification of registration success
+    async fn verify_registration(&self, netuid: u16, hotkey_account: &AccountId32) -> Result<()> {
+        println!("\n🔍 Verifying registration...");
+
+        let progress = Progress::spinner("Waiting for the next block");
+        for attempt in 1..=5 {
+            progress.set_message(format!("Attempt {}/5, waiting for the next block", attempt));
+            progress.plain_println(format!("   Attempt {}/5...", attempt));
+            self.client.pause(utils::block_time()).await?;
+
+            match self
+                .client
+                .check_registration(netuid, hotkey_account)
+                .await?
+            {
+                Some(neuron) => {
+                    drop(progress);
+                    println!("✅ Registration verified! Assigned UID: {}", neuron.uid);
+                    println!("   Active: {}", if neuron.active { "Yes" } else { "No" });
+                    println!(
+                        "   Stake: {}",
+                        utils::format_tao(neuron.stake.iter().map(|(_, s)| s).sum::<u64>() as u128)
+                    );
+                    return Ok(());
+                }
+                None => {
+                    if attempt < 5 {
+                        progress.plain_println("   Still processing, waiting for next block...");
+                    }
+                }
+            }
+        }
+        drop(progress);
+
+        warnings::emit(
+            WarningCode::RegistrationUnverified,
+            format!(
+                "Registration in subnet {} may still be processing. Check status manually in a few minutes.",
+                netuid
+            ),
+        );
+        Ok(())
+    }
+
+    // Walk through the steps every miner checks after registering
+    pub async fn run_post_register_checklist(&self, netuid: u16, hotkey_path: &str) -> Result<()> {
+        println!(
+            "📋 Running post-registration checklist for subnet {}...",
+            netuid
+        );
+
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+
+        let mut items = Vec::new();
+
+        // 1. UID assignment
+        let uid = self.client.get_uid(netuid, &hotkey_account).await?;
+        items.push(ChecklistItem {
+            name: "UID assigned",
+            passed: Some(uid.is_some()),
+            detail: match uid {
+                Some(uid) => format!("UID {}", uid),
+                None => "hotkey not found in Uids".to_string(),
+            },
+        });
+
+        if let Some(uid) = uid {
+            // 2. Axon served
+            let axon = self.client.get_axon_info(netuid, &hotkey_account).await?;
+            items.push(match axon {
+                Some(axon) if axon.ip != 0 && axon.port != 0 => ChecklistItem {
+                    name: "Axon served",
+                    passed: Some(true),
+                    detail: format!(
+                        "{}:{} (block {})",
+                        utils::format_ip(axon.ip, axon.ip_type),
+                        axon.port,
+                        axon.block
+                    ),
+                },
+                _ => ChecklistItem {
+                    name: "Axon served",
+                    passed: Some(false),
+                    detail: "no axon published".to_string(),
+                },
+            });
+
+            // 3. Prometheus served
+            let prometheus = self
+                .client
+                .get_prometheus_info(netuid, &hotkey_account)
+                .await?;
+            items.push(match prometheus {
+                Some(prometheus) if prometheus.ip != 0 && prometheus.port != 0 => ChecklistItem {
+                    name: "Prometheus served",
+                    passed: Some(true),
+                    detail: format!(
+                        "{}:{} (block {})",
+                        utils::format_ip(prometheus.ip, prometheus.ip_type),
+                        prometheus.port,
+                        prometheus.block
+                    ),
+                },
+                _ => ChecklistItem {
+                    name: "Prometheus served",
+                    passed: Some(false),
+                    detail: "no prometheus endpoint published".to_string(),
+                },
+            });
+
+            // 4. First weight received from a validator
+            let permits = self.client.get_validator_permits(netuid).await?;
+            let mut weighting_validators = 0usize;
+            for (validator_uid, _) in permits.iter().enumerate().filter(|(_, permit)| **permit) {
+                let weights = self
+                    .client
+                    .get_weights(netuid, validator_uid as u16)
+                    .await?;
+                if weights
+                    .iter()
+                    .any(|(target, weight)| *target == uid && *weight > 0)
+                {
+                    weighting_validators += 1;
+                }
+            }
+            items.push(ChecklistItem {
+                name: "Weight received",
+                passed: Some(weighting_validators > 0),
+                detail: format!(
+                    "{} validator(s) set weight on UID {}",
+                    weighting_validators, uid
+                ),
+            });
+
+            // 5. First nonzero emission
+            let emissions = self.client.get_emissions(netuid).await?;
+            let emission = emissions.get(uid as usize).copied().unwrap_or(0);
+            items.push(ChecklistItem {
+                name: "Nonzero emission",
+                passed: Some(emission > 0),
+                detail: format!("{} per tempo", utils::format_tao(emission as u128)),
+            });
+        } else {
+            for name in [
+                "Axon served",
+                "Prometheus served",
+                "Weight received",
+                "Nonzero emission",
+            ] {
+                items.push(ChecklistItem {
+                    name,
+                    passed: None,
+                    detail: "skipped (no UID)".to_string(),
+                });
+            }
+        }
+
+        println!("\n✔️ Checklist:");
+        for item in &items {
+            let mark = match item.passed {
+                Some(true) => "✅",
+                Some(false) => "❌",
+                None => "⏭️",
+            };
+            println!("   {} {:<18} {}", mark, item.name, item.detail);
+        }
+
+        let passed = items.iter().filter(|i| i.passed == Some(true)).count();
+        println!("\n   Result: {}/{} checks passed", passed, items.len());
+
+        for item in items.iter().filter(|i| i.passed == Some(false)) {
+            if let Some(code) = item.warning_code() {
+                warnings::emit(code, format!("{}: {}", item.name, item.detail));
+            }
+        }
+
+        if passed < items.len() {
+            return Err(anyhow!(
+                "{} of {} checklist items not passed",
+                items.len() - passed,
+                items.len()
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub async fn estimate_registration_cost(
+        &self,
+        netuid: u16,
+        history_intervals: usize,
+        history_path: Option<&str>,
+    ) -> Result<()> {
+        println!("💰 Estimating registration costs for subnet {}...", netuid);
+        println!("═══════════════════════════════════════════════════");
+
+        let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+
+        println!("\n📊 Cost Analysis:");
+        println!("┌─ Burn Registration (Instant)");
+        println!(
+            "│  ├─ Cost: {}",
             utils::format_tao(subnet_info.burn as u128)
         );
-        println!("   Tempo: {} blocks", subnet_info.tempo);
-        println!("   Immunity period: {} blocks", subnet_info.immunity_period);
         println!(
-            "   Min allowed weights: {}",
-            subnet_info.min_allowed_weights
+            "│  ├─ USD equivalent: {}",
+            self.format_usd(subnet_info.burn).await
+        );
+        println!(
+            "│  ├─ Bounds: {} - {}",
+            utils::format_tao(subnet_info.min_burn as u128),
+            utils::format_tao(subnet_info.max_burn as u128)
+        );
+        println!(
+            "│  └─ Processing time: 1-2 blocks ({})",
+            utils::format_duration(utils::blocks_to_duration(2))
+        );
+
+        // Since dTAO the burn is priced against the subnet's alpha
+        let pool = self.client.get_subnet_pool(netuid).await?;
+        if netuid != 0 && pool.alpha_in > 0 {
+            println!("┌─ In alpha terms");
+            println!("│  ├─ Alpha price: {:.6} TAO", pool.price());
+            println!(
+                "│  ├─ Burn at spot price: {}",
+                utils::format_alpha((subnet_info.burn as f64 / pool.price()) as u128)
+            );
+            println!(
+                "│  └─ Burn swapped through the pool: {}",
+                utils::format_alpha(pool.alpha_for_tao(subnet_info.burn) as u128)
+            );
+        }
+
+        let schedule = self.client.get_burn_schedule(netuid).await?;
+        let current_block = self.client.get_current_block().await?;
+        let next_adjustment = schedule.next_adjustment_block();
+        let projected = schedule.projected_burn();
+        let change = if schedule.burn > 0 {
+            (projected as f64 - schedule.burn as f64) / schedule.burn as f64 * 100.0
+        } else {
+            0.0
+        };
+
+        println!("\n📈 Burn Forecast:");
+        println!("┌─ Current vs projected");
+        println!(
+            "│  ├─ Current: {}",
+            utils::format_tao(schedule.burn as u128)
+        );
+        println!(
+            "│  ├─ Projected: {} ({:+.1}%)",
+            utils::format_tao(projected as u128),
+            change
+        );
+        println!(
+            "│  ├─ Registrations this interval: {} (target {})",
+            schedule.registrations_this_interval, schedule.target_registrations_per_interval
+        );
+        println!(
+            "│  └─ Next adjustment: block {} (in {})",
+            utils::format_thousands(next_adjustment),
+            utils::format_countdown(next_adjustment.saturating_sub(current_block))
+        );
+        if projected < schedule.burn {
+            println!("💡 The burn is projected to drop, waiting for the adjustment may be cheaper");
+        }
+
+        let owner_cut = self.client.get_subnet_owner_cut().await?;
+        let returns = analysis::miner_return(
+            subnet_info.emission_value,
+            owner_cut,
+            subnet_info.subnetwork_n,
+            subnet_info.burn,
+        );
+        println!("\n💹 Miner Return (average miner, per day):");
+        println!(
+            "┌─ Subnet emission: {}",
+            utils::format_tao(returns.gross_per_day as u128)
+        );
+        println!(
+            "│  ├─ Owner take ({:.1}%): -{}",
+            owner_cut as f64 / u16::MAX as f64 * 100.0,
+            utils::format_tao(returns.owner_take_per_day as u128)
+        );
+        println!(
+            "│  ├─ Miner pool ({:.0}% of the rest): {}",
+            analysis::MINER_EMISSION_SHARE * 100.0,
+            utils::format_tao(returns.miner_pool_per_day as u128)
+        );
+        println!(
+            "│  ├─ Per miner ({} neurons): {} ({})",
+            subnet_info.subnetwork_n,
+            utils::format_tao(returns.per_miner_per_day as u128),
+            self.format_usd(returns.per_miner_per_day).await
+        );
+        match returns.payback_days {
+            Some(days) => println!("│  └─ Burn payback: ~{:.1} days", days),
+            None => println!("│  └─ Burn payback: never at the current emission"),
+        }
+
+        if history_intervals > 0 {
+            let samples = match history_path {
+                Some(path) => recorded_burn_history(
+                    path,
+                    netuid,
+                    &schedule,
+                    history_intervals,
+                    current_block,
+                )?,
+                None => {
+                    self.sample_burn_history(netuid, &schedule, history_intervals)
+                        .await?
+                }
+            };
+            print_burn_history(&samples, history_intervals, schedule.burn);
+        }
+
+        println!("\n🚪 Registration Window:");
+        self.print_registration_window(&subnet_info);
+
+        Ok(())
+    }
+
+    // Burn right after each of the last adjustments, oldest first, skipping pruned state
+    async fn sample_burn_history(
+        &self,
+        netuid: u16,
+        schedule: &BurnSchedule,
+        intervals: usize,
+    ) -> Result<Vec<(String, u64)>> {
+        let interval = schedule.adjustment_interval.max(1) as u64;
+        let mut samples = Vec::with_capacity(intervals);
+        for k in (0..intervals as u64).rev() {
+            let Some(block) = schedule.last_adjustment_block.checked_sub(k * interval) else {
+                continue;
+            };
+            if let Some(burn) = self.client.get_burn_at(netuid, block).await? {
+                samples.push((format!("block {}", utils::format_thousands(block)), burn));
+            }
+        }
+        Ok(samples)
+    }
+
+    // Print registration details, returning the UID when registered
+    pub async fn check_status(&self, netuid: u16, hotkey_path: &str) -> Result<Option<u16>> {
+        println!("🔍 Checking registration status...");
+
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+
+        let uid = match self
+            .client
+            .check_registration(netuid, &hotkey_account)
+            .await?
+        {
+            Some(neuron) => {
+                println!("✅ Neuron is registered in subnet {}!", netuid);
+                println!("\n📊 Neuron Details:");
+                println!("   UID: {}", neuron.uid);
+                println!("   Hotkey: {}", neuron.hotkey.to_ss58check());
+                println!("   Coldkey: {}", neuron.coldkey.to_ss58check());
+                println!("   Active: {}", if neuron.active { "Yes" } else { "No" });
+                println!(
+                    "   Stake: {}",
+                    utils::format_tao(neuron.stake.iter().map(|(_, s)| s).sum::<u64>() as u128)
+                );
+                println!(
+                    "   Emission: {}",
+                    utils::format_tao(neuron.emission as u128)
+                );
+                println!("   Rank: {}", utils::format_u16_ratio(neuron.rank));
+                println!("   Trust: {}", utils::format_u16_ratio(neuron.trust));
+                println!(
+                    "   Consensus: {}",
+                    utils::format_u16_ratio(neuron.consensus)
+                );
+                println!(
+                    "   Incentive: {}",
+                    utils::format_u16_ratio(neuron.incentive)
+                );
+                println!(
+                    "   Dividends: {}",
+                    utils::format_u16_ratio(neuron.dividends)
+                );
+                println!(
+                    "   Validator trust: {}",
+                    utils::format_u16_ratio(neuron.validator_trust)
+                );
+                println!(
+                    "   Last update: block {}",
+                    utils::format_thousands(neuron.last_update)
+                );
+                println!("   Validator permit: {}", neuron.validator_permit);
+                if neuron.axon_info.ip != 0 {
+                    println!(
+                        "   Axon: {}:{}",
+                        utils::format_ip(neuron.axon_info.ip, neuron.axon_info.ip_type),
+                        neuron.axon_info.port
+                    );
+                } else {
+                    println!("   Axon: not served");
+                }
+
+                // Show additional statistics
+                let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+                println!("\n📈 Subnet Statistics:");
+                println!(
+                    "   Total neurons: {}/{}",
+                    subnet_info.registered_neurons, subnet_info.max_allowed_uids
+                );
+                println!("   Registration difficulty: {}", subnet_info.difficulty);
+                println!(
+                    "   Burn cost: {}",
+                    utils::format_tao(subnet_info.burn as u128)
+                );
+                Some(neuron.uid)
+            }
+            None => {
+                println!(
+                    "❌ Hotkey {} is NOT registered in subnet {}",
+                    hotkey_account.to_ss58check(),
+                    netuid
+                );
+
+                // Show possible registration information
+                let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+                println!("\n💡 Registration options:");
+                println!(
+                    "   Burn cost: {}",
+                    utils::format_tao(subnet_info.burn as u128)
+                );
+                None
+            }
+        };
+
+        Ok(uid)
+    }
+
+    // Netuid for --subnet-name
+    // Subnet info without printing, for callers that render it themselves
+    pub async fn subnet_info(&self, netuid: u16) -> Result<SubnetInfo> {
+        self.client.get_subnet_info(netuid, false).await
+    }
+
+    // UID of a hotkey in a subnet, None when it isn't registered
+    pub async fn registered_uid(&self, netuid: u16, hotkey: &AccountId32) -> Result<Option<u16>> {
+        self.client.get_uid(netuid, hotkey).await
+    }
+
+    pub async fn resolve_subnet_name(&self, name: &str) -> Result<u16> {
+        let netuid = self.client.find_subnet_by_name(name).await?;
+        println!("🏷️ Subnet {} is netuid {}", name, netuid);
+        Ok(netuid)
+    }
+
+    // Every subnet a hotkey is registered in, with its UID, stake and immunity
+    pub async fn show_registrations(
+        &self,
+        hotkey_path: &str,
+        format: &str,
+        output: Option<&str>,
+    ) -> Result<()> {
+        utils::check_output_format(format, &["text", "csv"], output)?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        if format == "text" {
+            println!(
+                "🔍 Searching every subnet for hotkey {}...",
+                hotkey_account.to_ss58check()
+            );
+        }
+
+        let registrations = self.client.find_registrations(&hotkey_account).await?;
+        let current_block = self.client.get_current_block().await?;
+        let mut rows = Vec::new();
+        for (netuid, uid) in registrations {
+            let name = self.client.get_subnet_name(netuid).await.unwrap_or(None);
+            let stake = self
+                .client
+                .get_hotkey_alpha(&hotkey_account, netuid)
+                .await?;
+            let registered_at = self.client.get_block_at_registration(netuid, uid).await?;
+            let immunity_period = self.client.get_immunity_period(netuid).await?;
+            let immunity_remaining =
+                (registered_at + immunity_period as u64).saturating_sub(current_block);
+            rows.push((netuid, name, uid, stake, registered_at, immunity_remaining));
+        }
+
+        if format == "csv" {
+            let rows: Vec<Vec<String>> = rows
+                .into_iter()
+                .map(|(netuid, name, uid, stake, registered_at, remaining)| {
+                    vec![
+                        netuid.to_string(),
+                        name.unwrap_or_default(),
+                        uid.to_string(),
+                        utils::rao_to_tao_string(stake as u128),
+                        registered_at.to_string(),
+                        remaining.to_string(),
+                    ]
+                })
+                .collect();
+            return utils::write_csv(output, utils::REGISTRATIONS_CSV_HEADER, &rows);
+        }
+
+        if rows.is_empty() {
+            println!("❌ Not registered in any subnet");
+            return Ok(());
+        }
+        println!("\n📋 Registered in {} subnets:", rows.len());
+        println!(
+            "   {:>6}  {:<20} {:>5}  {:>12}  Immunity",
+            "Netuid", "Name", "UID", "Stake"
+        );
+        for (netuid, name, uid, stake, _, remaining) in rows {
+            let immunity = if remaining > 0 {
+                format!("🛡️ {} left", utils::format_blocks(remaining))
+            } else {
+                "expired".to_string()
+            };
+            println!(
+                "   {:>6}  {:<20} {:>5}  {:>12}  {}",
+                netuid,
+                name.unwrap_or_else(|| "-".to_string()),
+                uid,
+                utils::format_tao(stake as u128),
+                immunity
+            );
+        }
+
+        Ok(())
+    }
+
+    // Replace a hotkey's children in a subnet, children given as (hotkey, proportion in
+    // billionths). An empty list revokes them all
+    pub async fn set_children(
+        &self,
+        wallet_path: &str,
+        hotkey_path: &str,
+        netuid: u16,
+        new_children: &[(String, u64)],
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            format!("👶 Setting Children in Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let mut proportions = Vec::with_capacity(new_children.len());
+        for (child, parts) in new_children {
+            let child = key_utils::account_id_from_string(child).kind_context(
+                ErrorKind::Fatal,
+                format!("Failed to load child hotkey {}", child),
+            )?;
+            proportions.push((children::to_proportion(*parts), child));
+        }
+        children::validate(&hotkey_account, &proportions)?;
+
+        // Only the coldkey owning the parent may set its children
+        let owner = self.client.get_hotkey_owner(&hotkey_account).await?;
+        if owner.as_ref() != Some(&coldkey_pair.account_id()) {
+            return Err(anyhow!(
+                "Hotkey {} is not owned by coldkey {}",
+                hotkey_account.to_ss58check(),
+                coldkey_pair.account_id().to_ss58check()
+            ));
+        }
+
+        println!("   Parent: {}", hotkey_account.to_ss58check());
+        let current = self.client.get_children(&hotkey_account, netuid).await?;
+        print_children("Current children", &current);
+        print_children("New children", &proportions);
+
+        if !assume_yes && !utils::confirm("Proceed with setting children?")? {
+            return Err(
+                RegistrationError::new(ErrorKind::Fatal, "Setting children cancelled").into(),
+            );
+        }
+
+        let inclusion = self
+            .client
+            .submit_set_children(&hotkey_account, netuid, &proportions, &coldkey_pair)
+            .await?;
+        println!("\n✅ Children set");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // A hotkey's children and parents in a subnet, with its own childkey take
+    pub async fn show_child_info(&self, hotkey_path: &str, netuid: u16) -> Result<()> {
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        println!(
+            "{}",
+            format!("👪 Child Hotkeys in Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+
+        let take = self
+            .client
+            .get_childkey_take(&hotkey_account, netuid)
+            .await?;
+        println!("   Childkey take: {:.2}%", take_percent(take));
+
+        let children = self.client.get_children(&hotkey_account, netuid).await?;
+        print_children("Children", &children);
+        for (_, child) in &children {
+            let take = self.client.get_childkey_take(child, netuid).await?;
+            println!(
+                "     {} takes {:.2}%",
+                child.to_ss58check(),
+                take_percent(take)
+            );
+        }
+        let parents = self.client.get_parents(&hotkey_account, netuid).await?;
+        print_children("Parents", &parents);
+
+        Ok(())
+    }
+
+    // Commit salted weights for a subnet with commit-reveal on, keeping the weights and salt
+    // locally for reveal_weights
+    pub async fn commit_weights(
+        &self,
+        hotkey_path: &str,
+        netuid: u16,
+        uids: Vec<u16>,
+        values: Vec<u16>,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            format!("🔒 Committing Weights in Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let hotkey_pair = key_utils::load_keypair_from_file(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let hotkey_account = hotkey_pair.account_id();
+        if self
+            .client
+            .get_uid(netuid, &hotkey_account)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow!(
+                "Hotkey {} is not registered in subnet {}",
+                hotkey_account.to_ss58check(),
+                netuid
+            ));
+        }
+        let params = self.client.get_commit_reveal_params(netuid).await?;
+        if !params.enabled {
+            return Err(anyhow!(
+                "Commit-reveal is off in subnet {}, its weights are set directly",
+                netuid
+            ));
+        }
+
+        let salt = commit_reveal::random_salt()?;
+        let commit_hash = commit_reveal::commit_hash(
+            &hotkey_account,
+            netuid,
+            &uids,
+            &values,
+            &salt,
+            params.version_key,
+        );
+        let current_block = self.client.get_current_block().await?;
+        let window = commit_reveal::reveal_window(
+            current_block + 1,
+            netuid,
+            params.tempo,
+            params.reveal_period,
+        );
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Weights: {} UIDs", uids.len());
+        println!("   Version key: {}", params.version_key);
+        println!("   Commit hash: {:?}", commit_hash);
+        println!(
+            "   Reveal window: blocks {}-{}, opens in {}",
+            window.first_block,
+            window.last_block,
+            utils::format_blocks(window.first_block.saturating_sub(current_block))
+        );
+
+        if !assume_yes && !utils::confirm("Proceed with the commit?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Commit cancelled").into());
+        }
+
+        let inclusion = self
+            .client
+            .submit_commit_weights(netuid, commit_hash, &hotkey_pair)
+            .await?;
+        let commit = WeightsCommit {
+            netuid,
+            hotkey: hotkey_account.to_ss58check(),
+            uids,
+            values,
+            salt,
+            version_key: params.version_key,
+            commit_hash: format!("{:?}", commit_hash),
+            commit_block: self.client.get_block_number(inclusion.block_hash).await?,
+            committed_at: chrono::Utc::now(),
+        };
+        let window = commit_reveal::reveal_window(
+            commit.commit_block,
+            netuid,
+            params.tempo,
+            params.reveal_period,
+        );
+
+        // Without the salt the commit can never be revealed, print it if it can't be kept
+        let store = CommitStore::open()?;
+        if let Err(e) = store.add(commit.clone()) {
+            println!(
+                "⚠️ Keep this to reveal by hand: {}",
+                serde_json::to_string(&commit)?
+            );
+            return Err(e);
+        }
+
+        println!("\n✅ Weights committed");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {}", commit.commit_block);
+        println!("   Salt saved to {}", store.path().display());
+        println!(
+            "   Reveal with reveal-weights between blocks {} and {}",
+            window.first_block, window.last_block
+        );
+
+        Ok(())
+    }
+
+    // Reveal the oldest pending commit of a hotkey in a subnet once its window is open,
+    // dropping commits whose window has passed
+    pub async fn reveal_weights(&self, hotkey_path: &str, netuid: u16, wait: bool) -> Result<()> {
+        println!(
+            "{}",
+            format!("🔓 Revealing Weights in Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let hotkey_pair = key_utils::load_keypair_from_file(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let hotkey = hotkey_pair.account_id().to_ss58check();
+        let store = CommitStore::open()?;
+        let mut pending: Vec<WeightsCommit> = store
+            .load()?
+            .into_iter()
+            .filter(|commit| commit.netuid == netuid && commit.hotkey == hotkey)
+            .collect();
+        pending.sort_by_key(|commit| commit.commit_block);
+        if pending.is_empty() {
+            return Err(anyhow!(
+                "No pending commits for {} in subnet {} in {}",
+                hotkey,
+                netuid,
+                store.path().display()
+            ));
+        }
+
+        let params = self.client.get_commit_reveal_params(netuid).await?;
+        let mut current_block = self.client.get_current_block().await?;
+        let mut next = None;
+        for commit in pending {
+            let window = commit_reveal::reveal_window(
+                commit.commit_block,
+                netuid,
+                params.tempo,
+                params.reveal_period,
+            );
+            if current_block > window.last_block {
+                println!(
+                    "⌛ Commit {} expired at block {}, dropping it",
+                    commit.commit_hash, window.last_block
+                );
+                store.remove(&commit.commit_hash)?;
+                continue;
+            }
+            next = Some((commit, window));
+            break;
+        }
+        let Some((commit, window)) = next else {
+            return Err(anyhow!("Every pending commit expired before its reveal"));
+        };
+
+        println!("   Hotkey: {}", hotkey);
+        println!(
+            "   Commit: {} at block {}",
+            commit.commit_hash, commit.commit_block
+        );
+        println!(
+            "   Reveal window: blocks {}-{}",
+            window.first_block, window.last_block
+        );
+        while current_block < window.first_block {
+            let remaining = window.first_block - current_block;
+            if !wait {
+                return Err(anyhow!(
+                    "The reveal window opens at block {}, in {}. Pass --wait to wait for it",
+                    window.first_block,
+                    utils::format_blocks(remaining)
+                ));
+            }
+            println!(
+                "⏳ Waiting {} for block {}...",
+                utils::format_blocks(remaining),
+                window.first_block
+            );
+            sleep(utils::blocks_to_duration(remaining)).await;
+            current_block = self.client.get_current_block().await?;
+        }
+
+        let inclusion = self
+            .client
+            .submit_reveal_weights(&commit, &hotkey_pair)
+            .await?;
+        store.remove(&commit.commit_hash)?;
+        println!("\n✅ Weights revealed");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Set the wallet coldkey's on-chain identity, replacing the whole of any existing one
+    pub async fn set_identity(
+        &self,
+        wallet_path: &str,
+        identity: &ChainIdentity,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!("{}", "🪪 Setting On-chain Identity".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        identity.validate()?;
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+
+        if let Some(current) = self.client.get_identity(&coldkey_account).await? {
+            println!("   Current identity:");
+            print_identity(&current);
+        }
+        println!("   New identity:");
+        print_identity(identity);
+
+        if !assume_yes && !utils::confirm("Proceed with setting the identity?")? {
+            return Err(
+                RegistrationError::new(ErrorKind::Fatal, "Setting identity cancelled").into(),
+            );
+        }
+
+        let inclusion = self
+            .client
+            .submit_set_identity(identity, &coldkey_pair)
+            .await?;
+        println!("\n✅ Identity set");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Identity of a coldkey, or of the coldkey owning a hotkey
+    pub async fn show_identity(&self, account: &str) -> Result<()> {
+        let account = key_utils::account_id_from_string(account).context("Invalid account")?;
+        let coldkey = match self.client.get_hotkey_owner(&account).await? {
+            Some(owner) => {
+                println!(
+                    "🔑 Hotkey {} is owned by coldkey {}",
+                    account.to_ss58check(),
+                    owner.to_ss58check()
+                );
+                owner
+            }
+            None => account,
+        };
+
+        match self.client.get_identity(&coldkey).await? {
+            Some(identity) => {
+                println!("🪪 Identity of {}:", coldkey.to_ss58check());
+                print_identity(&identity);
+            }
+            None => println!("❌ No identity set for {}", coldkey.to_ss58check()),
+        }
+        Ok(())
+    }
+
+    // Delegates from the DelegateInfo runtime API, most stake first
+    pub async fn show_delegates(
+        &self,
+        limit: Option<usize>,
+        format: &str,
+        output: Option<&str>,
+    ) -> Result<()> {
+        utils::check_output_format(format, &["text", "csv"], output)?;
+        let mut delegates = self.client.get_delegates().await?;
+        delegates.sort_by_key(|delegate| std::cmp::Reverse(delegate.total_stake()));
+        delegates.truncate(limit.unwrap_or(usize::MAX));
+
+        if format == "csv" {
+            let rows: Vec<Vec<String>> = delegates
+                .iter()
+                .map(|delegate| {
+                    vec![
+                        delegate.hotkey.to_ss58check(),
+                        delegate.owner.to_ss58check(),
+                        format!("{:.2}", take_percent(delegate.take)),
+                        utils::rao_to_tao_string(delegate.total_stake() as u128),
+                        delegate.nominators.len().to_string(),
+                        delegate
+                            .registrations
+                            .iter()
+                            .map(u16::to_string)
+                            .collect::<Vec<_>>()
+                            .join(" "),
+                    ]
+                })
+                .collect();
+            return utils::write_csv(output, utils::DELEGATES_CSV_HEADER, &rows);
+        }
+
+        println!("{}", "🤝 Delegates".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+        if delegates.is_empty() {
+            println!("❌ No delegates found");
+            return Ok(());
+        }
+        println!(
+            "   {:>4}  {:<48}  {:>6}  {:>16}  {:>10}",
+            "#", "Hotkey", "Take", "Total stake", "Nominators"
+        );
+        for (rank, delegate) in delegates.iter().enumerate() {
+            println!(
+                "   {:>4}  {:<48}  {:>5.2}%  {:>16}  {:>10}",
+                rank + 1,
+                delegate.hotkey.to_ss58check(),
+                take_percent(delegate.take),
+                utils::format_tao(delegate.total_stake() as u128),
+                delegate.nominators.len()
+            );
+        }
+
+        Ok(())
+    }
+
+    // Stake to a delegate's hotkey as a nominator, after checking it takes nominations
+    pub async fn delegate(
+        &self,
+        wallet_path: &str,
+        delegate_hotkey: &str,
+        amount: u64,
+        assume_yes: bool,
+    ) -> Result<()> {
+        self.print_delegate(delegate_hotkey).await?;
+        self.add_stake_alpha(
+            wallet_path,
+            delegate_hotkey,
+            constants::ROOT_NETUID,
+            amount,
+            assume_yes,
+        )
+        .await
+    }
+
+    // Take nominated stake back from a delegate
+    pub async fn undelegate(
+        &self,
+        wallet_path: &str,
+        delegate_hotkey: &str,
+        amount: u64,
+        assume_yes: bool,
+    ) -> Result<()> {
+        self.print_delegate(delegate_hotkey).await?;
+        self.remove_stake_alpha(
+            wallet_path,
+            delegate_hotkey,
+            constants::ROOT_NETUID,
+            amount,
+            assume_yes,
+        )
+        .await
+    }
+
+    // The delegate behind a hotkey, failing for hotkeys that don't take nominations
+    async fn print_delegate(&self, hotkey_path: &str) -> Result<()> {
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let delegates = self.client.get_delegates().await?;
+        let delegate = delegates
+            .iter()
+            .find(|delegate| delegate.hotkey == hotkey_account)
+            .ok_or_else(|| {
+                anyhow!(
+                    "{} is not a delegate, see the delegates command",
+                    hotkey_account.to_ss58check()
+                )
+            })?;
+
+        println!("🤝 Delegate {}", delegate.hotkey.to_ss58check());
+        println!("   Take: {:.2}%", take_percent(delegate.take));
+        println!(
+            "   Total stake: {} from {} nominators",
+            utils::format_tao(delegate.total_stake() as u128),
+            delegate.nominators.len()
+        );
+        Ok(())
+    }
+
+    pub async fn show_subnet_info(
+        &self,
+        netuid: u16,
+        format: &str,
+        output: Option<&str>,
+    ) -> Result<()> {
+        utils::check_output_format(format, &["text", "csv"], output)?;
+
+        if format == "csv" {
+            let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+            let pool = self.client.get_subnet_pool(netuid).await?;
+            let row = vec![
+                netuid.to_string(),
+                subnet_info.subnetwork_n.to_string(),
+                subnet_info.max_n.to_string(),
+                utils::rao_to_tao_string(subnet_info.burn as u128),
+                utils::rao_to_tao_string(subnet_info.min_burn as u128),
+                utils::rao_to_tao_string(subnet_info.max_burn as u128),
+                subnet_info.difficulty.to_string(),
+                subnet_info.tempo.to_string(),
+                subnet_info.immunity_period.to_string(),
+                subnet_info.registration_allowed.to_string(),
+                subnet_info.registrations_this_interval.to_string(),
+                subnet_info.target_registrations_per_interval.to_string(),
+                subnet_info.owner_ss58.clone(),
+                self.client
+                    .get_subnet_name(netuid)
+                    .await?
+                    .unwrap_or_default(),
+                format!("{:.9}", pool.price()),
+                utils::rao_to_tao_string(pool.tao_in as u128),
+                utils::rao_to_tao_string(pool.alpha_in as u128),
+                utils::rao_to_tao_string(pool.alpha_out as u128),
+            ];
+            return utils::write_csv(output, utils::SUBNET_INFO_CSV_HEADER, &[row]);
+        }
+
+        println!("📋 Fetching subnet {} information...", netuid);
+
+        let subnet_info = self.client.get_subnet_info(netuid, true).await?;
+        let identity = self.client.get_subnet_identity(netuid).await?;
+
+        match identity
+            .as_ref()
+            .filter(|identity| !identity.name.is_empty())
+        {
+            Some(identity) => println!("\n📊 Subnet {} ({}) Details:", netuid, identity.name),
+            None => println!("\n📊 Subnet {} Details:", netuid),
+        }
+        println!("═══════════════════════════════════════");
+        if let Some(identity) = &identity {
+            for (label, value) in [
+                ("Description", &identity.description),
+                ("GitHub", &identity.github),
+                ("Website", &identity.url),
+                ("Contact", &identity.contact),
+                ("Discord", &identity.discord),
+            ] {
+                if !value.is_empty() {
+                    println!("   {}: {}", label, value);
+                }
+            }
+        }
+        println!(
+            "   Registered neurons: {}/{}",
+            subnet_info.subnetwork_n, subnet_info.max_n
+        );
+        println!("   Registration difficulty: {}", subnet_info.difficulty);
+        println!(
+            "   Burn cost: {}",
+            utils::format_tao(subnet_info.burn as u128)
+        );
+        println!(
+            "   Burn bounds: {} - {}",
+            utils::format_tao(subnet_info.min_burn as u128),
+            utils::format_tao(subnet_info.max_burn as u128)
+        );
+        println!(
+            "   Tempo: {}",
+            utils::format_blocks(subnet_info.tempo as u64)
+        );
+        println!(
+            "   Immunity period: {}",
+            utils::format_blocks(subnet_info.immunity_period as u64)
+        );
+        println!(
+            "   Min allowed weights: {}",
+            subnet_info.min_allowed_weights
+        );
+        println!("   Max weight limit: {}", subnet_info.max_weight_limit);
+        println!(
+            "   Max allowed validators: {}",
+            subnet_info.max_allowed_validators
+        );
+        println!(
+            "   Owner: {}",
+            utils::format_ss58_short(&subnet_info.owner_ss58)
+        );
+        println!("   Network modality: {}", subnet_info.modality);
+        println!("   Emission value: {}", subnet_info.emission_value);
+        println!("   Rho: {}", subnet_info.rho);
+        println!("   Kappa: {}", subnet_info.kappa);
+        println!("   Scaling law power: {}", subnet_info.scaling_law_power);
+        println!(
+            "   Blocks since epoch: {}",
+            utils::format_blocks(subnet_info.blocks_since_epoch)
+        );
+        if subnet_info.tempo > 0 {
+            println!(
+                "   Next epoch in: {}",
+                utils::format_countdown(
+                    (subnet_info.tempo as u64).saturating_sub(subnet_info.blocks_since_epoch)
+                )
+            );
+        }
+
+        let pool = self.client.get_subnet_pool(netuid).await?;
+        if netuid != 0 {
+            println!("\n💧 dTAO Pool:");
+            println!("   Alpha price: {:.6} TAO", pool.price());
+            println!("   TAO reserve: {}", utils::format_tao(pool.tao_in as u128));
+            println!(
+                "   Alpha reserve: {}",
+                utils::format_alpha(pool.alpha_in as u128)
+            );
+            println!(
+                "   Alpha staked: {} (worth {})",
+                utils::format_alpha(pool.alpha_out as u128),
+                utils::format_tao(pool.alpha_out_value() as u128)
+            );
+        }
+
+        println!("\n🚪 Registration Window:");
+        self.print_registration_window(&subnet_info);
+
+        // Show registration statistics
+        let current_block = self.client.get_current_block().await?;
+
+        println!("\n⏱️ Registration Estimates:");
+        println!(
+            "   Current block: {}",
+            utils::format_thousands(current_block)
+        );
+        println!(
+            "   Burn cost in USD: {}",
+            self.format_usd(subnet_info.burn).await
+        );
+
+        Ok(())
+    }
+
+    fn print_registration_window(&self, subnet_info: &SubnetInfo) {
+        let status = match subnet_info.registration_window() {
+            RegistrationWindow::Open => "open".bright_green(),
+            RegistrationWindow::Disabled => "disabled".bright_red(),
+            RegistrationWindow::IntervalFull { .. } => "interval full".bright_yellow(),
+        };
+        println!("   Status: {}", status);
+        println!(
+            "   Registrations this interval: {}/{} (target {})",
+            subnet_info.registrations_this_interval,
+            subnet_info.registration_interval_limit(),
+            subnet_info.target_registrations_per_interval
+        );
+        println!(
+            "   Max registrations per block: {}",
+            subnet_info.max_registrations_per_block
+        );
+    }
+
+    // Spend a budget on registrations across candidate subnets before a deadline
+    pub async fn run_campaign(
+        &self,
+        netuids: &[u16],
+        wallet_path: &str,
+        hotkey_path: &str,
+        options: &CampaignOptions,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            "🗺️ Starting Registration Campaign".bright_cyan().bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Candidate subnets: {:?}", netuids);
+        println!("   Target registrations: {}", options.target);
+        println!("   Budget: {}", utils::format_tao(options.budget as u128));
+        println!("   Deadline: {}", utils::format_time(options.deadline));
+
+        // A hotkey registers at most once per subnet
+        let mut candidates = Vec::new();
+        for &netuid in netuids {
+            match self.client.get_uid(netuid, &hotkey_account).await? {
+                Some(uid) => println!(
+                    "   Subnet {}: already registered as UID {}, skipping",
+                    netuid, uid
+                ),
+                None => candidates.push(netuid),
+            }
+        }
+        if candidates.len() < options.target {
+            println!(
+                "⚠️ Only {} candidate subnet(s) left for a target of {}",
+                candidates.len(),
+                options.target
+            );
+        }
+
+        let mut registered: Vec<u16> = Vec::new();
+        let mut spent = 0u64;
+        let mut session = SessionSummary::new("campaign");
+        let mut subnet_changes = self.start_storage_cache(&candidates, &[]).await;
+
+        loop {
+            let current_block = self.client.get_current_block().await?;
+            let now = chrono::Utc::now();
+
+            let stop = if registered.len() >= options.target {
+                Some("target reached".to_string())
+            } else if now >= options.deadline {
+                Some("deadline reached".to_string())
+            } else if candidates.is_empty() {
+                Some("no candidate subnets left".to_string())
+            } else {
+                None
+            };
+            if let Some(reason) = stop {
+                campaign::log_decision(
+                    options.log_path.as_deref(),
+                    current_block,
+                    spent,
+                    &registered,
+                    &Decision::Stop { reason },
+                )?;
+                break;
+            }
+
+            let mut gauges = Vec::with_capacity(candidates.len());
+            for &netuid in &candidates {
+                let gauge = async {
+                    let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+                    let schedule = self.client.get_burn_schedule(netuid).await?;
+                    anyhow::Ok(Candidate {
+                        netuid,
+                        burn: schedule.burn,
+                        projected_burn: schedule.projected_burn(),
+                        blocks_to_adjustment: schedule
+                            .next_adjustment_block()
+                            .saturating_sub(current_block),
+                        competition: subnet_info.registrations_this_interval as f64
+                            / subnet_info.target_registrations_per_interval.max(1) as f64,
+                        open: subnet_info.registration_window() == RegistrationWindow::Open,
+                    })
+                };
+                match gauge.await {
+                    Ok(candidate) => gauges.push(candidate),
+                    Err(e) => println!("❌ Subnet {} check failed: {}", netuid, e),
+                }
+            }
+
+            let time_left = (options.deadline - now).to_std().unwrap_or_default();
+            let decision = campaign::decide(
+                &gauges,
+                options.budget.saturating_sub(spent),
+                options.max_burn,
+                time_left,
+            );
+            campaign::log_decision(
+                options.log_path.as_deref(),
+                current_block,
+                spent,
+                &registered,
+                &decision,
+            )?;
+
+            if let Decision::Register { netuid, burn, .. } = decision {
+                let max_burn = options
+                    .max_burn
+                    .unwrap_or(u64::MAX)
+                    .min(options.budget.saturating_sub(spent));
+                match self
+                    .register_to_subnet(
+                        netuid,
+                        wallet_path,
+                        hotkey_path,
+                        &RegistrationOptions {
+                            burn_amount: Some(burn),
+                            max_burn: Some(max_burn),
+                            assume_yes: true,
+                            ..Default::default()
+                        },
+                    )
+                    .await
+                {
+                    Ok(outcome) => {
+                        spent += outcome.burn;
+                        registered.push(netuid);
+                        candidates.retain(|&candidate| candidate != netuid);
+                        session.record_success(outcome.burn as u128, outcome.fee);
+                        continue;
+                    }
+                    Err(e) => {
+                        println!("❌ Registration in subnet {} failed: {}", netuid, e);
+                        session.record_failure(&format!("register subnet {}", netuid), &e);
+                    }
+                }
+            }
+
+            if !self
+                .wait_for_next_check(options.interval, &mut subnet_changes)
+                .await
+            {
+                println!("\n🛑 Campaign stopped");
+                break;
+            }
+        }
+
+        println!(
+            "\n🏁 Campaign finished: {}/{} registrations, spent {}",
+            registered.len(),
+            options.target,
+            utils::format_tao(spent as u128)
+        );
+        session.finish(options.summary_path.as_deref())
+    }
+
+    // Cache subnet storage for a long-running command, kept fresh by a change subscription
+    // that also reports the watched hotkeys' UIDs
+    pub async fn start_storage_cache(
+        &self,
+        netuids: &[u16],
+        hotkeys: &[(u16, AccountId32)],
+    ) -> Option<SubnetChangeWatch> {
+        match self.client.watch_subnet_changes(netuids, hotkeys).await {
+            Ok(changes) => {
+                self.client.enable_storage_cache(STORAGE_CACHE_TTL);
+                Some(changes)
+            }
+            Err(e) => {
+                println!("⚠️ Storage cache disabled: {}", e);
+                None
+            }
+        }
+    }
+
+    // Sleep until the next check while dropping cached storage of changed subnets. A burn
+    // change or a watched hotkey's UID change cuts it short. False on Ctrl+C
+    pub async fn wait_for_next_check(
+        &self,
+        interval: u64,
+        subnet_changes: &mut Option<SubnetChangeWatch>,
+    ) -> bool {
+        let next_check = sleep(Duration::from_secs(interval));
+        tokio::pin!(next_check);
+
+        loop {
+            let change = tokio::select! {
+                _ = &mut next_check => return true,
+                _ = self.client.cancellation_token().cancelled_owned() => return false,
+                change = next_subnet_change(subnet_changes) => change,
+            };
+
+            match change {
+                Some(Ok(changes)) => {
+                    for &netuid in &changes.netuids {
+                        self.client.invalidate_subnet(netuid);
+                    }
+                    for (netuid, burn) in &changes.burns {
+                        println!(
+                            "🔔 Subnet {} burn changed to {}",
+                            netuid,
+                            utils::format_tao(*burn as u128)
+                        );
+                    }
+                    for (netuid, hotkey, uid) in &changes.uids {
+                        match uid {
+                            Some(uid) => println!(
+                                "🔔 {} registered in subnet {} with UID {}",
+                                utils::format_account_short(hotkey),
+                                netuid,
+                                uid
+                            ),
+                            None => println!(
+                                "🔔 {} lost its UID in subnet {}",
+                                utils::format_account_short(hotkey),
+                                netuid
+                            ),
+                        }
+                    }
+                    if !changes.burns.is_empty() || !changes.uids.is_empty() {
+                        return true;
+                    }
+                }
+                Some(Err(e)) => {
+                    println!("⚠️ Storage cache disabled: {}", e);
+                    self.client.disable_storage_cache();
+                    *subnet_changes = None;
+                }
+                None => {
+                    println!("⚠️ Storage cache disabled: change subscription closed");
+                    self.client.disable_storage_cache();
+                    *subnet_changes = None;
+                }
+            }
+        }
+    }
+
+    // Watch subnets and send alerts when registration conditions are met
+    pub async fn watch_subnets(
+        &self,
+        netuids: &[u16],
+        options: &WatchOptions,
+        notifications: &mut Notifications,
+        records: Option<&dyn RecordStore>,
+        metrics: Option<&prom::Exporter>,
+    ) -> Result<()> {
+        println!("{}", "👁️ Starting Subnet Watch".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+        println!("   Subnets: {:?}", netuids);
+        if let Some(burn_below) = options.burn_below {
+            println!("   Burn below: {}", utils::format_tao(burn_below as u128));
+        }
+        println!("   Open slots: {}", options.open_slots);
+        println!("   Registration re-enabled: {}", options.registration_opens);
+        println!("   Watched hotkeys: {}", options.hotkeys.len());
+        println!("   Channels: {}", notifications.channel_names().join(", "));
+        println!(
+            "   Check interval: {}s, sooner when a burn or watched UID changes",
+            options.interval
+        );
+
+        let hotkeys = options
+            .hotkeys
+            .iter()
+            .map(|h| {
+                key_utils::account_id_from_string(h)
+                    .kind_context(ErrorKind::Fatal, "Failed to load hotkey")
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut registration_allowed: HashMap<u16, bool> = HashMap::new();
+        let mut uids: HashMap<(u16, AccountId32), Option<u16>> = HashMap::new();
+        let watched: Vec<(u16, AccountId32)> = netuids
+            .iter()
+            .flat_map(|&netuid| hotkeys.iter().map(move |hotkey| (netuid, hotkey.clone())))
+            .collect();
+        let mut subnet_changes = self.start_storage_cache(netuids, &watched).await;
+
+        loop {
+            let mut textfile = prom::Textfile::default();
+            for &netuid in netuids {
+                let subnet_info = match self.client.get_subnet_info(netuid, false).await {
+                    Ok(info) => info,
+                    Err(e) => {
+                        println!("❌ Subnet {} check failed: {}", netuid, e);
+                        continue;
+                    }
+                };
+                textfile.add_subnet(&subnet_info);
+
+                if let Some(records) = records {
+                    let snapshot = SubnetSnapshot::from_info(&subnet_info, chrono::Utc::now());
+                    if let Err(e) = records.write(Record::Snapshot(&snapshot)).await {
+                        warnings::emit(WarningCode::RecordFailed, e.to_string());
+                    }
+                }
+
+                if let Some(burn_below) = options.burn_below {
+                    if subnet_info.burn < burn_below {
+                        notifications
+                            .notify(
+                                &format!("burn:{}", netuid),
+                                &format!("Subnet {} burn is low", netuid),
+                                &format!(
+                                    "Burn is {} (threshold {})",
+                                    utils::format_tao(subnet_info.burn as u128),
+                                    utils::format_tao(burn_below as u128)
+                                ),
+                            )
+                            .await;
+                    }
+                }
+
+                if options.open_slots && subnet_info.subnetwork_n < subnet_info.max_n {
+                    notifications
+                        .notify(
+                            &format!("slots:{}", netuid),
+                            &format!("Subnet {} has open slots", netuid),
+                            &format!(
+                                "{} of {} UIDs in use",
+                                subnet_info.subnetwork_n, subnet_info.max_n
+                            ),
+                        )
+                        .await;
+                }
+
+                let allowed = subnet_info.registration_allowed;
+                let was_allowed = registration_allowed.insert(netuid, allowed);
+                if options.registration_opens && allowed && was_allowed == Some(false) {
+                    notifications
+                        .notify(
+                            &format!("registration:{}", netuid),
+                            &format!("Subnet {} registration re-enabled", netuid),
+                            &format!(
+                                "Registration is open again, burn {}",
+                                utils::format_tao(subnet_info.burn as u128)
+                            ),
+                        )
+                        .await;
+                }
+
+                for hotkey in &hotkeys {
+                    let uid = match self.client.get_uid(netuid, hotkey).await {
+                        Ok(uid) => uid,
+                        Err(e) => {
+                            println!("❌ Hotkey check failed on subnet {}: {}", netuid, e);
+                            continue;
+                        }
+                    };
+                    let mut status = NeuronStatus {
+                        netuid,
+                        hotkey: hotkey.to_ss58check(),
+                        registered: uid.is_some(),
+                        uid,
+                        pruning_rank: None,
+                        error: None,
+                    };
+                    if let (Some(uid), Some(_)) = (uid, metrics) {
+                        match self.add_watched_metrics(&mut textfile, netuid, uid).await {
+                            Ok(rank) => status.pruning_rank = rank,
+                            Err(e) => println!("⚠️ UID {} metrics not updated: {:#}", uid, e),
+                        }
+                    }
+                    textfile.add_monitored(&[status]);
+                    if let Some(Some(previous_uid)) = uids.insert((netuid, hotkey.clone()), uid) {
+                        if uid.is_none() {
+                            notifications
+                                .notify(
+                                    &format!("dereg:{}:{}", netuid, hotkey),
+                                    &format!("Hotkey deregistered from subnet {}", netuid),
+                                    &format!("{} lost UID {}", hotkey.to_ss58check(), previous_uid),
+                                )
+                                .await;
+                        }
+                    }
+                }
+            }
+            if let Some(metrics) = metrics {
+                match self.add_chain_metrics(&mut textfile).await {
+                    Ok(()) => metrics.publish(&textfile),
+                    Err(e) => println!("⚠️ Metrics not updated: {:#}", e),
+                }
+            }
+
+            if !self
+                .wait_for_next_check(options.interval, &mut subnet_changes)
+                .await
+            {
+                println!("\n🛑 Watch stopped");
+                return Ok(());
+            }
+        }
+    }
+
+    // Keep a receipt of a completed registration when a record store is configured
+    async fn record_receipt(&self, receipt: Receipt) {
+        if let Some(receipts) = &self.receipts {
+            if let Err(e) = receipts.write(Record::Receipt(&receipt)).await {
+                warnings::emit(
+                    WarningCode::RecordFailed,
+                    format!("Failed to record receipt: {}", e),
+                );
+            }
+        }
+    }
+
+    // Record a snapshot of each subnet into a record store
+    pub async fn record_subnet_snapshots(&self, records: &dyn RecordStore, netuids: &[u16]) {
+        for &netuid in netuids {
+            let recorded = async {
+                let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+                let snapshot = SubnetSnapshot::from_info(&subnet_info, chrono::Utc::now());
+                records.write(Record::Snapshot(&snapshot)).await
+            };
+            if let Err(e) = recorded.await {
+                println!("⚠️ Failed to record subnet {} snapshot: {}", netuid, e);
+            }
+        }
+    }
+
+    // Massive monitoring of multiple neurons
+    // Compare each subnet's hyperparameters with the last cycle and alert on changes. The
+    // first cycle only records them
+    pub async fn check_hyperparameters(
+        &self,
+        netuids: &[u16],
+        previous: &mut HashMap<u16, Hyperparameters>,
+        notifications: &mut Notifications,
+    ) -> Vec<ParamChange> {
+        let mut all_changes = Vec::new();
+        for &netuid in netuids {
+            let params = match self.client.get_subnet_info(netuid, false).await {
+                Ok(info) => Hyperparameters::from_info(&info),
+                Err(e) => {
+                    println!("❌ Subnet {} hyperparameter check failed: {}", netuid, e);
+                    continue;
+                }
+            };
+            let changes = previous
+                .insert(netuid, params.clone())
+                .map(|earlier| params.changes_since(&earlier))
+                .unwrap_or_default();
+            if changes.is_empty() {
+                continue;
+            }
+
+            println!("\n🛠️ Subnet {} hyperparameters changed:", netuid);
+            for change in &changes {
+                println!("   {}", change.describe());
+                notifications
+                    .notify(
+                        &format!("param:{}:{}:{}", netuid, change.name, change.new),
+                        &format!("Subnet {} {} changed", netuid, change.name),
+                        &change.describe(),
+                    )
+                    .await;
+            }
+            all_changes.extend(changes);
+        }
+        all_changes
+    }
+
+    pub async fn monitor_multiple_neurons(
+        &self,
+        registrations: Vec<(u16, String)>,
+        options: &MonitorOptions,
+    ) -> Result<Vec<NeuronStatus>> {
+        println!("👀 Monitoring {} registration(s)...", registrations.len());
+        println!("═══════════════════════════════════════════");
+
+        let mut statuses = Vec::with_capacity(registrations.len());
+        for (netuid, hotkey_path) in registrations {
+            let hotkey_account = key_utils::account_id_from_string(&hotkey_path)?;
+            println!(
+                "\n📍 Subnet {} - {}",
+                netuid,
+                utils::format_account_short(&hotkey_account)
+            );
+
+            let mut status = NeuronStatus {
+                netuid,
+                hotkey: hotkey_account.to_ss58check(),
+                registered: false,
+                uid: None,
+                pruning_rank: None,
+                error: None,
+            };
+            match self.check_status(netuid, &hotkey_path).await {
+                Ok(uid) => {
+                    status.registered = uid.is_some();
+                    status.uid = uid;
+                    if let (Some(uid), true) =
+                        (uid, options.show_risk || options.danger_zone.is_some())
+                    {
+                        match self.report_pruning_risk(netuid, uid, options).await {
+                            Ok(rank) => status.pruning_rank = rank,
+                            Err(e) => {
+                                println!("❌ Pruning risk check failed: {}", e);
+                                status.error = Some(e.to_string());
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("❌ Error: {}", e);
+                    status.error = Some(e.to_string());
+                }
+            }
+            statuses.push(status);
+        }
+
+        Ok(statuses)
+    }
+
+    // Gauges for the monitored hotkeys, every neuron of their subnets and the chain itself
+    // (--prom-file, --metrics-port)
+    pub async fn prometheus_metrics(
+        &self,
+        subnets: &[u16],
+        statuses: &[NeuronStatus],
+    ) -> Result<prom::Textfile> {
+        let mut textfile = prom::Textfile::default();
+        self.add_chain_metrics(&mut textfile).await?;
+        textfile.add_monitored(statuses);
+        for &netuid in subnets {
+            textfile.add_subnet(&self.client.get_subnet_info(netuid, false).await?);
+            textfile.add_neurons(&self.client.get_neurons_lite(netuid).await?);
+        }
+        Ok(textfile)
+    }
+
+    async fn add_chain_metrics(&self, textfile: &mut prom::Textfile) -> Result<()> {
+        let latency = self.client.measure_latency(1).await?;
+        let block = self.client.get_current_block().await?;
+        textfile.add_chain(block, latency.first().copied().unwrap_or_default());
+        Ok(())
+    }
+
+    // Stake, incentive and emission gauges of a watched UID, returning its pruning rank
+    async fn add_watched_metrics(
+        &self,
+        textfile: &mut prom::Textfile,
+        netuid: u16,
+        uid: u16,
+    ) -> Result<Option<usize>> {
+        let neuron = self
+            .client
+            .get_neuron_lite(netuid, uid)
+            .await?
+            .ok_or_else(|| anyhow!("No neuron info for UID {}", uid))?;
+        textfile.add_watched_neuron(&neuron);
+        Ok(self.pruning_risk(netuid, uid).await?.rank)
+    }
+
+    async fn pruning_risk(&self, netuid: u16, uid: u16) -> Result<analysis::PruningRisk> {
+        let subnet_info = self.client.get_subnet_info(netuid, false).await?;
+        let scores = self.client.get_pruning_scores(netuid).await?;
+        let registered_at = self
+            .client
+            .get_registration_blocks(netuid, subnet_info.subnetwork_n)
+            .await?;
+        let current_block = self.client.get_current_block().await?;
+
+        analysis::pruning_risk(
+            uid,
+            &scores,
+            &registered_at,
+            current_block,
+            subnet_info.immunity_period,
+            PRUNING_LOWEST_SHOWN,
+        )
+        .ok_or_else(|| anyhow!("No pruning score for UID {}", uid))
+    }
+
+    // Show where a neuron stands in the pruning queue, returning its rank
+    async fn report_pruning_risk(
+        &self,
+        netuid: u16,
+        uid: u16,
+        options: &MonitorOptions,
+    ) -> Result<Option<usize>> {
+        let risk = self.pruning_risk(netuid, uid).await?;
+        println!("\n⚠️ Deregistration Risk:");
+        println!("   Pruning score: {}", risk.score);
+        match risk.rank {
+            Some(rank) => println!(
+                "   Pruning rank: {} of {} non-immune neurons (1 = next pruned)",
+                rank, risk.candidates
+            ),
+            None => println!(
+                "   Immunity remaining: {}",
+                utils::format_countdown(risk.immunity_remaining)
+            ),
+        }
+        let lowest: Vec<String> = risk
+            .lowest
+            .iter()
+            .map(|(uid, score)| format!("UID {} ({})", uid, score))
+            .collect();
+        println!("   Lowest scores: {}", lowest.join(", "));
+
+        if let Some(zone) = options.danger_zone {
+            if risk.in_danger_zone(zone) {
+                println!(
+                    "{}",
+                    format!(
+                        "🚨 ALERT: UID {} is in the pruning danger zone (rank {} of {})",
+                        uid,
+                        risk.rank.unwrap_or_default(),
+                        risk.candidates
+                    )
+                    .bright_red()
+                    .bold()
+                );
+            }
+        }
+
+        Ok(risk.rank)
+    }
+
+    // Automatic registration with retry logic
+    pub async fn auto_register_with_retry(
+        &self,
+        netuid: u16,
+        wallet_path: &str,
+        hotkey_path: &str,
+        max_retries: usize,
+        options: &RegistrationOptions,
+    ) -> Result<RegistrationOutcome> {
+        // A dry run gives the same answer every time, one attempt is enough
+        let max_retries = if options.dry_run { 1 } else { max_retries };
+        println!(
+            "🔄 Auto registration with retry (max {} attempts)",
+            max_retries
+        );
+
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+
+        for attempt in 1..=max_retries {
+            println!("\n🚀 Registration attempt {}/{}", attempt, max_retries);
+
+            // A failed cooldown check is classified and retried like the registration
+            let result = async {
+                self.wait_for_registration_cooldown(netuid, &hotkey_account)
+                    .await?;
+                self.register_to_subnet(netuid, wallet_path, hotkey_path, options)
+                    .await
+            }
+            .await;
+            match result {
+                Ok(outcome) => {
+                    println!("✅ Registration successful on attempt {}", attempt);
+                    return Ok(outcome);
+                }
+                Err(e) => {
+                    let kind = errors::classify(&e);
+                    println!("❌ Attempt {} failed ({}): {}", attempt, kind, e);
+                    if !kind.is_retryable() {
+                        return Err(e.context(format!("Not retrying a {} error", kind)));
+                    }
+                    if attempt < max_retries {
+                        let delay = kind.retry_delay(&self.backoff, attempt as u32);
+                        println!(
+                            "⏳ Waiting {} before retry...",
+                            utils::format_duration(delay)
+                        );
+                        self.client.pause(delay).await?;
+                    }
+                }
+            }
+        }
+
+        Err(anyhow!("All registration attempts failed"))
+    }
+
+    // Register into the cheapest candidate subnet, falling through the list on failure
+    pub async fn register_cheapest(
+        &self,
+        subnets: &[u16],
+        wallet_path: &str,
+        hotkey_path: &str,
+        max_retries: usize,
+        options: &RegistrationOptions,
+    ) -> Result<RegistrationOutcome> {
+        println!(
+            "💸 Looking for the cheapest of {} candidate subnet(s)...",
+            subnets.len()
+        );
+
+        let mut candidates = Vec::new();
+        for &netuid in subnets {
+            let subnet_info = match self.client.get_subnet_info(netuid, false).await {
+                Ok(info) => info,
+                Err(e) => {
+                    println!("   Subnet {}: ❌ {}", netuid, e);
+                    continue;
+                }
+            };
+            let open_slots = subnet_info
+                .max_allowed_uids
+                .saturating_sub(subnet_info.registered_neurons);
+            let over_limit = options
+                .max_burn
+                .is_some_and(|max_burn| subnet_info.burn > max_burn);
+
+            println!(
+                "   Subnet {}: burn {} | {} open slot(s){}",
+                netuid,
+                utils::format_tao(subnet_info.burn as u128),
+                open_slots,
+                if over_limit { " | above max burn" } else { "" }
+            );
+            if open_slots > 0 && !over_limit {
+                candidates.push((netuid, subnet_info.burn));
+            }
+        }
+
+        if candidates.is_empty() {
+            return Err(anyhow!(
+                "No candidate subnet has open slots within the max burn"
+            ));
+        }
+        candidates.sort_by_key(|(_, burn)| *burn);
+
+        for (netuid, burn) in &candidates {
+            println!(
+                "\n🎯 Trying subnet {} (burn {})",
+                netuid,
+                utils::format_tao(*burn as u128)
+            );
+            match self
+                .auto_register_with_retry(*netuid, wallet_path, hotkey_path, max_retries, options)
+                .await
+            {
+                Ok(outcome) => return Ok(outcome),
+                Err(e) => println!("❌ Subnet {} failed: {}, trying the next one", netuid, e),
+            }
+        }
+
+        Err(anyhow!(
+            "Registration failed in all {} candidate subnet(s)",
+            candidates.len()
+        ))
+    }
+
+    // Advance the pool and load the next hotkey
+    fn rotate_hotkey(&self, pool: &mut HotkeyPool) -> Result<AccountId32> {
+        let next = pool.rotate().to_string();
+        let account = key_utils::account_id_from_string(&next)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        println!("🔀 Rotating to hotkey {}", account.to_ss58check());
+        Ok(account)
+    }
+
+    // Auto-register each hotkey in the pool in turn until one succeeds
+    pub async fn auto_register_with_pool(
+        &self,
+        netuid: u16,
+        wallet_path: &str,
+        pool: &HotkeyPool,
+        max_retries: usize,
+        options: &RegistrationOptions,
+    ) -> Result<RegistrationOutcome> {
+        let mut pool = pool.clone();
+
+        for i in 0..pool.len() {
+            if i > 0 {
+                pool.rotate();
+            }
+            println!("\n🔑 Hotkey {}/{}: {}", i + 1, pool.len(), pool.current());
+
+            match self
+                .auto_register_with_retry(netuid, wallet_path, pool.current(), max_retries, options)
+                .await
+            {
+                Ok(outcome) => return Ok(outcome),
+                // Another hotkey won't fix the coldkey's balance or a declined prompt
+                Err(e) if matches!(errors::classify(&e), ErrorKind::Balance | ErrorKind::Fatal) => {
+                    return Err(e)
+                }
+                Err(e) => println!("❌ Hotkey {} failed: {}", pool.current(), e),
+            }
+        }
+
+        Err(anyhow!(
+            "Registration failed for all {} hotkeys in the pool",
+            pool.len()
+        ))
+    }
+
+    // Register a fleet of hotkeys of one coldkey into a subnet, skipping those already in it,
+    // then print how each one went. max_retries switches to auto registration per hotkey.
+    // Above a concurrency of 1 registrations run side by side, the nonce manager keeping
+    // their submissions from the shared coldkey apart
+    pub async fn register_hotkeys(
+        &self,
+        netuid: u16,
+        wallet_path: &str,
+        hotkeys: &[String],
+        max_retries: Option<usize>,
+        concurrency: usize,
+        options: &RegistrationOptions,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            format!(
+                "🚀 Registering {} hotkeys into subnet {}",
+                hotkeys.len(),
+                netuid
+            )
+            .bright_cyan()
+            .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let mut reports: Vec<OperationReport> = hotkeys
+            .iter()
+            .enumerate()
+            .map(|(i, hotkey)| OperationReport {
+                index: i + 1,
+                operation: "register".to_string(),
+                subnet: Some(netuid),
+                hotkey: hotkey.clone(),
+                status: OperationStatus::Succeeded,
+                detail: String::new(),
+                tx_hash: None,
+            })
+            .collect();
+        let mut session = SessionSummary::new("register-hotkeys");
+
+        // Hotkeys already in the subnet or not loadable never reach the registration
+        let mut pending = Vec::new();
+        for (report, hotkey) in reports.iter_mut().zip(hotkeys) {
+            let registered = match key_utils::account_id_from_string(hotkey) {
+                Ok(account) => self.client.get_uid(netuid, &account).await,
+                Err(e) => Err(e).kind_context(ErrorKind::Fatal, "Failed to load hotkey"),
+            };
+            match registered {
+                Ok(Some(uid)) => {
+                    report.status = OperationStatus::Skipped;
+                    report.detail = format!("already registered with UID {}", uid);
+                }
+                Ok(None) => pending.push(report.index - 1),
+                Err(e) => {
+                    session.record_failure(&format!("register {}", hotkey), &e);
+                    report.status = OperationStatus::Failed;
+                    report.detail = format!("{:#}", e);
+                }
+            }
+        }
+        println!(
+            "🔑 {} to register, {} already registered",
+            pending.len(),
+            reports
+                .iter()
+                .filter(|report| report.status == OperationStatus::Skipped)
+                .count()
+        );
+
+        if !pending.is_empty() {
+            if let Some(sla) = &options.latency_sla {
+                self.check_endpoint_latency(sla).await?;
+            }
+            // One confirmation covers the whole fleet rather than a prompt per hotkey
+            if !options.dry_run && !options.assume_yes {
+                let burn = self.client.get_subnet_info(netuid, false).await?.burn as u128;
+                let coldkey = key_utils::Coldkey::load(wallet_path)
+                    .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+                let balance = self
+                    .client
+                    .get_account_balance(&coldkey.account_id())
+                    .await? as u128;
+                println!(
+                    "
+💸 About to spend:"
+                );
+                println!(
+                    "   Burn: {} × {} = {}",
+                    utils::format_tao(burn),
+                    pending.len(),
+                    utils::format_tao(burn * pending.len() as u128)
+                );
+                println!("   Balance: {}", utils::format_tao(balance));
+                if !utils::confirm(&format!("Register {} hotkeys?", pending.len()))? {
+                    return Err(
+                        RegistrationError::new(ErrorKind::Fatal, "Registration cancelled").into(),
+                    );
+                }
+            }
+
+            let options = RegistrationOptions {
+                assume_yes: true,
+                latency_sla: None,
+                ..options.clone()
+            };
+            let options = &options;
+            let progress = &Progress::items(pending.len() as u64, "Hotkeys");
+            let results: Vec<(usize, Result<RegistrationOutcome>)> = stream::iter(pending)
+                .map(|i| async move {
+                    let hotkey = &hotkeys[i];
+                    println!("\n🔑 Hotkey {}/{}: {}", i + 1, hotkeys.len(), hotkey);
+                    let result = match max_retries {
+                        Some(max_retries) => {
+                            self.auto_register_with_retry(
+                                netuid,
+                                wallet_path,
+                                hotkey,
+                                max_retries,
+                                options,
+                            )
+                            .await
+                        }
+                        None => {
+                            self.register_to_subnet(netuid, wallet_path, hotkey, options)
+                                .await
+                        }
+                    };
+                    if let Err(e) = &result {
+                        println!("❌ Hotkey {} failed: {}", hotkey, e);
+                    }
+                    progress.inc(1);
+                    (i, result)
+                })
+                .buffer_unordered(concurrency.max(1))
+                .collect()
+                .await;
+
+            for (i, result) in results {
+                let report = &mut reports[i];
+                match result {
+                    Ok(outcome) => {
+                        session.record_success(outcome.burn as u128, outcome.fee);
+                        report.detail = match outcome.tx_hash {
+                            Some(_) => {
+                                format!("burned {}", utils::format_tao(outcome.burn as u128))
+                            }
+                            None if options.dry_run => "dry run".to_string(),
+                            None => "nothing submitted".to_string(),
+                        };
+                        report.tx_hash = outcome.tx_hash.map(|tx_hash| format!("{:?}", tx_hash));
+                    }
+                    Err(e) => {
+                        session.record_failure(&format!("register {}", hotkeys[i]), &e);
+                        report.status = OperationStatus::Failed;
+                        report.detail = format!("{} error: {}", errors::classify(&e), e);
+                    }
+                }
+            }
+        }
+
+        for report in reports {
+            session.record_operation(report);
+        }
+        let failed = session.failed;
+        session.finish(None)?;
+        if failed > 0 {
+            return Err(anyhow!(
+                "{} of {} hotkeys failed to register",
+                failed,
+                hotkeys.len()
+            ));
+        }
+        Ok(())
+    }
+
+    // Keep a hotkey registered, re-registering after deregistration within limits
+    pub async fn guard_registration(
+        &self,
+        netuid: u16,
+        wallet_path: &str,
+        hotkey_path: &str,
+        options: &GuardOptions,
+    ) -> Result<()> {
+        let (max_burn, budget, interval) = (options.max_burn, options.budget, options.interval);
+        println!("{}", "🛡️ Starting Registration Guard".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let mut pool = HotkeyPool::new(
+            std::iter::once(hotkey_path.to_string())
+                .chain(options.hotkey_pool.iter().cloned())
+                .collect(),
+        )?;
+        let mut hotkey_account = key_utils::account_id_from_string(pool.current())
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+
+        println!("   Subnet: {}", netuid);
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        if pool.len() > 1 {
+            println!("   Hotkey pool: {} hotkeys", pool.len());
+        }
+        println!("   Max burn: {}", utils::format_tao(max_burn as u128));
+        println!("   Budget: {}", utils::format_tao(budget as u128));
+        println!("   Check interval: {}s", interval);
+
+        let mut last_uid: Option<u16> = None;
+        let mut spent = 0u64;
+        let mut session = SessionSummary::new("guard");
+        let mut status = DaemonStatus::new("guard");
+        let mut subnet_changes = self
+            .start_storage_cache(&[netuid], &[(netuid, hotkey_account.clone())])
+            .await;
+
+        let result = loop {
+            let mut check_error: Option<String> = None;
+            match self.client.get_uid(netuid, &hotkey_account).await {
+                Ok(Some(uid)) => {
+                    if last_uid != Some(uid) {
+                        println!("✅ Hotkey registered in subnet {} with UID {}", netuid, uid);
+                    }
+                    last_uid = Some(uid);
+                }
+                Ok(None) => {
+                    if let Some(uid) = last_uid.take() {
+                        let alert = format!(
+                            "Hotkey deregistered from subnet {} (was UID {})",
+                            netuid, uid
+                        );
+                        session.warn(warnings::emit(WarningCode::Deregistered, alert));
+
+                        // A deregistered hotkey hands the slot over to the next one in the pool
+                        if pool.len() > 1 {
+                            match self.rotate_hotkey(&mut pool) {
+                                Ok(account) => hotkey_account = account,
+                                Err(e) => println!("❌ Failed to rotate hotkey: {}", e),
+                            }
+                        }
+                    }
+
+                    match self.client.get_burn_schedule(netuid).await {
+                        Err(e) => {
+                            println!("❌ Failed to read burn: {}", e);
+                            check_error = Some(e.to_string());
+                        }
+                        Ok(schedule) if schedule.burn > max_burn => {
+                            println!(
+                                "⏳ Burn {} above max burn {}, waiting...",
+                                utils::format_tao(schedule.burn as u128),
+                                utils::format_tao(max_burn as u128)
+                            );
+                        }
+                        Ok(schedule) if spent + schedule.burn > budget => {
+                            println!(
+                                "{}",
+                                "🚨 ALERT: guard budget exhausted, stopping"
+                                    .bright_red()
+                                    .bold()
+                            );
+                            break Err(anyhow!(
+                                "Budget exhausted: spent {}, next registration costs {}, budget {}",
+                                utils::format_tao(spent as u128),
+                                utils::format_tao(schedule.burn as u128),
+                                utils::format_tao(budget as u128)
+                            ));
+                        }
+                        Ok(schedule) => {
+                            if let Err(e) = self
+                                .wait_for_registration_cooldown(netuid, &hotkey_account)
+                                .await
+                            {
+                                println!("❌ Failed to check registration cooldown: {}", e);
+                            }
+
+                            println!(
+                                "🔄 Re-registering for {}...",
+                                utils::format_tao(schedule.burn as u128)
+                            );
+                            match self
+                                .register_to_subnet(
+                                    netuid,
+                                    wallet_path,
+                                    pool.current(),
+                                    &RegistrationOptions {
+                                        burn_amount: Some(schedule.burn),
+                                        max_burn: Some(max_burn),
+                                        assume_yes: true,
+                                        wait_for_window: true,
+                                        ..Default::default()
+                                    },
+                                )
+                                .await
+                            {
+                                Ok(outcome) => {
+                                    spent += outcome.burn;
+                                    session.record_success(outcome.burn as u128, outcome.fee);
+                                    println!(
+                                        "{}",
+                                        format!(
+                                            "🔔 Re-registration submitted, spent {} of {} budget",
+                                            utils::format_tao(spent as u128),
+                                            utils::format_tao(budget as u128)
+                                        )
+                                        .bright_green()
+                                    );
+                                }
+                                Err(e) => {
+                                    println!("❌ Re-registration failed: {}", e);
+                                    session.record_failure("re-registration", &e);
+                                    check_error = Some(e.to_string());
+                                    if pool.len() > 1 {
+                                        match self.rotate_hotkey(&mut pool) {
+                                            Ok(account) => hotkey_account = account,
+                                            Err(e) => {
+                                                println!("❌ Failed to rotate hotkey: {}", e)
+                                            }
+                                        }
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                Err(e) => {
+                    println!("❌ Guard check failed: {}", e);
+                    check_error = Some(e.to_string());
+                }
+            }
+
+            status.record_check(vec![NeuronStatus {
+                netuid,
+                hotkey: hotkey_account.to_ss58check(),
+                registered: last_uid.is_some(),
+                uid: last_uid,
+                pruning_rank: None,
+                error: check_error,
+            }]);
+            status.write_or_warn(options.status_path.as_deref());
+
+            if !self
+                .wait_for_next_check(interval, &mut subnet_changes)
+                .await
+            {
+                println!("\n🛑 Guard stopped");
+                break Ok(());
+            }
+        };
+
+        if let Err(e) = &result {
+            status.record_error(e);
+            status.write_or_warn(options.status_path.as_deref());
+        }
+        session.finish(options.summary_path.as_deref())?;
+        result
+    }
+
+    /// This function provides an overview of the Bittensor network, including active subnets,
+    /// listing the top subnets by emission, or all of them in netuid order when top is None
+    pub async fn show_network_statistics(
+        &self,
+        format: &str,
+        output: Option<&str>,
+        top: Option<usize>,
+    ) -> Result<()> {
+        utils::check_output_format(format, &["text", "csv"], output)?;
+
+        let netuids = self.client.get_netuids().await?;
+        // Subnet info comes from a few batched storage queries, identities from another
+        let (subnet_infos, identities) = futures::join!(
+            self.client.get_subnet_infos(&netuids),
+            self.client.get_subnet_identities(&netuids)
+        );
+        // Names are a nicety, the table still prints without them
+        let names: Vec<String> = match identities {
+            Ok(identities) => identities
+                .into_iter()
+                .map(|identity| identity.map(|identity| identity.name).unwrap_or_default())
+                .collect(),
+            Err(_) => vec![String::new(); netuids.len()],
+        };
+        let mut subnets: Vec<(SubnetInfo, String)> = subnet_infos?
+            .into_iter()
+            .zip(names)
+            .filter_map(|(subnet_info, name)| Some((subnet_info.ok()?, name)))
+            .collect();
+        let active_subnets = subnets.len();
+        let total_neurons: u32 = subnets
+            .iter()
+            .map(|(subnet_info, _)| subnet_info.registered_neurons as u32)
+            .sum();
+        if let Some(top) = top {
+            subnets.sort_by_key(|(subnet_info, _)| std::cmp::Reverse(subnet_info.emission_value));
+            subnets.truncate(top);
+        }
+
+        if format == "csv" {
+            let rows: Vec<Vec<String>> = subnets
+                .into_iter()
+                .map(|(subnet_info, name)| {
+                    vec![
+                        subnet_info.netuid.to_string(),
+                        subnet_info.registered_neurons.to_string(),
+                        subnet_info.max_allowed_uids.to_string(),
+                        utils::rao_to_tao_string(subnet_info.burn as u128),
+                        subnet_info.difficulty.to_string(),
+                        name,
+                    ]
+                })
+                .collect();
+            return utils::write_csv(output, utils::NETWORK_STATS_CSV_HEADER, &rows);
+        }
+
+        println!("📊 Bittensor Network Statistics");
+        println!("═══════════════════════════════════════");
+
+        match top {
+            Some(_) => println!(
+                "\n🌐 Top {} of {} Active Subnets by Emission:",
+                subnets.len(),
+                active_subnets
+            ),
+            None => println!("\n🌐 Active Subnets:"),
+        }
+        println!(
+            "┌─────┬──────────────────┬─────────────┬──────────┬─────────────┬──────────────┐"
+        );
+        println!(
+            "│ UID │ Name             │   Neurons   │ Max Cap  │  Burn Cost  │ Difficulty   │"
+        );
+        println!(
+            "├─────┼──────────────────┼─────────────┼──────────┼─────────────┼──────────────┤"
+        );
+
+        for (subnet_info, name) in &subnets {
+            println!(
+                "│ {:>3} │ {:<16} │ {:>7}/{:<3} │ {:>8} │ {:>9} │ {:>10} │",
+                subnet_info.netuid,
+                name.chars().take(16).collect::<String>(),
+                subnet_info.registered_neurons,
+                subnet_info.max_allowed_uids,
+                subnet_info.max_allowed_uids,
+                utils::format_tao(subnet_info.burn as u128),
+                utils::format_difficulty(subnet_info.difficulty),
+            );
+        }
+
+        println!(
+            "└─────┴──────────────────┴─────────────┴──────────┴─────────────┴──────────────┘"
+        );
+
+        // Common statistics
+        let current_block = self.client.get_current_block().await?;
+
+        println!("\n📈 Network Overview:");
+        println!("   Active subnets: {}", active_subnets);
+        println!("   Total neurons: {:?}", total_neurons);
+        println!(
+            "   Current block: {}",
+            utils::format_thousands(current_block)
+        );
+        println!("   Network: Finney (Mainnet)");
+
+        // New users recomendations
+        println!("\n💡 Registration Tips:");
+        println!("   • Subnet 1: Text generation (high competition)");
+        println!("   • Subnet 3: Data scraping (moderate difficulty)");
+        println!("   • Subnet 8: Time series prediction");
+        println!("   • Check difficulty before registering");
+        println!("   • Consider burn registration for high-difficulty subnets");
+
+        Ok(())
+    }
+
+    // Stake concentration among subnet validators
+    pub async fn show_stake_distribution(&self, netuid: u16) -> Result<()> {
+        println!(
+            "⚖️ Analyzing validator stake distribution for subnet {}...",
+            netuid
+        );
+
+        let validator_stakes = self.client.get_validator_stakes(netuid).await?;
+        if validator_stakes.is_empty() {
+            println!("⚠️ No validator permits found in subnet {}", netuid);
+            return Ok(());
+        }
+
+        let stakes: Vec<u64> = validator_stakes.iter().map(|(_, _, s)| *s).collect();
+        let distribution = analysis::stake_distribution(&stakes);
+
+        println!("\n📊 Stake Distribution (subnet {}):", netuid);
+        println!("═══════════════════════════════════════");
+        println!("   Validators: {}", distribution.validators);
+        println!(
+            "   Total validator stake: {}",
+            utils::format_tao(distribution.total_stake)
+        );
+        for (k, share) in &distribution.top_k_shares {
+            println!("   Top-{} share: {:.1}%", k, share * 100.0);
+        }
+        println!("   Gini coefficient: {:.3}", distribution.gini);
+        println!("   Nakamoto coefficient: {}", distribution.nakamoto);
+
+        let mut largest = validator_stakes.clone();
+        largest.sort_by_key(|(_, _, stake)| std::cmp::Reverse(*stake));
+
+        println!("\n🏦 Largest validators:");
+        for (uid, hotkey, stake) in largest.iter().take(5) {
+            println!(
+                "   UID {:>3}  {}  {}",
+                uid,
+                utils::format_account_short(hotkey),
+                utils::format_tao(*stake as u128)
+            );
+        }
+
+        Ok(())
+    }
+
+    // Export configuration for automation
+    // Full neuron table for a subnet as text, JSON or CSV
+    pub async fn show_metagraph(
+        &self,
+        netuid: u16,
+        sort_by: &str,
+        format: &str,
+        output: Option<&str>,
+        stream: bool,
+    ) -> Result<()> {
+        utils::check_output_format(format, &["text", "json", "csv"], output)?;
+        if stream {
+            if sort_by != "uid" {
+                return Err(anyhow!(
+                    "--stream emits neurons in UID order as they arrive, sorting by {} needs all of them first",
+                    sort_by
+                ));
+            }
+            return self.stream_metagraph(netuid, format, output).await;
+        }
+
+        let mut neurons = self.client.get_neurons_lite(netuid).await?;
+        sort_neurons(&mut neurons, sort_by)?;
+
+        match format {
+            "json" => utils::write_output(output, &serde_json::to_string_pretty(&neurons)?)?,
+            "csv" => {
+                let rows: Vec<Vec<String>> = neurons.iter().map(metagraph_csv_row).collect();
+                utils::write_csv(output, utils::METAGRAPH_CSV_HEADER, &rows)?;
+            }
+            _ => {
+                let takes = self.delegate_takes().await;
+                println!(
+                    "🧠 Metagraph for subnet {} ({} neurons)",
+                    netuid,
+                    neurons.len()
+                );
+                println!("{}", METAGRAPH_TEXT_HEADER);
+                for neuron in &neurons {
+                    println!("{}", metagraph_text_row(neuron, &takes));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    // Metagraph written row by row while neurons are fetched a few at a time, so memory
+    // stays flat on subnets with thousands of UIDs
+    async fn stream_metagraph(
+        &self,
+        netuid: u16,
+        format: &str,
+        output: Option<&str>,
+    ) -> Result<()> {
+        let takes = if format == "text" {
+            self.delegate_takes().await
+        } else {
+            HashMap::new()
+        };
+        let mut out = utils::output_writer(output)?;
+        match format {
+            "json" => writeln!(out, "[")?,
+            "csv" => writeln!(out, "{}", utils::METAGRAPH_CSV_HEADER.join(","))?,
+            _ => {
+                writeln!(out, "🧠 Metagraph for subnet {} (streaming)", netuid)?;
+                writeln!(out, "{}", METAGRAPH_TEXT_HEADER)?;
+            }
+        }
+
+        let neurons = self.client.stream_neurons_lite(netuid).await?;
+        futures::pin_mut!(neurons);
+        let mut count = 0usize;
+        while let Some(neuron) = neurons.next().await {
+            let neuron = neuron?;
+            match format {
+                "json" => write!(
+                    out,
+                    "{}  {}",
+                    if count == 0 { "" } else { ",\n" },
+                    serde_json::to_string(&neuron)?
+                )?,
+                "csv" => {
+                    let fields: Vec<String> = metagraph_csv_row(&neuron)
+                        .iter()
+                        .map(|field| utils::csv_field(field))
+                        .collect();
+                    writeln!(out, "{}", fields.join(","))?
+                }
+                _ => writeln!(out, "{}", metagraph_text_row(&neuron, &takes))?,
+            }
+            count += 1;
+        }
+
+        match format {
+            "json" => writeln!(out, "{}]", if count == 0 { "" } else { "\n" })?,
+            "csv" => {}
+            _ => writeln!(out, "\n   {} neurons", count)?,
+        }
+        out.flush()?;
+        if let Some(path) = output {
+            eprintln!("💾 Written to: {}", path);
+        }
+        Ok(())
+    }
+
+    // Delegate take of validators, best effort: the table is still useful without it
+    async fn delegate_takes(&self) -> HashMap<AccountId32, u16> {
+        match self.client.get_delegates().await {
+            Ok(delegates) => delegates.into_iter().map(|d| (d.hotkey, d.take)).collect(),
+            Err(e) => {
+                println!("⚠️ Could not read delegates: {}", e);
+                HashMap::new()
+            }
+        }
+    }
+
+    pub async fn export_config(&self, netuid: u16, output_path: &str) -> Result<()> {
+        println!("📄 Exporting configuration for subnet {}...", netuid);
+
+        let subnet_info = self.client.get_subnet_info(netuid, true).await?;
+
+        let config = serde_json::json!({
+            "subnet_id": netuid,
+            "registration_info": {
+                "difficulty": subnet_info.difficulty.to_string(),
+                "burn_cost_rao": subnet_info.burn,
+                "burn_cost_tao": subnet_info.burn as f64 / 1e9,
+                "max_neurons": subnet_info.max_allowed_uids,
+                "current_neurons": subnet_info.registered_neurons,
+                "registration_open": subnet_info.registered_neurons < subnet_info.max_allowed_uids
+            },
+            "export_time": chrono::Utc::now().to_rfc3339(),
+            "network": "finney"
+        });
+
+        std::fs::write(output_path, serde_json::to_string_pretty(&config)?)?;
+        println!("✅ Configuration exported to: {}", output_path);
+
+        Ok(())
+    }
+
+    pub async fn execute_batch_operations(
+        &self,
+        config_path: &str,
+        summary_path: Option<&str>,
+        resume: bool,
+    ) -> Result<()> {
+        println!("📦 Executing batch operations from: {}", config_path);
+
+        let (batch_config, config_content) = BatchConfig::load(config_path)?;
+
+        println!("   Found {} operations", batch_config.operations.len());
+
+        let journal = if resume {
+            BatchJournal::resume(config_path, &config_content)?
+        } else {
+            BatchJournal::start(
+                config_path,
+                &config_content,
+                batch_config.operations.iter().map(|operation| {
+                    (
+                        operation.operation.clone(),
+                        operation.subnet,
+                        operation.hotkey.clone(),
+                    )
+                }),
+            )?
+        };
+        let to_run: Vec<usize> = (0..batch_config.operations.len())
+            .filter(|i| !journal.is_succeeded(*i))
+            .collect();
+        if resume {
+            println!(
+                "📓 Resuming from {}: {} completed, {} left to run",
+                journal.path(),
+                journal.count(EntryState::Succeeded),
+                to_run.len()
+            );
+        } else {
+            println!("📓 Progress journal: {}", journal.path());
+        }
+        let completed: Vec<_> = journal.entries.clone();
+        let journal = Mutex::new(journal);
+
+        let total = batch_config.operations.len();
+        let concurrency = batch_config.concurrency.max(1);
+        if concurrency > 1 {
+            println!("   Running up to {} operations at once", concurrency);
+        }
+
+        // Balance checks and burns from one coldkey would interleave, so they take turns
+        let coldkey_locks: HashMap<String, tokio::sync::Mutex<()>> = batch_config
+            .operations
+            .iter()
+            .filter_map(|operation| operation.wallet.as_deref().map(key_utils::resolve_wallet))
+            .map(|wallet| (wallet, tokio::sync::Mutex::new(())))
+            .collect();
+
+        let progress = Progress::items(to_run.len() as u64, "Operations");
+        let mut results: HashMap<usize, Result<BatchOutcome>> =
+            stream::iter(to_run.iter().copied().enumerate())
+                .map(|(position, i)| {
+                    let operation = &batch_config.operations[i];
+                    let coldkey_locks = &coldkey_locks;
+                    let journal = &journal;
+                    let progress = &progress;
+                    async move {
+                        let result = async {
+                            if concurrency == 1 && position > 0 {
+                                println!("⏳ Waiting 5s before next operation...");
+                                self.client.pause(BATCH_OPERATION_DELAY).await?;
+                            }
+                            self.client.ensure_not_cancelled()?;
+                            self.run_batch_operation(i, total, operation, coldkey_locks)
+                                .await
+                        }
+                        .await;
+                        if let Err(e) = &result {
+                            println!(
+                                "❌ Operation {} ({}) failed: {}",
+                                i + 1,
+                                operation.operation,
+                                e
+                            );
+                        }
+
+                        let (state, tx_hash, error) = match &result {
+                            Ok(BatchOutcome::Registered(outcome)) => (
+                                EntryState::Succeeded,
+                                outcome.tx_hash.map(|tx_hash| format!("{:?}", tx_hash)),
+                                None,
+                            ),
+                            Ok(BatchOutcome::Completed(_)) => (EntryState::Succeeded, None, None),
+                            Ok(BatchOutcome::Skipped(reason)) => {
+                                (EntryState::Failed, None, Some(reason.clone()))
+                            }
+                            Err(e) => (EntryState::Failed, None, Some(e.to_string())),
+                        };
+                        let recorded = journal
+                            .lock()
+                            .unwrap_or_else(|e| e.into_inner())
+                            .record(i, state, tx_hash, error);
+                        if let Err(e) = recorded {
+                            warnings::emit(
+                                WarningCode::RecordFailed,
+                                format!("Failed to update the batch journal: {}", e),
+                            );
+                        }
+                        progress.inc(1);
+                        (i, result)
+                    }
+                })
+                .buffer_unordered(concurrency)
+                .collect()
+                .await;
+
+        let mut session = SessionSummary::new("batch");
+        let mut submitted: Vec<(u16, AccountId32)> = Vec::new();
+        for (i, operation) in batch_config.operations.iter().enumerate() {
+            let Some(result) = results.remove(&i) else {
+                session.record_operation(OperationReport {
+                    index: i + 1,
+                    operation: operation.operation.clone(),
+                    subnet: operation.subnet,
+                    hotkey: operation.hotkey.clone(),
+                    status: OperationStatus::Skipped,
+                    detail: "completed in an earlier run".to_string(),
+                    tx_hash: completed[i].tx_hash.clone(),
+                });
+                continue;
+            };
+            let mut report = OperationReport {
+                index: i + 1,
+                operation: operation.operation.clone(),
+                subnet: operation.subnet,
+                hotkey: operation.hotkey.clone(),
+                status: OperationStatus::Succeeded,
+                detail: String::new(),
+                tx_hash: None,
+            };
+            match result {
+                Ok(BatchOutcome::Registered(outcome)) => {
+                    session.record_success(outcome.burn as u128, outcome.fee);
+                    match outcome.tx_hash {
+                        Some(tx_hash) => {
+                            let hotkey = key_utils::resolve_hotkey(
+                                operation.wallet.as_deref(),
+                                &operation.hotkey,
+                            );
+                            if let Some(netuid) = operation.subnet {
+                                submitted
+                                    .push((netuid, key_utils::account_id_from_string(&hotkey)?));
+                            }
+                            report.detail =
+                                format!("burned {}", utils::format_tao(outcome.burn as u128));
+                            report.tx_hash = Some(format!("{:?}", tx_hash));
+                        }
+                        None => report.detail = "nothing submitted".to_string(),
+                    }
+                }
+                Ok(BatchOutcome::Completed(detail)) => {
+                    session.record_success(0, None);
+                    report.detail = detail.to_string();
+                }
+                Ok(BatchOutcome::Skipped(reason)) => {
+                    report.status = OperationStatus::Skipped;
+                    report.detail = reason;
+                }
+                Err(e) => {
+                    session.record_failure(&operation.operation, &e);
+                    report.status = OperationStatus::Failed;
+                    report.detail = e.to_string();
+                }
+            }
+            session.record_operation(report);
+        }
+
+        if !submitted.is_empty() {
+            for (netuid, hotkey) in self.verify_registrations(submitted).await? {
+                session.warn(warnings::emit(
+                    WarningCode::RegistrationUnverified,
+                    format!(
+                        "Registration of {} in subnet {} not yet visible on chain",
+                        hotkey.to_ss58check(),
+                        netuid
+                    ),
+                ));
+            }
+        }
+
+        println!("\n🎉 Batch operations completed!");
+        session.finish(summary_path)
+    }
+
+    // One batch operation, holding its coldkey's lock while it runs
+    async fn run_batch_operation(
+        &self,
+        index: usize,
+        total: usize,
+        operation: &BatchOperation,
+        coldkey_locks: &HashMap<String, tokio::sync::Mutex<()>>,
+    ) -> Result<BatchOutcome> {
+        println!(
+            "\n🔄 Operation {}/{}: {} {}",
+            index + 1,
+            total,
+            operation.operation,
+            operation.target()
+        );
+
+        let wallet = operation.wallet.as_deref().map(key_utils::resolve_wallet);
+        let hotkey = key_utils::resolve_hotkey(operation.wallet.as_deref(), &operation.hotkey);
+        let _coldkey_turn = match wallet.as_ref().and_then(|w| coldkey_locks.get(w)) {
+            Some(lock) => Some(lock.lock().await),
+            None => None,
+        };
+        let options = RegistrationOptions {
+            assume_yes: true,
+            skip_verify: true,
+            ..Default::default()
+        };
+        // Validated up front, these only trip if the config skipped validation
+        let needs_wallet = || anyhow!("{} needs a wallet", operation.operation);
+        let netuid = || {
+            operation
+                .subnet
+                .ok_or_else(|| anyhow!("{} needs a subnet", operation.operation))
+        };
+        let missing = |block: &str| anyhow!("{} needs {} parameters", operation.operation, block);
+
+        match operation.operation.as_str() {
+            "register" => {
+                let wallet = wallet.ok_or_else(needs_wallet)?;
+                let outcome = self
+                    .register_to_subnet(netuid()?, &wallet, &hotkey, &options)
+                    .await?;
+                println!("✅ Registration completed");
+                Ok(BatchOutcome::Registered(outcome))
+            }
+            "check_status" => {
+                self.check_status(netuid()?, &hotkey).await?;
+                Ok(BatchOutcome::Completed("checked"))
+            }
+            "auto_register" => {
+                let wallet = wallet.ok_or_else(needs_wallet)?;
+                let max_retries = operation.max_retries.unwrap_or(3);
+                let outcome = self
+                    .auto_register_with_retry(netuid()?, &wallet, &hotkey, max_retries, &options)
+                    .await?;
+                println!("✅ Auto registration completed");
+                Ok(BatchOutcome::Registered(outcome))
+            }
+            "transfer" => {
+                let wallet = wallet.ok_or_else(needs_wallet)?;
+                let transfer = operation
+                    .transfer
+                    .as_ref()
+                    .ok_or_else(|| missing("transfer"))?;
+                self.transfer(&wallet, &transfer.to, transfer.amount, true)
+                    .await?;
+                Ok(BatchOutcome::Completed("transferred"))
+            }
+            "add_stake" => {
+                let wallet = wallet.ok_or_else(needs_wallet)?;
+                let stake = operation.stake.as_ref().ok_or_else(|| missing("stake"))?;
+                self.add_stake_alpha(&wallet, &hotkey, constants::ROOT_NETUID, stake.amount, true)
+                    .await?;
+                Ok(BatchOutcome::Completed("staked"))
+            }
+            "serve_axon" => {
+                let axon = operation.axon.as_ref().ok_or_else(|| missing("axon"))?;
+                self.serve_axon(
+                    netuid()?,
+                    &hotkey,
+                    &axon.ip,
+                    axon.port,
+                    axon.protocol,
+                    axon.version,
+                )
+                .await?;
+                Ok(BatchOutcome::Completed("axon served"))
+            }
+            "swap_hotkey" => {
+                let wallet = wallet.ok_or_else(needs_wallet)?;
+                let swap = operation.swap.as_ref().ok_or_else(|| missing("swap"))?;
+                let new_hotkey =
+                    key_utils::resolve_hotkey(operation.wallet.as_deref(), &swap.new_hotkey);
+                self.swap_hotkey(&wallet, &hotkey, &new_hotkey, true)
+                    .await?;
+                Ok(BatchOutcome::Completed("hotkey swapped"))
+            }
+            other => {
+                let warning = warnings::emit(
+                    WarningCode::UnknownOperation,
+                    format!("Unknown operation skipped: {}", other),
+                );
+                Ok(BatchOutcome::Skipped(warning.message))
+            }
+        }
+    }
+
+    // Verify many registrations at once, checking every pending one on each finalized
+    // block. Returns the registrations still missing when verification gives up
+    async fn verify_registrations(
+        &self,
+        mut pending: Vec<(u16, AccountId32)>,
+    ) -> Result<Vec<(u16, AccountId32)>> {
+        println!(
+            "\n🔍 Verifying {} registration(s) on finalized blocks...",
+            pending.len()
+        );
+
+        let mut heads = self.client.subscribe_finalized_heads().await?;
+        let mut blocks_seen = 0;
+        let progress = Progress::bar(BATCH_VERIFY_BLOCKS as u64, "Finalized blocks");
+
+        while blocks_seen < BATCH_VERIFY_BLOCKS {
+            let header = heads
+                .next()
+                .await
+                .ok_or_else(|| {
+                    RegistrationError::new(
+                        ErrorKind::Connection,
+                        "Finalized heads subscription closed",
+                    )
+                })?
+                .context("Invalid block header in subscription")?;
+            blocks_seen += 1;
+            progress.inc(1);
+
+            let mut still_pending = Vec::with_capacity(pending.len());
+            for (netuid, hotkey) in pending {
+                match self.client.get_uid(netuid, &hotkey).await? {
+                    Some(uid) => progress.println(format!(
+                        "✅ {} registered in subnet {} with UID {} (block {})",
+                        utils::format_account_short(&hotkey),
+                        netuid,
+                        uid,
+                        utils::format_thousands(header.number)
+                    )),
+                    None => still_pending.push((netuid, hotkey)),
+                }
+            }
+            pending = still_pending;
+
+            if pending.is_empty() {
+                progress.println("✅ All registrations verified");
+                return Ok(pending);
+            }
+            progress.set_message(format!("{} pending", pending.len()));
+            progress.plain_println(format!(
+                "   Block {}: {} registration(s) still pending",
+                utils::format_thousands(header.number),
+                pending.len()
+            ));
+        }
+        drop(progress);
+
+        println!(
+            "⚠️ {} registration(s) not visible after {} finalized blocks",
+            pending.len(),
+            BATCH_VERIFY_BLOCKS
+        );
+        Ok(pending)
+    }
+
+    // Check account balance
+    // Rotate a hotkey: swap it for a new one and check every UID moved over
+    pub async fn swap_hotkey(
+        &self,
+        wallet_path: &str,
+        old_hotkey_path: &str,
+        new_hotkey_path: &str,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!("{}", "🔁 Swapping Hotkey".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let old_hotkey = key_utils::account_id_from_string(old_hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load old hotkey")?;
+        let new_hotkey = key_utils::account_id_from_string(new_hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load new hotkey")?;
+
+        println!("   Coldkey: {}", coldkey_pair.account_id().to_ss58check());
+        println!("   Old hotkey: {}", old_hotkey.to_ss58check());
+        println!("   New hotkey: {}", new_hotkey.to_ss58check());
+
+        println!("\n🔍 Looking up registrations...");
+        let registrations = self.client.find_registrations(&old_hotkey).await?;
+        if registrations.is_empty() {
+            return Err(anyhow!("Old hotkey is not registered in any subnet"));
+        }
+        for (netuid, uid) in &registrations {
+            println!("   Subnet {}: UID {}", netuid, uid);
+        }
+
+        let new_registrations = self.client.find_registrations(&new_hotkey).await?;
+        if !new_registrations.is_empty() {
+            return Err(anyhow!(
+                "New hotkey is already registered in subnet(s) {:?}, the swap would fail",
+                new_registrations
+                    .iter()
+                    .map(|(netuid, _)| netuid)
+                    .collect::<Vec<_>>()
+            ));
+        }
+
+        if !assume_yes && !utils::confirm("Proceed with hotkey swap?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Hotkey swap cancelled").into());
+        }
+
+        let tx_hash = self
+            .client
+            .submit_swap_hotkey(&old_hotkey, &new_hotkey, &coldkey_pair)
+            .await?;
+        println!("   Transaction hash: {}", tx_hash);
+
+        println!("\n🔍 Verifying UIDs moved to the new hotkey...");
+        for attempt in 1..=5 {
+            self.client.pause(utils::block_time()).await?;
+
+            let mut pending = Vec::new();
+            for (netuid, uid) in &registrations {
+                if self.client.get_hotkey_by_uid(*netuid, *uid).await? != Some(new_hotkey.clone()) {
+                    pending.push(*netuid);
+                }
+            }
+
+            if pending.is_empty() {
+                println!(
+                    "✅ All {} registration(s) now map to the new hotkey",
+                    registrations.len()
+                );
+                return Ok(());
+            }
+            println!(
+                "   Attempt {}/5: subnet(s) {:?} not updated yet...",
+                attempt, pending
+            );
+        }
+
+        println!(
+            "⚠️ Swap not visible on every subnet yet. Check status manually in a few minutes."
+        );
+        Ok(())
+    }
+
+    // Publish the miner's axon endpoint and confirm the Axons entry updated
+    pub async fn serve_axon(
+        &self,
+        netuid: u16,
+        hotkey_path: &str,
+        ip: &str,
+        port: u16,
+        protocol: u8,
+        version: u32,
+    ) -> Result<()> {
+        println!("{}", "📡 Serving Axon".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let (ip, ip_type) = utils::parse_ip(ip)?;
+        let hotkey_pair = key_utils::load_keypair_from_file(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let hotkey_account = hotkey_pair.account_id();
+
+        println!("   Subnet: {}", netuid);
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Endpoint: {}:{}", utils::format_ip(ip, ip_type), port);
+
+        if self
+            .client
+            .get_uid(netuid, &hotkey_account)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow!(
+                "Hotkey is not registered in subnet {}, register before serving an axon",
+                netuid
+            ));
+        }
+
+        let axon = AxonInfo {
+            version,
+            ip,
+            port,
+            ip_type,
+            protocol,
+            ..Default::default()
+        };
+        let tx_hash = self
+            .client
+            .submit_serve_axon(netuid, &axon, &hotkey_pair)
+            .await?;
+        println!("   Transaction hash: {}", tx_hash);
+
+        println!("\n🔍 Verifying axon...");
+        for attempt in 1..=5 {
+            self.client.pause(utils::block_time()).await?;
+
+            match self.client.get_axon_info(netuid, &hotkey_account).await? {
+                Some(served) if served.ip == ip && served.port == port => {
+                    println!(
+                        "✅ Axon published at {}:{} (block {})",
+                        utils::format_ip(served.ip, served.ip_type),
+                        served.port,
+                        utils::format_thousands(served.block)
+                    );
+                    return Ok(());
+                }
+                _ => println!("   Attempt {}/5: not updated yet...", attempt),
+            }
+        }
+
+        println!("⚠️ Axon update not visible yet. Check status manually in a few minutes.");
+        Ok(())
+    }
+
+    // Transfer TAO with a fee preview, waiting for finalization
+    pub async fn transfer(
+        &self,
+        wallet_path: &str,
+        dest: &str,
+        amount: u64,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!("{}", "💸 Transferring TAO".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        let dest_account =
+            key_utils::account_id_from_string(dest).context("Invalid destination")?;
+
+        let context = self.client.fetch_signing_context(&coldkey_account).await?;
+        let extrinsic =
+            self.client
+                .build_transfer(&dest_account, amount, &coldkey_pair, &context)?;
+        let fee = self.client.get_extrinsic_fee(&extrinsic).await?;
+        let balance = self.client.get_account_balance(&coldkey_account).await? as u128;
+        let total = amount as u128 + fee;
+
+        println!("   From: {}", coldkey_account.to_ss58check());
+        println!("   To: {}", dest_account.to_ss58check());
+        println!("   Amount: {}", utils::format_tao(amount as u128));
+        println!("   Fee: {}", utils::format_tao(fee));
+        println!("   Balance: {}", utils::format_tao(balance));
+        println!(
+            "   Balance after: {}",
+            utils::format_tao(balance.saturating_sub(total))
+        );
+
+        if balance < total {
+            return Err(RegistrationError::new(
+                ErrorKind::Balance,
+                format!(
+                    "Insufficient balance. Required: {} with the fee, Available: {}",
+                    utils::format_tao(total),
+                    utils::format_tao(balance)
+                ),
+            )
+            .into());
+        }
+
+        if !assume_yes && !utils::confirm("Proceed with transfer?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Transfer cancelled").into());
+        }
+
+        println!("\n📤 Submitting transfer and waiting for finalization...");
+        let block_hash = self.client.submit_and_watch_extrinsic(&extrinsic).await?;
+
+        println!("\n✅ Transfer finalized");
+        println!(
+            "   Extrinsic hash: 0x{}",
+            hex::encode(sp_core::blake2_256(&extrinsic))
+        );
+        println!("   Block: {:?}", block_hash);
+        self.report_balance_change(&coldkey_account, block_hash)
+            .await;
+
+        Ok(())
+    }
+
+    // Test TAO for a coldkey from the faucet of a test or local network
+    pub async fn faucet(&self, wallet_path: &str) -> Result<()> {
+        println!("{}", "🚰 Faucet".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        match network::current() {
+            Some(network) if !network.is_mainnet() => {}
+            _ => {
+                return Err(anyhow!(
+                "The faucet only exists on test networks, pass --network test or --network local"
+            ))
+            }
+        }
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+
+        let difficulty = primitive_types::U256::from(faucet::FAUCET_DIFFICULTY);
+        let mut start = 0u64;
+        let progress = Progress::spinner("Solving proof of work");
+        let work = loop {
+            self.client.ensure_not_cancelled()?;
+            let block_number = self.client.get_current_block().await?;
+            let block_hash = self.client.get_block_hash(Some(block_number)).await?;
+            progress.set_message(format!(
+                "Solving proof of work for block {}, {} nonces tried",
+                block_number,
+                utils::format_thousands(start)
+            ));
+            progress.plain_println(format!(
+                "⛏️ Solving proof of work for block {}...",
+                block_number
+            ));
+
+            let account = coldkey_account.clone();
+            let solved = tokio::task::spawn_blocking(move || {
+                faucet::solve(
+                    block_number,
+                    &block_hash,
+                    &account,
+                    difficulty,
+                    start,
+                    faucet::FAUCET_DIFFICULTY * 4,
+                )
+            })
+            .await?;
+
+            // Work for a block the chain has moved past is refused, start over on a new one
+            let current = self.client.get_current_block().await?;
+            match solved {
+                Some(work)
+                    if current.saturating_sub(work.block_number) <= faucet::MAX_WORK_AGE_BLOCKS =>
+                {
+                    break work
+                }
+                Some(_) => progress.println(format!(
+                    "   Block {} is too old by now, retrying",
+                    block_number
+                )),
+                None => start += faucet::FAUCET_DIFFICULTY * 4,
+            }
+        };
+        drop(progress);
+        println!("✅ Solved with nonce {}", work.nonce);
+
+        println!("\n📤 Submitting faucet call...");
+        let inclusion = self.client.submit_faucet(&work, &coldkey_pair).await?;
+        println!("\n✅ Faucet call included");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+        self.report_balance_change(&coldkey_account, inclusion.block_hash)
+            .await;
+
+        Ok(())
+    }
+
+    // Work out what each campaign coldkey still needs and top them up from a treasury
+    pub async fn fund_plan(
+        &self,
+        treasury_wallet: &str,
+        targets: &[(String, u16)],
+        options: &FundPlanOptions,
+    ) -> Result<FundPlan> {
+        println!("{}", "🏦 Funding Plan".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        if targets.is_empty() {
+            return Err(anyhow!("No registrations to fund"));
+        }
+
+        let mut burns: HashMap<u16, u64> = HashMap::new();
+        for (_, netuid) in targets {
+            if !burns.contains_key(netuid) {
+                let info = self.client.get_subnet_info(*netuid, false).await?;
+                if !info.registration_allowed {
+                    warnings::emit(
+                        WarningCode::RegistrationClosed,
+                        format!("Registration is closed on subnet {}", netuid),
+                    );
+                }
+                burns.insert(*netuid, info.burn);
+            }
+        }
+
+        // Targets of one coldkey are funded together, in the order first seen
+        let mut needs: Vec<FundingNeed> = Vec::new();
+        for (wallet, netuid) in targets {
+            let address = key_utils::coldkey_address(wallet)
+                .with_context(|| format!("Failed to read the coldkey of {}", wallet))?
+                .to_ss58check();
+            let cost = funding::registration_cost(burns[netuid], options.margin_percent);
+            match needs.iter_mut().find(|need| need.address == address) {
+                Some(need) => {
+                    need.subnets.push(*netuid);
+                    need.required = need.required.saturating_add(cost);
+                }
+                None => needs.push(FundingNeed {
+                    wallet: wallet.clone(),
+                    address,
+                    subnets: vec![*netuid],
+                    required: cost,
+                    balance: 0,
+                }),
+            }
+        }
+        for need in &mut needs {
+            need.balance = self
+                .client
+                .get_account_balance(&key_utils::parse_address(&need.address)?)
+                .await?;
+        }
+
+        let treasury = key_utils::coldkey_address(treasury_wallet)
+            .context("Failed to read the treasury coldkey")?;
+        let treasury_balance = self.client.get_account_balance(&treasury).await?;
+        let plan = FundPlan::new(treasury.to_ss58check(), treasury_balance, needs);
+
+        println!(
+            "   Burn margin: {}% on top of current burns",
+            options.margin_percent
+        );
+        println!(
+            "\n {:<48}  {:<12}  {:>14}  {:>14}  {:>14}",
+            "Coldkey", "Subnets", "Required", "Balance", "Short"
+        );
+        for need in &plan.needs {
+            let subnets = need
+                .subnets
+                .iter()
+                .map(|netuid| netuid.to_string())
+                .collect::<Vec<_>>()
+                .join(",");
+            let short = need.shortfall();
+            println!(
+                " {:<48}  {:<12}  {:>14}  {:>14}  {:>14}",
+                need.address,
+                subnets,
+                utils::format_tao(need.required as u128),
+                utils::format_tao(need.balance as u128),
+                if short == 0 {
+                    "-".bright_green().to_string()
+                } else {
+                    utils::format_tao(short as u128).bright_red().to_string()
+                }
+            );
+        }
+
+        println!("\n💸 Transfers from treasury {}:", plan.treasury);
+        if plan.transfers.is_empty() {
+            println!("   None, every coldkey is already funded");
+        }
+        for transfer in &plan.transfers {
+            println!(
+                "   {} → {} ({})",
+                utils::format_tao(transfer.amount as u128),
+                transfer.to,
+                transfer.wallet
+            );
+        }
+        println!(
+            "   Treasury needs {}, holds {}",
+            utils::format_tao(plan.treasury_required as u128),
+            utils::format_tao(plan.treasury_balance as u128)
+        );
+
+        if let Some(path) = &options.output {
+            std::fs::write(path, serde_json::to_string_pretty(&plan)?)
+                .with_context(|| format!("Failed to write funding plan to {}", path))?;
+            println!("💾 Plan written to {}", path);
+        }
+
+        if plan.treasury_shortfall() > 0 {
+            return Err(anyhow!(
+                "Treasury is short by {}, top it up before running this plan",
+                utils::format_tao(plan.treasury_shortfall() as u128)
+            ));
+        }
+        if !options.execute || plan.transfers.is_empty() {
+            return Ok(plan);
+        }
+
+        if !options.assume_yes
+            && !utils::confirm(&format!("Send {} transfer(s)?", plan.transfers.len()))?
+        {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Funding cancelled").into());
+        }
+
+        let treasury_pair = key_utils::load_keypair_from_file(treasury_wallet)
+            .kind_context(ErrorKind::Fatal, "Failed to load treasury wallet")?;
+        if treasury_pair.account_id() != treasury {
+            return Err(anyhow!(
+                "Treasury key does not match its coldkeypub.txt address {}",
+                plan.treasury
+            ));
+        }
+        for transfer in &plan.transfers {
+            let dest = key_utils::parse_address(&transfer.to)?;
+            let tx_hash = self
+                .client
+                .submit_transfer(&dest, transfer.amount, &treasury_pair)
+                .await?;
+            println!(
+                "   ✅ {} → {}: {:?}",
+                utils::format_tao(transfer.amount as u128),
+                transfer.to,
+                tx_hash
+            );
+        }
+        println!("\n🎉 Transfers submitted, balances update once they are included");
+
+        Ok(plan)
+    }
+
+    // Register a hotkey on the root network with root_register. There is no burn: a full root
+    // network takes the hotkey only if its stake beats the lowest validator's, and the top
+    // stakes also sit in the senate
+    pub async fn register_root(
+        &self,
+        wallet_path: &str,
+        hotkey_path: &str,
+        allow_foreign_hotkey: bool,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!("{}", "🌳 Root Network Registration".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+
+        if let Some(uid) = self
+            .client
+            .get_uid(constants::ROOT_NETUID, &hotkey_account)
+            .await?
+        {
+            println!(
+                "✅ Already registered on the root network with UID: {}",
+                uid
+            );
+            return Ok(());
+        }
+        let owner = self.client.get_hotkey_owner(&hotkey_account).await?;
+        check_hotkey_owner(
+            &hotkey_account,
+            owner.as_ref(),
+            &coldkey_account,
+            allow_foreign_hotkey,
+        )?;
+
+        let stake = self.client.get_root_stake(&hotkey_account).await?;
+        let validators = self.client.get_root_validators().await?;
+        let max_validators = self.client.get_root_max_validators().await?;
+        println!("   Hotkey stake: {}", utils::format_tao(stake as u128));
+        println!(
+            "   Root validators: {}/{}",
+            validators.len(),
+            max_validators
+        );
+
+        match root_slot(stake, &validators, max_validators)? {
+            None => println!("✅ Open slot on the root network"),
+            Some((uid, lowest)) => println!(
+                "⚠️ Root network is full, this replaces UID {} with {} staked",
+                uid,
+                utils::format_tao(lowest as u128)
+            ),
+        }
+
+        let senate = self.client.get_senate_members().await?;
+        let mut senate_stakes = Vec::with_capacity(senate.len());
+        for member in &senate {
+            senate_stakes.push(self.client.get_root_stake(member).await?);
+        }
+        println!(
+            "   Senate: {}/{} members",
+            senate.len(),
+            constants::SENATE_MAX_MEMBERS
+        );
+        if joins_senate(stake, &senate_stakes) {
+            println!("🏛️ The hotkey's stake is enough for a senate seat");
+        } else {
+            println!(
+                "ℹ️ No senate seat: it needs more stake than {}",
+                utils::format_tao(senate_stakes.iter().copied().min().unwrap_or(0) as u128)
+            );
+        }
+
+        if !assume_yes && !utils::confirm("Proceed with root registration?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Registration cancelled").into());
+        }
+
+        let inclusion = self
+            .client
+            .submit_root_register(&hotkey_account, &coldkey_pair)
+            .await?;
+        println!("\n🎉 Root registration included");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Stake TAO into a subnet's alpha on a hotkey, the swap previewed against the pool first
+    pub async fn add_stake_alpha(
+        &self,
+        wallet_path: &str,
+        hotkey_path: &str,
+        netuid: u16,
+        amount: u64,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            format!("📥 Staking to Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        let balance = self.client.get_account_balance(&coldkey_account).await?;
+        let pool = self.client.get_subnet_pool(netuid).await?;
+
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Amount: {}", utils::format_tao(amount as u128));
+        println!("   Balance: {}", utils::format_tao(balance as u128));
+        println!("   Alpha price: {:.6} TAO", pool.price());
+        let (spot, expected) = pool.quote_stake(amount);
+        print_swap_preview(spot, expected, utils::format_alpha);
+
+        if balance < amount {
+            return Err(RegistrationError::new(
+                ErrorKind::Balance,
+                format!(
+                    "Insufficient balance. Required: {}, Available: {}",
+                    utils::format_tao(amount as u128),
+                    utils::format_tao(balance as u128)
+                ),
+            )
+            .into());
+        }
+        if !assume_yes && !utils::confirm("Proceed with staking?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Staking cancelled").into());
+        }
+
+        let inclusion = self
+            .client
+            .submit_add_stake_alpha(&hotkey_account, netuid, amount, &coldkey_pair)
+            .await?;
+        println!("\n✅ Stake included");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Unstake alpha from a hotkey in a subnet, swapped back to TAO on the wallet coldkey
+    pub async fn remove_stake_alpha(
+        &self,
+        wallet_path: &str,
+        hotkey_path: &str,
+        netuid: u16,
+        amount: u64,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!(
+            "{}",
+            format!("📤 Unstaking from Subnet {}", netuid)
+                .bright_cyan()
+                .bold()
+        );
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let coldkey_account = coldkey_pair.account_id();
+        let stake = self
+            .client
+            .get_alpha_stake(&hotkey_account, &coldkey_account, netuid)
+            .await?;
+        let pool = self.client.get_subnet_pool(netuid).await?;
+
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Amount: {}", utils::format_alpha(amount as u128));
+        println!("   Your stake: {}", utils::format_alpha(stake as u128));
+        println!("   Alpha price: {:.6} TAO", pool.price());
+        let (spot, expected) = pool.quote_unstake(amount);
+        print_swap_preview(spot, expected, utils::format_tao);
+
+        if stake < amount {
+            return Err(RegistrationError::new(
+                ErrorKind::Balance,
+                format!(
+                    "Insufficient stake. Requested: {}, Staked: {}",
+                    utils::format_alpha(amount as u128),
+                    utils::format_alpha(stake as u128)
+                ),
+            )
+            .into());
+        }
+        if !assume_yes && !utils::confirm("Proceed with unstaking?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Unstaking cancelled").into());
+        }
+
+        let inclusion = self
+            .client
+            .submit_remove_stake_alpha(&hotkey_account, netuid, amount, &coldkey_pair)
+            .await?;
+        println!("\n✅ Unstake included");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Move alpha between hotkeys and subnets. Across subnets it is sold into the origin pool
+    // and the TAO bought into the destination's, both swaps in the preview
+    pub async fn move_stake(
+        &self,
+        wallet_path: &str,
+        stake_move: &StakeMove,
+        assume_yes: bool,
+    ) -> Result<()> {
+        println!("{}", "🔀 Moving Stake".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let coldkey_pair = key_utils::load_keypair_from_file(wallet_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load wallet/coldkey")?;
+        let hotkey_alpha = self
+            .client
+            .get_hotkey_alpha(&stake_move.origin_hotkey, stake_move.origin_netuid)
+            .await?;
+
+        println!("   Coldkey: {}", coldkey_pair.account_id().to_ss58check());
+        println!(
+            "   From: {} in subnet {}",
+            stake_move.origin_hotkey.to_ss58check(),
+            stake_move.origin_netuid
+        );
+        println!(
+            "   To: {} in subnet {}",
+            stake_move.destination_hotkey.to_ss58check(),
+            stake_move.destination_netuid
+        );
+        println!(
+            "   Amount: {}",
+            utils::format_alpha(stake_move.amount as u128)
+        );
+        if stake_move.origin_netuid == stake_move.destination_netuid {
+            println!("   Same subnet, no swap: the alpha moves as it is");
+        } else {
+            let origin = self
+                .client
+                .get_subnet_pool(stake_move.origin_netuid)
+                .await?;
+            let destination = self
+                .client
+                .get_subnet_pool(stake_move.destination_netuid)
+                .await?;
+            let (tao, spot, expected) = quote_move(&origin, &destination, stake_move.amount);
+            println!("   Swapped through {}", utils::format_tao(tao as u128));
+            print_swap_preview(spot, expected, utils::format_alpha);
+        }
+
+        if hotkey_alpha < stake_move.amount {
+            return Err(RegistrationError::new(
+                ErrorKind::Balance,
+                format!(
+                    "Insufficient stake. Requested: {}, the origin hotkey holds {}",
+                    utils::format_alpha(stake_move.amount as u128),
+                    utils::format_alpha(hotkey_alpha as u128)
+                ),
+            )
+            .into());
+        }
+        if !assume_yes && !utils::confirm("Proceed with the move?")? {
+            return Err(RegistrationError::new(ErrorKind::Fatal, "Stake move cancelled").into());
+        }
+
+        let inclusion = self
+            .client
+            .submit_move_stake(stake_move, &coldkey_pair)
+            .await?;
+        println!("\n✅ Stake moved");
+        println!("   Transaction hash: {:?}", inclusion.tx_hash);
+        println!("   Block: {:?}", inclusion.block_hash);
+
+        Ok(())
+    }
+
+    // Alpha stake on a hotkey in a subnet, optionally for a single coldkey
+    pub async fn show_stake_info(
+        &self,
+        hotkey_path: &str,
+        netuid: u16,
+        coldkey: Option<&str>,
+    ) -> Result<()> {
+        println!("{}", "📊 Stake Info".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let hotkey_account = key_utils::account_id_from_string(hotkey_path)
+            .kind_context(ErrorKind::Fatal, "Failed to load hotkey")?;
+        let total = self
+            .client
+            .get_hotkey_alpha(&hotkey_account, netuid)
+            .await?;
+
+        println!("   Hotkey: {}", hotkey_account.to_ss58check());
+        println!("   Subnet: {}", netuid);
+        println!("   Total stake: {}", utils::format_alpha(total as u128));
+
+        if let Some(coldkey) = coldkey {
+            let coldkey_account = key_utils::account_id_from_string(coldkey)
+                .kind_context(ErrorKind::Fatal, "Failed to load coldkey")?;
+            let stake = self
+                .client
+                .get_alpha_stake(&hotkey_account, &coldkey_account, netuid)
+                .await?;
+            let share = if total > 0 {
+                stake as f64 / total as f64 * 100.0
+            } else {
+                0.0
+            };
+
+            println!("   Coldkey: {}", coldkey_account.to_ss58check());
+            println!(
+                "   Coldkey stake: {} ({:.2}% of total)",
+                utils::format_alpha(stake as u128),
+                share
+            );
+        }
+
+        Ok(())
+    }
+
+    // Every hotkey a coldkey owns with its registrations and stake, and the coldkey's total
+    // on-chain exposure: free balance plus its stake on those hotkeys
+    pub async fn show_wallet_overview(&self, coldkey: &str) -> Result<()> {
+        println!("{}", "👛 Wallet Overview".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+
+        let coldkey_account = key_utils::account_id_from_string(coldkey)
+            .kind_context(ErrorKind::Fatal, "Failed to load coldkey")?;
+        let balance = self.client.get_account_balance(&coldkey_account).await? as u128;
+        println!("   Coldkey: {}", coldkey_account.to_ss58check());
+        println!("   Free balance: {}", utils::format_tao(balance));
+
+        let current_block = self.client.get_current_block().await?;
+        let mut staked = 0u128;
+        let hotkeys = self.client.get_owned_hotkeys(&coldkey_account).await?;
+        println!("\n🔑 Hotkeys: {}", hotkeys.len());
+        for hotkey in &hotkeys {
+            // OwnedHotkeys keeps entries a hotkey swap has since moved elsewhere
+            let owner = self.client.get_hotkey_owner(hotkey).await?;
+            if owner.as_ref() != Some(&coldkey_account) {
+                println!(
+                    "\n   {} {}",
+                    hotkey.to_ss58check(),
+                    "(no longer owned)".dimmed()
+                );
+                continue;
+            }
+            let total = self.client.get_root_stake(hotkey).await?;
+            let own = self
+                .client
+                .get_alpha_stake(hotkey, &coldkey_account, constants::ROOT_NETUID)
+                .await?;
+            staked += own as u128;
+            println!("\n   {}", hotkey.to_ss58check().bright_white());
+            println!(
+                "     Stake: {} (yours: {})",
+                utils::format_tao(total as u128),
+                utils::format_tao(own as u128)
+            );
+
+            let registrations = self.client.find_registrations(hotkey).await?;
+            if registrations.is_empty() {
+                println!("     Registrations: none, free to reuse");
+            }
+            for (netuid, uid) in registrations {
+                let alpha = self.client.get_hotkey_alpha(hotkey, netuid).await?;
+                let registered_at = self.client.get_block_at_registration(netuid, uid).await?;
+                let immunity_period = self.client.get_immunity_period(netuid).await?;
+                let remaining =
+                    (registered_at + immunity_period as u64).saturating_sub(current_block);
+                println!(
+                    "     Subnet {}: UID {}, stake {}{}",
+                    netuid,
+                    uid,
+                    utils::format_tao(alpha as u128),
+                    if remaining > 0 {
+                        format!(", 🛡️ immune for {}", utils::format_blocks(remaining))
+                    } else {
+                        String::new()
+                    }
+                );
+            }
+        }
+
+        println!("\n📊 On-chain exposure:");
+        println!("   Free: {}", utils::format_tao(balance));
+        println!("   Staked: {}", utils::format_tao(staked));
+        println!("   Total: {}", utils::format_tao(balance + staked));
+
+        Ok(())
+    }
+
+    // Hardcoded encodings against the runtime's own metadata, an error when any drifted
+    pub async fn check_metadata(&self, save: Option<&str>) -> Result<()> {
+        println!("🧬 Checking encodings against runtime metadata...");
+        let bytes = self.client.get_metadata().await?;
+        if let Some(path) = save {
+            std::fs::write(path, &bytes).with_context(|| format!("Failed to write {}", path))?;
+            println!("💾 Metadata saved to {} ({} bytes)", path, bytes.len());
+        }
+
+        let capabilities = capabilities::Capabilities::current();
+        let drift = Metadata::decode(&bytes)?.drift(&capabilities);
+        if drift.is_empty() {
+            println!(
+                "✅ {} calls, storage layouts and signed extensions match the runtime",
+                capabilities.calls.len()
+            );
+            return Ok(());
+        }
+        for mismatch in &drift {
+            println!("❌ {}", mismatch);
+        }
+        Err(anyhow!(
+            "{} encoding mismatches with the runtime, don't submit without --force",
+            drift.len()
+        ))
+    }
+
+    pub async fn check_account_balance(&self, account_address: &str) -> Result<()> {
+        println!("💰 Checking account balance...");
+
+        // SS58 or hex public key
+        let account = key_utils::parse_address(account_address)?;
+
+        // Get account info with debug output
+        match self.client.get_account_balance(&account).await {
+            Ok(balance) => {
+                println!("✅ Account balance retrieved successfully!");
+                println!("💰 Address: {}", account.to_ss58check());
+                println!("💰 Public key: 0x{}", hex::encode(&account));
+                println!("💰 Balance: {} RAO", balance);
+                println!("💰 Balance: {:.6} TAO", utils::format_tao(balance as u128));
+
+                if balance == 0 {
+                    println!("ℹ️ Note: Account has zero balance or doesn't exist on-chain");
+                }
+            }
+            Err(e) => {
+                println!("❌ Failed to get account balance: {}", e);
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// Block time from the config, measured on chain unless fixed there
+async fn resolve_block_time<C: ChainApi>(
+    client: &BittensorClient<C>,
+    block_time: &BlockTimeConfig,
+) -> Duration {
+    let sample_blocks = match *block_time {
+        BlockTimeConfig::Fixed { seconds } => return Duration::from_secs_f64(seconds.max(0.1)),
+        BlockTimeConfig::Measured { sample_blocks } => sample_blocks,
+    };
+    match client.measure_block_time(sample_blocks).await {
+        Ok(Some(block_time)) => block_time,
+        // Estimates only, the default is close enough when the chain can't tell us
+        _ => Duration::from_secs(constants::DEFAULT_BLOCK_TIME),
+    }
+}
+
+fn format_axon(axon: &AxonInfo) -> String {
+    if axon.ip == 0 {
+        "-".to_string()
+    } else {
+        format!("{}:{}", utils::format_ip(axon.ip, axon.ip_type), axon.port)
+    }
+}
+
+// UID sorts ascending, every other column highest first
+const METAGRAPH_TEXT_HEADER: &str = "\n  UID  Hotkey                        Stake   Trust  Incentive      Emission  VPermit    Take  Axon";
+
+fn metagraph_text_row(neuron: &NeuronInfoLite, takes: &HashMap<AccountId32, u16>) -> String {
+    format!(
+        " {:>4}  {:<19}  {:>14}  {:>6}  {:>9}  {:>12}  {:>7}  {:>6}  {}",
+        neuron.uid,
+        utils::format_account_short(&neuron.hotkey),
+        utils::format_tao(neuron.stake as u128),
+        utils::format_u16_ratio(neuron.trust),
+        utils::format_u16_ratio(neuron.incentive),
+        utils::format_thousands(neuron.emission),
+        if neuron.validator_permit { "yes" } else { "no" },
+        takes
+            .get(&neuron.hotkey)
+            .map_or("-".to_string(), |take| format!(
+                "{:.1}%",
+                *take as f64 / u16::MAX as f64 * 100.0
+            )),
+        format_axon(&neuron.axon_info)
+    )
+}
+
+// In the order of utils::METAGRAPH_CSV_HEADER
+fn metagraph_csv_row(neuron: &NeuronInfoLite) -> Vec<String> {
+    vec![
+        neuron.uid.to_string(),
+        neuron.hotkey.to_ss58check(),
+        neuron.coldkey.to_ss58check(),
+        utils::rao_to_tao_string(neuron.stake as u128),
+        utils::format_u16_ratio(neuron.rank),
+        utils::format_u16_ratio(neuron.trust),
+        utils::format_u16_ratio(neuron.consensus),
+        utils::format_u16_ratio(neuron.incentive),
+        utils::format_u16_ratio(neuron.dividends),
+        neuron.emission.to_string(),
+        neuron.validator_permit.to_string(),
+        neuron.active.to_string(),
+        format_axon(&neuron.axon_info),
+    ]
+}
+
+fn sort_neurons(neurons: &mut [NeuronInfoLite], sort_by: &str) -> Result<()> {
+    match sort_by {
+        "uid" => neurons.sort_by_key(|n| n.uid),
+        "stake" => neurons.sort_by_key(|n| std::cmp::Reverse(n.stake)),
+        "rank" => neurons.sort_by_key(|n| std::cmp::Reverse(n.rank)),
+        "trust" => neurons.sort_by_key(|n| std::cmp::Reverse(n.trust)),
+        "consensus" => neurons.sort_by_key(|n| std::cmp::Reverse(n.consensus)),
+        "incentive" => neurons.sort_by_key(|n| std::cmp::Reverse(n.incentive)),
+        "dividends" => neurons.sort_by_key(|n| std::cmp::Reverse(n.dividends)),
+        "emission" => neurons.sort_by_key(|n| std::cmp::Reverse(n.emission)),
+        other => {
+            return Err(anyhow!(
+                "Unknown sort column: {}. Use uid, stake, rank, trust, consensus, incentive, dividends or emission",
+                other
+            ))
+        }
+    }
+    Ok(())
+}
+
+// Last recorded burn in each of the most recent adjustment intervals, oldest first
+fn recorded_burn_history(
+    path: &str,
+    netuid: u16,
+    schedule: &BurnSchedule,
+    intervals: usize,
+    current_block: u64,
+) -> Result<Vec<(String, u64)>> {
+    let interval = utils::blocks_to_duration(schedule.adjustment_interval.max(1) as u64);
+    let interval = chrono::Duration::from_std(interval)?;
+    // Interval boundaries in wall-clock time, anchored on the last adjustment
+    let last_adjustment = chrono::Utc::now()
+        - chrono::Duration::from_std(utils::blocks_to_duration(
+            current_block.saturating_sub(schedule.last_adjustment_block),
+        ))?;
+    let since = last_adjustment - interval * (intervals as i32 - 1);
+
+    let mut samples: Vec<(String, u64)> = Vec::new();
+    let mut last_bucket = None;
+    for snapshot in HistoryStore::open(path)?.snapshots(Some(netuid), since)? {
+        let bucket = (snapshot.recorded_at - since).num_seconds() / interval.num_seconds().max(1);
+        let label = format!(
+            "{}",
+            utils::display_time(snapshot.recorded_at).format("%m-%d %H:%M")
+        );
+        if last_bucket == Some(bucket) {
+            if let Some(last) = samples.last_mut() {
+                *last = (label, snapshot.burn);
+            }
+        } else {
+            samples.push((label, snapshot.burn));
+            last_bucket = Some(bucket);
+        }
+    }
+    Ok(samples)
+}
+
+// Sparkline and per-interval bars of burn samples
+fn print_burn_history(samples: &[(String, u64)], intervals: usize, current_burn: u64) {
+    const BAR_WIDTH: usize = 30;
+
+    println!(
+        "\n📉 Burn History (last {} adjustment intervals):",
+        intervals
+    );
+    if samples.is_empty() {
+        println!("   No burn history available. Old state may be pruned on this node,");
+        println!("   use an archive endpoint or --history with a database from --record");
+        return;
+    }
+
+    let burns: Vec<u64> = samples.iter().map(|(_, burn)| *burn).collect();
+    let first = burns[0];
+    let trend = if first > 0 {
+        (current_burn as f64 - first as f64) / first as f64 * 100.0
+    } else {
+        0.0
+    };
+    println!(
+        "   {}  now {} ({:+.1}% over the period)",
+        utils::sparkline(&burns),
+        utils::format_tao(current_burn as u128),
+        trend
+    );
+
+    let max = burns.iter().copied().max().unwrap_or(0);
+    for (label, burn) in samples {
+        println!(
+            "   {:>15} │{} {}",
+            label,
+            utils::format_bar(*burn, max, BAR_WIDTH),
+            utils::format_tao(*burn as u128)
+        );
+    }
+}
+
+// Next batch of changes, never resolves without a subscription
+async fn next_subnet_change(
+    subnet_changes: &mut Option<SubnetChangeWatch>,
+) -> Option<Result<ChainChanges>> {
+    match subnet_changes {
+        Some(changes) => changes.next().await,
+        None => std::future::pending().await,
+    }
+}
+
+// What a pool swap should return, next to what the spot price alone would give
+fn print_swap_preview(spot: u64, expected: u64, format: fn(u128) -> String) {
+    let slippage = slippage_percent(spot, expected);
+    println!(
+        "   Expected: {} (spot {}, slippage {:.2}%)",
+        format(expected as u128),
+        format(spot as u128),
+        slippage
+    );
+    if slippage >= 5.0 {
+        println!("⚠️ High slippage, a smaller amount moves the price less");
+    }
+}
+
+// Set fields only
+fn print_identity(identity: &ChainIdentity) {
+    for (field, value, _) in identity.fields() {
+        if !value.is_empty() {
+            println!("     {:<12} {}", field, value);
+        }
+    }
+}
+
+fn print_children(label: &str, relations: &[(u64, AccountId32)]) {
+    if relations.is_empty() {
+        println!("   {}: none", label);
+        return;
+    }
+    println!("   {}:", label);
+    for (proportion, hotkey) in relations {
+        println!(
+            "     {} {:>7.2}%",
+            hotkey.to_ss58check(),
+            children::proportion_percent(*proportion)
+        );
+    }
+}
+
+// A delegate's take, stored as a fraction of u16::MAX
+fn take_percent(take: u16) -> f64 {
+    take as f64 / u16::MAX as f64 * 100.0
+}
+
+// Where a hotkey with this stake lands on the root network: None for an open slot, or the
+// uid and stake of the validator it pushes out. validators are sorted lowest stake first
+fn root_slot(
+    stake: u64,
+    validators: &[(u16, AccountId32, u64)],
+    max_validators: u16,
+) -> Result<Option<(u16, u64)>> {
+    if validators.len() < max_validators as usize {
+        return Ok(None);
+    }
+    match validators.first() {
+        Some((uid, _, lowest)) if stake > *lowest => Ok(Some((*uid, *lowest))),
+        Some((_, _, lowest)) => Err(RegistrationError::new(
+            ErrorKind::Fatal,
+            format!(
+                "Root network is full and the hotkey's stake {} doesn't beat the lowest validator's {}",
+                utils::format_tao(stake as u128),
+                utils::format_tao(*lowest as u128)
+            ),
+        )
+        .into()),
+        None => Err(anyhow!("The root network has no slots")),
+    }
+}
+
+// A root validator takes a free senate seat, or the seat of the member with the least stake
+fn joins_senate(stake: u64, senate_stakes: &[u64]) -> bool {
+    senate_stakes.len() < constants::SENATE_MAX_MEMBERS
+        || senate_stakes.iter().any(|member| stake > *member)
+}
+
+fn burn_over_max(burn: u64, max_burn: u64) -> RegistrationError {
+    RegistrationError::new(
+        ErrorKind::RegistrationClosed,
+        format!(
+            "Burn {} exceeds max burn {}, aborting",
+            utils::format_tao(burn as u128),
+            utils::format_tao(max_burn as u128)
+        ),
+    )
+}
+
+// Fails when the hotkey is owned by a coldkey other than the registering one, unless allowed.
+// A hotkey without an owner is new and becomes the registering coldkey's
+fn check_hotkey_owner(
+    hotkey: &AccountId32,
+    owner: Option<&AccountId32>,
+    coldkey: &AccountId32,
+    allow_foreign: bool,
+) -> Result<()> {
+    let Some(owner) = owner.filter(|owner| *owner != coldkey) else {
+        return Ok(());
+    };
+    let message = format!(
+        "Hotkey {} is owned by coldkey {}, not {}",
+        hotkey.to_ss58check(),
+        owner.to_ss58check(),
+        coldkey.to_ss58check()
+    );
+    if !allow_foreign {
+        return Err(RegistrationError::new(
+            ErrorKind::Fatal,
+            format!(
+                "{}. Pass --allow-foreign-hotkey to register it anyway",
+                message
+            ),
+        )
+        .into());
+    }
+    warnings::emit(
+        WarningCode::ForeignHotkey,
+        format!("{}, the chain may still reject the registration", message),
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::chain::{event_record, MockChain};
+    use codec::{Compact, Decode, Encode};
+    use std::sync::Arc;
+
+    pub(crate) const NETUID: u16 = 1;
+    pub(crate) const BURN: u64 = 1_000_000_000;
+
+    pub(crate) fn uid_key(hotkey: &AccountId32) -> String {
+        storage_key(
+            "SubtensorModule",
+            "Uids",
+            &[
+                (StorageHasher::Identity, &NETUID.to_le_bytes()),
+                (StorageHasher::Blake2_128Concat, hotkey.as_ref()),
+            ],
+        )
+    }
+
+    // Subnet 1 open at a burn of 1 TAO, and Alice's coldkey holding the given free balance
+    pub(crate) fn mock_subnet(balance: u128) -> Arc<MockChain> {
+        let chain = MockChain::new(1_000);
+        let subnet_key = |name| {
+            storage_key(
+                "SubtensorModule",
+                name,
+                &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
+            )
+        };
+        chain.insert(&subnet_key("SubnetworkN"), 10u16);
+        chain.insert(&subnet_key("MaxAllowedUids"), 256u16);
+        chain.insert(&subnet_key("Burn"), BURN);
+        let alice = key_utils::account_id_from_string("//Alice").unwrap();
+        let account = (0u32, 0u32, 1u32, 0u32, (balance, 0u128, 0u128, 0u128));
+        chain.insert(
+            &storage_key(
+                "System",
+                "Account",
+                &[(StorageHasher::Blake2_128Concat, alice.as_ref())],
+            ),
+            account,
+        );
+        chain
+    }
+
+    // Retries and reconnects without the waits
+    fn mock_config() -> Config {
+        Config {
+            backoff: Backoff {
+                initial_ms: 1,
+                max_ms: 1,
+                ..Backoff::default()
+            },
+            ..Config::default()
+        }
+    }
+
+    pub(crate) fn mock_register(chain: &Arc<MockChain>) -> QuickRegister<MockChain> {
+        let config = mock_config();
+        let client = BittensorClient::with_chain("mock".to_string(), chain.clone(), config.backoff);
+        QuickRegister::with_client(client, &config).unwrap()
+    }
+
+    fn confirmed() -> RegistrationOptions {
+        RegistrationOptions {
+            assume_yes: true,
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_registration_flow() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        let register = mock_register(&chain);
+
+        let outcome = register
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap();
+        assert_eq!(outcome.burn, BURN);
+        let submitted = chain.submitted();
+        assert_eq!(submitted.len(), 1);
+        assert_eq!(
+            outcome.tx_hash,
+            Some(H256(sp_core::blake2_256(&submitted[0])))
+        );
+        // One query for all of the subnet's storage
+        assert_eq!(chain.requests("storage_batch"), 1);
+
+        // Nothing is submitted without the balance for the burn
+        let chain = mock_subnet(BURN as u128 / 2);
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        assert!(chain.submitted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_snipe_registration() {
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+
+        // Nothing fires while the burn is above the limit, until the heads run out
+        let chain = mock_subnet(10 * BURN as u128);
+        let target = SnipeTarget {
+            netuid: NETUID,
+            max_burn: BURN / 2,
+        };
+        let error = mock_register(&chain)
+            .snipe_registration(&[target], "//Alice", "//Bob", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Connection);
+        assert!(chain.submitted().is_empty());
+
+        // The cheapest subnet within its limit gets the pre-signed registration
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "Burn",
+                &[(StorageHasher::Identity, &2u16.to_le_bytes())],
+            ),
+            BURN / 2,
         );
-        println!("   Max weight limit: {}", subnet_info.max_weight_limit);
-        println!(
-            "   Max allowed validators: {}",
-            subnet_info.max_allowed_validators
+        let targets = [NETUID, 2].map(|netuid| SnipeTarget {
+            netuid,
+            max_burn: 2 * BURN,
+        });
+        let register = mock_register(&chain);
+        // Cancelled up front so the test stops at the block-time wait before verifying
+        register.cancellation_token().cancel();
+        let error = register
+            .snipe_registration(&targets, "//Alice", "//Bob", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Cancelled");
+        let call = [&[7, 7][..], &2u16.to_le_bytes(), bob.as_ref()].concat();
+        assert_eq!(chain.submitted().len(), 1);
+        assert!(chain.submitted()[0].ends_with(&call));
+    }
+
+    #[tokio::test]
+    async fn test_snipe_shared_budget() {
+        // Both subnets are within their targets, but the shared budget covers neither
+        let chain = mock_subnet(10 * BURN as u128);
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "Burn",
+                &[(StorageHasher::Identity, &2u16.to_le_bytes())],
+            ),
+            BURN,
         );
-        println!(
-            "   Owner: {}",
-            utils::format_ss58_short(&subnet_info.owner_ss58)
+        let targets = [NETUID, 2].map(|netuid| SnipeTarget {
+            netuid,
+            max_burn: 2 * BURN,
+        });
+        let error = mock_register(&chain)
+            .snipe_registration(&targets, "//Alice", "//Bob", Some(BURN / 2), None)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Connection);
+        assert!(chain.submitted().is_empty());
+
+        // The balance caps the spend the same way
+        let chain = mock_subnet(BURN as u128 / 2);
+        let error = mock_register(&chain)
+            .snipe_registration(&targets[..1], "//Alice", "//Bob", None, None)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Connection);
+        assert!(chain.submitted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_guard_registration() {
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let guard = |max_burn, budget| GuardOptions {
+            max_burn,
+            budget,
+            interval: 3_600,
+            ..Default::default()
+        };
+
+        // A deregistered hotkey is registered again, cancelled so only one check runs
+        let chain = mock_subnet(10 * BURN as u128);
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        let register = mock_register(&chain);
+        register.cancellation_token().cancel();
+        register
+            .guard_registration(NETUID, "//Alice", "//Bob", &guard(2 * BURN, 10 * BURN))
+            .await
+            .unwrap();
+        assert_eq!(chain.submitted().len(), 1);
+
+        // A burn above the limit is waited out
+        let chain = mock_subnet(10 * BURN as u128);
+        let register = mock_register(&chain);
+        register.cancellation_token().cancel();
+        register
+            .guard_registration(NETUID, "//Alice", "//Bob", &guard(BURN / 2, 10 * BURN))
+            .await
+            .unwrap();
+        assert!(chain.submitted().is_empty());
+
+        // A burn the remaining budget can't cover stops the guard
+        let error = mock_register(&chain)
+            .guard_registration(NETUID, "//Alice", "//Bob", &guard(2 * BURN, BURN / 2))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("Budget exhausted"));
+        assert!(chain.submitted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_storage_change_wakeups() {
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let chain = mock_subnet(10 * BURN as u128);
+        let register = mock_register(&chain);
+        let watched = [(NETUID, bob.clone())];
+        // Nodes without storage subscriptions leave the checks to the interval
+        assert!(register
+            .start_storage_cache(&[NETUID], &watched)
+            .await
+            .is_none());
+
+        // A burn change and a watched hotkey's new UID each wake the next check early
+        let burn_key = storage_key(
+            "SubtensorModule",
+            "Burn",
+            &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
         );
-        println!("   Network modality: {}", subnet_info.modality);
-        println!("   Emission value: {}", subnet_info.emission_value);
-        println!("   Rho: {}", subnet_info.rho);
-        println!("   Kappa: {}", subnet_info.kappa);
-        println!("   Scaling law power: {}", subnet_info.scaling_law_power);
-        println!("   Blocks since epoch: {}", subnet_info.blocks_since_epoch);
+        let bob_uid_key = uid_key(&bob);
+        for (key, value) in [
+            (burn_key, (2 * BURN).encode()),
+            (bob_uid_key, 3u16.encode()),
+        ] {
+            chain.push_change_set(&[(&key, Some(value))]);
+            let mut subnet_changes = register.start_storage_cache(&[NETUID], &watched).await;
+            assert!(subnet_changes.is_some());
+            let woken = tokio::time::timeout(
+                Duration::from_secs(5),
+                register.wait_for_next_check(3_600, &mut subnet_changes),
+            )
+            .await
+            .expect("change didn't wake the check");
+            assert!(woken);
+        }
+        assert_eq!(chain.requests("subscribe_storage"), 3);
+    }
 
-        // Show registration statistics
-        let current_block = self.client.get_current_block().await?;
+    #[tokio::test]
+    async fn test_burn_rechecked_before_submit() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let register = mock_register(&chain);
+        register.check_live_burn(NETUID, BURN).await.unwrap();
 
-        println!("\n⏱️ Registration Estimates:");
-        println!("   Current block: {}", current_block);
-        println!(
-            "   Burn cost in USD: ~${:.2}",
-            subnet_info.burn as f64 / 1e9 * 200.0
+        // A burn that rose past the cap while the prompt waited is refused, not read from cache
+        let burn_key = storage_key(
+            "SubtensorModule",
+            "Burn",
+            &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
         );
+        chain.insert(&burn_key, 2 * BURN);
+        let error = register.check_live_burn(NETUID, BURN).await.unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::RegistrationClosed);
+        assert!(chain.submitted().is_empty());
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_max_burn_guard() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let options = RegistrationOptions {
+            max_burn: Some(BURN / 2),
+            ..confirmed()
+        };
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &options)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::RegistrationClosed);
+        assert!(error.to_string().contains("exceeds max burn"));
+        assert!(chain.submitted().is_empty());
     }
 
-    // Massive monitoring of multiple neurons
-    pub async fn monitor_multiple_neurons(&self, registrations: Vec<(u16, String)>) -> Result<()> {
-        println!("👀 Monitoring {} registration(s)...", registrations.len());
-        println!("═══════════════════════════════════════════");
+    #[tokio::test]
+    async fn test_burn_bounds() {
+        let subnet_key = |name| {
+            storage_key(
+                "SubtensorModule",
+                name,
+                &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
+            )
+        };
+        let burn = |amount| RegistrationOptions {
+            burn_amount: amount,
+            ..confirmed()
+        };
 
-        for (netuid, hotkey_path) in registrations {
-            println!(
-                "\n📍 Subnet {} - {}",
-                netuid,
-                utils::format_account_short(&key_utils::account_id_from_string(&hotkey_path)?)
-            );
+        let chain = mock_subnet(10 * BURN as u128);
+        chain.insert(&subnet_key("MaxBurn"), 2 * BURN);
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &burn(Some(3 * BURN)))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("above the subnet 1 maximum"));
 
-            match self.check_status(netuid, &hotkey_path).await {
-                Ok(_) => {}
-                Err(e) => println!("❌ Error: {}", e),
-            }
-        }
+        chain.insert(&subnet_key("MinBurn"), 2 * BURN);
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &burn(None))
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("below the subnet 1 minimum"));
+        assert!(chain.submitted().is_empty());
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_registration_window() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let subnet_key = |name| {
+            storage_key(
+                "SubtensorModule",
+                name,
+                &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
+            )
+        };
+
+        chain.insert(&subnet_key("NetworkRegistrationAllowed"), false);
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::RegistrationClosed);
+        assert!(error.to_string().contains("disabled"));
+
+        // Three times the target per interval closes it until the next adjustment
+        chain.insert(&subnet_key("NetworkRegistrationAllowed"), true);
+        chain.insert(&subnet_key("TargetRegistrationsPerInterval"), 1u16);
+        chain.insert(&subnet_key("RegistrationsThisInterval"), 3u16);
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::RegistrationClosed);
+        assert!(error.to_string().contains("interval is full (3/3)"));
+        assert!(chain.submitted().is_empty());
     }
 
-    // Automatic registration with retry logic
-    pub async fn auto_register_with_retry(
-        &self,
-        netuid: u16,
-        wallet_path: &str,
-        hotkey_path: &str,
-        max_retries: usize,
-    ) -> Result<()> {
-        println!(
-            "🔄 Auto registration with retry (max {} attempts)",
-            max_retries
-        );
+    #[tokio::test]
+    async fn test_burn_amount_below_live() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let low = RegistrationOptions {
+            burn_amount: Some(BURN / 2),
+            ..confirmed()
+        };
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &low)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("below the current burn"));
 
-        for attempt in 1..=max_retries {
-            println!("\n🚀 Registration attempt {}/{}", attempt, max_retries);
+        // --auto-adjust raises it to the live burn, still capped by --max-burn
+        let adjusted = RegistrationOptions {
+            auto_adjust: true,
+            max_burn: Some(BURN / 2),
+            ..low
+        };
+        let error = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &adjusted)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("exceeds max burn"));
+        assert!(chain.submitted().is_empty());
 
-            match self
-                .register_to_subnet(netuid, wallet_path, hotkey_path, None)
-                .await
-            {
-                Ok(_) => {
-                    println!("✅ Registration successful on attempt {}", attempt);
-                    return Ok(());
-                }
-                Err(e) => {
-                    println!("❌ Attempt {} failed: {}", attempt, e);
-                    if attempt < max_retries {
-                        println!("⏳ Waiting 30s before retry...");
-                        sleep(Duration::from_secs(30)).await;
-                    }
-                }
-            }
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        let adjusted = RegistrationOptions {
+            max_burn: None,
+            ..adjusted
+        };
+        let outcome = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &adjusted)
+            .await
+            .unwrap();
+        assert_eq!(outcome.burn, BURN);
+        assert_eq!(chain.submitted().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_register_cheapest() {
+        // Subnet 2 is cheaper than subnet 1, subnet 3 cheaper still but full
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        for (netuid, burn, registered) in [(2u16, BURN / 2, 10u16), (3, BURN / 4, 256)] {
+            let key = |name| {
+                storage_key(
+                    "SubtensorModule",
+                    name,
+                    &[(StorageHasher::Identity, &netuid.to_le_bytes())],
+                )
+            };
+            chain.insert(&key("SubnetworkN"), registered);
+            chain.insert(&key("MaxAllowedUids"), 256u16);
+            chain.insert(&key("Burn"), burn);
         }
+        let register = mock_register(&chain);
 
-        Err(anyhow!("All registration attempts failed"))
+        let options = RegistrationOptions {
+            max_burn: Some(BURN / 4),
+            ..confirmed()
+        };
+        let error = register
+            .register_cheapest(&[1, 2, 3], "//Alice", "//Bob", 1, &options)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().starts_with("No candidate subnet"));
+        assert!(chain.submitted().is_empty());
+
+        chain.insert_on_inclusion(
+            &storage_key(
+                "SubtensorModule",
+                "Uids",
+                &[
+                    (StorageHasher::Identity, &2u16.to_le_bytes()),
+                    (StorageHasher::Blake2_128Concat, bob.as_ref()),
+                ],
+            ),
+            10u16,
+        );
+        let outcome = register
+            .register_cheapest(&[1, 2, 3], "//Alice", "//Bob", 1, &confirmed())
+            .await
+            .unwrap();
+        assert_eq!(outcome.burn, BURN / 2);
+        let call = [&[7, 7][..], &2u16.to_le_bytes(), bob.as_ref()].concat();
+        assert_eq!(chain.submitted().len(), 1);
+        assert!(chain.submitted()[0].ends_with(&call));
     }
 
-    /// This function provides an overview of the Bittensor network, including active subnets,
-    pub async fn show_network_statistics(&self) -> Result<()> {
-        println!("📊 Bittensor Network Statistics");
-        println!("═══════════════════════════════════════");
+    #[tokio::test]
+    async fn test_registration_survives_dropped_connection() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        chain.drop_connection(1);
 
-        // Getting information for basic subnets
-        let main_subnets = vec![0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11];
-        let mut total_neurons = 0u32;
-        let mut active_subnets = 0u32;
+        let outcome = mock_register(&chain)
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap();
+        assert!(outcome.tx_hash.is_some());
+        assert_eq!(chain.reconnects(), 1);
+        assert_eq!(chain.submitted().len(), 1);
+    }
 
-        println!("\n🌐 Active Subnets:");
-        println!("┌─────┬─────────────┬──────────┬─────────────┬──────────────┐");
-        println!("│ UID │   Neurons   │ Max Cap  │  Burn Cost  │ Difficulty   │");
-        println!("├─────┼─────────────┼──────────┼─────────────┼──────────────┤");
+    #[tokio::test]
+    async fn test_register_hotkeys_skips_registered() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let charlie = key_utils::account_id_from_string("//Charlie").unwrap();
+        chain.insert(&uid_key(&charlie), 3u16);
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
 
-        for netuid in main_subnets {
-            match self.client.get_subnet_info(netuid, false).await {
-                Ok(subnet_info) => {
-                    active_subnets += 1;
-                    total_neurons += subnet_info.registered_neurons as u32;
+        let hotkeys = ["//Bob", "//Charlie"].map(String::from);
+        mock_register(&chain)
+            .register_hotkeys(NETUID, "//Alice", &hotkeys, None, 2, &confirmed())
+            .await
+            .unwrap();
+        assert_eq!(chain.submitted().len(), 1);
+    }
 
-                    println!(
-                        "│ {:>3} │ {:>7}/{:<3} │ {:>8} │ {:>9} │ {:>10} │",
-                        netuid,
-                        subnet_info.registered_neurons,
-                        subnet_info.max_allowed_uids,
-                        subnet_info.max_allowed_uids,
-                        utils::format_tao(subnet_info.burn as u128),
-                        utils::format_difficulty(subnet_info.difficulty),
-                    );
-                }
-                Err(_) => {
-                    // Subnet doesn't exist or error, skip
-                }
-            }
-        }
+    #[tokio::test]
+    async fn test_registration_read_from_events() {
+        // The UID comes from NeuronRegistered, another extrinsic's failure in the block is not ours
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let registered = (NETUID, 10u16, bob.clone()).encode();
+        chain.emit_on_inclusion(event_record(0, 7, 6, &registered));
+        let dispatch_info = (Compact(1_000_000u64), Compact(0u64), 0u8, 0u8).encode();
+        chain.emit_on_inclusion(event_record(1, 0, 1, &[&[2][..], &dispatch_info].concat()));
+        let register = mock_register(&chain);
+        register
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap();
+        assert_eq!(chain.submitted().len(), 1);
 
-        println!("└─────┴─────────────┴──────────┴─────────────┴──────────────┘");
+        // Our own ExtrinsicFailed is reported by the error's name
+        chain.fail_next(vec![3, 7, 3, 0, 0, 0]);
+        let error = register
+            .register_to_subnet(NETUID, "//Alice", "//Bob", &confirmed())
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error)
+            .contains("dispatch error: Module(SubtensorModule::TooManyRegistrationsThisBlock)"));
+        assert_eq!(errors::classify(&error), ErrorKind::RegistrationClosed);
+        assert_eq!(chain.submitted().len(), 2);
+    }
 
-        // Common statistics
-        let current_block = self.client.get_current_block().await?;
+    #[tokio::test]
+    async fn test_retry_classification() {
+        // A connection still down after the reconnects fails the attempt, the next one succeeds
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
+        chain.drop_connection(6);
+        let outcome = mock_register(&chain)
+            .auto_register_with_retry(NETUID, "//Alice", "//Bob", 3, &confirmed())
+            .await
+            .unwrap();
+        assert!(outcome.tx_hash.is_some());
+        assert_eq!(chain.submitted().len(), 1);
 
-        println!("\n📈 Network Overview:");
-        println!("   Active subnets: {}", active_subnets);
-        println!("   Total neurons: {:?}", total_neurons);
-        println!("   Current block: {:?}", current_block);
-        println!("   Network: Finney (Mainnet)");
+        // Not enough balance won't change between attempts, the first failure is final
+        let chain = mock_subnet(BURN as u128 / 2);
+        let error = mock_register(&chain)
+            .auto_register_with_retry(NETUID, "//Alice", "//Bob", 3, &confirmed())
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        assert!(format!("{:#}", error).contains("Not retrying a balance error"));
+        assert_eq!(chain.requests("storage_batch"), 1);
+    }
 
-        // New users recomendations
-        println!("\n💡 Registration Tips:");
-        println!("   • Subnet 1: Text generation (high competition)");
-        println!("   • Subnet 3: Data scraping (moderate difficulty)");
-        println!("   • Subnet 8: Time series prediction");
-        println!("   • Check difficulty before registering");
-        println!("   • Consider burn registration for high-difficulty subnets");
+    #[tokio::test]
+    async fn test_batch_operations() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        chain.insert_on_inclusion(&uid_key(&bob), 10u16);
 
-        Ok(())
+        let dir = std::env::temp_dir().join(format!("btreg-batch-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("batch.yaml");
+        let config_path = config_path.to_str().unwrap();
+        std::fs::write(
+            config_path,
+            "concurrency: 2
+operations:
+  - { operation: register, subnet: 1, wallet: //Alice, hotkey: //Bob }
+  - operation: transfer
+    wallet: //Alice
+    transfer: { to: 5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y, amount: 1000000 }
+  - { operation: register, subnet: 9, wallet: //Alice, hotkey: //Dave }
+",
+        )
+        .unwrap();
+
+        // The missing subnet fails its operation only, the others complete and are journaled
+        mock_register(&chain)
+            .execute_batch_operations(config_path, None, false)
+            .await
+            .unwrap();
+        assert_eq!(chain.submitted().len(), 2);
+        let journal =
+            BatchJournal::resume(config_path, &std::fs::read_to_string(config_path).unwrap())
+                .unwrap();
+        let states: Vec<EntryState> = journal.entries.iter().map(|entry| entry.state).collect();
+        assert_eq!(
+            states,
+            vec![
+                EntryState::Succeeded,
+                EntryState::Succeeded,
+                EntryState::Failed
+            ]
+        );
+
+        // Resuming runs only what failed
+        mock_register(&chain)
+            .execute_batch_operations(config_path, None, true)
+            .await
+            .unwrap();
+        assert_eq!(chain.submitted().len(), 2);
+        std::fs::remove_dir_all(&dir).unwrap();
     }
 
-    // Export configuration for automation
-    pub async fn export_config(&self, netuid: u16, output_path: &str) -> Result<()> {
-        println!("📄 Exporting configuration for subnet {}...", netuid);
+    // The nonce a signed extrinsic carries, after its length, version, signer, sr25519
+    // signature and era
+    fn extrinsic_nonce(extrinsic: &[u8]) -> u32 {
+        let mut input = extrinsic;
+        Compact::<u32>::decode(&mut input).unwrap();
+        let era = 1 + 33 + 65;
+        let era_len = if input[era] == 0 { 1 } else { 2 };
+        Compact::<u32>::decode(&mut &input[era + era_len..])
+            .unwrap()
+            .0
+    }
 
-        let subnet_info = self.client.get_subnet_info(netuid, true).await?;
+    #[tokio::test]
+    async fn test_parallel_batch() {
+        let chain = mock_subnet(10 * BURN as u128);
+        for hotkey in ["//Bob", "//Charlie", "//Dave"] {
+            let hotkey = key_utils::account_id_from_string(hotkey).unwrap();
+            chain.insert_on_inclusion(&uid_key(&hotkey), 10u16);
+        }
+        let dir = std::env::temp_dir().join(format!("btreg-batch-parallel-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("batch.yaml");
+        let config_path = config_path.to_str().unwrap();
+        let register = |hotkey| {
+            format!(
+                "  - {{ operation: register, subnet: 1, wallet: //Alice, hotkey: {} }}\n",
+                hotkey
+            )
+        };
+        std::fs::write(
+            config_path,
+            format!(
+                "concurrency: 3\noperations:\n{}{}{}",
+                register("//Bob"),
+                register("//Charlie"),
+                register("//Dave")
+            ),
+        )
+        .unwrap();
 
-        let config = serde_json::json!({
-            "subnet_id": netuid,
-            "registration_info": {
-                "difficulty": subnet_info.difficulty.to_string(),
-                "burn_cost_rao": subnet_info.burn,
-                "burn_cost_tao": subnet_info.burn as f64 / 1e9,
-                "max_neurons": subnet_info.max_allowed_uids,
-                "current_neurons": subnet_info.registered_neurons,
-                "registration_open": subnet_info.registered_neurons < subnet_info.max_allowed_uids
-            },
-            "export_time": chrono::Utc::now().to_rfc3339(),
-            "network": "finney"
-        });
+        // Run side by side, registrations paid by one coldkey still take consecutive nonces
+        mock_register(&chain)
+            .execute_batch_operations(config_path, None, false)
+            .await
+            .unwrap();
+        let mut nonces: Vec<u32> = chain
+            .submitted()
+            .iter()
+            .map(|x| extrinsic_nonce(x))
+            .collect();
+        nonces.sort();
+        assert_eq!(nonces, [0, 1, 2]);
+        let journal =
+            BatchJournal::resume(config_path, &std::fs::read_to_string(config_path).unwrap())
+                .unwrap();
+        assert!(journal
+            .entries
+            .iter()
+            .all(|entry| entry.state == EntryState::Succeeded));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
-        std::fs::write(output_path, serde_json::to_string_pretty(&config)?)?;
-        println!("✅ Configuration exported to: {}", output_path);
+    #[tokio::test]
+    async fn test_verify_registrations() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let [bob, charlie, dave] = ["//Bob", "//Charlie", "//Dave"]
+            .map(|key| key_utils::account_id_from_string(key).unwrap());
+        chain.insert(&uid_key(&bob), 3u16);
+        chain.insert(&uid_key(&charlie), 4u16);
+        let register = mock_register(&chain);
 
-        Ok(())
+        // Every pending registration is checked on each finalized block of one subscription
+        let pending = register
+            .verify_registrations(vec![(NETUID, bob.clone()), (NETUID, charlie.clone())])
+            .await
+            .unwrap();
+        assert!(pending.is_empty());
+        assert_eq!(chain.requests("subscribe_heads"), 1);
+
+        // MockChain serves a single finalized block, a registration still missing after it
+        // runs the subscription out
+        let error = register
+            .verify_registrations(vec![(NETUID, bob), (NETUID, dave)])
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Connection);
     }
 
-    pub async fn execute_batch_operations(&self, config_path: &str) -> Result<()> {
-        println!("📦 Executing batch operations from: {}", config_path);
+    #[tokio::test]
+    async fn test_batch_operation_types() {
+        let chain = mock_subnet(10 * BURN as u128);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let charlie = key_utils::account_id_from_string("//Charlie").unwrap();
+        let dir = std::env::temp_dir().join(format!("btreg-batch-types-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let config_path = dir.join("batch.yaml");
+        let config_path = config_path.to_str().unwrap();
+        std::fs::write(
+            config_path,
+            "concurrency: 4
+operations:
+  - operation: transfer
+    wallet: //Alice
+    transfer: { to: 5FLSigC9HGRKVhB9FiEo4Y3koPsNmBmLJbpXg2mp1hXcS59Y, amount: 1000000 }
+  - { operation: add_stake, wallet: //Alice, hotkey: //Bob, stake: { amount: 2000000 } }
+  - operation: serve_axon
+    subnet: 1
+    hotkey: //Bob
+    axon: { ip: 10.0.0.1, port: 8091 }
+  - { operation: swap_hotkey, wallet: //Alice, hotkey: //Bob, swap: { new_hotkey: //Charlie } }
+",
+        )
+        .unwrap();
 
-        let config_content = std::fs::read_to_string(config_path)?;
-        let batch_config: BatchConfig = serde_json::from_str(&config_content)?;
+        // Bob isn't registered, so serving an axon and swapping fail before anything is signed
+        mock_register(&chain)
+            .execute_batch_operations(config_path, None, false)
+            .await
+            .unwrap();
+        let journal =
+            BatchJournal::resume(config_path, &std::fs::read_to_string(config_path).unwrap())
+                .unwrap();
+        let states: Vec<EntryState> = journal.entries.iter().map(|entry| entry.state).collect();
+        assert_eq!(
+            states,
+            [
+                EntryState::Succeeded,
+                EntryState::Succeeded,
+                EntryState::Failed,
+                EntryState::Failed
+            ]
+        );
 
-        println!("   Found {} operations", batch_config.operations.len());
+        // Balances transfer_keep_alive to Charlie and SubtensorModule add_stake to Bob on root
+        let calls = [
+            [
+                &[5, 3, 0][..],
+                charlie.as_ref(),
+                &Compact(1_000_000u64).encode(),
+            ]
+            .concat(),
+            [
+                &[7, 2][..],
+                bob.as_ref(),
+                &constants::ROOT_NETUID.to_le_bytes(),
+                &2_000_000u64.to_le_bytes(),
+            ]
+            .concat(),
+        ];
+        let submitted = chain.submitted();
+        assert_eq!(submitted.len(), 2);
+        for call in &calls {
+            assert!(submitted.iter().any(|extrinsic| extrinsic.ends_with(call)));
+        }
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 
-        for (i, operation) in batch_config.operations.iter().enumerate() {
-            println!(
-                "\n🔄 Operation {}/{}: {}",
-                i + 1,
-                batch_config.operations.len(),
-                operation.operation
-            );
+    // Subnet 1's pool holds 1,000 TAO against 4,000 alpha, 0.25 TAO per alpha, subnet 2's
+    // 500 of each
+    fn mock_pools(chain: &MockChain) {
+        for (netuid, tao_in, alpha_in) in [(NETUID, 1_000, 4_000), (2, 500, 500)] {
+            let key = |name| {
+                storage_key(
+                    "SubtensorModule",
+                    name,
+                    &[(StorageHasher::Identity, &u16::to_le_bytes(netuid))],
+                )
+            };
+            chain.insert(&key("SubnetTAO"), tao_in * BURN);
+            chain.insert(&key("SubnetAlphaIn"), alpha_in * BURN);
+        }
+    }
 
-            match operation.operation.as_str() {
-                "register" => {
-                    if let Some(wallet) = &operation.wallet {
-                        match self
-                            .register_to_subnet(operation.subnet, wallet, &operation.hotkey, None)
-                            .await
-                        {
-                            Ok(_) => println!("✅ Registration completed"),
-                            Err(e) => println!("❌ Registration failed: {}", e),
-                        }
-                    }
-                }
-                "check_status" => {
-                    match self.check_status(operation.subnet, &operation.hotkey).await {
-                        Ok(_) => {}
-                        Err(e) => println!("❌ Status check failed: {}", e),
-                    }
-                }
-                "auto_register" => {
-                    if let Some(wallet) = &operation.wallet {
-                        let max_retries = operation.max_retries.unwrap_or(3);
-                        match self
-                            .auto_register_with_retry(
-                                operation.subnet,
-                                wallet,
-                                &operation.hotkey,
-                                max_retries,
-                            )
-                            .await
-                        {
-                            Ok(_) => println!("✅ Auto registration completed"),
-                            Err(e) => println!("❌ Auto registration failed: {}", e),
-                        }
-                    }
-                }
-                _ => {
-                    println!("⚠️ Unknown operation: {}", operation.operation);
-                }
-            }
+    // All of Bob's alpha in subnet 1 staked by Alice
+    fn mock_alpha_stake(chain: &MockChain, alpha: u64) {
+        let alice = key_utils::account_id_from_string("//Alice").unwrap();
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let netuid = NETUID.to_le_bytes();
+        let hotkey = [
+            (StorageHasher::Blake2_128Concat, bob.as_ref()),
+            (StorageHasher::Identity, &netuid[..]),
+        ];
+        let shares = (alpha as u128) << 64;
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "Alpha",
+                &[
+                    (StorageHasher::Blake2_128Concat, bob.as_ref()),
+                    (StorageHasher::Blake2_128Concat, alice.as_ref()),
+                    (StorageHasher::Identity, &netuid),
+                ],
+            ),
+            shares,
+        );
+        chain.insert(
+            &storage_key("SubtensorModule", "TotalHotkeyShares", &hotkey),
+            shares,
+        );
+        chain.insert(
+            &storage_key("SubtensorModule", "TotalHotkeyAlpha", &hotkey),
+            alpha,
+        );
+    }
 
-            // Small delay between operations
-            if i < batch_config.operations.len() - 1 {
-                println!("⏳ Waiting 5s before next operation...");
-                sleep(Duration::from_secs(5)).await;
-            }
+    fn bob_move(amount: u64) -> StakeMove {
+        StakeMove {
+            origin_hotkey: key_utils::account_id_from_string("//Bob").unwrap(),
+            destination_hotkey: key_utils::account_id_from_string("//Charlie").unwrap(),
+            origin_netuid: NETUID,
+            destination_netuid: 2,
+            amount,
         }
+    }
 
-        println!("\n🎉 Batch operations completed!");
-        Ok(())
+    #[tokio::test]
+    async fn test_alpha_stake_preview() {
+        let chain = mock_subnet(0);
+        mock_pools(&chain);
+        let register = mock_register(&chain);
+        let pool = register.client.get_subnet_pool(NETUID).await.unwrap();
+        assert_eq!(pool.price(), 0.25);
+
+        // 100 TAO is 400 alpha at spot, the constant product pool gives back 363.6
+        assert_eq!(pool.quote_stake(100 * BURN), (400 * BURN, 363_636_363_636));
+        assert_eq!(pool.quote_unstake(400 * BURN), (100 * BURN, 90_909_090_909));
+
+        // Moving to subnet 2 sells into subnet 1's pool and buys from subnet 2's
+        let destination = register.client.get_subnet_pool(2).await.unwrap();
+        assert_eq!(
+            quote_move(&pool, &destination, 400 * BURN),
+            (90_909_090_909, 100 * BURN, 76_923_076_923)
+        );
+
+        // Root has no pool, TAO stakes one to one
+        let root = register.client.get_subnet_pool(0).await.unwrap();
+        assert_eq!(root.quote_stake(5 * BURN), (5 * BURN, 5 * BURN));
+        assert_eq!(root.quote_unstake(5 * BURN), (5 * BURN, 5 * BURN));
     }
 
-    // Check account balance
-    pub async fn check_account_balance(&self, account_address: &str) -> Result<()> {
-        println!("💰 Checking account balance...");
+    #[tokio::test]
+    async fn test_alpha_stake_refused() {
+        let chain = mock_subnet(50 * BURN as u128);
+        mock_pools(&chain);
+        mock_alpha_stake(&chain, 10 * BURN);
+        let register = mock_register(&chain);
 
-        // Parse the account address using SS58 codec
-        let account = AccountId32::from_ss58check(account_address).map_err(|e| {
-            anyhow::anyhow!(
-                "Invalid SS58 account address format: {:?}. Address: {}",
-                e,
-                account_address
-            )
-        })?;
+        let error = register
+            .add_stake_alpha("//Alice", "//Bob", NETUID, 100 * BURN, true)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        let error = register
+            .remove_stake_alpha("//Alice", "//Bob", NETUID, 20 * BURN, true)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        let error = register
+            .move_stake("//Alice", &bob_move(20 * BURN), true)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        assert!(chain.submitted().is_empty());
+    }
 
-        // Get account info with debug output
-        match self.client.get_account_balance(&account).await {
-            Ok(balance) => {
-                println!("✅ Account balance retrieved successfully!");
-                println!("💰 Address: {}", account_address);
-                println!("💰 Balance: {} RAO", balance);
-                println!("💰 Balance: {:.6} TAO", utils::format_tao(balance as u128));
+    #[tokio::test]
+    async fn test_alpha_stake_calls() {
+        let chain = mock_subnet(500 * BURN as u128);
+        mock_pools(&chain);
+        mock_alpha_stake(&chain, 400 * BURN);
+        let register = mock_register(&chain);
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let charlie = key_utils::account_id_from_string("//Charlie").unwrap();
 
-                if balance == 0 {
-                    println!("ℹ️ Note: Account has zero balance or doesn't exist on-chain");
-                }
-            }
-            Err(e) => {
-                println!("❌ Failed to get account balance: {}", e);
-                return Err(e);
-            }
+        register
+            .add_stake_alpha("//Alice", "//Bob", NETUID, 100 * BURN, true)
+            .await
+            .unwrap();
+        register
+            .remove_stake_alpha("//Alice", "//Bob", NETUID, 400 * BURN, true)
+            .await
+            .unwrap();
+        register
+            .move_stake("//Alice", &bob_move(400 * BURN), true)
+            .await
+            .unwrap();
+
+        // SubtensorModule (7) add_stake (2) and remove_stake (3) take (hotkey, netuid, amount),
+        // move_stake (85) (origin hotkey, destination hotkey, origin netuid, destination
+        // netuid, alpha)
+        let netuid = NETUID.to_le_bytes();
+        let calls = [
+            [
+                &[7, 2][..],
+                bob.as_ref(),
+                &netuid,
+                &(100 * BURN).to_le_bytes(),
+            ]
+            .concat(),
+            [
+                &[7, 3][..],
+                bob.as_ref(),
+                &netuid,
+                &(400 * BURN).to_le_bytes(),
+            ]
+            .concat(),
+            [
+                &[7, 85][..],
+                bob.as_ref(),
+                charlie.as_ref(),
+                &netuid,
+                &2u16.to_le_bytes(),
+                &(400 * BURN).to_le_bytes(),
+            ]
+            .concat(),
+        ];
+        let submitted = chain.submitted();
+        assert_eq!(submitted.len(), 3);
+        for (extrinsic, call) in submitted.iter().zip(&calls) {
+            assert!(extrinsic.ends_with(call), "{}", hex::encode(call));
         }
+    }
 
-        Ok(())
+    #[tokio::test]
+    async fn test_transfer_fee_preview() {
+        // MockChain charges 125,000 RAO for any extrinsic, the balance has to cover it too
+        let fee = 125_000;
+        let charlie = key_utils::account_id_from_string("//Charlie").unwrap();
+        let chain = mock_subnet(BURN as u128 + fee - 1);
+        let error = mock_register(&chain)
+            .transfer("//Alice", "//Charlie", BURN, true)
+            .await
+            .unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Balance);
+        assert_eq!(chain.requests("partial_fee"), 1);
+        assert!(chain.submitted().is_empty());
+
+        // Balances (5) transfer_keep_alive (3) to MultiAddress::Id, a compact amount
+        let chain = mock_subnet(BURN as u128 + fee);
+        mock_register(&chain)
+            .transfer("//Alice", "//Charlie", BURN, true)
+            .await
+            .unwrap();
+        let call = [&[5, 3, 0][..], charlie.as_ref(), &Compact(BURN).encode()].concat();
+        let submitted = chain.submitted();
+        assert_eq!(submitted.len(), 1);
+        assert!(submitted[0].ends_with(&call));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[tokio::test]
+    async fn test_view_only_coldkey() {
+        // Alice by address only: reads work, anything to sign fails before it is submitted
+        let alice = "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY";
+        let chain = mock_subnet(10 * BURN as u128);
+        let register = mock_register(&chain);
+        register.show_wallet_overview(alice).await.unwrap();
+
+        let error = register
+            .transfer(alice, "//Charlie", BURN, true)
+            .await
+            .unwrap_err();
+        assert!(format!("{:#}", error).contains("Cannot sign with view-only coldkey"));
+        let error = register
+            .register_to_subnet(NETUID, alice, "//Bob", &confirmed())
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .starts_with("Cannot register with view-only coldkey"));
+        assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+
+        // A dry run needs only the address
+        let dry_run = RegistrationOptions {
+            dry_run: true,
+            ..confirmed()
+        };
+        register
+            .register_to_subnet(NETUID, alice, "//Bob", &dry_run)
+            .await
+            .unwrap();
+        assert!(chain.submitted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_set_identity() {
+        // Each field at its byte limit is accepted
+        let identity = ChainIdentity {
+            name: "n".repeat(256),
+            url: "u".into(),
+            github: "g".into(),
+            image: "i".into(),
+            discord: "d".into(),
+            description: "x".repeat(1024),
+            additional: "a".into(),
+        };
+        let chain = mock_subnet(10 * BURN as u128);
+        let register = mock_register(&chain);
+        register
+            .set_identity("//Alice", &identity, true)
+            .await
+            .unwrap();
+
+        // SubtensorModule (7) set_identity (68): name, url, github_repo, image, discord,
+        // description, additional, each a length-prefixed byte string
+        let field = |value: &str| [Compact(value.len() as u32).encode(), value.into()].concat();
+        let call = [
+            vec![7, 68],
+            field(&identity.name),
+            field("u"),
+            field("g"),
+            field("i"),
+            field("d"),
+            field(&identity.description),
+            field("a"),
+        ]
+        .concat();
+        assert_eq!(chain.submitted().len(), 1);
+        assert!(chain.submitted()[0].ends_with(&call));
+
+        // One byte over is refused before the chain is asked anything
+        let chain = mock_subnet(10 * BURN as u128);
+        for over in [
+            ChainIdentity {
+                name: "n".repeat(257),
+                ..Default::default()
+            },
+            ChainIdentity {
+                image: "i".repeat(1025),
+                ..Default::default()
+            },
+        ] {
+            let error = mock_register(&chain)
+                .set_identity("//Alice", &over, true)
+                .await
+                .unwrap_err();
+            assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+            assert!(error.to_string().contains("the limit is"));
+        }
+        assert_eq!(chain.requests("storage"), 0);
+        assert_eq!(chain.requests("partial_fee"), 0);
+        assert!(chain.submitted().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_swap_hotkey() {
+        let bob = key_utils::account_id_from_string("//Bob").unwrap();
+        let charlie = key_utils::account_id_from_string("//Charlie").unwrap();
+        let chain = mock_subnet(10 * BURN as u128);
+        chain.insert(
+            &storage_key(
+                "SubtensorModule",
+                "NetworksAdded",
+                &[(StorageHasher::Identity, &NETUID.to_le_bytes())],
+            ),
+            true,
+        );
+        let register = mock_register(&chain);
+
+        // An unregistered old hotkey has nothing to swap
+        let error = register
+            .swap_hotkey("//Alice", "//Bob", "//Charlie", true)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("not registered in any subnet"));
+
+        // A registered new hotkey would make the chain reject the swap
+        chain.insert(&uid_key(&bob), 3u16);
+        chain.insert(&uid_key(&charlie), 4u16);
+        let error = register
+            .swap_hotkey("//Alice", "//Bob", "//Charlie", true)
+            .await
+            .unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("already registered in subnet(s) [1]"));
+        assert!(chain.submitted().is_empty());
+
+        // SubtensorModule (7) swap_hotkey (70): old hotkey, then new
+        let alice = key_utils::load_keypair_from_file("//Alice").unwrap();
+        register
+            .client
+            .submit_swap_hotkey(&bob, &charlie, &alice)
+            .await
+            .unwrap();
+        let call = [&[7, 70][..], bob.as_ref(), charlie.as_ref()].concat();
+        assert_eq!(chain.submitted().len(), 1);
+        assert!(chain.submitted()[0].ends_with(&call));
+    }
 
     #[tokio::test]
     async fn test_client_creation() {
@@ -567,4 +6517,51 @@ mod tests {
         // Will not collected in test environment but structure should creates
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_hotkey_pool_rotation() {
+        let mut pool = HotkeyPool::new(vec!["a".into(), "b".into(), "c".into()]).unwrap();
+        assert_eq!(pool.current(), "a");
+        assert_eq!(pool.rotate(), "b");
+        assert_eq!(pool.rotate(), "c");
+        assert_eq!(pool.rotate(), "a");
+        assert!(HotkeyPool::new(Vec::new()).is_err());
+    }
+
+    #[test]
+    fn test_hotkey_owner_check() {
+        let hotkey = key_utils::account_id_from_string("//Bob").unwrap();
+        let coldkey = key_utils::account_id_from_string("//Alice").unwrap();
+        let other = key_utils::account_id_from_string("//Charlie").unwrap();
+
+        assert!(check_hotkey_owner(&hotkey, None, &coldkey, false).is_ok());
+        assert!(check_hotkey_owner(&hotkey, Some(&coldkey), &coldkey, false).is_ok());
+        let error = check_hotkey_owner(&hotkey, Some(&other), &coldkey, false).unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+        assert!(error.to_string().contains("--allow-foreign-hotkey"));
+        assert!(check_hotkey_owner(&hotkey, Some(&other), &coldkey, true).is_ok());
+    }
+
+    #[test]
+    fn test_root_slot_and_senate() {
+        let validator = |uid, stake| (uid, AccountId32::new([uid as u8; 32]), stake);
+        let validators = vec![validator(4, 100), validator(1, 500)];
+
+        assert_eq!(root_slot(0, &validators, 64).unwrap(), None);
+        assert_eq!(root_slot(101, &validators, 2).unwrap(), Some((4, 100)));
+        let error = root_slot(100, &validators, 2).unwrap_err();
+        assert_eq!(errors::classify(&error), ErrorKind::Fatal);
+
+        assert!(joins_senate(0, &[1_000; 3]));
+        let full = [1_000; constants::SENATE_MAX_MEMBERS];
+        assert!(!joins_senate(1_000, &full));
+        assert!(joins_senate(1_001, &full));
+    }
+
+    #[test]
+    fn test_take_percent() {
+        assert_eq!(take_percent(0), 0.0);
+        assert_eq!(take_percent(u16::MAX), 100.0);
+        assert_eq!(format!("{:.2}", take_percent(11_796)), "18.00");
+    }
 }
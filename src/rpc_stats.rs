@@ -0,0 +1,166 @@
+//! Per-run RPC usage statistics (calls, bytes, retries, latency)
+use colored::*;
+use std::{
+    collections::BTreeMap,
+    sync::{Mutex, OnceLock},
+    time::Duration,
+};
+
+static RPC_STATS: OnceLock<Mutex<RpcStats>> = OnceLock::new();
+
+#[derive(Debug, Clone, Default)]
+pub struct MethodStats {
+    pub calls: u64,
+    pub errors: u64,
+    pub retries: u64,
+    pub bytes_sent: u64,
+    pub bytes_received: u64,
+    pub total_latency: Duration,
+    pub max_latency: Duration,
+}
+
+impl MethodStats {
+    pub fn average_latency(&self) -> Duration {
+        if self.calls == 0 {
+            Duration::ZERO
+        } else {
+            self.total_latency / self.calls as u32
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct RpcStats {
+    pub methods: BTreeMap<String, MethodStats>,
+}
+
+impl RpcStats {
+    pub fn record(
+        &mut self,
+        method: &str,
+        sent: usize,
+        received: usize,
+        latency: Duration,
+        ok: bool,
+    ) {
+        let stats = self.methods.entry(method.to_string()).or_default();
+        stats.calls += 1;
+        if !ok {
+            stats.errors += 1;
+        }
+        stats.bytes_sent += sent as u64;
+        stats.bytes_received += received as u64;
+        stats.total_latency += latency;
+        stats.max_latency = stats.max_latency.max(latency);
+    }
+
+    pub fn record_retry(&mut self, method: &str) {
+        self.methods.entry(method.to_string()).or_default().retries += 1;
+    }
+
+    // Totals across all methods
+    pub fn total(&self) -> MethodStats {
+        self.methods
+            .values()
+            .fold(MethodStats::default(), |mut total, stats| {
+                total.calls += stats.calls;
+                total.errors += stats.errors;
+                total.retries += stats.retries;
+                total.bytes_sent += stats.bytes_sent;
+                total.bytes_received += stats.bytes_received;
+                total.total_latency += stats.total_latency;
+                total.max_latency = total.max_latency.max(stats.max_latency);
+                total
+            })
+    }
+}
+
+fn stats() -> &'static Mutex<RpcStats> {
+    RPC_STATS.get_or_init(|| Mutex::new(RpcStats::default()))
+}
+
+pub fn record_call(method: &str, sent: usize, received: usize, latency: Duration, ok: bool) {
+    if let Ok(mut stats) = stats().lock() {
+        stats.record(method, sent, received, latency, ok);
+    }
+}
+
+pub fn record_retry(method: &str) {
+    if let Ok(mut stats) = stats().lock() {
+        stats.record_retry(method);
+    }
+}
+
+pub fn snapshot() -> RpcStats {
+    stats()
+        .lock()
+        .map(|stats| stats.clone())
+        .unwrap_or_default()
+}
+
+pub fn print_report() {
+    let stats = snapshot();
+    let total = stats.total();
+
+    println!("\n{}", "📡 RPC Statistics".bright_cyan().bold());
+    println!("═══════════════════════════════════════");
+    println!(
+        "   Calls: {} ({} errors, {} retries)",
+        total.calls, total.errors, total.retries
+    );
+    println!(
+        "   Transferred: {} bytes sent, {} bytes received",
+        total.bytes_sent, total.bytes_received
+    );
+    println!("   Time in RPC: {:.2?}", total.total_latency);
+
+    if stats.methods.is_empty() {
+        return;
+    }
+
+    println!(
+        "\n   {:<42} {:>6} {:>6} {:>10} {:>10}",
+        "Method", "Calls", "Errors", "Avg", "Max"
+    );
+    for (method, method_stats) in &stats.methods {
+        println!(
+            "   {:<42} {:>6} {:>6} {:>10.2?} {:>10.2?}",
+            method,
+            method_stats.calls,
+            method_stats.errors,
+            method_stats.average_latency(),
+            method_stats.max_latency
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rpc_stats_totals() {
+        let mut stats = RpcStats::default();
+        stats.record(
+            "state_getStorage",
+            100,
+            300,
+            Duration::from_millis(10),
+            true,
+        );
+        stats.record("state_getStorage", 100, 0, Duration::from_millis(30), false);
+        stats.record("chain_getHeader", 50, 500, Duration::from_millis(5), true);
+        stats.record_retry("state_getStorage");
+
+        let storage = &stats.methods["state_getStorage"];
+        assert_eq!(storage.average_latency(), Duration::from_millis(20));
+        assert_eq!(storage.max_latency, Duration::from_millis(30));
+
+        let total = stats.total();
+        assert_eq!(total.calls, 3);
+        assert_eq!(total.errors, 1);
+        assert_eq!(total.retries, 1);
+        assert_eq!(total.bytes_sent, 250);
+        assert_eq!(total.bytes_received, 800);
+    }
+}
@@ -0,0 +1,176 @@
+//! End-of-session summary for multi-step runs (batch, guard)
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use colored::*;
+use serde::Serialize;
+
+use crate::utils;
+use crate::warnings::{Warning, WarningCode};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OperationStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+// What a single operation of a multi-step run did
+#[derive(Debug, Clone, Serialize)]
+pub struct OperationReport {
+    pub index: usize, // 1-based position in the run
+    pub operation: String,
+    pub subnet: Option<u16>,
+    pub hotkey: String,
+    pub status: OperationStatus,
+    pub detail: String,
+    pub tx_hash: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SessionSummary {
+    pub mode: String,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub attempted: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub spent_rao: u128,
+    pub fees_rao: u128,
+    pub warnings: Vec<Warning>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub operations: Vec<OperationReport>,
+}
+
+impl SessionSummary {
+    pub fn new(mode: &str) -> Self {
+        Self {
+            mode: mode.to_string(),
+            started_at: Utc::now(),
+            finished_at: None,
+            attempted: 0,
+            succeeded: 0,
+            failed: 0,
+            spent_rao: 0,
+            fees_rao: 0,
+            warnings: Vec::new(),
+            operations: Vec::new(),
+        }
+    }
+
+    pub fn record_success(&mut self, spent: u128, fee: Option<u128>) {
+        self.attempted += 1;
+        self.succeeded += 1;
+        self.spent_rao += spent;
+        self.fees_rao += fee.unwrap_or(0);
+    }
+
+    pub fn record_failure(&mut self, operation: &str, error: &anyhow::Error) {
+        self.attempted += 1;
+        self.failed += 1;
+        self.warn(Warning::new(
+            WarningCode::OperationFailed,
+            format!("{} failed: {}", operation, error),
+        ));
+    }
+
+    pub fn warn(&mut self, warning: Warning) {
+        self.warnings.push(warning);
+    }
+
+    pub fn record_operation(&mut self, report: OperationReport) {
+        self.operations.push(report);
+    }
+
+    // Print the summary and optionally write it as JSON
+    pub fn finish(&mut self, output_path: Option<&str>) -> Result<()> {
+        let finished_at = Utc::now();
+        self.finished_at = Some(finished_at);
+        let elapsed = (finished_at - self.started_at).to_std().unwrap_or_default();
+
+        println!("\n{}", "📋 Session Summary".bright_cyan().bold());
+        println!("═══════════════════════════════════════");
+        println!("   Mode: {}", self.mode);
+        println!("   Duration: {}", utils::format_duration(elapsed));
+        println!("   Operations attempted: {}", self.attempted);
+        println!(
+            "   Succeeded: {}",
+            self.succeeded.to_string().bright_green()
+        );
+        println!("   Failed: {}", self.failed.to_string().bright_red());
+        println!("   TAO spent: {}", utils::format_tao(self.spent_rao));
+        println!("   Fees paid: {}", utils::format_tao(self.fees_rao));
+        if !self.operations.is_empty() {
+            println!("   Operations:");
+            for report in &self.operations {
+                let status = match report.status {
+                    OperationStatus::Succeeded => "✅",
+                    OperationStatus::Failed => "❌",
+                    OperationStatus::Skipped => "⏭️",
+                };
+                let subnet = report
+                    .subnet
+                    .map(|netuid| format!(" subnet {}", netuid))
+                    .unwrap_or_default();
+                println!(
+                    "     {} #{} {}{} {}: {}",
+                    status, report.index, report.operation, subnet, report.hotkey, report.detail
+                );
+            }
+        }
+        if !self.warnings.is_empty() {
+            println!("   Warnings:");
+            for warning in &self.warnings {
+                println!("     {}", warning.to_text());
+            }
+        }
+
+        if let Some(path) = output_path {
+            std::fs::write(path, serde_json::to_string_pretty(self)?)
+                .with_context(|| format!("Failed to write session summary to {}", path))?;
+            println!("💾 Session summary written to: {}", path);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_session_totals() {
+        let mut summary = SessionSummary::new("batch");
+        summary.record_success(1_000, Some(10));
+        summary.record_success(0, None);
+        summary.record_failure("register", &anyhow::anyhow!("Insufficient balance"));
+
+        assert_eq!(summary.attempted, 3);
+        assert_eq!(summary.succeeded, 2);
+        assert_eq!(summary.failed, 1);
+        assert_eq!(summary.spent_rao, 1_000);
+        assert_eq!(summary.fees_rao, 10);
+        assert_eq!(summary.warnings.len(), 1);
+        assert_eq!(summary.warnings[0].code, WarningCode::OperationFailed);
+        assert_eq!(
+            summary.warnings[0].message,
+            "register failed: Insufficient balance"
+        );
+
+        // Per-operation reports only appear in the JSON once recorded
+        let json = serde_json::to_value(&summary).unwrap();
+        assert!(json.get("operations").is_none());
+        summary.record_operation(OperationReport {
+            index: 1,
+            operation: "register".into(),
+            subnet: Some(1),
+            hotkey: "default".into(),
+            status: OperationStatus::Failed,
+            detail: "Insufficient balance".into(),
+            tx_hash: None,
+        });
+        let json = serde_json::to_value(&summary).unwrap();
+        assert_eq!(json["operations"][0]["status"], "failed");
+    }
+}
@@ -0,0 +1,128 @@
+//! Machine-readable daemon status for external health checks (monitor, guard)
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fmt::Display;
+
+use crate::hyperparams::ParamChange;
+use crate::utils;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NeuronStatus {
+    pub netuid: u16,
+    pub hotkey: String, // SS58 address
+    pub registered: bool,
+    pub uid: Option<u16>,
+    pub pruning_rank: Option<usize>, // 1 = next to be pruned, when risk is tracked
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DaemonStatus {
+    pub mode: String,
+    pub pid: u32,
+    pub started_at: DateTime<Utc>,
+    pub last_check: Option<DateTime<Utc>>,
+    pub neurons: Vec<NeuronStatus>,
+    pub last_error: Option<String>,
+    pub last_error_at: Option<DateTime<Utc>>,
+    // Most recent hyperparameter changes, when the monitor tracks them
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub param_changes: Vec<ParamChange>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub param_changed_at: Option<DateTime<Utc>>,
+}
+
+impl DaemonStatus {
+    pub fn new(mode: &str) -> Self {
+        Self {
+            mode: mode.to_string(),
+            pid: std::process::id(),
+            started_at: Utc::now(),
+            last_check: None,
+            neurons: Vec::new(),
+            last_error: None,
+            last_error_at: None,
+            param_changes: Vec::new(),
+            param_changed_at: None,
+        }
+    }
+
+    // Record a finished check, keeping the most recent neuron error as last_error
+    pub fn record_check(&mut self, neurons: Vec<NeuronStatus>) {
+        self.last_check = Some(Utc::now());
+        if let Some(error) = neurons.iter().rev().find_map(|n| n.error.clone()) {
+            self.last_error = Some(error);
+            self.last_error_at = self.last_check;
+        }
+        self.neurons = neurons;
+    }
+
+    // Changes of one cycle replace the previous ones, a quiet cycle keeps them
+    pub fn record_param_changes(&mut self, changes: Vec<ParamChange>) {
+        if !changes.is_empty() {
+            self.param_changes = changes;
+            self.param_changed_at = Some(Utc::now());
+        }
+    }
+
+    pub fn record_error(&mut self, error: impl Display) {
+        self.last_error = Some(error.to_string());
+        self.last_error_at = Some(Utc::now());
+    }
+
+    pub fn write(&self, path: &str) -> Result<()> {
+        utils::write_atomic(path, &serde_json::to_string_pretty(self)?)
+            .context("Failed to write status file")
+    }
+
+    // Daemons keep running when the status file cannot be written
+    pub fn write_or_warn(&self, path: Option<&str>) {
+        if let Some(path) = path {
+            if let Err(e) = self.write(path) {
+                println!("⚠️ {}", e);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_file_write() {
+        let mut status = DaemonStatus::new("monitor");
+        status.record_check(vec![
+            NeuronStatus {
+                netuid: 1,
+                hotkey: "5Hotkey".to_string(),
+                registered: true,
+                uid: Some(7),
+                pruning_rank: Some(12),
+                error: None,
+            },
+            NeuronStatus {
+                netuid: 3,
+                hotkey: "5Other".to_string(),
+                registered: false,
+                uid: None,
+                pruning_rank: None,
+                error: Some("RPC timeout".to_string()),
+            },
+        ]);
+
+        let path = std::env::temp_dir().join(format!("btreg-status-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        status.write(path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+
+        assert_eq!(json["mode"], "monitor");
+        assert_eq!(json["neurons"][0]["uid"], 7);
+        assert_eq!(json["last_error"], "RPC timeout");
+        assert!(!std::path::Path::new(&format!("{}.tmp", path)).exists());
+    }
+}
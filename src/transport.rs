@@ -0,0 +1,343 @@
+//! How the client reaches the chain: a WebSocket RPC endpoint, or with the light-client
+//! feature an embedded smoldot node synced from a chain spec. Both end up behind the same
+//! jsonrpsee client, so storage queries and submissions don't care which one is in use
+use anyhow::{Context, Result};
+use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+use std::{
+    path::{Path, PathBuf},
+    sync::OnceLock,
+    time::Duration,
+};
+
+use crate::paths;
+
+// Set with --light-client, None inside when the chain spec is fetched from the endpoint
+static LIGHT_CLIENT_SPEC: OnceLock<Option<PathBuf>> = OnceLock::new();
+
+// Sync through a light client instead of the RPC endpoint, from this chain spec or, without
+// one, from the spec the endpoint's node generates
+pub fn set_light_client(chain_spec: Option<&str>) {
+    let _ = LIGHT_CLIENT_SPEC.set(chain_spec.map(paths::expand_home));
+}
+
+pub fn light_client() -> bool {
+    LIGHT_CLIENT_SPEC.get().is_some()
+}
+
+// Label for the connection messages: the endpoint, or the chain spec the light client syncs
+pub fn describe(endpoint: &str) -> String {
+    match LIGHT_CLIENT_SPEC.get() {
+        Some(Some(spec)) => format!("light client ({})", spec.display()),
+        Some(None) => format!("light client (chain spec from {})", endpoint),
+        None => endpoint.to_string(),
+    }
+}
+
+pub async fn connect(endpoint: &str) -> Result<WsClient> {
+    match LIGHT_CLIENT_SPEC.get() {
+        Some(spec) => connect_light(endpoint, spec.as_deref()).await,
+        None => WsClientBuilder::default()
+            .connection_timeout(Duration::from_secs(30))
+            .request_timeout(Duration::from_secs(60))
+            .build(endpoint)
+            .await
+            .context("Failed to connect to Bittensor RPC endpoint"),
+    }
+}
+
+#[cfg(not(feature = "light-client"))]
+async fn connect_light(_endpoint: &str, _spec: Option<&Path>) -> Result<WsClient> {
+    Err(anyhow::anyhow!(
+        "This binary was built without the light client, rebuild with --features light-client"
+    ))
+}
+
+#[cfg(feature = "light-client")]
+async fn connect_light(endpoint: &str, spec: Option<&Path>) -> Result<WsClient> {
+    light::connect(endpoint, spec).await
+}
+
+#[cfg(feature = "light-client")]
+mod light {
+    use anyhow::{anyhow, Context, Result};
+    use jsonrpsee::core::{
+        async_trait,
+        client::{ClientBuilder, ClientT, ReceivedMessage, TransportReceiverT, TransportSenderT},
+    };
+    use jsonrpsee::rpc_params;
+    use jsonrpsee::ws_client::{WsClient, WsClientBuilder};
+    use serde_json::Value;
+    use smoldot_light::{
+        platform::DefaultPlatform, AddChainConfig, AddChainConfigJsonRpc, AddChainSuccess, ChainId,
+        JsonRpcResponses,
+    };
+    use std::{
+        fmt, iter,
+        num::NonZeroU32,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::{Duration, Instant},
+    };
+
+    use crate::constants;
+    use crate::errors::{ErrorKind, RegistrationError};
+    use crate::paths;
+    use crate::progress::Progress;
+    use crate::utils;
+
+    type LightClient = smoldot_light::Client<Arc<DefaultPlatform>>;
+
+    #[derive(Debug)]
+    pub struct LightClientError(String);
+
+    impl fmt::Display for LightClientError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "light client: {}", self.0)
+        }
+    }
+
+    impl std::error::Error for LightClientError {}
+
+    // Owns the smoldot client, so the node lives as long as the jsonrpsee client using it
+    struct Sender {
+        client: LightClient,
+        chain_id: ChainId,
+    }
+
+    struct Receiver {
+        responses: JsonRpcResponses<Arc<DefaultPlatform>>,
+    }
+
+    #[async_trait]
+    impl TransportSenderT for Sender {
+        type Error = LightClientError;
+
+        async fn send(&mut self, msg: String) -> Result<(), Self::Error> {
+            self.client
+                .json_rpc_request(msg, self.chain_id)
+                .map_err(|e| LightClientError(e.to_string()))
+        }
+    }
+
+    #[async_trait]
+    impl TransportReceiverT for Receiver {
+        type Error = LightClientError;
+
+        async fn receive(&mut self) -> Result<ReceivedMessage, Self::Error> {
+            self.responses
+                .next()
+                .await
+                .map(ReceivedMessage::Text)
+                .ok_or_else(|| LightClientError("node stopped".to_string()))
+        }
+    }
+
+    // The spec given, else the one cached for this endpoint, else the one its node generates
+    // with sync_state_genSyncSpec. That trusts the node once, for the genesis and a recent
+    // finalized checkpoint, everything synced after it is verified
+    async fn chain_spec(endpoint: &str, spec: Option<&Path>) -> Result<String> {
+        if let Some(spec) = spec {
+            return std::fs::read_to_string(spec)
+                .with_context(|| format!("Failed to read chain spec {}", spec.display()));
+        }
+        let cached = paths::cache_dir()
+            .join("chain_specs")
+            .join(format!("{}.json", file_name(endpoint)));
+        if let Ok(spec) = std::fs::read_to_string(&cached) {
+            return Ok(spec);
+        }
+
+        println!("📥 Fetching the chain spec from {}", endpoint);
+        let node = WsClientBuilder::default()
+            .connection_timeout(Duration::from_secs(30))
+            .build(endpoint)
+            .await
+            .context("Failed to connect to fetch the chain spec")?;
+        let spec: Value = node
+            .request("sync_state_genSyncSpec", rpc_params![true])
+            .await
+            .with_context(|| {
+                format!(
+                    "{} doesn't serve its chain spec, pass one with --light-client <CHAIN_SPEC>",
+                    endpoint
+                )
+            })?;
+        let spec = spec.to_string();
+        if let Err(e) = write(&cached, &spec) {
+            log::warn!(
+                "Failed to cache the chain spec at {}: {}",
+                cached.display(),
+                e
+            );
+        }
+        Ok(spec)
+    }
+
+    // An endpoint as a file name, "entrypoint-finney.opentensor.ai_443"
+    fn file_name(endpoint: &str) -> String {
+        let host = endpoint
+            .split_once("://")
+            .map_or(endpoint, |(_, rest)| rest);
+        host.trim_end_matches('/')
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '.' || c == '-' {
+                    c
+                } else {
+                    '_'
+                }
+            })
+            .collect()
+    }
+
+    fn write(path: &Path, contents: &str) -> std::io::Result<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, contents)
+    }
+
+    // Where smoldot's finalized chain is kept for a chain spec, so the next start or reconnect
+    // picks up from there instead of syncing from the spec's checkpoint again
+    fn database_path(specification: &str) -> PathBuf {
+        let hash = sp_core::blake2_256(specification.as_bytes());
+        paths::cache_dir()
+            .join("light_client")
+            .join(format!("{}.db", hex::encode(&hash[..8])))
+    }
+
+    // Requests before the light client caught up would read stale state, or fail for lack of
+    // peers. Waits until it has peers and is no longer syncing
+    async fn wait_for_sync(client: &WsClient) -> Result<()> {
+        let timeout = Duration::from_secs(constants::LIGHT_CLIENT_SYNC_TIMEOUT);
+        let deadline = Instant::now() + timeout;
+        let progress = Progress::spinner("Light client syncing");
+        progress.plain_println("⏳ Light client syncing...");
+        loop {
+            let health: Value = client
+                .request("system_health", rpc_params![])
+                .await
+                .context("Light client health check failed")?;
+            let peers = health["peers"].as_u64().unwrap_or(0);
+            if peers > 0 && health["isSyncing"] == Value::Bool(false) {
+                progress.println("✅ Light client synced");
+                return Ok(());
+            }
+            if Instant::now() >= deadline {
+                return Err(anyhow!(
+                    "Light client not synced after {}s ({} peers)",
+                    timeout.as_secs(),
+                    peers
+                ));
+            }
+            progress.set_message(format!("Light client syncing, {} peers", peers));
+            tokio::select! {
+                _ = tokio::time::sleep(Duration::from_secs(1)) => {}
+                _ = utils::shutdown_token().cancelled() => {
+                    return Err(
+                        RegistrationError::new(ErrorKind::Fatal, "Light client sync cancelled")
+                            .into(),
+                    );
+                }
+            }
+        }
+    }
+
+    // smoldot's own chainHead_unstable_finalizedDatabase, what add_chain takes back as
+    // database_content
+    async fn save_database(client: &WsClient, path: &Path) {
+        let database: Result<String, _> = client
+            .request("chainHead_unstable_finalizedDatabase", rpc_params![])
+            .await;
+        let saved = match database {
+            Ok(database) => write(path, &database).map_err(|e| e.to_string()),
+            Err(e) => Err(e.to_string()),
+        };
+        if let Err(e) = saved {
+            log::debug!("Light client database not saved: {}", e);
+        }
+    }
+
+    pub async fn connect(endpoint: &str, spec: Option<&Path>) -> Result<WsClient> {
+        let specification = chain_spec(endpoint, spec).await?;
+        let database_path = database_path(&specification);
+        let database = std::fs::read_to_string(&database_path).unwrap_or_default();
+        let mut client = smoldot_light::Client::new(DefaultPlatform::new(
+            env!("CARGO_PKG_NAME").into(),
+            env!("CARGO_PKG_VERSION").into(),
+        ));
+        // Subtensor is a solo chain, there is no relay chain to sync first
+        let AddChainSuccess {
+            chain_id,
+            json_rpc_responses,
+        } = client
+            .add_chain(AddChainConfig {
+                user_data: (),
+                specification: &specification,
+                database_content: &database,
+                potential_relay_chains: iter::empty(),
+                json_rpc: AddChainConfigJsonRpc::Enabled {
+                    max_pending_requests: NonZeroU32::new(128).unwrap(),
+                    max_subscriptions: 1024,
+                },
+            })
+            .map_err(|e| anyhow!("Failed to start the light client: {}", e))?;
+        let responses =
+            json_rpc_responses.ok_or_else(|| anyhow!("Light client has JSON-RPC disabled"))?;
+
+        let client = ClientBuilder::default()
+            .build_with_tokio(Sender { client, chain_id }, Receiver { responses });
+        wait_for_sync(&client).await?;
+        save_database(&client, &database_path).await;
+        Ok(client)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_light_client_cache_paths() {
+            assert_eq!(
+                file_name("wss://entrypoint-finney.opentensor.ai:443/"),
+                "entrypoint-finney.opentensor.ai_443"
+            );
+            assert_eq!(file_name("127.0.0.1:9944"), "127.0.0.1_9944");
+
+            // One database per chain spec
+            let spec = r#"{"name":"Bittensor"}"#;
+            assert_eq!(database_path(spec), database_path(spec));
+            assert_ne!(database_path(spec), database_path("{}"));
+        }
+
+        #[tokio::test]
+        async fn test_chain_spec_given() {
+            // A spec passed with --light-client is read as is, the endpoint is never asked
+            let dir = std::env::temp_dir().join(format!("btreg-chain-spec-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("bittensor.json");
+            std::fs::write(&path, r#"{"name":"Bittensor"}"#).unwrap();
+            let spec = chain_spec("ws://127.0.0.1:1", Some(&path)).await.unwrap();
+            assert_eq!(spec, r#"{"name":"Bittensor"}"#);
+            assert!(
+                chain_spec("ws://127.0.0.1:1", Some(&dir.join("missing.json")))
+                    .await
+                    .is_err()
+            );
+            std::fs::remove_dir_all(&dir).unwrap();
+        }
+    }
+}
+
+#[cfg(all(test, not(feature = "light-client")))]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_light_client_unavailable() {
+        let error = connect_light("wss://example.com:443", None)
+            .await
+            .unwrap_err();
+        assert!(error.to_string().contains("--features light-client"));
+    }
+}
@@ -1,12 +1,59 @@
 //! Utility functions for Bittensor registration
+use anyhow::{anyhow, Result};
+use chrono::{DateTime, FixedOffset, Local, Utc};
 use sp_core::{
     crypto::{AccountId32, Ss58Codec},
     U256,
 };
-use std::time::Duration;
+use std::{
+    io::{self, BufRead, IsTerminal, Write},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr},
+    sync::OnceLock,
+    time::Duration,
+};
+use tokio_util::sync::CancellationToken;
+
+use crate::constants::DEFAULT_BLOCK_TIME;
+use crate::network;
+
+// Cancelled on Ctrl+C, every client's cancellation token is a child of it
+static SHUTDOWN: OnceLock<CancellationToken> = OnceLock::new();
+
+pub fn shutdown_token() -> &'static CancellationToken {
+    SHUTDOWN.get_or_init(CancellationToken::new)
+}
+
+// Wall-clock times are shown in the system timezone unless --utc
+static UTC_TIMES: OnceLock<bool> = OnceLock::new();
+
+// Measured or configured once per run, DEFAULT_BLOCK_TIME until then
+static BLOCK_TIME: OnceLock<Duration> = OnceLock::new();
+
+// Ask a yes/no question on the terminal, defaulting to no
+pub fn confirm(prompt: &str) -> Result<bool> {
+    // Nothing of value is spent on test networks
+    if let Some(network) = network::current().filter(|network| !network.is_mainnet()) {
+        println!("{} [auto-confirmed on {}]", prompt, network);
+        return Ok(true);
+    }
+
+    if !io::stdin().is_terminal() {
+        return Err(anyhow!(
+            "Confirmation required but stdin is not a terminal, pass --yes to skip it"
+        ));
+    }
+
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
 
 pub fn format_tao(rao: u128) -> String {
-    let tao = rao as f64 / 1_000_000_000 as f64;
+    let tao = rao as f64 / 1_000_000_000f64;
     if tao >= 1000.0 {
         format!("{:.1}K TAO", tao / 1000.0)
     } else if tao >= 1.0 {
@@ -20,6 +67,149 @@ pub fn format_tao(rao: u128) -> String {
     }
 }
 
+// Subnet alpha, which has the same 9 decimals as TAO
+pub fn format_alpha(amount: u128) -> String {
+    let alpha = amount as f64 / 1_000_000_000f64;
+    if alpha >= 1000.0 {
+        format!("{:.1}K α", alpha / 1000.0)
+    } else {
+        format!("{:.3} α", alpha)
+    }
+}
+
+pub fn format_thousands(value: u64) -> String {
+    let digits = value.to_string();
+    let mut formatted = String::with_capacity(digits.len() + digits.len() / 3);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            formatted.push(',');
+        }
+        formatted.push(digit);
+    }
+    formatted
+}
+
+// One-line chart of values scaled between their min and max, e.g. "▁▃▅█"
+pub fn sparkline(values: &[u64]) -> String {
+    const LEVELS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    values
+        .iter()
+        .map(|&value| {
+            let level = if max == min {
+                LEVELS.len() / 2
+            } else {
+                ((value - min) as u128 * (LEVELS.len() - 1) as u128 / (max - min) as u128) as usize
+            };
+            LEVELS[level]
+        })
+        .collect()
+}
+
+// Horizontal bar proportional to value/max, at most width characters
+pub fn format_bar(value: u64, max: u64, width: usize) -> String {
+    let filled = if max == 0 {
+        0
+    } else {
+        (value as u128 * width as u128 / max as u128) as usize
+    };
+    format!("{:<width$}", "█".repeat(filled.min(width)), width = width)
+}
+
+// Approximate human duration with the two most significant units, e.g. "~3h 24m"
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (days, hours, minutes, seconds) = (
+        secs / 86_400,
+        secs % 86_400 / 3_600,
+        secs % 3_600 / 60,
+        secs % 60,
+    );
+
+    if days > 0 {
+        format!("~{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("~{}h {}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("~{}m {}s", minutes, seconds)
+    } else {
+        format!("~{}s", seconds)
+    }
+}
+
+pub fn set_block_time(block_time: Duration) {
+    let _ = BLOCK_TIME.set(block_time);
+}
+
+pub fn block_time() -> Duration {
+    BLOCK_TIME
+        .get()
+        .copied()
+        .unwrap_or(Duration::from_secs(DEFAULT_BLOCK_TIME))
+}
+
+pub fn blocks_to_duration(blocks: u64) -> Duration {
+    block_time() * blocks.min(u32::MAX as u64) as u32
+}
+
+// Block count with its wall-clock estimate, e.g. "1,024 blocks (~3h 24m)"
+pub fn format_blocks(blocks: u64) -> String {
+    format!(
+        "{} blocks ({})",
+        format_thousands(blocks),
+        format_duration(blocks_to_duration(blocks))
+    )
+}
+
+// Block count until something happens, with how long that is and when, e.g.
+// "1,024 blocks (~3h 24m, 2026-10-16 17:29 +02:00)"
+pub fn format_countdown(blocks: u64) -> String {
+    format!(
+        "{} blocks ({}, {})",
+        format_thousands(blocks),
+        format_duration(blocks_to_duration(blocks)),
+        format_time(block_eta(blocks))
+    )
+}
+
+// When a block that many blocks ahead is expected, at the measured block time
+pub fn block_eta(blocks: u64) -> DateTime<Utc> {
+    Utc::now() + chrono::Duration::from_std(blocks_to_duration(blocks)).unwrap_or_default()
+}
+
+pub fn set_utc_times(utc: bool) {
+    let _ = UTC_TIMES.set(utc);
+}
+
+// In the system timezone, or UTC with --utc
+pub fn display_time(time: DateTime<Utc>) -> DateTime<FixedOffset> {
+    to_display_time(time, UTC_TIMES.get().copied().unwrap_or(false))
+}
+
+fn to_display_time(time: DateTime<Utc>, utc: bool) -> DateTime<FixedOffset> {
+    if utc {
+        time.fixed_offset()
+    } else {
+        time.with_timezone(&Local).fixed_offset()
+    }
+}
+
+// e.g. "2026-10-16 14:05 +02:00", "2026-10-16 12:05 UTC" with --utc
+pub fn format_time(time: DateTime<Utc>) -> String {
+    format_display_time(display_time(time))
+}
+
+fn format_display_time(time: DateTime<FixedOffset>) -> String {
+    if time.offset().local_minus_utc() == 0 {
+        time.format("%Y-%m-%d %H:%M UTC").to_string()
+    } else {
+        time.format("%Y-%m-%d %H:%M %:z").to_string()
+    }
+}
+
 pub fn format_hash_rate(attempts: u64, duration: Duration) -> String {
     let rate = attempts as f64 / duration.as_secs_f64();
     if rate >= 1_000_000.0 {
@@ -44,6 +234,57 @@ pub fn format_ss58_short(ss58: &str) -> String {
     }
 }
 
+// Chain stores rank, trust, incentive etc. as u16 fractions of u16::MAX
+pub fn format_u16_ratio(value: u16) -> String {
+    format!("{:.4}", value as f64 / u16::MAX as f64)
+}
+
+pub fn format_ip(ip: u128, ip_type: u8) -> String {
+    if ip_type == 4 {
+        Ipv4Addr::from(ip as u32).to_string()
+    } else {
+        Ipv6Addr::from(ip).to_string()
+    }
+}
+
+// Parse an RFC 3339 timestamp or a duration from now such as "90m", "6h" or "2d"
+pub fn parse_deadline(value: &str, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    if let Ok(deadline) = DateTime::parse_from_rfc3339(value) {
+        return Ok(deadline.with_timezone(&Utc));
+    }
+
+    let (amount, unit) = value.split_at(value.len().saturating_sub(1));
+    let amount: i64 = amount.parse().map_err(|_| {
+        anyhow!(
+            "Invalid deadline: {}. Use e.g. 6h or 2025-06-01T12:00:00Z",
+            value
+        )
+    })?;
+    let seconds = match unit {
+        "s" => amount,
+        "m" => amount * 60,
+        "h" => amount * 3600,
+        "d" => amount * 86400,
+        _ => {
+            return Err(anyhow!(
+                "Invalid deadline unit in {}. Use s, m, h or d",
+                value
+            ))
+        }
+    };
+
+    Ok(now + chrono::Duration::seconds(seconds))
+}
+
+// Parse an IPv4/IPv6 address into the (ip, ip_type) pair stored in AxonInfo
+pub fn parse_ip(ip: &str) -> Result<(u128, u8)> {
+    match ip.parse::<IpAddr>() {
+        Ok(IpAddr::V4(v4)) => Ok((u32::from(v4) as u128, 4)),
+        Ok(IpAddr::V6(v6)) => Ok((u128::from(v6), 6)),
+        Err(_) => Err(anyhow!("Invalid IP address: {}", ip)),
+    }
+}
+
 pub fn format_difficulty(difficulty: U256) -> String {
     if difficulty > U256::from(1_000_000_000_000_000_000u64) {
         format!("{:.2}E", difficulty.as_u128() as f64 / 1e18)
@@ -60,6 +301,164 @@ pub fn format_difficulty(difficulty: U256) -> String {
     }
 }
 
+// CSV headers, kept stable so spreadsheets and pipelines keep working across releases
+pub const NETWORK_STATS_CSV_HEADER: &[&str] = &[
+    "netuid",
+    "neurons",
+    "max_neurons",
+    "burn_tao",
+    "difficulty",
+    "name",
+];
+pub const SUBNET_INFO_CSV_HEADER: &[&str] = &[
+    "netuid",
+    "neurons",
+    "max_neurons",
+    "burn_tao",
+    "min_burn_tao",
+    "max_burn_tao",
+    "difficulty",
+    "tempo",
+    "immunity_period",
+    "registration_allowed",
+    "registrations_this_interval",
+    "target_registrations_per_interval",
+    "owner",
+    "name",
+    "alpha_price_tao",
+    "tao_in",
+    "alpha_in",
+    "alpha_out",
+];
+pub const METAGRAPH_CSV_HEADER: &[&str] = &[
+    "uid",
+    "hotkey",
+    "coldkey",
+    "stake_tao",
+    "rank",
+    "trust",
+    "consensus",
+    "incentive",
+    "dividends",
+    "emission",
+    "validator_permit",
+    "active",
+    "axon",
+];
+pub const REGISTRATIONS_CSV_HEADER: &[&str] = &[
+    "netuid",
+    "name",
+    "uid",
+    "stake_tao",
+    "registered_at",
+    "immunity_remaining_blocks",
+];
+pub const DELEGATES_CSV_HEADER: &[&str] = &[
+    "hotkey",
+    "owner",
+    "take_percent",
+    "total_stake_tao",
+    "nominators",
+    "registrations",
+];
+
+// Plain decimal TAO for machine-readable output
+pub fn rao_to_tao_string(rao: u128) -> String {
+    format!("{}.{:09}", rao / 1_000_000_000, rao % 1_000_000_000)
+}
+
+// Decimal TAO, e.g. "1.5", to RAO without going through floating point
+pub fn parse_tao(tao: &str) -> Result<u64> {
+    let invalid = || anyhow!("Invalid TAO amount: {}", tao);
+    let (whole, fraction) = tao.trim().split_once('.').unwrap_or((tao.trim(), ""));
+    if fraction.len() > 9 || (whole.is_empty() && fraction.is_empty()) {
+        return Err(invalid());
+    }
+    let digits = |part: &str| -> Result<u64> {
+        if part.is_empty() {
+            return Ok(0);
+        }
+        if !part.chars().all(|c| c.is_ascii_digit()) {
+            return Err(invalid());
+        }
+        part.parse().map_err(|_| invalid())
+    };
+    let fraction_rao = digits(fraction)? * 10u64.pow(9 - fraction.len() as u32);
+    digits(whole)?
+        .checked_mul(1_000_000_000)
+        .and_then(|rao| rao.checked_add(fraction_rao))
+        .ok_or_else(invalid)
+}
+
+// Quote a CSV field when it holds a separator, quote or line break
+pub fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+// Write CSV to a file, or stdout when no path is given
+pub fn write_csv(path: Option<&str>, header: &[&str], rows: &[Vec<String>]) -> Result<()> {
+    let mut csv = header.join(",");
+    csv.push('\n');
+    for row in rows {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+
+    write_output(path, &csv)
+}
+
+// Write machine-readable output to a file, or stdout when no path is given
+pub fn write_output(path: Option<&str>, content: &str) -> Result<()> {
+    match path {
+        Some(path) => {
+            std::fs::write(path, content)
+                .map_err(|e| anyhow!("Failed to write {}: {}", path, e))?;
+            eprintln!("💾 Written to: {}", path);
+        }
+        None => print!("{}", content),
+    }
+    Ok(())
+}
+
+// Buffered writer for output produced piece by piece, a file or stdout
+pub fn output_writer(path: Option<&str>) -> Result<Box<dyn Write>> {
+    Ok(match path {
+        Some(path) => Box::new(io::BufWriter::new(
+            std::fs::File::create(path).map_err(|e| anyhow!("Failed to write {}: {}", path, e))?,
+        )),
+        None => Box::new(io::BufWriter::new(io::stdout().lock())),
+    })
+}
+
+// Write through a temp file and rename, so readers never see a partial file
+pub fn write_atomic(path: &str, content: &str) -> Result<()> {
+    let tmp_path = format!("{}.tmp", path);
+    std::fs::write(&tmp_path, content)
+        .map_err(|e| anyhow!("Failed to write {}: {}", tmp_path, e))?;
+    std::fs::rename(&tmp_path, path)
+        .map_err(|e| anyhow!("Failed to move {} into place: {}", path, e))
+}
+
+// Reject unknown --format values and --output without a file format
+pub fn check_output_format(format: &str, allowed: &[&str], output: Option<&str>) -> Result<()> {
+    if !allowed.contains(&format) {
+        return Err(anyhow!(
+            "Unknown format: {}. Use {}",
+            format,
+            allowed.join(", ")
+        ));
+    }
+    if format == "text" && output.is_some() {
+        return Err(anyhow!("--output needs a file format, e.g. --format csv"));
+    }
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -73,4 +472,88 @@ mod tests {
         let hash_rate = format_hash_rate(50000, Duration::from_secs(10));
         assert!(hash_rate.contains("KH/s"));
     }
+
+    #[test]
+    fn test_format_ip() {
+        assert_eq!(format_ip(0x7f000001, 4), "127.0.0.1");
+        assert_eq!(format_ip(1, 6), "::1");
+    }
+
+    #[test]
+    fn test_block_formatting() {
+        assert_eq!(format_thousands(4_000_000), "4,000,000");
+        assert_eq!(format_thousands(999), "999");
+        assert_eq!(format_duration(Duration::from_secs(12_240)), "~3h 24m");
+        assert_eq!(format_duration(Duration::from_secs(45)), "~45s");
+        assert_eq!(format_blocks(360), "360 blocks (~1h 12m)");
+    }
+
+    #[test]
+    fn test_parse_ip() {
+        assert_eq!(parse_ip("127.0.0.1").unwrap(), (0x7f000001, 4));
+        assert_eq!(parse_ip("::1").unwrap(), (1, 6));
+        assert!(parse_ip("localhost").is_err());
+    }
+
+    #[test]
+    fn test_display_times() {
+        let time = DateTime::parse_from_rfc3339("2026-10-16T12:05:30Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(
+            format_display_time(to_display_time(time, true)),
+            "2026-10-16 12:05 UTC"
+        );
+        let east = time.with_timezone(&FixedOffset::east_opt(2 * 3600).unwrap());
+        assert_eq!(format_display_time(east), "2026-10-16 14:05 +02:00");
+        assert!(format_countdown(360).starts_with("360 blocks (~1h 12m, 20"));
+    }
+
+    #[test]
+    fn test_parse_tao() {
+        assert_eq!(parse_tao("1.5").unwrap(), 1_500_000_000);
+        assert_eq!(parse_tao("0.000000001").unwrap(), 1);
+        assert_eq!(parse_tao("2").unwrap(), 2_000_000_000);
+        assert!(parse_tao("0.0000000001").is_err());
+        assert!(parse_tao("-1").is_err());
+    }
+
+    #[test]
+    fn test_format_alpha() {
+        assert_eq!(format_alpha(2_500_000_000), "2.500 α");
+        assert_eq!(format_alpha(12_345_000_000_000), "12.3K α");
+    }
+
+    #[test]
+    fn test_charts() {
+        assert_eq!(sparkline(&[100, 200, 450, 800]), "▁▂▄█");
+        assert_eq!(sparkline(&[5, 5]), "▅▅");
+        assert_eq!(sparkline(&[]), "");
+        assert_eq!(format_bar(50, 100, 4), "██  ");
+        assert_eq!(format_bar(1, 0, 2), "  ");
+    }
+
+    #[test]
+    fn test_csv_output() {
+        assert_eq!(csv_field("5Hotkey"), "5Hotkey");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(rao_to_tao_string(1_500_000_000), "1.500000000");
+        assert!(check_output_format("csv", &["text", "csv"], Some("out.csv")).is_ok());
+        assert!(check_output_format("text", &["text", "csv"], Some("out.csv")).is_err());
+        assert!(check_output_format("xml", &["text", "csv"], None).is_err());
+
+        let now = Utc::now();
+        assert_eq!(
+            parse_deadline("6h", now).unwrap(),
+            now + chrono::Duration::hours(6)
+        );
+        assert_eq!(
+            parse_deadline("2025-06-01T12:00:00Z", now)
+                .unwrap()
+                .to_rfc3339(),
+            "2025-06-01T12:00:00+00:00"
+        );
+        assert!(parse_deadline("soon", now).is_err());
+    }
 }
@@ -0,0 +1,157 @@
+//! Creation of new wallets in the btcli directory layout
+use anyhow::{anyhow, Context, Result};
+use colored::*;
+use sp_core::{
+    crypto::{AccountId32, Ss58Codec},
+    sr25519::Pair as Sr25519Pair,
+    Pair,
+};
+use std::{fs, path::Path};
+
+use crate::key_utils;
+use crate::keyfile;
+
+// What NewKey should create
+#[derive(Debug, Clone, Default)]
+pub struct NewKeyOptions {
+    pub wallet: String,
+    pub hotkey: String,
+    pub hotkey_only: bool, // Add a hotkey to an existing wallet, keep its coldkey
+    pub encrypt: bool,     // Encrypt the coldkey with a password
+    pub overwrite: bool,   // Replace existing key files
+}
+
+// Generated key, kept around to print its mnemonic once everything is written
+struct NewKey {
+    role: &'static str,
+    address: String,
+    phrase: String,
+}
+
+// Create coldkey and/or hotkey files under <wallets_dir>/<wallet>
+pub fn create_keys(wallets_dir: &Path, options: &NewKeyOptions) -> Result<()> {
+    println!("{}", "🔑 Creating New Keys".bright_cyan().bold());
+    println!("═══════════════════════════════════════");
+
+    let wallet_dir = wallets_dir.join(&options.wallet);
+    let mut created = Vec::new();
+
+    if !options.hotkey_only {
+        let (pair, phrase, seed) = Sr25519Pair::generate_with_phrase(None);
+        let contents = key_utils::keyfile_json(&pair, &phrase, &seed).to_string();
+        let coldkey_path = wallet_dir.join("coldkey");
+        let contents = if options.encrypt {
+            let password = key_utils::read_new_password(&format!("{} coldkey", options.wallet))?;
+            println!("🔐 Encrypting coldkey...");
+            let encrypted = keyfile::encrypt(contents.as_bytes(), &password)?;
+            key_utils::remember_password(&coldkey_path.to_string_lossy(), &password);
+            encrypted
+        } else {
+            contents.into_bytes()
+        };
+
+        write_key_file(&coldkey_path, &contents, options.overwrite)?;
+        write_key_file(
+            &wallet_dir.join("coldkeypub.txt"),
+            key_utils::public_keyfile_json(&pair).to_string().as_bytes(),
+            options.overwrite,
+        )?;
+        created.push(NewKey {
+            role: "Coldkey",
+            address: AccountId32::from(pair.public().0).to_ss58check(),
+            phrase,
+        });
+    } else if !wallet_dir.join("coldkey").is_file() {
+        return Err(anyhow!(
+            "Wallet {} has no coldkey at {}",
+            options.wallet,
+            wallet_dir.display()
+        ));
+    }
+
+    let (pair, phrase, seed) = Sr25519Pair::generate_with_phrase(None);
+    write_key_file(
+        &wallet_dir.join("hotkeys").join(&options.hotkey),
+        key_utils::keyfile_json(&pair, &phrase, &seed)
+            .to_string()
+            .as_bytes(),
+        options.overwrite,
+    )?;
+    created.push(NewKey {
+        role: "Hotkey",
+        address: AccountId32::from(pair.public().0).to_ss58check(),
+        phrase,
+    });
+
+    println!("\n✅ Keys written to {}", wallet_dir.display());
+    for key in &created {
+        println!("\n   {}: {}", key.role, key.address.bright_green());
+        println!("   Mnemonic: {}", key.phrase.bright_yellow());
+    }
+    println!(
+        "\n{}",
+        "⚠️ Write the mnemonics down and keep them offline, they are the only way to recover these keys"
+            .bright_red()
+    );
+
+    Ok(())
+}
+
+// Write a key file readable only by its owner, refusing to replace one unless asked
+fn write_key_file(path: &Path, contents: &[u8], overwrite: bool) -> Result<()> {
+    if path.exists() && !overwrite {
+        return Err(anyhow!(
+            "{} already exists, use --overwrite to replace it",
+            path.display()
+        ));
+    }
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+
+    fs::write(path, contents).with_context(|| format!("Failed to write {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_keys() {
+        let wallets = std::env::temp_dir().join(format!("btreg-newkey-{}", std::process::id()));
+        let mut options = NewKeyOptions {
+            wallet: "fresh".into(),
+            hotkey: "default".into(),
+            ..Default::default()
+        };
+        create_keys(&wallets, &options).unwrap();
+
+        let wallet_dir = wallets.join("fresh");
+        let coldkey =
+            key_utils::load_keypair_from_file(wallet_dir.join("coldkey").to_str().unwrap())
+                .unwrap();
+        let public: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(wallet_dir.join("coldkeypub.txt")).unwrap())
+                .unwrap();
+        assert_eq!(public["ss58Address"], coldkey.account_id().to_ss58check());
+        assert!(wallet_dir.join("hotkeys").join("default").is_file());
+
+        // Existing keys are never replaced silently
+        assert!(create_keys(&wallets, &options).is_err());
+
+        options.hotkey_only = true;
+        options.hotkey = "second".into();
+        create_keys(&wallets, &options).unwrap();
+        assert!(wallet_dir.join("hotkeys").join("second").is_file());
+
+        fs::remove_dir_all(wallets).unwrap();
+    }
+}
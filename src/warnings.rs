@@ -0,0 +1,159 @@
+//! Structured warnings with stable codes, rendered as text or JSON lines (--warnings)
+use anyhow::{anyhow, Result};
+use colored::*;
+use serde::Serialize;
+use std::sync::OnceLock;
+
+static JSON_OUTPUT: OnceLock<bool> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    Info,
+    Warning,
+    Critical,
+}
+
+// Stable identifiers automation can match on, serialized in snake_case
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum WarningCode {
+    BurnAdjusted,
+    FeeUnknown,
+    RegistrationUnverified,
+    SpendLimitBelowTarget,
+    AxonNotServed,
+    PrometheusNotServed,
+    NoWeights,
+    NoEmission,
+    Deregistered,
+    UnknownOperation,
+    OperationFailed,
+    RecordFailed,
+    SlowEndpoint,
+    RegistrationClosed,
+    ForeignHotkey,
+    StorageDefaulted,
+}
+
+impl WarningCode {
+    pub fn severity(&self) -> Severity {
+        match self {
+            WarningCode::FeeUnknown
+            | WarningCode::BurnAdjusted
+            | WarningCode::NoWeights
+            | WarningCode::NoEmission => Severity::Info,
+            WarningCode::Deregistered | WarningCode::OperationFailed => Severity::Critical,
+            _ => Severity::Warning,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WarningCode::BurnAdjusted => "burn_adjusted",
+            WarningCode::FeeUnknown => "fee_unknown",
+            WarningCode::RegistrationUnverified => "registration_unverified",
+            WarningCode::SpendLimitBelowTarget => "spend_limit_below_target",
+            WarningCode::AxonNotServed => "axon_not_served",
+            WarningCode::PrometheusNotServed => "prometheus_not_served",
+            WarningCode::NoWeights => "no_weights",
+            WarningCode::NoEmission => "no_emission",
+            WarningCode::Deregistered => "deregistered",
+            WarningCode::UnknownOperation => "unknown_operation",
+            WarningCode::OperationFailed => "operation_failed",
+            WarningCode::RecordFailed => "record_failed",
+            WarningCode::SlowEndpoint => "slow_endpoint",
+            WarningCode::RegistrationClosed => "registration_closed",
+            WarningCode::ForeignHotkey => "foreign_hotkey",
+            WarningCode::StorageDefaulted => "storage_defaulted",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Warning {
+    pub code: WarningCode,
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Warning {
+    pub fn new(code: WarningCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            severity: code.severity(),
+            message: message.into(),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let icon = match self.severity {
+            Severity::Info => "ℹ️",
+            Severity::Warning => "⚠️",
+            Severity::Critical => "🚨",
+        };
+        format!("{} {} [{}]", icon, self.message, self.code.as_str())
+    }
+}
+
+pub fn set_format(format: &str) -> Result<()> {
+    let json = match format {
+        "text" => false,
+        "json" => true,
+        other => {
+            return Err(anyhow!(
+                "Unknown warnings format: {}. Use text or json",
+                other
+            ))
+        }
+    };
+    let _ = JSON_OUTPUT.set(json);
+    Ok(())
+}
+
+// Report a warning and hand it back for session summaries. JSON lines go to stderr
+// so they never mix with machine-readable output on stdout
+pub fn emit(code: WarningCode, message: impl Into<String>) -> Warning {
+    let warning = Warning::new(code, message);
+
+    if JSON_OUTPUT.get().copied().unwrap_or(false) {
+        match serde_json::to_string(&warning) {
+            Ok(line) => eprintln!("{}", line),
+            Err(_) => eprintln!("{}", warning.to_text()),
+        }
+    } else if warning.severity == Severity::Critical {
+        println!("{}", warning.to_text().bright_red().bold());
+    } else {
+        println!("{}", warning.to_text());
+    }
+
+    warning
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_warning_rendering() {
+        let warning = Warning::new(WarningCode::AxonNotServed, "No axon published for UID 5");
+        assert_eq!(warning.severity, Severity::Warning);
+        assert_eq!(
+            warning.to_text(),
+            "⚠️ No axon published for UID 5 [axon_not_served]"
+        );
+        assert_eq!(
+            serde_json::to_value(&warning).unwrap(),
+            serde_json::json!({
+                "code": "axon_not_served",
+                "severity": "warning",
+                "message": "No axon published for UID 5",
+            })
+        );
+        assert_eq!(
+            Warning::new(WarningCode::Deregistered, "").severity,
+            Severity::Critical
+        );
+        assert!(set_format("yaml").is_err());
+    }
+}